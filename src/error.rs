@@ -0,0 +1,224 @@
+use std::error::Error;
+use std::fmt;
+
+///
+/// The kind of an `RDFError`, allowing to filter errors without looking at their messages.
+///
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum ErrorKind{
+    /// The input doesn't follow the syntax of the format (e.g. missing dot, unexpected token)
+    Syntax,
+    /// An IRI is not valid
+    InvalidIri,
+    /// A literal is malformed or its value is not valid for its datatype
+    InvalidLiteral,
+    /// A prefixed name uses a prefix which was never declared
+    UnknownPrefix,
+    /// Reading from or writing to a file or stream failed
+    Io,
+    /// The format is not supported or couldn't be guessed
+    UnsupportedFormat,
+    /// A named graph doesn't exist in the `Database`
+    UnknownGraph,
+    /// A named graph should be created, but already exists in the `Database`
+    GraphAlreadyExists,
+    /// A computation exceeded its configured limit (e.g. the deep iterations of the canonicalization)
+    LimitExceeded,
+    /// A rule is not valid (e.g. a variable of its head isn't bound by its body)
+    InvalidRule,
+    /// A graph is inconsistent with respect to the constraints of a reasoner (e.g. an individual of `owl:Nothing`)
+    Inconsistent,
+    /// A shapes graph is ill-formed (e.g. `sh:minCount` isn't a non-negative integer)
+    InvalidShape,
+    /// A query is not valid or uses a feature which is not supported (e.g. an aggregate)
+    InvalidQuery
+}
+
+impl fmt::Display for ErrorKind {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let name = match self{
+            ErrorKind::Syntax => "syntax error",
+            ErrorKind::InvalidIri => "invalid IRI",
+            ErrorKind::InvalidLiteral => "invalid literal",
+            ErrorKind::UnknownPrefix => "unknown prefix",
+            ErrorKind::Io => "IO error",
+            ErrorKind::UnsupportedFormat => "unsupported format",
+            ErrorKind::UnknownGraph => "unknown graph",
+            ErrorKind::GraphAlreadyExists => "graph already exists",
+            ErrorKind::LimitExceeded => "limit exceeded",
+            ErrorKind::InvalidRule => "invalid rule",
+            ErrorKind::Inconsistent => "inconsistent",
+            ErrorKind::InvalidShape => "invalid shape",
+            ErrorKind::InvalidQuery => "invalid query"
+        };
+        write!(f, "{}", name)
+    }
+}
+
+///
+/// The error type used throughout rdf4rust.
+///
+/// Every error has an `ErrorKind` and a message.
+/// If the error can be located in the input, `line`, `column`, `byte_offset` and `text` are set as well.
+/// Errors caused by another error (e.g. an invalid IRI inside a literal datatype) keep it as their `source()`.
+///
+/// `ParserError`, `ObjectParserError`, `IOError`, `InvalidLiteralError` and `IRIInvalidError` are aliases of this type.
+///
+/// # Example
+///
+/// ```
+/// use rdf4rust::error::{RDFError, ErrorKind};
+/// use rdf4rust::util::iri::IRI;
+/// use std::error::Error;
+///
+/// let iri_err = IRI::create_iri(&String::from("no-scheme")).err().expect("Is not a valid IRI");
+/// assert_eq!(ErrorKind::InvalidIri, iri_err.get_kind());
+///
+/// let err = RDFError::create(ErrorKind::Syntax, String::from("Object is not valid."), 2, "<a> <b> <no-scheme> .", 8)
+///     .with_source(iri_err);
+///
+/// assert_eq!(Some(2), err.get_line());
+/// assert_eq!(Some(9), err.get_column());
+/// assert_eq!(Some(8), err.get_byte_offset());
+/// assert!(err.source().is_some());
+/// ```
+///
+#[derive(Debug)]
+pub struct RDFError{
+    kind: ErrorKind,
+    msg: String,
+    line: Option<usize>,
+    column: Option<usize>,
+    byte_offset: Option<usize>,
+    text: Option<String>,
+    source: Option<Box<dyn Error + Send + Sync + 'static>>
+}
+
+impl Error for RDFError {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        match &self.source {
+            Some(source) => Some(source.as_ref()),
+            None => None
+        }
+    }
+}
+
+impl fmt::Display for RDFError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match (self.line, self.column) {
+            (Some(line), Some(column)) => write!(f, "Line [#line: {}, col: {}] {}", line, column, self.msg),
+            (Some(line), None) => write!(f, "Line [#line: {}] {}", line, self.msg),
+            _ => write!(f, "{}", self.msg)
+        }
+    }
+}
+
+impl RDFError {
+
+    pub fn new(kind: ErrorKind, msg: String) -> RDFError {
+        RDFError {
+            kind,
+            msg,
+            line: None,
+            column: None,
+            byte_offset: None,
+            text: None,
+            source: None
+        }
+    }
+
+    ///
+    /// Creates an error located at the byte position `pos` in `line`.
+    ///
+    /// # Parameters
+    ///
+    /// * `kind` - The kind of the error
+    /// * `msg` - The error message without any position information
+    /// * `line_no` - The line number (starting at 1) of the line in the input
+    /// * `line` - The offending line
+    /// * `pos` - The byte position inside `line` at which the error occurred
+    ///
+    pub fn create(kind: ErrorKind, msg: String, line_no: usize, line: &str, pos: usize) -> RDFError {
+        RDFError::new(kind, msg).with_position(line_no, line, pos)
+    }
+
+    ///
+    /// Locates the error at the byte position `pos` in `line`.
+    ///
+    /// The byte offset is relative to the start of `line`, use `offset_by` to make it relative to the whole input.
+    ///
+    pub fn with_position(mut self, line_no: usize, line: &str, pos: usize) -> RDFError {
+        //column counts chars not bytes, starting at 1
+        let column = match line.get(..pos) {
+            Some(prefix) => prefix.chars().count() + 1,
+            None => line.chars().count() + 1
+        };
+        self.line = Some(line_no);
+        self.column = Some(column);
+        self.byte_offset = Some(pos);
+        self.text = Some(String::from(line));
+        self
+    }
+
+    ///
+    /// Sets the error which caused this error, available through `source()`
+    ///
+    pub fn with_source<E: Error + Send + Sync + 'static>(mut self, source: E) -> RDFError {
+        self.source = Some(Box::new(source));
+        self
+    }
+
+    ///
+    /// Shifts the byte offset by `offset`, e.g. the offset of the line start in the input.
+    ///
+    pub fn offset_by(mut self, offset: usize) -> RDFError {
+        if let Some(byte_offset) = self.byte_offset {
+            self.byte_offset = Some(byte_offset + offset);
+        }
+        self
+    }
+
+    ///
+    /// Shifts the line number by `lines`, e.g. the number of lines before the chunk the error occurred in.
+    ///
+    pub fn offset_lines_by(mut self, lines: usize) -> RDFError {
+        if let Some(line) = self.line {
+            self.line = Some(line + lines);
+        }
+        self
+    }
+
+    pub fn get_kind(&self) -> ErrorKind {
+        self.kind
+    }
+
+    pub fn get_msg(&self) -> &str {
+        &self.msg
+    }
+
+    /// The line number (starting at 1) the error occurred in
+    pub fn get_line(&self) -> Option<usize> {
+        self.line
+    }
+
+    /// The column (starting at 1, counted in chars) the error occurred at
+    pub fn get_column(&self) -> Option<usize> {
+        self.column
+    }
+
+    /// The byte offset the error occurred at
+    pub fn get_byte_offset(&self) -> Option<usize> {
+        self.byte_offset
+    }
+
+    /// The offending line
+    pub fn get_text(&self) -> Option<&str> {
+        self.text.as_deref()
+    }
+}
+
+impl From<std::io::Error> for RDFError {
+    fn from(err: std::io::Error) -> Self {
+        RDFError::new(ErrorKind::Io, err.to_string()).with_source(err)
+    }
+}
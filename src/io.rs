@@ -1,2 +1,5 @@
 pub mod reader;
-pub mod buffered_reader;
\ No newline at end of file
+pub mod buffered_reader;
+pub mod bulk_loader;
+pub mod writer;
+mod turtle;
\ No newline at end of file
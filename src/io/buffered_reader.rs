@@ -1,7 +1,6 @@
 use std::fs::File;
 use std::io::Read;
-use std::fmt;
-use std::error::Error;
+use crate::error::RDFError;
 
 
 const BUFFER_SIZE: usize = 1024;
@@ -9,7 +8,7 @@ const BUFFER_SIZE: usize = 1024;
 pub struct BufferedReader{
     file: File,
     buffer_lines: Vec<String>,
-    unresolved_line: String,
+    unresolved_line: Vec<u8>,
     eof: bool
 }
 
@@ -18,12 +17,12 @@ impl BufferedReader{
     pub fn new(file_name: &str) -> Result<BufferedReader, IOError>{
         let file = match File::open(&file_name){
             Ok(file) => {file}
-            Err(err) => {return Err(IOError::new(err.to_string()))}
+            Err(err) => {return Err(IOError::from(err))}
         };
         Ok(BufferedReader{
             file,
             buffer_lines: Vec::new(),
-            unresolved_line: String::new(),
+            unresolved_line: Vec::new(),
             eof: false
         })
     }
@@ -39,31 +38,27 @@ impl BufferedReader{
 
         size_read = match self.file.read(&mut buffer) {
             Ok(num) => {num}
-            Err(err) => {return Err(IOError::new(err.to_string()))}
+            Err(err) => {return Err(IOError::from(err))}
         };
         if size_read != 0 {
             let mut complete_buffer: Vec<u8> = Vec::new();
-
-            let mut unresolved: Vec<_> = self.unresolved_line.bytes().collect();
-            complete_buffer.append(&mut unresolved);
-            let tmp = &buffer[..size_read];
-            complete_buffer.append(&mut Vec::from(tmp));
-            self.unresolved_line.clear();
-            //buffer to string
-            let utf8str = String::from_utf8_lossy(&complete_buffer);
-            let mut lines: Vec<_> = utf8str.split("\n").collect();
-            //check if last byte is new line
-            if buffer[size_read-1] != b'\n'{
-                self.unresolved_line += &lines.pop().expect("Vector is unexpectedly empty.");
-            }
-            let mut tmp: Vec<String> = lines.iter().map(|&x| String::from(x)).collect();
+            complete_buffer.append(&mut self.unresolved_line);
+            complete_buffer.extend_from_slice(&buffer[..size_read]);
+            //split on bytes, so chars spanning two buffers stay intact
+            let mut lines: Vec<&[u8]> = complete_buffer.split(|&b| b == b'\n').collect();
+            //the part after the last new line is either empty or not finished yet
+            let last = lines.pop().expect("Vector is unexpectedly empty.");
+            self.unresolved_line.extend_from_slice(last);
+            let mut tmp: Vec<String> = lines.iter().map(|&x| String::from_utf8_lossy(x).into_owned()).collect();
             self.buffer_lines.append(&mut tmp);
         }
         else if !self.eof{
 
             self.eof = true;
-            self.buffer_lines.insert(0, String::from(&self.unresolved_line));
-            self.unresolved_line.clear();
+            if !self.unresolved_line.is_empty() {
+                self.buffer_lines.push(String::from_utf8_lossy(&self.unresolved_line).into_owned());
+                self.unresolved_line.clear();
+            }
         }
         return Ok(self.buffer_lines.len());
     }
@@ -71,31 +66,17 @@ impl BufferedReader{
     pub fn read_line(&mut self) -> Option<String>{
 
         while self.buffer_lines.is_empty(){
+            if self.eof{
+                //EOF
+                return None
+            }
             self.read_lines_to_buffer().expect("");
         }
-        if self.eof{
-            //EOF
-            return None
-        }
         Some(self.buffer_lines.remove(0))
     }
 }
 
-#[derive(Debug)]
-pub struct IOError{
-    msg: String
-}
-
-impl Error for IOError {}
-
-impl fmt::Display for IOError {
-    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
-        write!(f,"{}",self.msg)
-    }
-}
-
-impl IOError {
-    pub fn new(msg: String) -> IOError {
-        IOError {msg}
-    }
-}
\ No newline at end of file
+///
+/// Error which occurred while reading a file, always of kind `ErrorKind::Io`
+///
+pub type IOError = RDFError;
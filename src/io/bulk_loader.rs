@@ -0,0 +1,352 @@
+use crate::io::reader::{invalid_utf8, Lang, Reader, ParseOptions, ParseMode, ParseReport, ParserError};
+use crate::rdf::database::Database;
+use crate::rdf::graph::Quad;
+use crate::rdf::node_factory::BlankNodeScope;
+use crate::error::ErrorKind;
+use std::fs::File;
+use std::io::{Read, Seek, SeekFrom};
+use std::sync::atomic::{AtomicBool, AtomicUsize, Ordering};
+use std::sync::mpsc::sync_channel;
+use std::sync::Mutex;
+use std::thread;
+use std::time::{Duration, Instant};
+use std::fmt;
+
+const DEFAULT_CHUNK_SIZE: usize = 8 * 1024 * 1024;
+const BATCH_SIZE: usize = 10_000;
+
+///
+/// Throughput statistics of a `BulkLoader::load` run.
+///
+pub struct LoadStatistics{
+    bytes: usize,
+    lines: usize,
+    statements: usize,
+    errors: usize,
+    threads: usize,
+    duration: Duration
+}
+
+impl LoadStatistics{
+
+    pub fn get_bytes(&self) -> usize{
+        self.bytes
+    }
+
+    pub fn get_lines(&self) -> usize{
+        self.lines
+    }
+
+    pub fn get_statements(&self) -> usize{
+        self.statements
+    }
+
+    pub fn get_errors(&self) -> usize{
+        self.errors
+    }
+
+    pub fn get_threads(&self) -> usize{
+        self.threads
+    }
+
+    pub fn get_duration(&self) -> Duration{
+        self.duration
+    }
+
+    pub fn get_statements_per_second(&self) -> f64{
+        self.statements as f64 / self.duration.as_secs_f64().max(f64::EPSILON)
+    }
+
+    pub fn get_bytes_per_second(&self) -> f64{
+        self.bytes as f64 / self.duration.as_secs_f64().max(f64::EPSILON)
+    }
+}
+
+impl fmt::Display for LoadStatistics{
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "Loaded {} statements ({} errors) from {} lines / {} bytes in {:.3}s using {} threads [{:.0} statements/s, {:.2} MiB/s]",
+               self.statements, self.errors, self.lines, self.bytes, self.duration.as_secs_f64(), self.threads,
+               self.get_statements_per_second(), self.get_bytes_per_second() / (1024.0 * 1024.0))
+    }
+}
+
+///
+/// Loads large N-Triples and N-Quads files in parallel.
+///
+/// The file is split into chunks at line breaks, which are parsed by a pool of threads.
+/// Parsed statements are merged into the `Database` by the calling thread while parsing continues.
+///
+/// The `ParseOptions` are respected as in `Reader::read_to_rdf_with_options`, however in strict mode statements of
+/// other chunks may already be added to the database when the error is returned.
+///
+/// All chunks of a file share one `BlankNodeScope`, which differs for every call of `load`.
+///
+/// # Example
+///
+/// ```
+/// use rdf4rust::io::bulk_loader::BulkLoader;
+/// use rdf4rust::io::reader::{Lang, ParseOptions};
+/// use rdf4rust::rdf::database::Database;
+/// use rdf4rust::rdf::graph::GraphType;
+///
+/// let file = std::env::temp_dir().join("rdf4rust_bulk_loader_example.nq");
+/// std::fs::write(&file, "<http://example.com/s> <http://example.com/p> \"o\" .\n\
+///     <http://example.com/s> <http://example.com/p> <http://example.com/o> <http://example.com/g> .\n\
+///     \"invalid\" <http://example.com/p> <http://example.com/o> .\n").expect("Cannot write example file");
+///
+/// let mut database = Database::new(GraphType::SimpleGraph);
+/// let (report, statistics) = BulkLoader::new()
+///     .with_threads(2)
+///     .with_options(ParseOptions::lenient())
+///     .load(&mut database, file.to_str().unwrap(), Lang::NQ)
+///     .expect("Cannot read file");
+///
+/// assert_eq!(2, database.count());
+/// assert_eq!(2, report.get_statement_count());
+/// assert_eq!(Some(3), report.get_errors()[0].get_line());
+/// assert_eq!(3, statistics.get_lines());
+/// ```
+///
+pub struct BulkLoader{
+    threads: usize,
+    chunk_size: usize,
+    options: ParseOptions
+}
+
+struct Chunk{
+    start: usize,
+    end: usize
+}
+
+struct ChunkResult{
+    lines: usize,
+    errors: Vec<ParserError>
+}
+
+impl Default for BulkLoader{
+    fn default() -> Self {
+        BulkLoader::new()
+    }
+}
+
+impl BulkLoader{
+
+    pub fn new() -> Self{
+        let threads = match thread::available_parallelism(){
+            Ok(num) => num.get(),
+            Err(_) => 1
+        };
+        BulkLoader{
+            threads,
+            chunk_size: DEFAULT_CHUNK_SIZE,
+            options: ParseOptions::default()
+        }
+    }
+
+    /// Sets the number of parsing threads, defaults to the available parallelism
+    pub fn with_threads(mut self, threads: usize) -> Self{
+        self.threads = threads.max(1);
+        self
+    }
+
+    /// Sets the approximate size of the chunks in bytes, defaults to 8 MiB
+    pub fn with_chunk_size(mut self, chunk_size: usize) -> Self{
+        self.chunk_size = chunk_size.max(1);
+        self
+    }
+
+    pub fn with_options(mut self, options: ParseOptions) -> Self{
+        self.options = options;
+        self
+    }
+
+    ///
+    /// Loads `file` into `database`
+    ///
+    /// # Returns
+    ///
+    /// The `ParseReport` containing the errors in order of their lines and the `LoadStatistics`.
+    ///
+    /// Fails if `lang` is neither N-Triples nor N-Quads, the file cannot be read
+    /// or with the first error in the file if the options are strict.
+    ///
+    pub fn load(&self, database: &mut Database, file: &str, lang: Lang) -> Result<(ParseReport, LoadStatistics), ParserError>{
+        match lang {
+            Lang::NT | Lang::NTRIPLE | Lang::NQ | Lang::NQUADS => {}
+            _ => return Err(ParserError::new(ErrorKind::UnsupportedFormat, String::from("Bulk loading is only supported for N-Triples and N-Quads.")))
+        }
+        let start_time = Instant::now();
+        let chunks = match BulkLoader::split(file, self.chunk_size) {
+            Ok(chunks) => chunks,
+            Err(err) => return Err(ParserError::new(ErrorKind::Io, format!("Cannot read file {}", file)).with_source(err))
+        };
+        let bytes = match chunks.last() {
+            Some(chunk) => chunk.end,
+            None => 0
+        };
+        let mode = self.options.get_mode();
+        let normalize_iris = self.options.get_normalize_iris();
+        let skolemization = self.options.get_skolemization();
+        //all chunks belong to the same document, so they share the blank nodes
+        let blank_nodes = if self.options.get_preserve_blank_node_labels() {BlankNodeScope::preserving()} else {BlankNodeScope::new()};
+        let next_chunk = AtomicUsize::new(0);
+        let abort = AtomicBool::new(false);
+        let results: Mutex<Vec<Option<Result<ChunkResult, ParserError>>>> = Mutex::new(chunks.iter().map(|_| None).collect());
+        let mut statements = 0;
+
+        thread::scope(|scope| {
+            let (sender, receiver) = sync_channel::<Vec<Quad>>(self.threads * 2);
+            for _ in 0..self.threads {
+                let sender = sender.clone();
+                let (chunks, next_chunk, abort, results, lang, blank_nodes) = (&chunks, &next_chunk, &abort, &results, &lang, &blank_nodes);
+                scope.spawn(move || {
+                    let mut parser = Reader::get_parser(lang).expect("The format is checked before loading");
+                    loop {
+                        let index = next_chunk.fetch_add(1, Ordering::SeqCst);
+                        if index >= chunks.len() || abort.load(Ordering::SeqCst) {
+                            break;
+                        }
+                        let chunk = &chunks[index];
+                        let result = match BulkLoader::read_chunk(file, chunk) {
+                            Ok(content) => {
+                                let mut lines = 0;
+                                let mut errors = Vec::new();
+                                let mut batch = Vec::new();
+                                let mut line_offset = chunk.start;
+                                for raw_line in content.split(|byte| *byte == b'\n') {
+                                    lines += 1;
+                                    if !raw_line.is_empty() && !abort.load(Ordering::Relaxed) {
+                                        let parsed = match std::str::from_utf8(raw_line) {
+                                            Ok(line) => parser.parse_line(line.strip_suffix('\r').unwrap_or(line), lines),
+                                            Err(err) => Err(invalid_utf8(raw_line, lines, err))
+                                        };
+                                        match parsed {
+                                            Ok(Some(quad)) => {
+                                                let quad = if blank_nodes.is_preserving() { quad } else { quad.scope_blank_nodes(blank_nodes) };
+                                                let quad = if normalize_iris { quad.normalize_iris() } else { quad };
+                                                batch.push(match skolemization {
+                                                    Some(skolemization) => skolemization.apply(quad),
+                                                    None => quad
+                                                });
+                                                if batch.len() >= BATCH_SIZE {
+                                                    //receiver only hangs up if loading was aborted
+                                                    let _ = sender.send(batch);
+                                                    batch = Vec::new();
+                                                }
+                                            }
+                                            Ok(None) => {}
+                                            Err(err) => {
+                                                if mode == ParseMode::Strict {
+                                                    abort.store(true, Ordering::SeqCst);
+                                                }
+                                                errors.push(err.offset_by(line_offset));
+                                            }
+                                        }
+                                    }
+                                    line_offset += raw_line.len() + 1;
+                                }
+                                if !batch.is_empty() {
+                                    let _ = sender.send(batch);
+                                }
+                                //the chunk ends with a line break, which doesn't start another line
+                                if content.ends_with(b"\n") {
+                                    lines -= 1;
+                                }
+                                Ok(ChunkResult{ lines, errors })
+                            }
+                            Err(err) => {
+                                abort.store(true, Ordering::SeqCst);
+                                Err(ParserError::new(ErrorKind::Io, format!("Cannot read file {}", file)).with_source(err))
+                            }
+                        };
+                        results.lock().unwrap()[index] = Some(result);
+                    }
+                });
+            }
+            //only the workers should hold a sender, so the receiver finishes after all of them are done
+            drop(sender);
+            for batch in receiver {
+                statements += batch.len();
+                if mode != ParseMode::ReportOnly {
+                    for quad in batch {
+                        database.add_quad(quad);
+                    }
+                }
+            }
+        });
+
+        let mut lines = 0;
+        let mut errors = Vec::new();
+        for (index, result) in results.into_inner().unwrap().into_iter().enumerate() {
+            let result = match result {
+                Some(result) => result?,
+                None => {
+                    //chunk was skipped due to an abort, count its lines to locate errors in later chunks
+                    match BulkLoader::read_chunk(file, &chunks[index]) {
+                        Ok(content) => ChunkResult{ lines: content.iter().filter(|byte| **byte == b'\n').count(), errors: Vec::new() },
+                        Err(err) => return Err(ParserError::new(ErrorKind::Io, format!("Cannot read file {}", file)).with_source(err))
+                    }
+                }
+            };
+            for err in result.errors {
+                let err = err.offset_lines_by(lines);
+                if mode == ParseMode::Strict {
+                    return Err(err)
+                }
+                errors.push(err);
+            }
+            lines += result.lines;
+        }
+        let statistics = LoadStatistics{
+            bytes,
+            lines,
+            statements,
+            errors: errors.len(),
+            threads: self.threads,
+            duration: start_time.elapsed()
+        };
+        Ok((ParseReport::new(statements, errors), statistics))
+    }
+
+    ///
+    /// Splits the file into chunks of about `chunk_size` bytes, each ending directly after a line break or at the end of the file.
+    ///
+    fn split(file: &str, chunk_size: usize) -> Result<Vec<Chunk>, std::io::Error>{
+        let mut reader = File::open(file)?;
+        let len = reader.metadata()?.len() as usize;
+        let mut chunks = Vec::new();
+        let mut start = 0;
+        let mut buffer = [0u8; 4096];
+        while start < len {
+            let mut end = (start + chunk_size).min(len);
+            if end < len {
+                //move end behind the next line break
+                reader.seek(SeekFrom::Start(end as u64))?;
+                'search: loop {
+                    let read = reader.read(&mut buffer)?;
+                    if read == 0 {
+                        end = len;
+                        break;
+                    }
+                    for byte in &buffer[..read] {
+                        end += 1;
+                        if *byte == b'\n' {
+                            break 'search;
+                        }
+                    }
+                }
+            }
+            chunks.push(Chunk{ start, end });
+            start = end;
+        }
+        Ok(chunks)
+    }
+
+    fn read_chunk(file: &str, chunk: &Chunk) -> Result<Vec<u8>, std::io::Error>{
+        let mut reader = File::open(file)?;
+        reader.seek(SeekFrom::Start(chunk.start as u64))?;
+        let mut content = vec![0u8; chunk.end - chunk.start];
+        reader.read_exact(&mut content)?;
+        Ok(content)
+    }
+}
@@ -1,20 +1,17 @@
-use crate::rdf::node_factory::{RDFNode, BlankNode, IRIResource, Literal};
+use crate::rdf::node_factory::{RDFNode, BlankNode, BlankNodeScope, IRIResource, Literal};
 use crate::rdf::graph::Node::{BNode, IRINode, LiteralNode};
 use crate::util::iri::IRI;
-use std::error::Error;
 use std::collections::HashMap;
-use std::fmt;
 use std::ops::Add;
-use crate::rdf::graph::{Node, Graph, Statement, ResourceNode};
+use crate::rdf::graph::{Node, Statement, ResourceNode, Quad};
 
-use std::fs;
-use std::path::Path;
-use std::io::{Read, Seek, SeekFrom};
+use std::io::{BufRead, BufReader};
 use std::fs::File;
-use std::borrow::Cow;
-use std::string::FromUtf8Error;
-use crate::io::buffered_reader::BufferedReader;
 use crate::rdf::database::Database;
+use crate::rdf::skolem::Skolemization;
+use crate::error::{RDFError, ErrorKind};
+use crate::io::turtle::TurtleReader;
+use std::collections::VecDeque;
 
 
 pub enum Lang{
@@ -53,9 +50,223 @@ impl Lang{
     }
 }
 
+///
+/// Defines how a `Reader` reacts on lines which cannot be parsed.
+///
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum ParseMode{
+    /// Aborts reading on the first error and returns it
+    Strict,
+    /// Skips lines which cannot be parsed and collects their errors
+    Lenient,
+    /// Parses the whole file and collects all errors, but doesn't add any statement to the database
+    ReportOnly
+}
+
+///
+/// Options for `Reader::read_to_rdf_with_options`
+///
+/// # Example
+///
+/// ```
+/// use rdf4rust::io::reader::{ParseOptions, ParseMode};
+///
+/// let options = ParseOptions::lenient();
+/// assert_eq!(ParseMode::Lenient, options.get_mode());
+///
+/// // default is strict
+/// assert_eq!(ParseMode::Strict, ParseOptions::default().get_mode());
+///
+/// // IRIs are kept as they are unless normalization is enabled
+/// assert!(!options.get_normalize_iris());
+/// assert!(ParseOptions::lenient().with_normalized_iris(true).get_normalize_iris());
+///
+/// // blank node labels are scoped to the file unless preserved
+/// assert!(!options.get_preserve_blank_node_labels());
+///
+/// // neither skolemized nor unskolemized unless enabled
+/// assert!(options.get_skolemization().is_none());
+/// ```
+///
+#[derive(Clone, Debug)]
+pub struct ParseOptions{
+    mode: ParseMode,
+    normalize_iris: bool,
+    preserve_blank_node_labels: bool,
+    skolemization: Option<Skolemization>,
+    base: Option<IRI>
+}
+
+impl ParseOptions{
+
+    pub fn new(mode: ParseMode) -> Self{
+        ParseOptions{
+            mode,
+            normalize_iris: false,
+            preserve_blank_node_labels: false,
+            skolemization: None,
+            base: None
+        }
+    }
+
+    ///
+    /// Keeps the blank node labels of the file as IDs, disabled by default.
+    ///
+    /// By default every read gets its own `BlankNodeScope`, so `_:b0` of two files are different nodes.
+    /// Preserving labels is meant for round-tripping a single file, reading several files would merge their blank nodes.
+    ///
+    pub fn with_preserved_blank_node_labels(mut self, preserve_blank_node_labels: bool) -> Self{
+        self.preserve_blank_node_labels = preserve_blank_node_labels;
+        self
+    }
+
+    pub fn get_preserve_blank_node_labels(&self) -> bool{
+        self.preserve_blank_node_labels
+    }
+
+    ///
+    /// Skolemizes the blank nodes resp. unskolemizes the skolem IRIs of the input while reading, disabled by default
+    ///
+    pub fn with_skolemization(mut self, skolemization: Skolemization) -> Self{
+        self.skolemization = Some(skolemization);
+        self
+    }
+
+    pub fn get_skolemization(&self) -> Option<&Skolemization>{
+        self.skolemization.as_ref()
+    }
+
+    ///
+    /// Enables the normalization of all parsed IRIs using `IRI::normalize`, disabled by default
+    ///
+    pub fn with_normalized_iris(mut self, normalize_iris: bool) -> Self{
+        self.normalize_iris = normalize_iris;
+        self
+    }
+
+    pub fn get_normalize_iris(&self) -> bool{
+        self.normalize_iris
+    }
+
+    ///
+    /// Sets the IRI relative IRIs of Turtle and TriG files are resolved against, e.g. the location of the file
+    ///
+    pub fn with_base_iri(mut self, base: IRI) -> Self{
+        self.base = Some(base);
+        self
+    }
+
+    pub fn get_base_iri(&self) -> Option<&IRI>{
+        self.base.as_ref()
+    }
+
+    pub fn strict() -> Self{
+        ParseOptions::new(ParseMode::Strict)
+    }
+
+    pub fn lenient() -> Self{
+        ParseOptions::new(ParseMode::Lenient)
+    }
+
+    pub fn report_only() -> Self{
+        ParseOptions::new(ParseMode::ReportOnly)
+    }
+
+    pub fn get_mode(&self) -> ParseMode{
+        self.mode
+    }
+}
+
+impl Default for ParseOptions{
+    fn default() -> Self {
+        ParseOptions::strict()
+    }
+}
+
+///
+/// The result of reading a file with `Reader::read_to_rdf_with_options`
+///
+/// * `statements` - number of statements which were parsed successfully
+/// * `errors` - all errors which occurred, in order of their lines
+///
+pub struct ParseReport{
+    statements: usize,
+    errors: Vec<ParserError>
+}
+
+impl ParseReport{
+
+    pub(crate) fn new(statements: usize, errors: Vec<ParserError>) -> Self{
+        ParseReport{
+            statements,
+            errors
+        }
+    }
+
+    pub fn get_statement_count(&self) -> usize{
+        self.statements
+    }
+
+    pub fn get_errors(&self) -> &Vec<ParserError>{
+        &self.errors
+    }
+
+    pub fn has_errors(&self) -> bool{
+        !self.errors.is_empty()
+    }
+}
+
 pub trait Parser {
 
-    fn read_from_line(&mut self, line: &str, line_no: usize, database: &mut Database) -> Result<bool, ParserError>;
+    ///
+    /// Parses a single line.
+    ///
+    /// # Returns
+    ///
+    /// The statement as `Quad`, or `None` if the line doesn't contain a statement (e.g. it is a comment)
+    ///
+    fn parse_line(&mut self, line: &str, line_no: usize) -> Result<Option<Quad>, ParserError>;
+
+    ///
+    /// Parses a line of a format whose statements may span several lines and contain several triples (e.g. Turtle).
+    ///
+    /// Lines which don't complete a statement are kept, so the result may contain the statements of previous lines.
+    /// The byte offsets of the errors are relative to the input, `line_offset` is the offset of `line`.
+    ///
+    /// The default implementation parses the line with `parse_line`.
+    ///
+    fn parse_statements(&mut self, line: &str, line_no: usize, line_offset: usize) -> Vec<Result<Quad, ParserError>>{
+        match self.parse_line(line, line_no) {
+            Ok(quad) => quad.into_iter().map(Ok).collect(),
+            Err(err) => vec![Err(err.offset_by(line_offset))]
+        }
+    }
+
+    ///
+    /// Signals the end of the input
+    ///
+    /// # Returns
+    ///
+    /// The statements of the kept lines, or an error if the input ended within a statement
+    ///
+    fn finish(&mut self) -> Vec<Result<Quad, ParserError>>{
+        Vec::new()
+    }
+
+    ///
+    /// Sets the IRI relative IRIs are resolved against, ignored by formats which don't allow relative IRIs
+    ///
+    fn set_base_iri(&mut self, _base: IRI){}
+
+    fn read_from_line(&mut self, line: &str, line_no: usize, database: &mut Database) -> Result<bool, ParserError>{
+        match self.parse_line(line, line_no)? {
+            Some(quad) => {
+                database.add_quad(quad);
+                Ok(true)
+            }
+            None => Ok(false)
+        }
+    }
 
 }
 
@@ -67,46 +278,342 @@ impl Reader {
 
     pub fn read_to_rdf_guess_lang(database: &mut Database, file: &str) -> Result<usize, ParserError>{
         let lang = match Lang::guess_lang(file){
-            None => {return Err(ParserError::new(format!("Cannot guess language for file {}", file)))}
+            None => {return Err(ParserError::new(ErrorKind::UnsupportedFormat, format!("Cannot guess language for file {}", file)))}
             Some(val) => {val}
         };
         Reader::read_to_rdf(database, file, lang)
     }
 
-    fn get_parser(lang: &Lang) -> Box<dyn Parser>{
+    ///
+    /// Creates the parser of `lang`, fails with `ErrorKind::UnsupportedFormat` for RDF/XML and TriX
+    ///
+    pub(crate) fn get_parser(lang: &Lang) -> Result<Box<dyn Parser>, RDFError>{
         match lang{
-            Lang::TTL | Lang::TURTLE => {return Box::new(TurtleReader::new())}
-            Lang::NTRIPLE | Lang::NT => {return Box::new(NTripleReader{ tokenizer: SimpleTokenizer {} })}
-            Lang::NQ | Lang::NQUADS => {return Box::new(NQuadsReader{ tokenizer: SimpleTokenizer {} })}
-            Lang::RdfXml => {}
-            Lang::TRIG => {}
-            Lang::TRIX => {}
+            Lang::TTL | Lang::TURTLE => Ok(Box::new(TurtleReader::new(false))),
+            Lang::NTRIPLE | Lang::NT => Ok(Box::new(NTripleReader{ tokenizer: SimpleTokenizer {} })),
+            Lang::NQ | Lang::NQUADS => Ok(Box::new(NQuadsReader{ tokenizer: SimpleTokenizer {} })),
+            Lang::TRIG => Ok(Box::new(TurtleReader::new(true))),
+            Lang::RdfXml | Lang::TRIX => Err(RDFError::new(ErrorKind::UnsupportedFormat, String::from("Reading is only supported for N-Triples, N-Quads, Turtle and TriG.")))
         }
-        return Box::new( NTripleReader{ tokenizer: SimpleTokenizer {} })
     }
 
+    ///
+    /// Reads `file` into `database` and aborts on the first error.
+    ///
+    /// # Returns
+    ///
+    /// The number of statements in `database` after reading
+    ///
     pub fn read_to_rdf(database: &mut Database, file: &str, lang: Lang) -> Result<usize, ParserError>{
-        //nt and nq won't need mut, but ttl will
-        let mut reader = Reader::get_parser(&lang);
+        Reader::read_to_rdf_with_options(database, file, lang, &ParseOptions::strict())?;
+        Ok(database.count())
+    }
 
-        let mut buffered_reader = BufferedReader::new(file).expect("");
-        let mut line_count = 1;
-        while let Some(line) = buffered_reader.read_line(){
-            if !line.is_empty() {
-                reader.read_from_line(line.as_str(), line_count, database);
+    ///
+    /// Reads `file` into `database`, handling lines which cannot be parsed as set in `options`.
+    ///
+    /// # Returns
+    ///
+    /// A `ParseReport` containing the number of parsed statements and all collected errors.
+    ///
+    /// Fails if the file cannot be opened, or with the first error if `options` is strict.
+    ///
+    pub fn read_to_rdf_with_options(database: &mut Database, file: &str, lang: Lang, options: &ParseOptions) -> Result<ParseReport, ParserError>{
+        let mut report = ParseReport::new(0, Vec::new());
+        let mut quads = Reader::read_quads(file, lang)?
+            .with_normalized_iris(options.normalize_iris)
+            .with_preserved_blank_node_labels(options.preserve_blank_node_labels);
+        if let Some(skolemization) = &options.skolemization {
+            quads = quads.with_skolemization(skolemization.clone());
+        }
+        if let Some(base) = &options.base {
+            quads = quads.with_base_iri(base.clone());
+        }
+        for quad in quads {
+            match quad {
+                Ok(quad) => {
+                    if options.mode != ParseMode::ReportOnly {
+                        database.add_quad(quad);
+                    }
+                    report.statements += 1;
+                }
+                Err(err) => {
+                    if options.mode == ParseMode::Strict || err.get_kind() == ErrorKind::Io {
+                        return Err(err)
+                    }
+                    report.errors.push(err);
+                }
+            }
+        }
+        Ok(report)
+    }
 
+    ///
+    /// Opens `file` for streaming its statements one by one, without loading them into a `Database`.
+    ///
+    /// See `QuadIterator` for details.
+    ///
+    /// Fails if the format isn't supported or the file cannot be opened.
+    ///
+    pub fn read_quads(file: &str, lang: Lang) -> Result<QuadIterator<BufReader<File>>, ParserError>{
+        let parser = Reader::get_parser(&lang)?;
+        match File::open(file){
+            Ok(val) => Ok(QuadIterator::from_parser(BufReader::new(val), Ok(parser))),
+            Err(err) => Err(ParserError::new(ErrorKind::Io, format!("Cannot read file {}", file)).with_source(err))
+        }
+    }
 
-            }
-            line_count += 1;
+}
+
+///
+/// Pull-based parser returning the statements of the input one by one as `Quad`.
+///
+/// Only the current line is kept in memory, so arbitrary large inputs can be filtered,
+/// transformed or forwarded in constant memory.
+/// Turtle and TriG keep the lines of the current statement, whose triples are returned once it is complete.
+///
+/// Lines which cannot be parsed or are not valid UTF-8 result in an `Err` containing the position in the input,
+/// after which iteration continues.
+/// If the input cannot be read, an error of kind `ErrorKind::Io` is returned and the iteration ends.
+///
+/// Blank node labels are scoped to the iterator (see `BlankNodeScope`), so two iterators never return the same blank node,
+/// unless labels are preserved with `with_preserved_blank_node_labels`.
+///
+/// N-Triples and N-Quads may contain quoted triples of RDF-star (`<< s p o >>`) as subject or object, nested as well.
+/// Turtle and TriG may contain them too, as well as annotations (`s p o {| p2 o2 |}`), which assert the triple
+/// and add the triples of the annotation with the quoted triple as subject.
+///
+/// # Example
+///
+/// ```
+/// use rdf4rust::io::reader::{QuadIterator, Lang};
+/// use rdf4rust::rdf::node_factory::RDFNode;
+///
+/// let input = "<http://example.com/s> <http://example.com/p> \"o\" .\n\
+///     ## comment\n\
+///     <http://example.com/s> <http://example.com/q> <http://example.com/o> <http://example.com/g> .\n\
+///     \"invalid\" <http://example.com/p> <http://example.com/o> .\n";
+///
+/// let mut quads = QuadIterator::new(input.as_bytes(), &Lang::NQ);
+///
+/// let quad = quads.next().expect("Has a first line").expect("Is valid");
+/// assert_eq!("http://example.com/p", quad.get_predicate().get_value());
+/// assert!(quad.get_graph().is_none());
+///
+/// let quad = quads.next().expect("Has a third line").expect("Is valid");
+/// assert_eq!("http://example.com/g", quad.get_graph().as_ref().expect("Is in named graph").as_string());
+///
+/// let err = quads.next().expect("Has a fourth line").err().expect("Is not valid");
+/// assert_eq!(Some(4), err.get_line());
+///
+/// assert!(quads.next().is_none());
+/// ```
+///
+/// Filtering a file, keeping only the statements of the default graph
+///
+/// ```no_run
+/// use rdf4rust::io::reader::{Reader, Lang};
+///
+/// for quad in Reader::read_quads("data.nq", Lang::NQ).expect("Cannot open file")
+///     .filter_map(|quad| quad.ok())
+///     .filter(|quad| quad.get_graph().is_none()) {
+///     println!("{}", quad);
+/// }
+/// ```
+///
+pub struct QuadIterator<R: BufRead>{
+    reader: R,
+    /// None if the format isn't supported, the iterator only returns that error then
+    parser: Option<Box<dyn Parser>>,
+    buffer: Vec<u8>,
+    line_no: usize,
+    line_offset: usize,
+    finished: bool,
+    normalize_iris: bool,
+    blank_nodes: BlankNodeScope,
+    skolemization: Option<Skolemization>,
+    /// Parsed statements and errors which weren't returned yet, as a line of Turtle may contain several statements
+    pending: VecDeque<Result<Quad, ParserError>>
+}
+
+impl<R: BufRead> QuadIterator<R>{
+
+    ///
+    /// Creates an iterator over the statements of `reader` in the format `lang`
+    ///
+    /// If `lang` isn't supported (RDF/XML and TriX), the only item is an error of kind `ErrorKind::UnsupportedFormat`.
+    ///
+    pub fn new(reader: R, lang: &Lang) -> Self{
+        QuadIterator::from_parser(reader, Reader::get_parser(lang))
+    }
+
+    fn from_parser(reader: R, parser: Result<Box<dyn Parser>, RDFError>) -> Self{
+        let (parser, pending) = match parser {
+            Ok(parser) => (Some(parser), VecDeque::new()),
+            Err(err) => (None, VecDeque::from(vec![Err(err)]))
+        };
+        QuadIterator{
+            reader,
+            finished: parser.is_none(),
+            parser,
+            buffer: Vec::new(),
+            line_no: 0,
+            line_offset: 0,
+            normalize_iris: false,
+            blank_nodes: BlankNodeScope::new(),
+            skolemization: None,
+            pending
         }
-        Ok(database.count())
     }
 
+    ///
+    /// Enables the normalization of all parsed IRIs using `IRI::normalize`, disabled by default
+    ///
+    pub fn with_normalized_iris(mut self, normalize_iris: bool) -> Self{
+        self.normalize_iris = normalize_iris;
+        self
+    }
+
+    ///
+    /// Keeps the blank node labels of the input as IDs instead of scoping them to this iterator, disabled by default
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use rdf4rust::io::reader::{QuadIterator, Lang};
+    /// use rdf4rust::rdf::node_factory::RDFNode;
+    ///
+    /// let input = "_:b0 <http://example.com/p> _:b0 .\n";
+    /// let first = QuadIterator::new(input.as_bytes(), &Lang::NT).next().unwrap().unwrap();
+    /// let second = QuadIterator::new(input.as_bytes(), &Lang::NT).next().unwrap().unwrap();
+    /// assert_eq!(first.get_subject().as_string(), first.get_object().as_string());
+    /// assert_ne!(first.get_subject().as_string(), second.get_subject().as_string());
+    ///
+    /// let preserved = QuadIterator::new(input.as_bytes(), &Lang::NT).with_preserved_blank_node_labels(true).next().unwrap().unwrap();
+    /// assert_eq!("_:b0", preserved.get_subject().as_string());
+    /// ```
+    ///
+    pub fn with_preserved_blank_node_labels(mut self, preserve_blank_node_labels: bool) -> Self{
+        self.blank_nodes = if preserve_blank_node_labels {BlankNodeScope::preserving()} else {BlankNodeScope::new()};
+        self
+    }
+
+    ///
+    /// Uses `scope` for the blank nodes of the input, e.g. to continue a document split into several inputs
+    ///
+    pub fn with_blank_node_scope(mut self, scope: BlankNodeScope) -> Self{
+        self.blank_nodes = scope;
+        self
+    }
+
+    ///
+    /// Sets the IRI relative IRIs of Turtle and TriG are resolved against until the input declares a base itself
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use rdf4rust::io::reader::{QuadIterator, Lang};
+    /// use rdf4rust::util::iri::IRI;
+    ///
+    /// let base = IRI::create_iri(&String::from("http://example.com/data/")).unwrap();
+    /// let quad = QuadIterator::new("<s> <#p> <../o> .".as_bytes(), &Lang::TTL).with_base_iri(base).next().unwrap().unwrap();
+    /// assert_eq!("<http://example.com/data/s> <http://example.com/data/#p> <http://example.com/o> .", quad.to_string());
+    /// ```
+    ///
+    pub fn with_base_iri(mut self, base: IRI) -> Self{
+        if let Some(parser) = self.parser.as_mut() {
+            parser.set_base_iri(base);
+        }
+        self
+    }
+
+    ///
+    /// Skolemizes the blank nodes resp. unskolemizes the skolem IRIs of the input, disabled by default
+    ///
+    /// Skolemization is applied after the blank nodes were scoped and the IRIs were normalized.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use rdf4rust::io::reader::{QuadIterator, Lang};
+    /// use rdf4rust::rdf::skolem::{Skolemizer, Skolemization};
+    ///
+    /// let skolemizer = Skolemizer::new("https://example.com").unwrap();
+    /// let input = "<https://example.com/.well-known/genid/b1> <http://example.com/p> _:b0 .\n";
+    ///
+    /// let quad = QuadIterator::new(input.as_bytes(), &Lang::NT).with_preserved_blank_node_labels(true)
+    ///     .with_skolemization(Skolemization::Skolemize(skolemizer.clone())).next().unwrap().unwrap();
+    /// assert_eq!("<https://example.com/.well-known/genid/b1> <http://example.com/p> <https://example.com/.well-known/genid/b0> .", quad.to_string());
+    ///
+    /// let quad = QuadIterator::new(input.as_bytes(), &Lang::NT).with_preserved_blank_node_labels(true)
+    ///     .with_skolemization(Skolemization::Unskolemize(skolemizer)).next().unwrap().unwrap();
+    /// assert_eq!("_:b1 <http://example.com/p> _:b0 .", quad.to_string());
+    /// ```
+    ///
+    pub fn with_skolemization(mut self, skolemization: Skolemization) -> Self{
+        self.skolemization = Some(skolemization);
+        self
+    }
+}
+
+impl<R: BufRead> QuadIterator<R>{
+
+    /// Scopes the blank nodes, normalizes the IRIs and skolemizes a parsed quad as configured
+    fn prepare(&self, quad: Quad) -> Quad{
+        let quad = if self.blank_nodes.is_preserving() {quad} else {quad.scope_blank_nodes(&self.blank_nodes)};
+        let quad = if self.normalize_iris {quad.normalize_iris()} else {quad};
+        match &self.skolemization {
+            Some(skolemization) => skolemization.apply(quad),
+            None => quad
+        }
+    }
+}
+
+impl<R: BufRead> Iterator for QuadIterator<R>{
+    type Item = Result<Quad, ParserError>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        loop {
+            if let Some(parsed) = self.pending.pop_front() {
+                return Some(parsed.map(|quad| self.prepare(quad)))
+            }
+            let parser = match self.parser.as_mut() {
+                Some(parser) if !self.finished => parser,
+                _ => return None
+            };
+            self.buffer.clear();
+            let read = match self.reader.read_until(b'\n', &mut self.buffer) {
+                Ok(read) => read,
+                Err(err) => {
+                    self.finished = true;
+                    return Some(Err(ParserError::from(err)))
+                }
+            };
+            if read == 0 {
+                self.finished = true;
+                self.pending.extend(parser.finish());
+                continue;
+            }
+            self.line_no += 1;
+            let line_offset = self.line_offset;
+            self.line_offset += read;
+            let line = match std::str::from_utf8(&self.buffer) {
+                Ok(line) => line,
+                Err(err) => return Some(Err(invalid_utf8(&self.buffer, self.line_no, err).offset_by(line_offset)))
+            };
+            let line = line.trim_end_matches(['\n', '\r']);
+            self.pending.extend(parser.parse_statements(line, self.line_no, line_offset));
+        }
+    }
 }
 
-struct TurtleReader{
-    //next token has to be one of -> next token -> next token has to be one of
-    next_token_type: Vec<String>
+///
+/// Creates the error for a line which is not valid UTF-8, located at the first invalid byte.
+///
+pub(crate) fn invalid_utf8(line: &[u8], line_no: usize, err: std::str::Utf8Error) -> ParserError{
+    let text = String::from_utf8_lossy(line);
+    let text = text.trim_end_matches(['\n', '\r']);
+    ParserError::create(ErrorKind::Syntax, String::from("Invalid UTF-8 sequence."), line_no, text, err.valid_up_to()).with_source(err)
 }
 
 struct NTripleReader{
@@ -119,10 +626,7 @@ struct NQuadsReader{
 
 impl NQuadsReader{
     fn check_end(&mut self, line: &str,) -> bool{
-        return match self.tokenizer.peek_next_token(&line) {
-            Token::DOT { pos } => true,
-            _ => false
-        }
+        matches!(self.tokenizer.peek_next_token(line), Token::DOT { .. })
     }
 }
 
@@ -144,11 +648,27 @@ enum Token{
     PREFIX{pos: usize, prefix: String, suffix: String},
     GRAPH_OPEN{pos: usize},
     GRAPH_CLOSE{pos: usize},
+    QuotedTripleOpen{pos: usize},
+    QuotedTripleClose{pos: usize},
     ///
     /// Marks tokens which can be ignored, as they are optional
     ///
     OPTIONAL{pos: usize},
-    ERROR
+    ///
+    /// No valid token could be read, `err` is the cause or `None` if the line ended
+    ///
+    Error{pos: usize, err: Option<ObjectParserError>}
+}
+
+///
+/// Creates the error for a token which isn't allowed at `current_pos`, keeping the cause of `Token::Error`.
+///
+fn unexpected_token(token: Token, line: &str, line_no: usize, current_pos: usize) -> ParserError{
+    match token {
+        Token::Error { pos, err: Some(err) } => err.with_position(line_no, line, current_pos + pos),
+        Token::Error { pos, err: None } => ParserError::create(ErrorKind::Syntax, String::from("Unexpected end of line."), line_no, line, current_pos + pos),
+        _ => ParserError::create(ErrorKind::Syntax, String::from("Unexpected token."), line_no, line, current_pos)
+    }
 }
 
 trait Tokenizer {
@@ -157,23 +677,27 @@ trait Tokenizer {
 
     fn subject(&self, line: &str, line_no: usize, mut current_pos: usize) -> Result<Option<(ResourceNode, usize)>, ParserError> {
         let subject = match self.peek_next_token(&line[current_pos..]) {
-            Token::IRI { node, pos, len } => {
-                current_pos =  len;
+            Token::IRI { node, len, .. } => {
+                current_pos +=  len;
                 node
             }
-            Token::BNode { node, pos, len } => {
-                current_pos =  len;
+            Token::BNode { node, len, .. } => {
+                current_pos +=  len;
                 node
             }
-            Token::Literal { node, pos, len } => { return Err(ParserError::new(format!("Line [#line: {}, pos: {}] Literal is not allowed as subject.", line_no, current_pos + pos))) }
-            Token::DOT { pos } => { return Err(ParserError::new(format!("Line [#line: {}, pos: {}] starts with dot, not allowed in NQuads syntax.", line_no, current_pos + pos))) }
-            Token::COMMENT { pos } => { return Ok(None) }
-            _ => { return Err(ParserError::new(format!("Line [#line: {}] unexpected error.", line_no))) }
+            Token::QuotedTripleOpen { pos } => {
+                let (triple, current_pos) = self.quoted_triple(line, line_no, current_pos + pos + 2)?;
+                return Ok(Some((ResourceNode::TripleNode { triple: Box::new(triple) }, current_pos)))
+            }
+            Token::Literal { pos, .. } => { return Err(ParserError::create(ErrorKind::Syntax, String::from("Literal is not allowed as subject."), line_no, line, current_pos + pos)) }
+            Token::DOT { pos } => { return Err(ParserError::create(ErrorKind::Syntax, String::from("Line starts with dot, not allowed in NQuads syntax."), line_no, line, current_pos + pos)) }
+            Token::COMMENT { .. } => { return Ok(None) }
+            token => { return Err(unexpected_token(token, line, line_no, current_pos)) }
         };
         let subject = match subject{
             Node::IRINode { iri } => {ResourceNode::IRINode {iri}}
             Node::BNode { bnode } => {ResourceNode::BNode {bnode}}
-            _ => {return Err(ParserError::new(format!("WTF")));}
+            _ => {return Err(ParserError::create(ErrorKind::Syntax, String::from("Subject has to be an IRI or a blank node."), line_no, line, current_pos));}
 
         };
         Ok(Some((subject, current_pos)))
@@ -181,15 +705,15 @@ trait Tokenizer {
 
     fn predicate(&self, line: &str, line_no: usize, mut current_pos: usize) -> Result<(IRIResource, usize), ParserError> {
         let predicate = match self.peek_next_token(&line[current_pos..]) {
-            Token::IRI { node, pos, len } => {
+            Token::IRI { node, len, .. } => {
                 current_pos += len;
                 node
             }
-            Token::BNode { node, pos, len } => { return Err(ParserError::new(format!("Line [#line: {}, pos: {}] BNode not allowed as predicate. Only IRI.", line_no, current_pos + pos))) }
-            Token::Literal { node, pos, len } => { return Err(ParserError::new(format!("Line [#line: {}, pos: {}] Literal is not allowed as predicate. Only IRI.", line_no, current_pos + pos))) }
-            Token::DOT { pos } => { return Err(ParserError::new(format!("Line [#line: {}, pos: {}] Found dot, but not allowed here.", line_no, current_pos + pos))) }
-            Token::COMMENT { pos } => { return Err(ParserError::new(format!("Line [#line: {}, pos: {}] Comment starts within quad, which is not allowed in NQuads Syntax.", line_no, current_pos + pos))) }
-            _ => { return Err(ParserError::new(format!("Line [#line: {}] unexpected error.", line_no))) }
+            Token::BNode { pos, .. } => { return Err(ParserError::create(ErrorKind::Syntax, String::from("BNode not allowed as predicate. Only IRI."), line_no, line, current_pos + pos)) }
+            Token::Literal { pos, .. } => { return Err(ParserError::create(ErrorKind::Syntax, String::from("Literal is not allowed as predicate. Only IRI."), line_no, line, current_pos + pos)) }
+            Token::DOT { pos } => { return Err(ParserError::create(ErrorKind::Syntax, String::from("Found dot, but not allowed here."), line_no, line, current_pos + pos)) }
+            Token::COMMENT { pos } => { return Err(ParserError::create(ErrorKind::Syntax, String::from("Comment starts within quad, which is not allowed in NQuads Syntax."), line_no, line, current_pos + pos)) }
+            token => { return Err(unexpected_token(token, line, line_no, current_pos)) }
         };
         let predicate = IRIResource::create_resource(IRI::create_iri(&predicate.as_uri_resource().expect("").as_string(false)).expect(""));
         Ok((predicate, current_pos))
@@ -197,38 +721,63 @@ trait Tokenizer {
 
     fn object(&self, line: &str, line_no: usize, mut current_pos: usize) -> Result<(Node, usize), ParserError> {
         let object = match self.peek_next_token(&line[current_pos..]) {
-            Token::IRI { node, pos, len } => {
+            Token::IRI { node, len, .. } => {
                 current_pos +=  len;
                 node
             }
-            Token::BNode { node, pos, len } => {
+            Token::BNode { node, len, .. } => {
                 current_pos +=  len;
                 node
             }
-            Token::Literal { node, pos, len } => {
+            Token::Literal { node, len, .. } => {
                 current_pos +=  len;
                 node
             }
-            Token::DOT { pos } => { return Err(ParserError::new(format!("Line [#line: {}, pos: {}] Found dot, but not allowed here.", line_no, current_pos + pos))) }
-            Token::COMMENT { pos } => { return Err(ParserError::new(format!("Line [#line: {}, pos: {}] Comment starts within quad, which is not allowed in NQuads Syntax.", line_no, current_pos + pos))) }
-            _ => { return Err(ParserError::new(format!("Line [#line: {}] unexpected error.", line_no))) }
+            Token::QuotedTripleOpen { pos } => {
+                let (triple, current_pos) = self.quoted_triple(line, line_no, current_pos + pos + 2)?;
+                return Ok((Node::TripleNode { triple: Box::new(triple) }, current_pos))
+            }
+            Token::DOT { pos } => { return Err(ParserError::create(ErrorKind::Syntax, String::from("Found dot, but not allowed here."), line_no, line, current_pos + pos)) }
+            Token::COMMENT { pos } => { return Err(ParserError::create(ErrorKind::Syntax, String::from("Comment starts within quad, which is not allowed in NQuads Syntax."), line_no, line, current_pos + pos)) }
+            token => { return Err(unexpected_token(token, line, line_no, current_pos)) }
         };
-        return Ok((object,current_pos))
+        Ok((object,current_pos))
     }
 
-    fn graph(&self, line: &str, line_no: usize, mut current_pos: usize) -> Result<(IRIResource, usize), ParserError> {
+    fn graph(&self, line: &str, line_no: usize, mut current_pos: usize) -> Result<(ResourceNode, usize), ParserError> {
         let graph_node =  match self.peek_next_token(&line[current_pos..]){
-            Token::IRI{node, pos, len} => {current_pos += len; node}
-            Token::BNode{node, pos, len} => {return Err(ParserError::new(format!("Line [#line: {}, pos: {}] Graph cannot be blank node.", line_no, current_pos+pos)))}
-            Token::Literal{node, pos, len} => {return Err(ParserError::new(format!("Line [#line: {}, pos: {}] Graph cannot be literal.", line_no, current_pos+pos)))}
-            Token::DOT{pos} => {return Err(ParserError::new(format!("Line [#line: {}, pos: {}] Found dot, but not allowed here.", line_no, current_pos+pos)))}
-            Token::COMMENT{pos} => {return Err(ParserError::new(format!("Line [#line: {}, pos: {}] Comment starts within quad, which is not allowed in NQuads Syntax.", line_no, current_pos+pos)))}
-            _  => {return Err(ParserError::new(format!("Line [#line: {}] unexpected error.", line_no)))}
+            Token::IRI{node, len, ..} => {current_pos += len; node}
+            Token::BNode{node, len, ..} => {current_pos += len; node}
+            Token::Literal{pos, ..} => {return Err(ParserError::create(ErrorKind::Syntax, String::from("Graph cannot be literal."), line_no, line, current_pos + pos))}
+            Token::QuotedTripleOpen{pos} => {return Err(ParserError::create(ErrorKind::Syntax, String::from("Graph cannot be a quoted triple."), line_no, line, current_pos + pos))}
+            Token::DOT{pos} => {return Err(ParserError::create(ErrorKind::Syntax, String::from("Found dot, but not allowed here."), line_no, line, current_pos + pos))}
+            Token::COMMENT{pos} => {return Err(ParserError::create(ErrorKind::Syntax, String::from("Comment starts within quad, which is not allowed in NQuads Syntax."), line_no, line, current_pos + pos))}
+            token => {return Err(unexpected_token(token, line, line_no, current_pos))}
+        };
+        let graph_node = match graph_node{
+            Node::IRINode { iri } => {ResourceNode::IRINode {iri}}
+            Node::BNode { bnode } => {ResourceNode::BNode {bnode}}
+            _ => {return Err(ParserError::create(ErrorKind::Syntax, String::from("Graph has to be an IRI or a blank node."), line_no, line, current_pos));}
         };
-        let graph_node = IRIResource::create_resource(IRI::create_iri(&graph_node.as_uri_resource().expect("").as_string(false)).expect(""));
         Ok((graph_node, current_pos))
     }
 
+    ///
+    /// Parses the subject, predicate and object of a quoted triple and its closing `>>`, `current_pos` is right after `<<`
+    ///
+    fn quoted_triple(&self, line: &str, line_no: usize, current_pos: usize) -> Result<(Statement, usize), ParserError> {
+        let (subject, current_pos) = match self.subject(line, line_no, current_pos)? {
+            Some(subject) => subject,
+            None => return Err(ParserError::create(ErrorKind::Syntax, String::from("Comment starts within quoted triple."), line_no, line, current_pos))
+        };
+        let (predicate, current_pos) = self.predicate(line, line_no, current_pos)?;
+        let (object, current_pos) = self.object(line, line_no, current_pos)?;
+        match self.peek_next_token(&line[current_pos..]) {
+            Token::QuotedTripleClose { pos } => Ok((Statement::create(subject, predicate, object), current_pos + pos + 2)),
+            _ => Err(ParserError::create(ErrorKind::Syntax, String::from("Quoted triple doesn't end on >>."), line_no, line, current_pos))
+        }
+    }
+
 }
 
 struct XmlTokenizer{
@@ -242,66 +791,6 @@ impl Tokenizer for XmlTokenizer{
     }
 }
 
-impl TurtleReader{
-
-    fn new()->Self{
-        let mut allowed_tokens = Vec::new();
-        allowed_tokens.push(String::from("iri"));
-        allowed_tokens.push(String::from("bnode"));
-        allowed_tokens.push(String::from("comment"));
-        allowed_tokens.push(String::from("base"));
-        allowed_tokens.push(String::from("prefix"));
-        allowed_tokens.push(String::from("dot"));
-        allowed_tokens.push(String::from("bnode_open"));
-        TurtleReader{
-            next_token_type: allowed_tokens
-        }
-    }
-}
-
-struct TurtleTokenizer{
-    nested_bnode_count: usize,
-    simple_tokenizer: SimpleTokenizer,
-    prefix_mapping: HashMap<String, String>,
-    current_base: Option<String>
-}
-
-impl TurtleTokenizer{
-    fn new()-> Self{
-        TurtleTokenizer{
-            nested_bnode_count:0,
-            simple_tokenizer: SimpleTokenizer{},
-            prefix_mapping: HashMap::new(),
-            current_base: None
-        }
-    }
-}
-
-impl Tokenizer for TurtleTokenizer{
-    fn peek_next_token(&self, rest_line: &str) -> Token {
-        todo!()
-        //check ttl specific tokens if next token has to be simple use SimpleTokenizer
-    }
-}
-
-impl Parser for TurtleReader{
-
-
-    fn read_from_line(&mut self, line: &str, line_no: usize, database: &mut Database) -> Result<bool, ParserError> {
-        todo!()
-        //read next token
-        //is token allowed?
-        // no => error
-        // yes:
-        //   0. check bnode counter (incr if token is bnode open, decr if close)
-        //   0.1 if bnode counter ==0 after decr => next token #;,. if bnode was object . if was subject
-        //   1. is triple end? yes=> add triple
-        //   2. set new allowed tokens
-        //   start from beginning
-        //Comment just ignored allowed_tokens stay
-    }
-}
-
 struct SimpleTokenizer{}
 
 impl Tokenizer for SimpleTokenizer{
@@ -309,14 +798,20 @@ impl Tokenizer for SimpleTokenizer{
     fn peek_next_token(&self, rest_line: &str) -> Token{
 
         let pos= match rest_line.find(|c: char| !c.is_whitespace()){
-            None => {return Token::ERROR}
+            None => {return Token::Error{pos: rest_line.len(), err: None}}
             Some(position) => {position}
         };
 
         let line = &rest_line[pos..];
+        if line.starts_with("<<"){
+            return Token::QuotedTripleOpen {pos}
+        }
+        if line.starts_with(">>"){
+            return Token::QuotedTripleClose {pos}
+        }
         //end is either end of string or depends
         let end = if line.starts_with("_:"){
-            match line.find(|c: char| c.is_whitespace() || c=='<' || c=='\"' || c=='\'') {
+            match line.find(|c: char| c.is_whitespace() || c=='<' || c=='>' || c=='\"' || c=='\'') {
                 None => { rest_line.len() }
                 Some(position) => { position+pos }
             }
@@ -344,14 +839,23 @@ impl Tokenizer for SimpleTokenizer{
                 // check every encountered rof start_token until one is found which is not escaped
 
                 while let Some(position) = line[current_start..].find(start_token) {
-                    if !line[position - 1..].starts_with("\\") {
-                        ret = match line[current_start+position..].find(|c: char| c.is_whitespace()) {
-                            None => { rest_line.len() }
-                            Some(position2) => {current_start+ position2 + position + pos  }
-                        };
+                    let end = current_start + position;
+                    //the end token is escaped if an odd number of backslashes precedes it
+                    let backslashes = line[..end].bytes().rev().take_while(|&b| b == b'\\').count();
+                    if backslashes % 2 == 0 {
+                        let suffix = &line[end..];
+                        let mut suffix_end = suffix.find(|c: char| c.is_whitespace()).unwrap_or(suffix.len());
+                        //a quoted triple may be closed right after the literal, but not within the datatype IRI
+                        if let Some(close) = suffix[..suffix_end].find(">>") {
+                            suffix_end = match suffix[start_token.len()..].strip_prefix("^^<") {
+                                Some(_) => suffix.find('>').map_or(suffix_end, |position| position + 1),
+                                None => close
+                            };
+                        }
+                        ret = end + suffix_end + pos;
                         break;
                     }
-                    current_start += position + 1;
+                    current_start = end + 1;
                 }
             }else{
                 ret = match line.find(|c: char| c.is_whitespace()) {
@@ -370,13 +874,13 @@ impl Tokenizer for SimpleTokenizer{
         let node = match parse_resolved_object(&rest_line[pos..end]){
             Ok(node) => {node}
             Err(err) => {
-                println!("{}", err);
-                return Token::ERROR}
+                return Token::Error{pos, err: Some(err)}}
         };
         match node{
             Node::IRINode { .. } => { Token::IRI {node, pos, len: end }}
             Node::LiteralNode { .. } => {Token::Literal {node, pos, len: end }}
             Node::BNode { .. } => {Token::BNode {node, pos, len: end }}
+            Node::TripleNode { .. } => {Token::Error {pos, err: None}}
         }
     }
 }
@@ -384,9 +888,9 @@ impl Tokenizer for SimpleTokenizer{
 
 
 impl Parser for NQuadsReader{
-    fn read_from_line(&mut self, line: &str, line_no: usize, database: &mut Database) -> Result<bool, ParserError> {
-        if line.is_empty(){
-            return Ok(false)
+    fn parse_line(&mut self, line: &str, line_no: usize) -> Result<Option<Quad>, ParserError> {
+        if line.trim().is_empty(){
+            return Ok(None)
         }
         //peek_next_token() -> expect _: or < expect < -> literal -> expect .
         let mut current_pos =0;
@@ -394,34 +898,29 @@ impl Parser for NQuadsReader{
             Ok(Some(node)) => {
                 node
             },
-            Ok(None) => return Ok(false),
+            Ok(None) => return Ok(None),
             Err(err) => return Err(err)
         };
         if !line[current_pos..].starts_with(|c: char| c.is_whitespace()){
-            return Err(ParserError::new(format!("Line [no: {}, pos: {}]: Items have to be separated by whitespace char. ", line_no, current_pos)))
+            return Err(ParserError::create(ErrorKind::Syntax, String::from("Items have to be separated by whitespace char."), line_no, line, current_pos))
         }
         let (predicate, current_pos) = match self.tokenizer.predicate(&line, line_no, current_pos){
             Ok(node) => node,
             Err(err) => return Err(err)
         };
         if !line[current_pos..].starts_with(|c: char| c.is_whitespace()){
-            return Err(ParserError::new(format!("Line [no: {}, pos: {}]: Items have to be separated by whitespace char. ", line_no, current_pos)))
+            return Err(ParserError::create(ErrorKind::Syntax, String::from("Items have to be separated by whitespace char."), line_no, line, current_pos))
         }
         let (object, current_pos)  = match self.tokenizer.object(&line, line_no, current_pos){
             Ok(node) => node,
             Err(err) => return Err(err)
         };
         if !line[current_pos..].starts_with(|c: char| c.is_whitespace()){
-            return Err(ParserError::new(format!("Line [no: {}, pos: {}]: Items have to be separated by whitespace char. ", line_no, current_pos)))
+            return Err(ParserError::create(ErrorKind::Syntax, String::from("Items have to be separated by whitespace char."), line_no, line, current_pos))
         }
 
         if self.check_end(&line[current_pos..]){
-                database.add_statement(None, Statement::create(
-                    subject,
-                    predicate,
-                    object
-                ));
-                return Ok(true)
+                return Ok(Some(Quad::create(subject, predicate, object, None)))
         }
 
         let (graph_node, current_pos) = match self.tokenizer.graph(&line, line_no, current_pos){
@@ -431,14 +930,9 @@ impl Parser for NQuadsReader{
 
 
         return if self.check_end(&line[current_pos..]) {
-            database.add_statement(Some(graph_node), Statement::create(
-                subject,
-                predicate,
-                object
-            ));
-            Ok(true)
+            Ok(Some(Quad::create(subject, predicate, object, Some(graph_node))))
         } else {
-            Err(ParserError::new(format!("Line [no: {}] doesn't end on dot.", line_no)))
+            Err(ParserError::create(ErrorKind::Syntax, String::from("Line doesn't end on dot."), line_no, line, current_pos))
         }
     }
 }
@@ -450,9 +944,9 @@ impl NTripleReader{
 
 impl Parser for NTripleReader {
 
-    fn read_from_line(&mut self, line: &str, line_no: usize, database: &mut Database) -> Result<bool, ParserError> {
-        if line.is_empty(){
-            return Ok(false)
+    fn parse_line(&mut self, line: &str, line_no: usize) -> Result<Option<Quad>, ParserError> {
+        if line.trim().is_empty(){
+            return Ok(None)
         }
         //peek_next_token() -> expect _: or < expect < -> literal -> expect .
         let mut current_pos =0;
@@ -460,18 +954,18 @@ impl Parser for NTripleReader {
             Ok(Some(node)) => {
                 node
             },
-            Ok(None) => return Ok(false),
+            Ok(None) => return Ok(None),
             Err(err) => return Err(err)
         };
         if !line[current_pos..].starts_with(|c: char| c.is_whitespace()){
-            return Err(ParserError::new(format!("Line [no: {}, pos: {}]: Items have to be separated by whitespace char. ", line_no, current_pos)))
+            return Err(ParserError::create(ErrorKind::Syntax, String::from("Items have to be separated by whitespace char."), line_no, line, current_pos))
         }
         let (predicate, current_pos) = match self.tokenizer.predicate(&line, line_no, current_pos){
             Ok(node) => node,
             Err(err) => return Err(err)
         };
         if !line[current_pos..].starts_with(|c: char| c.is_whitespace()){
-            return Err(ParserError::new(format!("Line [no: {}, pos: {}]: Items have to be separated by whitespace char. ", line_no, current_pos)))
+            return Err(ParserError::create(ErrorKind::Syntax, String::from("Items have to be separated by whitespace char."), line_no, line, current_pos))
         }
         let (object, current_pos)  = match self.tokenizer.object(&line, line_no, current_pos){
             Ok(node) => node,
@@ -479,15 +973,10 @@ impl Parser for NTripleReader {
         };
 
         match self.tokenizer.peek_next_token(&line[current_pos..]){
-            Token::DOT { pos } => {
-                database.add_statement(None, Statement::create(
-                    subject,
-                    predicate,
-                    object
-                ));
-                Ok(true)
+            Token::DOT { .. } => {
+                Ok(Some(Quad::create(subject, predicate, object, None)))
             },
-            _ => {return Err(ParserError::new(format!("Line [#line: {}] doesn't end on dot.", line_no)));}
+            _ => Err(ParserError::create(ErrorKind::Syntax, String::from("Line doesn't end on dot."), line_no, line, current_pos))
         }
     }
 }
@@ -522,7 +1011,7 @@ impl Parser for NTripleReader {
 /// if let Some(node) = parse_resolved_object("\"true\"^^<http://www.w3.org/2001/XMLSchema#boolean>").ok(){
 ///     match node{
 ///         Node::LiteralNode { literal } => {
-///             assert_eq!(xsd::xsd_boolean(), literal.get_datatype());
+///             assert_eq!(&xsd::xsd_boolean(), literal.get_datatype());
 ///             assert_eq!("true", literal.get_value());
 ///         }
 ///         _ => {}
@@ -537,9 +1026,8 @@ impl Parser for NTripleReader {
 /// if let Some(node) = parse_resolved_object("_:abc").ok(){
 ///     match node{
 ///         Node::BNode { bnode } => {
-///             //Note that we generate an ID here
-///             let expected = BlankNode::generate_from_string("abc").get_value();
-///             assert_eq!(expected, bnode.get_value());
+///             //Note that the label is kept, the parsers scope it to the document (see `BlankNodeScope`)
+///             assert_eq!("abc", bnode.get_value());
 ///         }
 ///         _ => {}
 ///     };
@@ -585,7 +1073,7 @@ pub fn parse_resolved_object(object: &str) -> Result<Node, ObjectParserError> {
 /// if let Some(node) = parse_object("true", &HashMap::new(), false).ok(){
 ///
 ///     let literal = node.as_literal().expect("Should be a Literal");
-///     assert_eq!("\"true\"^^<http://www.w3.org/2001/XMLSchema#boolean>", literal.as_string());
+///     assert_eq!("\"true\"^^<http://www.w3.org/2001/XMLSchema#boolean>", literal.as_string(false));
 /// }
 /// ```
 ///
@@ -600,7 +1088,7 @@ pub fn parse_resolved_object(object: &str) -> Result<Node, ObjectParserError> {
 /// if let Some(node) = parse_object("true", &HashMap::new(), false).ok(){
 ///     match node{
 ///         Node::LiteralNode { literal } => {
-///             assert_eq!(xsd::xsd_boolean(), literal.get_datatype());
+///             assert_eq!(&xsd::xsd_boolean(), literal.get_datatype());
 ///             assert_eq!("true", literal.get_value());
 ///         }
 ///         _ => {}
@@ -626,9 +1114,8 @@ pub fn parse_resolved_object(object: &str) -> Result<Node, ObjectParserError> {
 /// if let Some(node) = parse_object("_:abc", &HashMap::new(), true).ok(){
 ///     match node{
 ///         Node::BNode { bnode } => {
-///             //Note that we generate an ID here
-///             let expected = BlankNode::generate_from_string("abc").get_value();
-///             assert_eq!(expected, bnode.get_value());
+///             //Note that the label is kept, the parsers scope it to the document (see `BlankNodeScope`)
+///             assert_eq!("abc", bnode.get_value());
 ///         }
 ///         _ => {}
 ///     };
@@ -648,80 +1135,48 @@ pub fn parse_resolved_object(object: &str) -> Result<Node, ObjectParserError> {
 /// ```
 pub fn parse_object(object: &str, prefix_mapping: &HashMap<String, String>, strict_literal: bool) -> Result<Node, ObjectParserError> {
     if object.starts_with("<"){
-        let iri = match IRI::create_iri(&String::from(&object[1..object.len()-1])){
-            Ok(val) => val,
-            Err(err) => return Err(ObjectParserError::new(err.msg))
-        };
+        let iri = IRI::create_iri(&String::from(&object[1..object.len()-1]))?;
         Ok(IRINode { iri: IRIResource::create_resource(iri)})
     }
-    else if object.starts_with("_:"){
-        Ok(BNode {bnode: BlankNode::generate_from_string(&object[2..])})
+    else if let Some(label) = object.strip_prefix("_:"){
+        Ok(BNode {bnode: BlankNode::create_blank_node(label)})
     }
     else if !(object.starts_with("\"") || object.starts_with("'")) && object.contains(":") {
         //prefixed uri
         if let Some((prefix, suffix)) = object.split_once(":"){
             if let Some(uri) = prefix_mapping.get(prefix){
-                let iri = match IRI::create_iri(&String::from(uri).add(suffix)){
-                    Ok(val) => val,
-                    Err(err) => return Err(ObjectParserError::new(err.msg))
-                };
+                let iri = IRI::create_iri(&String::from(uri).add(suffix))?;
                 Ok(IRINode{ iri: IRIResource::create_resource(iri)})
             }
             else{
-                Err(ObjectParserError::new(String::from("Unresolved prefix mapping for prefix: ").add(prefix)))
+                Err(ObjectParserError::new(ErrorKind::UnknownPrefix, String::from("Unresolved prefix mapping for prefix: ").add(prefix)))
             }
         }
         else{
-            Err(ObjectParserError::new(String::from("Unknown Error occurred")))
+            Err(ObjectParserError::new(ErrorKind::Syntax, String::from("Prefixed name has to be of form prefix:suffix.")))
         }
     }
     else{
         if !strict_literal || object.starts_with("\"") {
             return match Literal::parse_literal(object, &prefix_mapping) {
                 Ok(literal) => Ok(LiteralNode { literal }),
-                Err(err) => Err(ObjectParserError::new(err.msg))
+                Err(err) => Err(err)
             }
         }
-        Err(ObjectParserError::new(format!("Literals need to be enclosed by \" if strict_literal is true (e.g. NT, NQ)")))
+        Err(ObjectParserError::new(ErrorKind::Syntax, String::from("Literals need to be enclosed by \" if strict_literal is true (e.g. NT, NQ)")))
     }
 }
 
 
 
-#[derive(Debug)]
-pub struct ParserError {
-    msg: String
-}
-
-impl Error for ParserError {}
-
-impl fmt::Display for ParserError {
-    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
-        write!(f,"{}",self.msg)
-    }
-}
-
-impl ParserError {
-    pub fn new(msg: String) -> ParserError {
-        ParserError {msg}
-    }
-}
-
-#[derive(Debug)]
-pub struct ObjectParserError {
-    msg: String
-}
-
-impl Error for ObjectParserError {}
-
-impl fmt::Display for ObjectParserError {
-    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
-        write!(f,"{}",self.msg)
-    }
-}
+///
+/// Error which occurred while reading an RDF file.
+///
+/// If the error can be located in the file, line, column, byte offset and the offending line are set.
+///
+pub type ParserError = RDFError;
 
-impl ObjectParserError {
-    pub fn new(msg: String) -> ObjectParserError {
-        ObjectParserError {msg}
-    }
-}
\ No newline at end of file
+///
+/// Error which occurred while parsing a single RDF term.
+///
+pub type ObjectParserError = RDFError;
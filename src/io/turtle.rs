@@ -0,0 +1,860 @@
+use crate::error::{RDFError, ErrorKind};
+use crate::io::reader::{Parser, ParserError};
+use crate::rdf::graph::{Node, Quad, ResourceNode, Statement};
+use crate::rdf::node_factory::{BaseDirection, BlankNode, IRIResource, Literal};
+use crate::rdf::xsd::XSDDataType;
+use crate::util::iri::IRI;
+use std::collections::HashMap;
+use std::ops::Add;
+
+const RDF_NS: &str = "http://www.w3.org/1999/02/22-rdf-syntax-ns#";
+
+///
+/// Parser of Turtle and TriG, including quoted triples (`<< s p o >>`) and annotations (`s p o {| p2 o2 |}`) of RDF-star.
+///
+/// Statements may span several lines, so lines are buffered until the statements in them are complete.
+/// After an error the buffered lines are dropped and parsing continues with the next line.
+/// The triples of a statement are only returned if the whole statement is valid.
+///
+pub(crate) struct TurtleReader{
+    trig: bool,
+    base: Option<IRI>,
+    prefixes: HashMap<String, String>,
+    /// The lines which weren't parsed completely, each followed by a line break
+    buffer: String,
+    lines: Vec<BufferedLine>,
+    /// The position in `buffer` up to which the statements were parsed
+    pos: usize,
+    /// The graph of the TriG block the parser is in, `Some(None)` for a block of the default graph
+    graph: Option<Option<ResourceNode>>
+}
+
+struct BufferedLine{
+    line_no: usize,
+    /// The byte offset of the line in the input
+    offset: usize,
+    /// The byte position of the line in the buffer
+    start: usize
+}
+
+enum Failure{
+    /// The input ended before the statement did, so the next lines have to be read
+    Incomplete,
+    /// The statement is not valid, `pos` is the byte position of the error in the buffer
+    Invalid{pos: usize, err: Box<RDFError>}
+}
+
+/// A statement, directive or the start or end of a TriG block
+enum Unit{
+    Base(IRI),
+    Prefix(String, String),
+    Triples(Vec<Quad>),
+    GraphOpen(Option<ResourceNode>),
+    GraphClose
+}
+
+/// How a subject was written, which decides what may follow it
+#[derive(PartialEq)]
+enum SubjectForm{
+    /// IRI or blank node, which may name a graph in TriG
+    Label,
+    /// Blank node property list (`[ p o ]`), which may stand on its own
+    PropertyList,
+    Other
+}
+
+impl TurtleReader{
+
+    pub(crate) fn new(trig: bool) -> Self{
+        TurtleReader{
+            trig,
+            base: None,
+            prefixes: HashMap::new(),
+            buffer: String::new(),
+            lines: Vec::new(),
+            pos: 0,
+            graph: None
+        }
+    }
+
+    ///
+    /// Parses all complete statements of the buffer, at the end of the input an incomplete statement is an error.
+    ///
+    fn parse_buffer(&mut self, at_end: bool) -> Vec<Result<Quad, ParserError>>{
+        let mut ret = Vec::new();
+        loop {
+            let mut parser = StatementParser{
+                input: &self.buffer,
+                pos: self.pos,
+                base: &self.base,
+                prefixes: &self.prefixes,
+                trig: self.trig,
+                graph: None,
+                quads: Vec::new()
+            };
+            match parser.unit(self.graph.as_ref()) {
+                Ok(Some(unit)) => {
+                    self.pos = parser.pos;
+                    match unit {
+                        Unit::Base(base) => self.base = Some(base),
+                        Unit::Prefix(prefix, namespace) => {
+                            self.prefixes.insert(prefix, namespace);
+                        }
+                        Unit::Triples(quads) => ret.extend(quads.into_iter().map(Ok)),
+                        Unit::GraphOpen(graph) => self.graph = Some(graph),
+                        Unit::GraphClose => self.graph = None
+                    }
+                }
+                Ok(None) => {
+                    self.pos = self.buffer.len();
+                    break;
+                }
+                Err(Failure::Incomplete) => {
+                    if at_end {
+                        let err = RDFError::new(ErrorKind::Syntax, String::from("Unexpected end of input, the statement isn't complete."));
+                        ret.push(Err(self.locate(self.buffer.len(), err)));
+                        self.pos = self.buffer.len();
+                    }
+                    break;
+                }
+                Err(Failure::Invalid { pos, err }) => {
+                    ret.push(Err(self.locate(pos, *err)));
+                    //skip the rest of the statement as far as it was read, a closing brace in it ends the TriG block as well
+                    if self.buffer[pos..].contains('}') {
+                        self.graph = None;
+                    }
+                    self.pos = self.buffer.len();
+                    break;
+                }
+            }
+        }
+        if at_end && self.graph.is_some() {
+            let err = RDFError::new(ErrorKind::Syntax, String::from("Unexpected end of input, the graph isn't closed with }."));
+            ret.push(Err(self.locate(self.buffer.len(), err)));
+            self.graph = None;
+        }
+        self.drain();
+        ret
+    }
+
+    /// Removes the lines which were parsed completely from the buffer
+    fn drain(&mut self){
+        let pos = self.pos;
+        let buffer_len = self.buffer.len();
+        let first = self.lines.iter().enumerate()
+            .position(|(index, _)| self.lines.get(index + 1).map_or(buffer_len, |next| next.start) > pos);
+        match first {
+            Some(first) => {
+                let start = self.lines[first].start;
+                self.buffer.drain(..start);
+                self.lines.drain(..first);
+                for line in self.lines.iter_mut() {
+                    line.start -= start;
+                }
+                self.pos -= start;
+            }
+            None => {
+                self.buffer.clear();
+                self.lines.clear();
+                self.pos = 0;
+            }
+        }
+    }
+
+    /// Locates `err` at the byte position `pos` of the buffer
+    fn locate(&self, pos: usize, err: RDFError) -> ParserError{
+        match self.lines.iter().rposition(|line| line.start <= pos) {
+            Some(index) => {
+                let line = &self.lines[index];
+                let end = self.lines.get(index + 1).map_or(self.buffer.len(), |next| next.start);
+                let text = self.buffer[line.start..end].trim_end_matches('\n');
+                err.with_position(line.line_no, text, (pos - line.start).min(text.len())).offset_by(line.offset)
+            }
+            None => err
+        }
+    }
+}
+
+impl Parser for TurtleReader{
+
+    fn parse_line(&mut self, _line: &str, _line_no: usize) -> Result<Option<Quad>, ParserError> {
+        Err(ParserError::new(ErrorKind::UnsupportedFormat, String::from("Turtle and TriG statements may span several lines and contain several triples, use parse_statements.")))
+    }
+
+    fn parse_statements(&mut self, line: &str, line_no: usize, line_offset: usize) -> Vec<Result<Quad, ParserError>> {
+        self.lines.push(BufferedLine{ line_no, offset: line_offset, start: self.buffer.len() });
+        self.buffer.push_str(line);
+        self.buffer.push('\n');
+        //statements end on a dot or a closing brace, parsing lines without them would only be repeated by the next line
+        if !line.contains(['.', '}']) {
+            return Vec::new()
+        }
+        self.parse_buffer(false)
+    }
+
+    fn finish(&mut self) -> Vec<Result<Quad, ParserError>> {
+        self.parse_buffer(true)
+    }
+
+    fn set_base_iri(&mut self, base: IRI) {
+        self.base = Some(base);
+    }
+}
+
+///
+/// Recursive descent parser of a single unit (see `Unit`) starting at `pos`.
+///
+struct StatementParser<'a>{
+    input: &'a str,
+    pos: usize,
+    base: &'a Option<IRI>,
+    prefixes: &'a HashMap<String, String>,
+    trig: bool,
+    graph: Option<ResourceNode>,
+    quads: Vec<Quad>
+}
+
+impl<'a> StatementParser<'a>{
+
+    ///
+    /// Parses the next unit, `block` is the graph of the TriG block the unit is in.
+    ///
+    /// # Returns
+    ///
+    /// The unit or `None` if only whitespace and comments are left
+    ///
+    fn unit(&mut self, block: Option<&Option<ResourceNode>>) -> Result<Option<Unit>, Failure>{
+        self.skip_whitespace();
+        if self.at_end() {
+            return Ok(None)
+        }
+        if let Some(graph) = block {
+            if self.eat("}") {
+                return Ok(Some(Unit::GraphClose))
+            }
+            self.graph = graph.clone();
+            let (subject, form) = self.subject()?;
+            self.triples(subject, form)?;
+            self.skip_whitespace();
+            //the last triples of a block don't need a dot
+            if !self.eat(".") && !self.peek_is("}") {
+                return Err(self.expected("Triples have to end on . or }."))
+            }
+            return Ok(Some(Unit::Triples(std::mem::take(&mut self.quads))))
+        }
+        if let Some(unit) = self.directive()? {
+            return Ok(Some(unit))
+        }
+        if self.trig {
+            if self.eat_keyword("GRAPH", false) {
+                self.skip_whitespace();
+                let start = self.pos;
+                let (graph, form) = self.subject()?;
+                if form != SubjectForm::Label || graph.is_triple() {
+                    return Err(self.invalid(start, ErrorKind::Syntax, "Graph has to be an IRI or a blank node."))
+                }
+                self.skip_whitespace();
+                if !self.eat("{") {
+                    return Err(self.expected("Graph name has to be followed by {."))
+                }
+                return Ok(Some(Unit::GraphOpen(Some(graph))))
+            }
+            if self.eat("{") {
+                return Ok(Some(Unit::GraphOpen(None)))
+            }
+        }
+        let (subject, form) = self.subject()?;
+        if self.trig && form == SubjectForm::Label && !subject.is_triple() {
+            self.skip_whitespace();
+            if self.eat("{") {
+                return Ok(Some(Unit::GraphOpen(Some(subject))))
+            }
+        }
+        self.triples(subject, form)?;
+        self.expect_dot()?;
+        Ok(Some(Unit::Triples(std::mem::take(&mut self.quads))))
+    }
+
+    /// Parses `@prefix`, `@base`, `PREFIX` and `BASE`, the latter two are case-insensitive and don't end on a dot
+    fn directive(&mut self) -> Result<Option<Unit>, Failure>{
+        if self.eat_keyword("@prefix", true) {
+            let unit = self.prefix()?;
+            self.expect_dot()?;
+            return Ok(Some(unit))
+        }
+        if self.eat_keyword("@base", true) {
+            let unit = Unit::Base(self.base_iri()?);
+            self.expect_dot()?;
+            return Ok(Some(unit))
+        }
+        if self.eat_keyword("PREFIX", false) {
+            return Ok(Some(self.prefix()?))
+        }
+        if self.eat_keyword("BASE", false) {
+            return Ok(Some(Unit::Base(self.base_iri()?)))
+        }
+        Ok(None)
+    }
+
+    fn prefix(&mut self) -> Result<Unit, Failure>{
+        self.skip_whitespace();
+        let start = self.pos;
+        let prefix = self.pn_prefix();
+        if !self.eat(":") {
+            return Err(self.expected_at(start, "Prefix has to be of form prefix: and followed by an IRI."))
+        }
+        self.skip_whitespace();
+        if !self.peek_is("<") {
+            return Err(self.expected("Prefix has to be followed by an IRI."))
+        }
+        let namespace = self.iri_ref()?;
+        Ok(Unit::Prefix(String::from(prefix), namespace.as_string()))
+    }
+
+    fn base_iri(&mut self) -> Result<IRI, Failure>{
+        self.skip_whitespace();
+        if !self.peek_is("<") {
+            return Err(self.expected("Base has to be an IRI."))
+        }
+        self.iri_ref()
+    }
+
+    /// Parses the predicate object list of `subject`, which may be omitted after a blank node property list
+    fn triples(&mut self, subject: ResourceNode, form: SubjectForm) -> Result<(), Failure>{
+        if form == SubjectForm::PropertyList {
+            self.skip_whitespace();
+            if self.peek_is(".") || self.peek_is("}") {
+                return Ok(())
+            }
+        }
+        self.predicate_object_list(&subject)
+    }
+
+    fn predicate_object_list(&mut self, subject: &ResourceNode) -> Result<(), Failure>{
+        loop {
+            let predicate = self.verb()?;
+            self.object_list(subject, &predicate)?;
+            let mut separated = false;
+            loop {
+                self.skip_whitespace();
+                if !self.eat(";") {
+                    break;
+                }
+                separated = true;
+            }
+            //the list may end on a semicolon
+            if !separated || self.at_end() || self.peek_is(".") || self.peek_is("]") || self.peek_is("}") || self.peek_is("|}") {
+                return Ok(())
+            }
+        }
+    }
+
+    fn object_list(&mut self, subject: &ResourceNode, predicate: &IRIResource) -> Result<(), Failure>{
+        loop {
+            let object = self.object()?;
+            self.emit(subject.clone(), predicate.clone(), object.clone());
+            self.skip_whitespace();
+            if self.eat("{|") {
+                //the annotation is about the triple, which is asserted as well
+                let triple = ResourceNode::TripleNode { triple: Box::new(Statement::create(subject.clone(), predicate.clone(), object)) };
+                self.predicate_object_list(&triple)?;
+                self.skip_whitespace();
+                if !self.eat("|}") {
+                    return Err(self.expected("Annotation doesn't end on |}."))
+                }
+                self.skip_whitespace();
+            }
+            if !self.eat(",") {
+                return Ok(())
+            }
+        }
+    }
+
+    fn subject(&mut self) -> Result<(ResourceNode, SubjectForm), Failure>{
+        self.skip_whitespace();
+        let start = self.pos;
+        match self.peek() {
+            None => Err(Failure::Incomplete),
+            Some('[') => {
+                self.pos += 1;
+                let bnode = ResourceNode::BNode { bnode: BlankNode::generate_blank_node() };
+                self.skip_whitespace();
+                if self.eat("]") {
+                    return Ok((bnode, SubjectForm::Label))
+                }
+                self.predicate_object_list(&bnode)?;
+                self.expect("]", "Blank node property list doesn't end on ].")?;
+                Ok((bnode, SubjectForm::PropertyList))
+            }
+            Some('(') => {
+                let collection = self.collection()?;
+                Ok((collection.to_resource().expect("A collection is an IRI or a blank node"), SubjectForm::Other))
+            }
+            Some('<') if self.peek_is("<<") => {
+                let triple = self.quoted_triple()?;
+                Ok((ResourceNode::TripleNode { triple: Box::new(triple) }, SubjectForm::Other))
+            }
+            Some('<') => Ok((ResourceNode::IRINode { iri: IRIResource::create_resource(self.iri_ref()?) }, SubjectForm::Label)),
+            Some('_') if self.peek_is("_:") => Ok((ResourceNode::BNode { bnode: self.blank_node_label()? }, SubjectForm::Label)),
+            Some(c) if c == ':' || is_pn_chars_base(c) => {
+                match self.name()? {
+                    Node::IRINode { iri } => Ok((ResourceNode::IRINode { iri }, SubjectForm::Label)),
+                    _ => Err(self.invalid(start, ErrorKind::Syntax, "Literal is not allowed as subject."))
+                }
+            }
+            Some(c) if c == '"' || c == '\'' || c == '+' || c == '-' || c == '.' || c.is_ascii_digit() => {
+                Err(self.invalid(start, ErrorKind::Syntax, "Literal is not allowed as subject."))
+            }
+            Some(_) => Err(self.invalid(start, ErrorKind::Syntax, "Subject has to be an IRI, a blank node, a collection or a quoted triple."))
+        }
+    }
+
+    fn verb(&mut self) -> Result<IRIResource, Failure>{
+        self.skip_whitespace();
+        let start = self.pos;
+        match self.peek() {
+            None => Err(Failure::Incomplete),
+            Some('a') if !self.input[start + 1..].starts_with(|c: char| is_pn_chars(c) || c == ':' || c == '.') => {
+                self.pos += 1;
+                Ok(rdf("type"))
+            }
+            Some('<') if !self.peek_is("<<") => Ok(IRIResource::create_resource(self.iri_ref()?)),
+            Some(c) if c == ':' || is_pn_chars_base(c) => {
+                match self.name()? {
+                    Node::IRINode { iri } => Ok(iri),
+                    _ => Err(self.invalid(start, ErrorKind::Syntax, "Literal is not allowed as predicate. Only IRI."))
+                }
+            }
+            Some(_) => Err(self.invalid(start, ErrorKind::Syntax, "Predicate has to be an IRI."))
+        }
+    }
+
+    fn object(&mut self) -> Result<Node, Failure>{
+        self.skip_whitespace();
+        let start = self.pos;
+        match self.peek() {
+            None => Err(Failure::Incomplete),
+            Some('[') => Ok(Node::from(self.subject()?.0)),
+            Some('_') if self.peek_is("_:") => Ok(Node::BNode { bnode: self.blank_node_label()? }),
+            Some('(') => self.collection(),
+            Some('<') if self.peek_is("<<") => Ok(Node::TripleNode { triple: Box::new(self.quoted_triple()?) }),
+            Some('<') => Ok(Node::IRINode { iri: IRIResource::create_resource(self.iri_ref()?) }),
+            Some('"') | Some('\'') => Ok(Node::LiteralNode { literal: self.rdf_literal()? }),
+            Some(c) if c == '+' || c == '-' || c == '.' || c.is_ascii_digit() => Ok(Node::LiteralNode { literal: self.number()? }),
+            Some(c) if c == ':' || is_pn_chars_base(c) => self.name(),
+            Some(_) => Err(self.invalid(start, ErrorKind::Syntax, "Object has to be an IRI, a blank node, a literal, a collection or a quoted triple."))
+        }
+    }
+
+    /// Parses `( o1 o2 ... )` into an RDF list
+    fn collection(&mut self) -> Result<Node, Failure>{
+        self.pos += 1;
+        let mut items = Vec::new();
+        loop {
+            self.skip_whitespace();
+            if self.at_end() {
+                return Err(Failure::Incomplete)
+            }
+            if self.eat(")") {
+                break;
+            }
+            items.push(self.object()?);
+        }
+        let mut list = Node::IRINode { iri: rdf("nil") };
+        for item in items.into_iter().rev() {
+            let node = ResourceNode::BNode { bnode: BlankNode::generate_blank_node() };
+            self.emit(node.clone(), rdf("first"), item);
+            self.emit(node.clone(), rdf("rest"), list);
+            list = Node::from(node);
+        }
+        Ok(list)
+    }
+
+    /// Parses `<< s p o >>`, whose terms can't be collections or blank node property lists
+    fn quoted_triple(&mut self) -> Result<Statement, Failure>{
+        self.pos += 2;
+        let subject = self.quoted_term(false)?.to_resource().expect("A subject is no literal");
+        let predicate = self.verb()?;
+        let object = self.quoted_term(true)?;
+        self.skip_whitespace();
+        if !self.eat(">>") {
+            return Err(self.expected("Quoted triple doesn't end on >>."))
+        }
+        Ok(Statement::create(subject, predicate, object))
+    }
+
+    fn quoted_term(&mut self, is_object: bool) -> Result<Node, Failure>{
+        self.skip_whitespace();
+        let start = self.pos;
+        match self.peek() {
+            Some('[') => {
+                self.pos += 1;
+                self.skip_whitespace();
+                if !self.eat("]") {
+                    return Err(self.expected_at(start, "Blank node property lists are not allowed in quoted triples."))
+                }
+                Ok(Node::BNode { bnode: BlankNode::generate_blank_node() })
+            }
+            Some('(') => Err(self.invalid(start, ErrorKind::Syntax, "Collections are not allowed in quoted triples.")),
+            _ if is_object => self.object(),
+            _ => Ok(Node::from(self.subject()?.0))
+        }
+    }
+
+    /// Parses a prefixed name or the boolean literals `true` and `false`
+    fn name(&mut self) -> Result<Node, Failure>{
+        let start = self.pos;
+        let prefix = self.pn_prefix();
+        if !self.eat(":") {
+            return match prefix {
+                "true" | "false" => Ok(Node::LiteralNode { literal: Literal::from(prefix == "true") }),
+                _ => Err(self.invalid(start, ErrorKind::Syntax, "Prefixed name has to be of form prefix:suffix."))
+            }
+        }
+        let local = self.pn_local()?;
+        let namespace = match self.prefixes.get(prefix) {
+            Some(namespace) => namespace,
+            None => return Err(self.invalid(start, ErrorKind::UnknownPrefix, &String::from("Unresolved prefix mapping for prefix: ").add(prefix)))
+        };
+        match IRI::create_iri(&String::from(namespace).add(&local)) {
+            Ok(iri) => Ok(Node::IRINode { iri: IRIResource::create_resource(iri) }),
+            Err(err) => Err(Failure::Invalid { pos: start, err: Box::new(err) })
+        }
+    }
+
+    /// Reads the prefix of a prefixed name, which may be empty
+    fn pn_prefix(&mut self) -> &'a str{
+        let start = self.pos;
+        if !self.peek().is_some_and(is_pn_chars_base) {
+            return ""
+        }
+        let mut end = start;
+        for (index, c) in self.input[start..].char_indices() {
+            if !(is_pn_chars(c) || c == '.') {
+                break;
+            }
+            if c != '.' {
+                end = start + index + c.len_utf8();
+            }
+        }
+        //the prefix must not end on a dot
+        self.pos = end;
+        &self.input[start..end]
+    }
+
+    /// Reads the local name of a prefixed name, resolving its escapes
+    fn pn_local(&mut self) -> Result<String, Failure>{
+        let mut local = String::new();
+        //length of `local` and position after its last character which isn't a dot, as the name must not end on a dot
+        let mut valid = (0, self.pos);
+        while let Some(c) = self.peek() {
+            let first = local.is_empty();
+            if c == '%' {
+                let hex = self.input[self.pos + 1..].chars().take(2).collect::<String>();
+                if hex.len() != 2 || !hex.chars().all(|h| h.is_ascii_hexdigit()) {
+                    return Err(self.invalid(self.pos, ErrorKind::Syntax, "Percent-encoding in local name needs two hex digits."))
+                }
+                local.push('%');
+                local.push_str(&hex);
+                self.pos += 3;
+            } else if c == '\\' {
+                match self.input[self.pos + 1..].chars().next() {
+                    Some(escaped) if "_~.-!$&'()*+,;=/?#@%".contains(escaped) => {
+                        local.push(escaped);
+                        self.pos += 2;
+                    }
+                    _ => return Err(self.invalid(self.pos, ErrorKind::Syntax, "Invalid escape sequence in local name."))
+                }
+            } else if is_pn_chars_u(c) || c == ':' || c.is_ascii_digit() || (!first && (is_pn_chars(c) || c == '.')) {
+                local.push(c);
+                self.pos += c.len_utf8();
+                if c == '.' {
+                    continue;
+                }
+            } else {
+                break;
+            }
+            valid = (local.len(), self.pos);
+        }
+        local.truncate(valid.0);
+        self.pos = valid.1;
+        Ok(local)
+    }
+
+    fn blank_node_label(&mut self) -> Result<BlankNode, Failure>{
+        let start = self.pos;
+        self.pos += 2;
+        if !self.peek().is_some_and(|c| is_pn_chars_u(c) || c.is_ascii_digit()) {
+            return Err(self.invalid(start, ErrorKind::Syntax, "Blank node label is empty."))
+        }
+        let label_start = self.pos;
+        let mut end = label_start;
+        for (index, c) in self.input[label_start..].char_indices() {
+            if !(is_pn_chars(c) || c == '.') {
+                break;
+            }
+            if c != '.' {
+                end = label_start + index + c.len_utf8();
+            }
+        }
+        self.pos = end;
+        Ok(BlankNode::create_blank_node(&self.input[label_start..end]))
+    }
+
+    /// Reads `<iri>`, resolving its escapes and resolving it against the base IRI
+    fn iri_ref(&mut self) -> Result<IRI, Failure>{
+        let start = self.pos;
+        self.pos += 1;
+        let mut value = String::new();
+        loop {
+            let c = match self.peek() {
+                Some(c) => c,
+                None => return Err(self.invalid(start, ErrorKind::Syntax, "IRI doesn't end on >."))
+            };
+            let char_pos = self.pos;
+            self.pos += c.len_utf8();
+            match c {
+                '>' => break,
+                '\\' => {
+                    let escape = self.peek();
+                    self.pos += 1;
+                    match escape {
+                        Some('u') => value.push(self.hex_escape(char_pos, 4)?),
+                        Some('U') => value.push(self.hex_escape(char_pos, 8)?),
+                        _ => return Err(self.invalid(char_pos, ErrorKind::Syntax, "IRIs may only contain \\u and \\U escape sequences."))
+                    }
+                }
+                c if c <= ' ' || "<\"{}|^`".contains(c) => {
+                    return Err(self.invalid(char_pos, ErrorKind::Syntax, &format!("Character {:?} is not allowed in an IRI.", c)))
+                }
+                c => value.push(c)
+            }
+        }
+        let iri = match self.base {
+            Some(base) => base.resolve(&value),
+            None => IRI::create_iri(&value)
+        };
+        iri.map_err(|err| Failure::Invalid { pos: start, err: Box::new(err) })
+    }
+
+    /// Reads the hex digits of a `\u` or `\U` escape sequence starting at `start`
+    fn hex_escape(&mut self, start: usize, len: usize) -> Result<char, Failure>{
+        let decoded = self.input.get(self.pos..self.pos + len)
+            .filter(|hex| hex.chars().all(|h| h.is_ascii_hexdigit()))
+            .and_then(|hex| u32::from_str_radix(hex, 16).ok())
+            .and_then(char::from_u32);
+        match decoded {
+            Some(decoded) => {
+                self.pos += len;
+                Ok(decoded)
+            }
+            None => Err(self.invalid(start, ErrorKind::Syntax, "Invalid escape sequence."))
+        }
+    }
+
+    /// Reads a string with an optional language tag or datatype
+    fn rdf_literal(&mut self) -> Result<Literal, Failure>{
+        let value = self.string()?;
+        if self.eat("@") {
+            let start = self.pos;
+            let len = self.input[start..].find(|c: char| !(c.is_ascii_alphanumeric() || c == '-')).unwrap_or(self.input.len() - start);
+            self.pos += len;
+            let tag = &self.input[start..self.pos];
+            let literal = match tag.split_once("--") {
+                Some((lang, direction)) => direction.parse::<BaseDirection>().and_then(|direction| Literal::create_dir_lang_literal(value, String::from(lang), direction)),
+                None if tag.is_empty() => Err(RDFError::new(ErrorKind::InvalidLiteral, String::from("Language tag must not be empty."))),
+                None => Literal::create_lang_literal(value, String::from(tag))
+            };
+            return literal.map_err(|err| Failure::Invalid { pos: start, err: Box::new(err) })
+        }
+        let end = self.pos;
+        self.skip_whitespace();
+        if self.eat("^^") {
+            self.skip_whitespace();
+            let start = self.pos;
+            let datatype = match self.peek() {
+                Some('<') if !self.peek_is("<<") => self.iri_ref()?,
+                Some(c) if c == ':' || is_pn_chars_base(c) => match self.name()? {
+                    Node::IRINode { iri } => iri.get_iri().clone(),
+                    _ => return Err(self.invalid(start, ErrorKind::InvalidLiteral, "Datatype has to be an IRI."))
+                },
+                None => return Err(Failure::Incomplete),
+                Some(_) => return Err(self.invalid(start, ErrorKind::InvalidLiteral, "Datatype has to be an IRI."))
+            };
+            return Ok(Literal::create_typed_literal_from_ref(value, XSDDataType::get_or_default(datatype)))
+        }
+        self.pos = end;
+        Ok(Literal::from(value))
+    }
+
+    /// Reads a string in one of the four quote styles, resolving its escapes
+    fn string(&mut self) -> Result<String, Failure>{
+        let start = self.pos;
+        let quote = ["\"\"\"", "'''", "\"", "'"].iter().find(|quote| self.peek_is(quote)).copied().unwrap_or("\"");
+        let long = quote.len() == 3;
+        self.pos += quote.len();
+        let mut value = String::new();
+        loop {
+            if self.eat(quote) {
+                return Ok(value)
+            }
+            let c = match self.peek() {
+                Some(c) => c,
+                //long strings may continue on the next lines
+                None if long => return Err(Failure::Incomplete),
+                None => return Err(self.invalid(start, ErrorKind::Syntax, "String doesn't end on a quote."))
+            };
+            let char_pos = self.pos;
+            self.pos += c.len_utf8();
+            match c {
+                '\\' => {
+                    let escape = self.peek();
+                    self.pos += escape.map_or(0, |escape| escape.len_utf8());
+                    match escape {
+                        Some('t') => value.push('\t'),
+                        Some('b') => value.push('\u{8}'),
+                        Some('n') => value.push('\n'),
+                        Some('r') => value.push('\r'),
+                        Some('f') => value.push('\u{C}'),
+                        Some(c) if c == '"' || c == '\'' || c == '\\' => value.push(c),
+                        Some('u') => value.push(self.hex_escape(char_pos, 4)?),
+                        Some('U') => value.push(self.hex_escape(char_pos, 8)?),
+                        _ => return Err(self.invalid(char_pos, ErrorKind::Syntax, "Invalid escape sequence."))
+                    }
+                }
+                '\n' | '\r' if !long => return Err(self.invalid(start, ErrorKind::Syntax, "String doesn't end on a quote.")),
+                c => value.push(c)
+            }
+        }
+    }
+
+    /// Reads an integer, decimal or double, keeping its lexical form
+    fn number(&mut self) -> Result<Literal, Failure>{
+        let start = self.pos;
+        let digits = |input: &str| input.find(|c: char| !c.is_ascii_digit()).unwrap_or(input.len());
+        let mut end = start;
+        if self.input[end..].starts_with(['+', '-']) {
+            end += 1;
+        }
+        let integer = digits(&self.input[end..]);
+        end += integer;
+        let mut fraction = 0;
+        let exponent = |input: &str| {
+            let rest = input.strip_prefix(['e', 'E'])?;
+            let sign = usize::from(rest.starts_with(['+', '-']));
+            let len = digits(&rest[sign..]);
+            if len == 0 {None} else {Some(1 + sign + len)}
+        };
+        if self.input[end..].starts_with('.') {
+            fraction = digits(&self.input[end + 1..]);
+            //a dot which isn't followed by digits ends the statement, unless an exponent follows
+            if fraction > 0 || (integer > 0 && exponent(&self.input[end + 1..]).is_some()) {
+                end += 1 + fraction;
+            }
+        }
+        if integer + fraction == 0 {
+            return Err(self.invalid(start, ErrorKind::Syntax, "Number has to contain a digit."))
+        }
+        if let Some(len) = exponent(&self.input[end..]) {
+            end += len;
+        }
+        self.pos = end;
+        Literal::parse_literal(&self.input[start..end], self.prefixes).map_err(|err| Failure::Invalid { pos: start, err: Box::new(err) })
+    }
+
+    fn emit(&mut self, subject: ResourceNode, predicate: IRIResource, object: Node){
+        self.quads.push(Quad::create(subject, predicate, object, self.graph.clone()));
+    }
+
+    /// Skips whitespace and comments
+    fn skip_whitespace(&mut self){
+        loop {
+            let rest = &self.input[self.pos..];
+            let trimmed = rest.trim_start();
+            self.pos += rest.len() - trimmed.len();
+            if !trimmed.starts_with('#') {
+                return;
+            }
+            self.pos += trimmed.find('\n').unwrap_or(trimmed.len());
+        }
+    }
+
+    fn expect_dot(&mut self) -> Result<(), Failure>{
+        self.expect(".", "Statement doesn't end on dot.")
+    }
+
+    fn expect(&mut self, token: &str, msg: &str) -> Result<(), Failure>{
+        self.skip_whitespace();
+        if self.eat(token) {
+            return Ok(())
+        }
+        Err(self.expected(msg))
+    }
+
+    /// The error for a missing token at the current position, which may still follow if the input ended
+    fn expected(&self, msg: &str) -> Failure{
+        self.expected_at(self.pos, msg)
+    }
+
+    fn expected_at(&self, pos: usize, msg: &str) -> Failure{
+        if self.at_end() {
+            return Failure::Incomplete
+        }
+        self.invalid(pos, ErrorKind::Syntax, msg)
+    }
+
+    fn invalid(&self, pos: usize, kind: ErrorKind, msg: &str) -> Failure{
+        Failure::Invalid { pos, err: Box::new(RDFError::new(kind, String::from(msg))) }
+    }
+
+    /// Consumes `word` if it isn't the start of a longer name, e.g. the prefix of `base:`
+    fn eat_keyword(&mut self, word: &str, case_sensitive: bool) -> bool{
+        let matches = match self.input.get(self.pos..self.pos + word.len()) {
+            Some(text) => if case_sensitive {text == word} else {text.eq_ignore_ascii_case(word)},
+            None => false
+        };
+        if !matches || self.input[self.pos + word.len()..].starts_with(|c: char| is_pn_chars(c) || c == ':' || c == '.') {
+            return false
+        }
+        self.pos += word.len();
+        true
+    }
+
+    fn eat(&mut self, token: &str) -> bool{
+        if self.peek_is(token) {
+            self.pos += token.len();
+            return true
+        }
+        false
+    }
+
+    fn peek_is(&self, token: &str) -> bool{
+        self.input[self.pos..].starts_with(token)
+    }
+
+    fn peek(&self) -> Option<char>{
+        self.input[self.pos..].chars().next()
+    }
+
+    fn at_end(&self) -> bool{
+        self.pos >= self.input.len()
+    }
+}
+
+fn rdf(name: &str) -> IRIResource{
+    IRIResource::create_resource(IRI::create_iri(&String::from(RDF_NS).add(name)).expect("RDF vocabulary IRIs are valid"))
+}
+
+fn is_pn_chars_base(c: char) -> bool{
+    matches!(c, 'A'..='Z' | 'a'..='z' | '\u{C0}'..='\u{D6}' | '\u{D8}'..='\u{F6}' | '\u{F8}'..='\u{2FF}' | '\u{370}'..='\u{37D}'
+        | '\u{37F}'..='\u{1FFF}' | '\u{200C}'..='\u{200D}' | '\u{2070}'..='\u{218F}' | '\u{2C00}'..='\u{2FEF}' | '\u{3001}'..='\u{D7FF}'
+        | '\u{F900}'..='\u{FDCF}' | '\u{FDF0}'..='\u{FFFD}' | '\u{10000}'..='\u{EFFFF}')
+}
+
+pub(crate) fn is_pn_chars_u(c: char) -> bool{
+    is_pn_chars_base(c) || c == '_'
+}
+
+pub(crate) fn is_pn_chars(c: char) -> bool{
+    is_pn_chars_u(c) || c == '-' || c.is_ascii_digit() || c == '\u{B7}' || ('\u{300}'..='\u{36F}').contains(&c) || ('\u{203F}'..='\u{2040}').contains(&c)
+}
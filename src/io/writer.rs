@@ -0,0 +1,281 @@
+use crate::error::{RDFError, ErrorKind};
+use crate::io::reader::Lang;
+use crate::io::turtle::{is_pn_chars, is_pn_chars_u};
+use crate::rdf::database::{Database, GraphPattern};
+use crate::rdf::graph::{Node, Quad, ResourceNode, Statement};
+use crate::rdf::node_factory::{escape_string, IRIResource, Literal, RDFNode};
+use crate::rdf::xsd::{XSD_BOOLEAN, XSD_DECIMAL, XSD_DOUBLE, XSD_INTEGER};
+use std::collections::{BTreeMap, HashMap, HashSet};
+use std::fs::File;
+use std::io::{BufWriter, Write};
+
+const RDF_TYPE: &str = "http://www.w3.org/1999/02/22-rdf-syntax-ns#type";
+
+///
+/// Writes quads as N-Triples, N-Quads, Turtle or TriG, including the quoted triples of RDF-star.
+///
+/// N-Triples and Turtle only contain the default graph, so the quads of named graphs are left out.
+///
+/// Turtle and TriG group the triples by subject and predicate, and shorten IRIs with the prefixes added by `with_prefix`.
+/// A quoted triple which is asserted in the same graph is written as annotation (`s p o {| p2 o2 |}`) of the asserted triple,
+/// instead of as subject of its own. Subjects, predicates and objects are sorted, so the output is deterministic.
+///
+/// # Example
+///
+/// ```
+/// use rdf4rust::io::reader::{Lang, QuadIterator};
+/// use rdf4rust::io::writer::Writer;
+/// use rdf4rust::rdf::graph::Quad;
+///
+/// let input = "<http://example.com/alice> <http://example.com/knows> <http://example.com/bob> .\n\
+///     << <http://example.com/alice> <http://example.com/knows> <http://example.com/bob> >> <http://example.com/since> \"2020\"^^<http://www.w3.org/2001/XMLSchema#integer> .\n";
+/// let quads: Vec<Quad> = QuadIterator::new(input.as_bytes(), &Lang::NT).map(|quad| quad.unwrap()).collect();
+///
+/// let mut turtle = Vec::new();
+/// Writer::new(Lang::TTL).with_prefix("ex", "http://example.com/").write(&quads, &mut turtle).unwrap();
+/// assert_eq!("@prefix ex: <http://example.com/> .\n\nex:alice ex:knows ex:bob {| ex:since 2020 |} .\n", String::from_utf8(turtle).unwrap());
+///
+/// let mut n_triples = Vec::new();
+/// Writer::new(Lang::NT).write(&quads, &mut n_triples).unwrap();
+/// assert_eq!(input, String::from_utf8(n_triples).unwrap());
+/// ```
+///
+pub struct Writer{
+    lang: Lang,
+    prefixes: Vec<(String, String)>
+}
+
+impl Writer{
+
+    ///
+    /// Creates a writer for `lang`, writing fails for RDF/XML and TriX as they aren't supported
+    ///
+    pub fn new(lang: Lang) -> Self{
+        Writer{
+            lang,
+            prefixes: Vec::new()
+        }
+    }
+
+    ///
+    /// Adds a prefix for Turtle and TriG, IRIs starting with `namespace` are written as `prefix:local` if possible
+    ///
+    pub fn with_prefix(mut self, prefix: &str, namespace: &str) -> Self{
+        self.prefixes.push((String::from(prefix), String::from(namespace)));
+        self
+    }
+
+    ///
+    /// Writes `quads` to `out`
+    ///
+    /// Fails if the format isn't supported or `out` cannot be written.
+    ///
+    pub fn write<W: Write>(&self, quads: &[Quad], out: &mut W) -> Result<(), RDFError>{
+        match self.lang {
+            Lang::NT | Lang::NTRIPLE => {
+                for quad in quads.iter().filter(|quad| quad.get_graph().is_none()) {
+                    writeln!(out, "{}", quad)?;
+                }
+            }
+            Lang::NQ | Lang::NQUADS => {
+                for quad in quads {
+                    writeln!(out, "{}", quad)?;
+                }
+            }
+            Lang::TTL | Lang::TURTLE => {
+                self.write_prefixes(out)?;
+                let triples: Vec<Statement> = quads.iter().filter(|quad| quad.get_graph().is_none())
+                    .map(|quad| quad.clone().into_statement().1)
+                    .collect();
+                self.write_triples(&triples, "", out)?;
+            }
+            Lang::TRIG => {
+                self.write_prefixes(out)?;
+                let mut graphs: BTreeMap<String, (Option<ResourceNode>, Vec<Statement>)> = BTreeMap::new();
+                for quad in quads {
+                    let (graph, statement) = quad.clone().into_statement();
+                    //the default graph is written first
+                    let key = graph.as_ref().map_or(String::new(), |graph| self.resource(graph));
+                    graphs.entry(key).or_insert_with(|| (graph, Vec::new())).1.push(statement);
+                }
+                for (name, (graph, triples)) in graphs {
+                    match graph {
+                        None => self.write_triples(&triples, "", out)?,
+                        Some(_) => {
+                            writeln!(out, "{} {{", name)?;
+                            self.write_triples(&triples, "    ", out)?;
+                            writeln!(out, "}}")?;
+                        }
+                    }
+                }
+            }
+            Lang::RdfXml | Lang::TRIX => {
+                return Err(RDFError::new(ErrorKind::UnsupportedFormat, String::from("Writing is only supported for N-Triples, N-Quads, Turtle and TriG.")))
+            }
+        }
+        Ok(())
+    }
+
+    ///
+    /// Writes all quads of `database` to `out`
+    ///
+    pub fn write_database<W: Write>(&self, database: &Database, out: &mut W) -> Result<(), RDFError>{
+        self.write(&database.quads_for_pattern(None, None, None, &GraphPattern::AnyGraph), out)
+    }
+
+    ///
+    /// Writes all quads of `database` to `file`, which is created or truncated
+    ///
+    pub fn write_to_file(&self, database: &Database, file: &str) -> Result<(), RDFError>{
+        let created = match File::create(file) {
+            Ok(created) => created,
+            Err(err) => return Err(RDFError::new(ErrorKind::Io, format!("Cannot write file {}", file)).with_source(err))
+        };
+        let mut out = BufWriter::new(created);
+        self.write_database(database, &mut out)?;
+        out.flush()?;
+        Ok(())
+    }
+
+    fn write_prefixes<W: Write>(&self, out: &mut W) -> Result<(), RDFError>{
+        for (prefix, namespace) in &self.prefixes {
+            writeln!(out, "@prefix {}: <{}> .", prefix, namespace)?;
+        }
+        if !self.prefixes.is_empty() {
+            writeln!(out)?;
+        }
+        Ok(())
+    }
+
+    /// Writes the triples of a graph grouped by subject, each line starting with `indent`
+    fn write_triples<W: Write>(&self, triples: &[Statement], indent: &str, out: &mut W) -> Result<(), RDFError>{
+        let asserted: HashSet<&Statement> = triples.iter().collect();
+        //triples about asserted triples become their annotations
+        let mut annotations: HashMap<&Statement, Vec<&Statement>> = HashMap::new();
+        let mut subjects: BTreeMap<String, Vec<&Statement>> = BTreeMap::new();
+        for triple in triples {
+            match triple.get_subject() {
+                ResourceNode::TripleNode { triple: quoted } if asserted.contains(quoted.as_ref()) => {
+                    annotations.entry(quoted.as_ref()).or_default().push(triple);
+                }
+                subject => subjects.entry(self.resource(subject)).or_default().push(triple)
+            }
+        }
+        for (subject, triples) in subjects {
+            write!(out, "{}{} ", indent, subject)?;
+            self.write_predicate_objects(&triples, &annotations, &format!("{}    ", indent), out)?;
+            writeln!(out, " .")?;
+        }
+        Ok(())
+    }
+
+    /// Writes `p1 o1, o2 ; p2 o3` for `triples` of the same subject, `rdf:type` first
+    fn write_predicate_objects<W: Write>(&self, triples: &[&Statement], annotations: &HashMap<&Statement, Vec<&Statement>>, indent: &str, out: &mut W) -> Result<(), RDFError>{
+        let mut predicates: BTreeMap<(bool, String), Vec<&Statement>> = BTreeMap::new();
+        for triple in triples {
+            let predicate = triple.get_predicate();
+            predicates.entry((predicate.get_value() != RDF_TYPE, self.predicate(predicate))).or_default().push(triple);
+        }
+        for (index, ((_, predicate), mut triples)) in predicates.into_iter().enumerate() {
+            if index > 0 {
+                write!(out, " ;\n{}", indent)?;
+            }
+            write!(out, "{} ", predicate)?;
+            triples.sort_by_cached_key(|triple| self.node(triple.get_object()));
+            for (index, triple) in triples.iter().enumerate() {
+                if index > 0 {
+                    write!(out, ", ")?;
+                }
+                write!(out, "{}", self.node(triple.get_object()))?;
+                if let Some(annotation) = annotations.get(*triple) {
+                    write!(out, " {{| ")?;
+                    self.write_predicate_objects(annotation, annotations, indent, out)?;
+                    write!(out, " |}}")?;
+                }
+            }
+        }
+        Ok(())
+    }
+
+    fn predicate(&self, predicate: &IRIResource) -> String{
+        if predicate.get_value() == RDF_TYPE {
+            return String::from("a")
+        }
+        self.iri(&predicate.get_value())
+    }
+
+    fn resource(&self, resource: &ResourceNode) -> String{
+        match resource {
+            ResourceNode::IRINode { iri } => self.iri(&iri.get_value()),
+            ResourceNode::BNode { bnode } => bnode.as_string(true),
+            ResourceNode::TripleNode { triple } => self.quoted_triple(triple)
+        }
+    }
+
+    fn node(&self, node: &Node) -> String{
+        match node {
+            Node::IRINode { iri } => self.iri(&iri.get_value()),
+            Node::BNode { bnode } => bnode.as_string(true),
+            Node::LiteralNode { literal } => self.literal(literal),
+            Node::TripleNode { triple } => self.quoted_triple(triple)
+        }
+    }
+
+    fn quoted_triple(&self, triple: &Statement) -> String{
+        format!("<< {} {} {} >>", self.resource(triple.get_subject()), self.predicate(triple.get_predicate()), self.node(triple.get_object()))
+    }
+
+    /// Writes the IRI as prefixed name if one of the prefixes matches and the rest is a valid local name
+    fn iri(&self, iri: &str) -> String{
+        let prefixed = self.prefixes.iter()
+            .filter_map(|(prefix, namespace)| iri.strip_prefix(namespace.as_str()).map(|local| (prefix, local)))
+            .filter(|(_, local)| is_local_name(local))
+            .min_by_key(|(_, local)| local.len());
+        match prefixed {
+            Some((prefix, local)) => format!("{}:{}", prefix, local),
+            None => format!("<{}>", iri)
+        }
+    }
+
+    /// Writes numbers and booleans without quotes if Turtle reads them with the same lexical form and datatype
+    fn literal(&self, literal: &Literal) -> String{
+        let value = literal.get_value();
+        let dtype = literal.get_datatype();
+        let unsigned = value.strip_prefix(['+', '-']).unwrap_or(value);
+        let digits = |text: &str| !text.is_empty() && text.chars().all(|c| c.is_ascii_digit());
+        let bare = if dtype == &*XSD_INTEGER {
+            digits(unsigned)
+        } else if dtype == &*XSD_DECIMAL {
+            unsigned.split_once('.').is_some_and(|(integer, fraction)| (integer.is_empty() || digits(integer)) && digits(fraction))
+        } else if dtype == &*XSD_DOUBLE {
+            unsigned.split_once(['e', 'E']).is_some_and(|(mantissa, exponent)| {
+                let exponent = exponent.strip_prefix(['+', '-']).unwrap_or(exponent);
+                let mantissa_valid = match mantissa.split_once('.') {
+                    Some((integer, fraction)) => (integer.is_empty() || digits(integer)) && (fraction.is_empty() || digits(fraction)) && !(integer.is_empty() && fraction.is_empty()),
+                    None => digits(mantissa)
+                };
+                mantissa_valid && digits(exponent)
+            })
+        } else {
+            dtype == &*XSD_BOOLEAN && (value == "true" || value == "false")
+        };
+        if bare {
+            return value.clone()
+        }
+        if literal.get_lang().is_some() || dtype.is_xsd_string() {
+            return literal.as_string(true)
+        }
+        format!("\"{}\"^^{}", escape_string(value), self.iri(dtype.get_value()))
+    }
+}
+
+/// Checks if `local` can be written as local name of a prefixed name without escapes
+fn is_local_name(local: &str) -> bool{
+    let mut chars = local.chars();
+    match chars.next() {
+        None => true,
+        Some(first) => (is_pn_chars_u(first) || first.is_ascii_digit())
+            && chars.all(|c| is_pn_chars(c) || c == '.')
+            && !local.ends_with('.')
+    }
+}
@@ -3,4 +3,7 @@ extern crate lazy_static;
 
 pub mod rdf;
 pub mod util;
-pub mod io;
\ No newline at end of file
+pub mod io;
+pub mod error;
+pub mod reasoning;
+pub mod shacl;pub mod sparql;
@@ -1,4 +1,7 @@
 pub mod node_factory;
 pub mod xsd;
 pub mod graph;
-pub mod database;
\ No newline at end of file
+pub mod database;
+pub mod canonicalization;
+pub mod skolem;
+pub mod term;
\ No newline at end of file
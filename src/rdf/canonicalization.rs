@@ -0,0 +1,510 @@
+//!
+//! RDF Dataset Canonicalization according to the W3C recommendation RDFC-1.0 (<https://www.w3.org/TR/rdf-canon/>).
+//!
+//! The canonicalization assigns deterministic labels (`_:c14n0`, `_:c14n1`, ...) to the blank nodes of a dataset,
+//! so isomorphic datasets result in the same canonical N-Quads, which can be hashed and signed.
+//!
+
+use crate::rdf::graph::{Quad, Node};
+use crate::rdf::node_factory::{BlankNode, RDFNode};
+use crate::error::{RDFError, ErrorKind};
+use sha2::{Digest, Sha256, Sha384};
+use std::collections::{BTreeMap, HashMap, HashSet};
+
+/// The default maximum of deep iterations per blank node, enough for e.g. a clique of six indistinguishable blank nodes
+pub const DEFAULT_MAX_DEEP_ITERATIONS: usize = 1000;
+
+///
+/// The hash algorithm used by the canonicalization, RDFC-1.0 uses SHA-256 by default
+///
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum HashAlgorithm{
+    Sha256,
+    Sha384
+}
+
+impl HashAlgorithm{
+
+    /// Hashes `data` and returns the digest as lowercase hex string
+    pub fn hash(&self, data: &str) -> String{
+        let digest = match self{
+            HashAlgorithm::Sha256 => Sha256::digest(data.as_bytes()).to_vec(),
+            HashAlgorithm::Sha384 => Sha384::digest(data.as_bytes()).to_vec()
+        };
+        digest.iter().map(|b| format!("{:02x}", b)).collect()
+    }
+}
+
+///
+/// Canonicalizes datasets using RDFC-1.0
+///
+/// Datasets with many blank nodes which can't be distinguished by their direct neighbourhood need many deep iterations
+/// (calls of the Hash N-Degree Quads algorithm), for specially crafted "poison" datasets exponentially many.
+/// Thus the deep iterations per blank node are limited, datasets exceeding the limit fail with `ErrorKind::LimitExceeded`.
+///
+/// # Example
+///
+/// ```
+/// use rdf4rust::rdf::canonicalization::{Canonicalizer, HashAlgorithm};
+/// use rdf4rust::io::reader::{QuadIterator, Lang};
+///
+/// let input = "_:e0 <http://example.org/vocab#next> _:e1 .\n\
+///     _:e1 <http://example.org/vocab#next> _:e0 .\n\
+///     _:e1 <http://example.org/vocab#name> \"last\" .\n";
+/// let quads: Vec<_> = QuadIterator::new(input.as_bytes(), &Lang::NQ).with_preserved_blank_node_labels(true)
+///     .map(|quad| quad.expect("Is valid")).collect();
+///
+/// let canonical = Canonicalizer::new().canonicalize(&quads).expect("Within limits");
+/// assert_eq!("_:c14n0 <http://example.org/vocab#next> _:c14n1 .\n\
+///     _:c14n1 <http://example.org/vocab#name> \"last\" .\n\
+///     _:c14n1 <http://example.org/vocab#next> _:c14n0 .\n", canonical.get_nquads());
+/// assert_eq!("c14n1", canonical.get_issued_identifiers()["e1"]);
+///
+/// // the hash of the canonical N-Quads doesn't depend on the original labels
+/// let relabeled = input.replace("_:e", "_:x");
+/// let quads: Vec<_> = QuadIterator::new(relabeled.as_bytes(), &Lang::NQ).map(|quad| quad.expect("Is valid")).collect();
+/// assert_eq!(canonical.get_hash(), Canonicalizer::new().canonicalize(&quads).unwrap().get_hash());
+///
+/// // the hash algorithm is used for the canonicalization as well, thus the labels may differ
+/// let sha384 = Canonicalizer::new().with_hash_algorithm(HashAlgorithm::Sha384).canonicalize(&quads).unwrap();
+/// assert_eq!(96, sha384.get_hash().len());
+/// ```
+///
+#[derive(Clone, Debug)]
+pub struct Canonicalizer{
+    algorithm: HashAlgorithm,
+    max_deep_iterations: usize
+}
+
+impl Default for Canonicalizer{
+    fn default() -> Self {
+        Canonicalizer::new()
+    }
+}
+
+impl Canonicalizer{
+
+    pub fn new() -> Self{
+        Canonicalizer{
+            algorithm: HashAlgorithm::Sha256,
+            max_deep_iterations: DEFAULT_MAX_DEEP_ITERATIONS
+        }
+    }
+
+    /// Sets the hash algorithm, defaults to SHA-256
+    pub fn with_hash_algorithm(mut self, algorithm: HashAlgorithm) -> Self{
+        self.algorithm = algorithm;
+        self
+    }
+
+    ///
+    /// Sets the maximum number of deep iterations per blank node, defaults to `DEFAULT_MAX_DEEP_ITERATIONS`
+    ///
+    /// 0 allows only datasets whose blank nodes all have a unique first degree hash.
+    ///
+    pub fn with_max_deep_iterations(mut self, max_deep_iterations: usize) -> Self{
+        self.max_deep_iterations = max_deep_iterations;
+        self
+    }
+
+    pub fn get_hash_algorithm(&self) -> HashAlgorithm{
+        self.algorithm
+    }
+
+    pub fn get_max_deep_iterations(&self) -> usize{
+        self.max_deep_iterations
+    }
+
+    ///
+    /// Canonicalizes the dataset consisting of `quads`, duplicates are ignored
+    ///
+    /// # Returns
+    ///
+    /// The canonical dataset or an error of kind `ErrorKind::LimitExceeded` if the deep iteration limit is exceeded
+    ///
+    pub fn canonicalize(&self, quads: &[Quad]) -> Result<CanonicalDataset, RDFError>{
+        let mut state = State{
+            algorithm: self.algorithm,
+            blank_node_quads: HashMap::new(),
+            canonical_issuer: IdentifierIssuer::new("c14n"),
+            deep_iterations: HashMap::new(),
+            max_deep_iterations: self.max_deep_iterations
+        };
+        let mut seen = HashSet::new();
+        let quads: Vec<&Quad> = quads.iter().filter(|quad| seen.insert(*quad)).collect();
+        // step 2: map every blank node to the quads it occurs in
+        for quad in &quads {
+            let mut bnodes = blank_nodes(quad);
+            bnodes.sort();
+            bnodes.dedup();
+            for bnode in bnodes {
+                state.blank_node_quads.entry(bnode).or_default().push(quad);
+            }
+        }
+        // step 3: group the blank nodes by their first degree hash, ordered by hash
+        let mut hash_to_blank_nodes: BTreeMap<String, Vec<String>> = BTreeMap::new();
+        let mut ids: Vec<&String> = state.blank_node_quads.keys().collect();
+        ids.sort();
+        for id in ids {
+            hash_to_blank_nodes.entry(state.hash_first_degree_quads(id)).or_default().push(id.clone());
+        }
+        // step 4: blank nodes with a unique hash get their canonical identifier right away
+        let mut non_unique = Vec::new();
+        for (_, ids) in hash_to_blank_nodes {
+            if ids.len() == 1 {
+                state.canonical_issuer.issue(&ids[0]);
+            } else {
+                non_unique.push(ids);
+            }
+        }
+        // step 5: all others by the hash of their n-degree neighbourhood
+        for ids in non_unique {
+            let mut hash_path_list = Vec::new();
+            for id in ids {
+                if state.canonical_issuer.get(&id).is_some() {
+                    continue;
+                }
+                let mut issuer = IdentifierIssuer::new("b");
+                issuer.issue(&id);
+                hash_path_list.push(state.hash_n_degree_quads(&id, issuer)?);
+            }
+            hash_path_list.sort_by(|a, b| a.0.cmp(&b.0));
+            for (_, issuer) in hash_path_list {
+                for id in &issuer.order {
+                    state.canonical_issuer.issue(id);
+                }
+            }
+        }
+        // step 6: relabel all quads, distinct quads stay distinct as every blank node gets its own identifier
+        let mut lines: Vec<(String, Quad)> = quads.iter()
+            .map(|quad| relabel(quad, &|id| state.canonical_issuer.get(id).cloned().unwrap_or_else(|| String::from(id))))
+            .map(|quad| (format!("{}\n", quad), quad))
+            .collect();
+        lines.sort_by(|a, b| a.0.cmp(&b.0));
+        let nquads = lines.iter().map(|(line, _)| line.as_str()).collect();
+        Ok(CanonicalDataset{
+            algorithm: self.algorithm,
+            quads: lines.into_iter().map(|(_, quad)| quad).collect(),
+            nquads,
+            issued: state.canonical_issuer.issued
+        })
+    }
+}
+
+///
+/// The result of the canonicalization: the canonical N-Quads and the map of the original to the canonical blank node identifiers
+///
+pub struct CanonicalDataset{
+    algorithm: HashAlgorithm,
+    quads: Vec<Quad>,
+    nquads: String,
+    issued: HashMap<String, String>
+}
+
+impl CanonicalDataset{
+
+    /// Gets the relabeled quads in the order of the canonical N-Quads
+    pub fn get_quads(&self) -> &Vec<Quad>{
+        &self.quads
+    }
+
+    /// Gets the canonical N-Quads document, each line ending with a line break
+    pub fn get_nquads(&self) -> &str{
+        &self.nquads
+    }
+
+    /// Gets the canonical identifiers (e.g. `c14n0`) by the original blank node identifiers, both without `_:`
+    pub fn get_issued_identifiers(&self) -> &HashMap<String, String>{
+        &self.issued
+    }
+
+    /// Hashes the canonical N-Quads with the hash algorithm of the canonicalization
+    pub fn get_hash(&self) -> String{
+        self.algorithm.hash(&self.nquads)
+    }
+
+    /// Hashes the canonical N-Quads with `algorithm`
+    pub fn get_hash_with(&self, algorithm: HashAlgorithm) -> String{
+        algorithm.hash(&self.nquads)
+    }
+}
+
+///
+/// Issues identifiers with a prefix and a counter, remembering the order of issuing
+///
+#[derive(Clone)]
+struct IdentifierIssuer{
+    prefix: &'static str,
+    issued: HashMap<String, String>,
+    order: Vec<String>
+}
+
+impl IdentifierIssuer{
+
+    fn new(prefix: &'static str) -> Self{
+        IdentifierIssuer{
+            prefix,
+            issued: HashMap::new(),
+            order: Vec::new()
+        }
+    }
+
+    fn get(&self, existing: &str) -> Option<&String>{
+        self.issued.get(existing)
+    }
+
+    fn issue(&mut self, existing: &str) -> String{
+        if let Some(id) = self.issued.get(existing) {
+            return id.clone()
+        }
+        let id = format!("{}{}", self.prefix, self.order.len());
+        self.issued.insert(String::from(existing), id.clone());
+        self.order.push(String::from(existing));
+        id
+    }
+}
+
+struct State<'a>{
+    algorithm: HashAlgorithm,
+    blank_node_quads: HashMap<String, Vec<&'a Quad>>,
+    canonical_issuer: IdentifierIssuer,
+    deep_iterations: HashMap<String, usize>,
+    max_deep_iterations: usize
+}
+
+impl<'a> State<'a>{
+
+    /// Hash First Degree Quads (RDFC-1.0 section 4.6)
+    fn hash_first_degree_quads(&self, reference: &str) -> String{
+        let mut nquads: Vec<String> = self.blank_node_quads[reference].iter()
+            .map(|quad| format!("{}\n", relabel(quad, &|id| String::from(if id == reference {"a"} else {"z"}))))
+            .collect();
+        nquads.sort();
+        self.algorithm.hash(&nquads.concat())
+    }
+
+    /// Hash Related Blank Node (RDFC-1.0 section 4.7)
+    fn hash_related_blank_node(&self, related: &str, quad: &Quad, issuer: &IdentifierIssuer, position: &str) -> String{
+        let mut input = String::from(position);
+        if position != "g" {
+            input.push_str(&quad.get_predicate().as_string(true));
+        }
+        match self.canonical_issuer.get(related).or_else(|| issuer.get(related)) {
+            Some(id) => {
+                input.push_str("_:");
+                input.push_str(id);
+            }
+            None => input.push_str(&self.hash_first_degree_quads(related))
+        }
+        self.algorithm.hash(&input)
+    }
+
+    /// Hash N-Degree Quads (RDFC-1.0 section 4.8)
+    fn hash_n_degree_quads(&mut self, identifier: &str, issuer: IdentifierIssuer) -> Result<(String, IdentifierIssuer), RDFError>{
+        let iterations = self.deep_iterations.entry(String::from(identifier)).or_insert(0);
+        *iterations += 1;
+        if *iterations > self.max_deep_iterations {
+            return Err(RDFError::new(ErrorKind::LimitExceeded, format!("Exceeded the maximum of {} deep iterations for blank node _:{}.", self.max_deep_iterations, identifier)))
+        }
+        let mut issuer = issuer;
+        let mut related_hashes: BTreeMap<String, Vec<String>> = BTreeMap::new();
+        for quad in &self.blank_node_quads[identifier] {
+            for (related, position) in blank_node_positions(quad) {
+                if related != identifier {
+                    let hash = self.hash_related_blank_node(&related, quad, &issuer, position);
+                    related_hashes.entry(hash).or_default().push(related);
+                }
+            }
+        }
+        let mut data_to_hash = String::new();
+        for (related_hash, blank_nodes) in related_hashes {
+            data_to_hash.push_str(&related_hash);
+            let mut chosen_path = String::new();
+            let mut chosen_issuer = None;
+            for permutation in permutations(&blank_nodes) {
+                let mut issuer_copy = issuer.clone();
+                let mut path = String::new();
+                let mut recursion_list = Vec::new();
+                let mut skip = false;
+                for related in &permutation {
+                    match self.canonical_issuer.get(related) {
+                        Some(id) => path.push_str(&format!("_:{}", id)),
+                        None => {
+                            if issuer_copy.get(related).is_none() {
+                                recursion_list.push(related.clone());
+                            }
+                            path.push_str(&format!("_:{}", issuer_copy.issue(related)));
+                        }
+                    }
+                    if is_worse_path(&path, &chosen_path) {
+                        skip = true;
+                        break;
+                    }
+                }
+                if skip {
+                    continue;
+                }
+                for related in recursion_list {
+                    let (hash, result_issuer) = self.hash_n_degree_quads(&related, issuer_copy.clone())?;
+                    issuer_copy = result_issuer;
+                    path.push_str(&format!("_:{}<{}>", issuer_copy.issue(&related), hash));
+                    if is_worse_path(&path, &chosen_path) {
+                        skip = true;
+                        break;
+                    }
+                }
+                if skip {
+                    continue;
+                }
+                if chosen_path.is_empty() || path < chosen_path {
+                    chosen_path = path;
+                    chosen_issuer = Some(issuer_copy);
+                }
+            }
+            data_to_hash.push_str(&chosen_path);
+            if let Some(chosen_issuer) = chosen_issuer {
+                issuer = chosen_issuer;
+            }
+        }
+        Ok((self.algorithm.hash(&data_to_hash), issuer))
+    }
+}
+
+/// Checks if `path` can't become better than `chosen_path` anymore, so the permutation can be skipped
+fn is_worse_path(path: &str, chosen_path: &str) -> bool{
+    !chosen_path.is_empty() && path.len() >= chosen_path.len() && path > chosen_path
+}
+
+/// All permutations of `items` (Heap's algorithm)
+fn permutations(items: &[String]) -> Vec<Vec<String>>{
+    let mut items = items.to_vec();
+    let mut ret = vec![items.clone()];
+    let mut counters = vec![0; items.len()];
+    let mut i = 0;
+    while i < items.len() {
+        if counters[i] < i {
+            if i % 2 == 0 {
+                items.swap(0, i);
+            } else {
+                items.swap(counters[i], i);
+            }
+            ret.push(items.clone());
+            counters[i] += 1;
+            i = 0;
+        } else {
+            counters[i] = 0;
+            i += 1;
+        }
+    }
+    ret
+}
+
+/// The identifiers of the blank nodes in subject, object and graph of `quad` with their position `s`, `o` resp. `g`,
+/// blank nodes within quoted triples get the position of the quoted triple
+fn blank_node_positions(quad: &Quad) -> Vec<(String, &'static str)>{
+    fn collect(node: &Node, position: &'static str, ret: &mut Vec<(String, &'static str)>){
+        match node {
+            Node::BNode { bnode } => ret.push((bnode.get_value(), position)),
+            Node::TripleNode { triple } => {
+                collect(&Node::from(triple.get_subject().clone()), position, ret);
+                collect(triple.get_object(), position, ret);
+            }
+            _ => {}
+        }
+    }
+    let mut ret = Vec::new();
+    collect(&Node::from(quad.get_subject().clone()), "s", &mut ret);
+    collect(quad.get_object(), "o", &mut ret);
+    if let Some(graph) = quad.get_graph() {
+        collect(&Node::from(graph.clone()), "g", &mut ret);
+    }
+    ret
+}
+
+fn blank_nodes(quad: &Quad) -> Vec<String>{
+    blank_node_positions(quad).into_iter().map(|(id, _)| id).collect()
+}
+
+/// Replaces the identifiers of all blank nodes in `quad` using `label`
+fn relabel(quad: &Quad, label: &dyn Fn(&str) -> String) -> Quad{
+    quad.clone().map_terms(&|node| match node {
+        Node::BNode { bnode } => Node::BNode { bnode: BlankNode::create_blank_node(&label(&bnode.get_value())) },
+        node => node
+    }, &|predicate| predicate)
+}
+
+///
+/// Compares two datasets with blank nodes matched up to isomorphism
+///
+/// The quads are split into components, a ground quad is a component on its own and quads sharing blank nodes
+/// belong to the same component. Components of `old` and `new` with the same canonical form are matched.
+///
+/// # Returns
+///
+/// The quads of the unmatched components of `new` (added) and `old` (removed), in their order of the input
+///
+pub(crate) fn diff_quads(old: &[Quad], new: &[Quad]) -> (Vec<Quad>, Vec<Quad>){
+    // isomorphism has to be decided, so the deep iterations are not limited
+    let canonicalizer = Canonicalizer::new().with_max_deep_iterations(usize::MAX);
+    let canonical_form = |component: &[Quad]| canonicalizer.canonicalize(component)
+        .expect("Deep iterations are unlimited")
+        .get_nquads()
+        .to_string();
+    let old_components = components(old);
+    let mut unmatched: HashMap<String, Vec<usize>> = HashMap::new();
+    for (i, component) in old_components.iter().enumerate().rev() {
+        unmatched.entry(canonical_form(component)).or_default().push(i);
+    }
+    let mut matched = vec![false; old_components.len()];
+    let mut added = Vec::new();
+    for component in components(new) {
+        match unmatched.get_mut(&canonical_form(&component)).and_then(|indices| indices.pop()) {
+            Some(i) => matched[i] = true,
+            None => added.extend(component)
+        }
+    }
+    let removed = old_components.into_iter().zip(matched).filter(|(_, matched)| !matched).flat_map(|(component, _)| component).collect();
+    (added, removed)
+}
+
+/// Splits `quads` into ground quads and groups of quads connected by blank nodes, duplicates are removed
+fn components(quads: &[Quad]) -> Vec<Vec<Quad>>{
+    fn find(parent: &mut HashMap<String, String>, id: &str) -> String{
+        let next = parent.get(id).cloned().unwrap_or_else(|| String::from(id));
+        if next == id {
+            return next
+        }
+        let root = find(parent, &next);
+        parent.insert(String::from(id), root.clone());
+        root
+    }
+    let mut seen = HashSet::new();
+    let quads: Vec<&Quad> = quads.iter().filter(|quad| seen.insert(*quad)).collect();
+    let mut parent: HashMap<String, String> = HashMap::new();
+    for quad in &quads {
+        let ids = blank_nodes(quad);
+        for id in ids.iter().skip(1) {
+            let (a, b) = (find(&mut parent, &ids[0]), find(&mut parent, id));
+            if a != b {
+                parent.insert(a, b);
+            }
+        }
+    }
+    let mut ret: Vec<Vec<Quad>> = Vec::new();
+    let mut component_by_root: HashMap<String, usize> = HashMap::new();
+    for quad in quads {
+        let ids = blank_nodes(quad);
+        let index = match ids.first() {
+            Some(id) => *component_by_root.entry(find(&mut parent, id)).or_insert_with(|| {
+                ret.push(Vec::new());
+                ret.len() - 1
+            }),
+            None => {
+                ret.push(Vec::new());
+                ret.len() - 1
+            }
+        };
+        ret[index].push(quad.clone());
+    }
+    ret
+}
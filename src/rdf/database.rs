@@ -1,11 +1,40 @@
-use crate::rdf::graph::{Graph, GraphType, IndexedGraph, FullIndexedGraph, SimpleGraph, Statement};
+use crate::rdf::graph::{Graph, GraphType, IndexedGraph, SimpleGraph, Statement, Quad, ResourceNode, Node};
 use std::collections::{HashMap, HashSet};
-use crate::rdf::node_factory::{IRIResource, RDFNode};
+use crate::rdf::node_factory::{IRIResource, BlankNode};
+use crate::util::iri::IRI;
+use crate::error::{RDFError, ErrorKind};
+use crate::rdf::canonicalization::{Canonicalizer, CanonicalDataset, diff_quads};
+use crate::rdf::skolem::Skolemizer;
 
+///
+/// Selects the graphs of a `Database` a pattern is matched against
+///
+#[allow(clippy::large_enum_variant)]
+pub enum GraphPattern{
+    /// The default graph and all named graphs
+    AnyGraph,
+    /// Only the default graph
+    DefaultGraph,
+    /// All named graphs, but not the default graph
+    NamedGraphs,
+    /// Only the named graph `name`
+    NamedGraph{
+        name: ResourceNode
+    }
+}
+
+///
+/// An RDF dataset consisting of a default graph and named graphs.
+///
+/// Named graphs are identified by an IRI or a blank node and stored by their string form,
+/// which is the plain IRI resp. `_:id` for blank nodes.
+///
 pub struct Database{
     graph_type: GraphType,
     default_graph: Box<dyn Graph>,
-    named_graphs: HashMap<String, Box<dyn Graph>>
+    named_graphs: HashMap<String, Box<dyn Graph>>,
+    union_default_graph: bool,
+    canonical_literals: bool
 }
 
 impl Database{
@@ -22,23 +51,33 @@ impl Database{
         &self.named_graphs
     }
 
-    fn add_new_graph(&mut self, graph: &str){
+    fn new_graph(&self) -> Box<dyn Graph>{
         match self.graph_type {
-            GraphType::SimpleGraph => {
-                self.named_graphs.insert(String::from(graph), Box::new(SimpleGraph::new()));
-            }
-            GraphType::IndexedGraph => {
-                self.named_graphs.insert(String::from(graph), Box::new(IndexedGraph::new()));
-            }
-            GraphType::FullIndexedGraph => {
-                self.named_graphs.insert(String::from(graph), Box::new(SimpleGraph::new()));
-            }
+            GraphType::IndexedGraph => Box::new(IndexedGraph::new().with_canonical_literals(self.canonical_literals)),
+            _ => Box::new(SimpleGraph::new().with_canonical_literals(self.canonical_literals))
         }
     }
 
+    fn add_new_graph(&mut self, graph: &str){
+        let new_graph = self.new_graph();
+        self.named_graphs.insert(String::from(graph), new_graph);
+    }
+
     pub fn add_statement(&mut self, graph: Option<IRIResource>, stmt: Statement){
-        if let Some(graph_iri) = graph{
-            let graph_str = graph_iri.as_string(false);
+        self.add_to_graph(graph.map(|iri| ResourceNode::IRINode { iri }), stmt);
+    }
+
+    ///
+    /// Adds the statement of `quad` to its graph
+    ///
+    pub fn add_quad(&mut self, quad: Quad){
+        let (graph, stmt) = quad.into_statement();
+        self.add_to_graph(graph, stmt);
+    }
+
+    fn add_to_graph(&mut self, graph: Option<ResourceNode>, stmt: Statement){
+        if let Some(graph_name) = graph{
+            let graph_str = graph_name.as_string();
             match self.named_graphs.get_mut(&graph_str) {
                 None => {
                     self.add_new_graph(graph_str.as_str());
@@ -55,29 +94,499 @@ impl Database{
         }
     }
 
-    pub fn new(graph_type: GraphType)-> Self{
-        match graph_type{
-            GraphType::SimpleGraph => {
-                Database{
-                    graph_type: GraphType::SimpleGraph,
-                    default_graph: Box::new(SimpleGraph::new()),
-                    named_graphs: HashMap::new()
+    ///
+    /// Enables or disables the union default graph mode.
+    ///
+    /// If enabled, the default graph appears as the merge of all named graphs for reading (e.g. in `quads_for_pattern`),
+    /// while the statements added to the actual default graph are hidden until the mode is disabled again.
+    /// Writing is not affected.
+    ///
+    pub fn set_union_default_graph(&mut self, union_default_graph: bool){
+        self.union_default_graph = union_default_graph;
+    }
+
+    pub fn is_union_default_graph(&self) -> bool{
+        self.union_default_graph
+    }
+
+    pub fn contains_named_graph(&self, name: &ResourceNode) -> bool{
+        self.named_graphs.contains_key(&name.as_string())
+    }
+
+    ///
+    /// Creates the empty named graph `name`.
+    ///
+    /// Fails with `ErrorKind::GraphAlreadyExists` if the graph already exists.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use rdf4rust::rdf::database::Database;
+    /// use rdf4rust::rdf::graph::{GraphType, ResourceNode};
+    /// use rdf4rust::rdf::node_factory::iri;
+    ///
+    /// let name = ResourceNode::IRINode { iri: iri("http://example.com/g") };
+    ///
+    /// let mut database = Database::new(GraphType::SimpleGraph);
+    /// assert!(database.create_named_graph(&name).is_ok());
+    /// assert!(database.create_named_graph(&name).is_err());
+    /// assert!(database.contains_named_graph(&name));
+    ///
+    /// assert!(database.drop_named_graph(&name).is_ok());
+    /// assert!(!database.contains_named_graph(&name));
+    /// ```
+    ///
+    pub fn create_named_graph(&mut self, name: &ResourceNode) -> Result<(), RDFError>{
+        if self.contains_named_graph(name) {
+            return Err(RDFError::new(ErrorKind::GraphAlreadyExists, format!("Graph {} already exists.", name.as_string())))
+        }
+        self.add_new_graph(&name.as_string());
+        Ok(())
+    }
+
+    ///
+    /// Removes the named graph `name` including all its statements.
+    ///
+    /// # Returns
+    ///
+    /// The removed graph, or an error of kind `ErrorKind::UnknownGraph` if it doesn't exist
+    ///
+    pub fn drop_named_graph(&mut self, name: &ResourceNode) -> Result<Box<dyn Graph>, RDFError>{
+        match self.named_graphs.remove(&name.as_string()) {
+            Some(graph) => Ok(graph),
+            None => Err(Database::unknown_graph(name))
+        }
+    }
+
+    ///
+    /// Renames the named graph `from` to `to`.
+    ///
+    /// Fails if `from` doesn't exist or `to` already exists, renaming a graph to itself does nothing.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use rdf4rust::rdf::database::Database;
+    /// use rdf4rust::rdf::graph::{GraphType, ResourceNode};
+    /// use rdf4rust::rdf::node_factory::iri;
+    ///
+    /// let name = |s: &str| ResourceNode::IRINode { iri: iri(s) };
+    ///
+    /// let mut database = Database::new(GraphType::SimpleGraph);
+    /// database.create_named_graph(&name("http://example.com/g")).unwrap();
+    /// assert!(database.rename_named_graph(&name("http://example.com/g"), &name("http://example.com/g")).is_ok());
+    /// assert!(database.rename_named_graph(&name("http://example.com/g"), &name("http://example.com/h")).is_ok());
+    /// assert!(database.contains_named_graph(&name("http://example.com/h")));
+    /// assert!(database.rename_named_graph(&name("http://example.com/g"), &name("http://example.com/g")).is_err());
+    /// ```
+    ///
+    pub fn rename_named_graph(&mut self, from: &ResourceNode, to: &ResourceNode) -> Result<(), RDFError>{
+        if from == to && self.contains_named_graph(from) {
+            return Ok(())
+        }
+        if self.contains_named_graph(to) {
+            return Err(RDFError::new(ErrorKind::GraphAlreadyExists, format!("Graph {} already exists.", to.as_string())))
+        }
+        let graph = self.drop_named_graph(from)?;
+        self.named_graphs.insert(to.as_string(), graph);
+        Ok(())
+    }
+
+    ///
+    /// Removes all statements from the graph, `None` is the default graph.
+    ///
+    /// The named graph itself is kept. Fails if the named graph doesn't exist.
+    ///
+    pub fn clear_graph(&mut self, graph: Option<&ResourceNode>) -> Result<(), RDFError>{
+        let new_graph = self.new_graph();
+        match graph {
+            None => self.default_graph = new_graph,
+            Some(name) => {
+                match self.named_graphs.get_mut(&name.as_string()) {
+                    Some(g) => *g = new_graph,
+                    None => return Err(Database::unknown_graph(name))
+                }
+            }
+        }
+        Ok(())
+    }
+
+    ///
+    /// Replaces all statements of the graph `to` by the statements of `from`, `None` is the default graph.
+    ///
+    /// The named graph `to` will be created if it doesn't exist. Fails if the named graph `from` doesn't exist.
+    ///
+    pub fn copy_graph(&mut self, from: Option<&ResourceNode>, to: Option<&ResourceNode>) -> Result<(), RDFError>{
+        if from == to {
+            return Ok(())
+        }
+        let stmts = self.list_graph_statements(from, None, None, None)?;
+        match to {
+            Some(name) if !self.contains_named_graph(name) => self.add_new_graph(&name.as_string()),
+            _ => self.clear_graph(to)?
+        }
+        self.graph_mut(to).add_all_statements(&stmts);
+        Ok(())
+    }
+
+    ///
+    /// Adds all statements of the graph `from` to the graph `to`, `None` is the default graph.
+    ///
+    /// The named graph `to` will be created if it doesn't exist. Fails if the named graph `from` doesn't exist.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use rdf4rust::rdf::database::Database;
+    /// use rdf4rust::rdf::graph::{GraphType, Quad, Node, ResourceNode};
+    /// use rdf4rust::rdf::node_factory::{Literal, iri};
+    ///
+    /// let g1 = ResourceNode::IRINode { iri: iri("http://example.com/g1") };
+    /// let g2 = ResourceNode::IRINode { iri: iri("http://example.com/g2") };
+    /// let quad = |value: &str, graph: &ResourceNode| Quad::create(ResourceNode::IRINode { iri: iri("http://example.com/s") }, iri("http://example.com/p"),
+    ///     Node::LiteralNode { literal: Literal::from(value) }, Some(graph.clone()));
+    ///
+    /// let mut database = Database::new(GraphType::IndexedGraph);
+    /// database.add_quad(quad("a", &g1));
+    /// database.add_quad(quad("b", &g1));
+    /// database.add_quad(quad("b", &g2));
+    ///
+    /// database.merge_graph(Some(&g1), Some(&g2)).expect("g1 exists");
+    /// assert_eq!(2, database.get_named_graph("http://example.com/g2").expect("g2 exists").count());
+    ///
+    /// // moves the statement with the value "a" from g2 into the default graph
+    /// let object = Node::LiteralNode { literal: Literal::from("a") };
+    /// assert_eq!(1, database.move_statements(None, None, Some(&object), Some(&g2), None).expect("g2 exists"));
+    /// assert_eq!(1, database.get_default_graph().count());
+    /// assert_eq!(1, database.get_named_graph("http://example.com/g2").expect("g2 exists").count());
+    /// ```
+    ///
+    pub fn merge_graph(&mut self, from: Option<&ResourceNode>, to: Option<&ResourceNode>) -> Result<(), RDFError>{
+        if from == to {
+            return Ok(())
+        }
+        let stmts = self.list_graph_statements(from, None, None, None)?;
+        if let Some(name) = to {
+            if !self.contains_named_graph(name) {
+                self.add_new_graph(&name.as_string());
+            }
+        }
+        self.graph_mut(to).add_all_statements(&stmts);
+        Ok(())
+    }
+
+    ///
+    /// Moves all statements matching the pattern from the graph `from` to the graph `to`, `None` is the default graph.
+    ///
+    /// The named graph `to` will be created if it doesn't exist. Fails if the named graph `from` doesn't exist.
+    ///
+    /// # Returns
+    ///
+    /// The number of moved statements
+    ///
+    pub fn move_statements(&mut self, subject: Option<&ResourceNode>, predicate: Option<&IRIResource>, object: Option<&Node>,
+                           from: Option<&ResourceNode>, to: Option<&ResourceNode>) -> Result<usize, RDFError>{
+        let stmts = self.list_graph_statements(from, subject, predicate, object)?;
+        if from == to {
+            return Ok(stmts.len())
+        }
+        self.graph_mut(from).remove_all_statements(&stmts);
+        if let Some(name) = to {
+            if !self.contains_named_graph(name) {
+                self.add_new_graph(&name.as_string());
+            }
+        }
+        self.graph_mut(to).add_all_statements(&stmts);
+        Ok(stmts.len())
+    }
+
+    fn list_graph_statements(&self, graph: Option<&ResourceNode>, subject: Option<&ResourceNode>, predicate: Option<&IRIResource>, object: Option<&Node>) -> Result<Vec<Statement>, RDFError>{
+        let g = match graph {
+            None => &self.default_graph,
+            Some(name) => match self.named_graphs.get(&name.as_string()) {
+                Some(g) => g,
+                None => return Err(Database::unknown_graph(name))
+            }
+        };
+        Ok(g.list_statements(subject.cloned(), predicate.cloned(), object.cloned()).into_iter().cloned().collect())
+    }
+
+    ///
+    /// The graph `graph` which has to exist, `None` is the default graph
+    ///
+    fn graph_mut(&mut self, graph: Option<&ResourceNode>) -> &mut Box<dyn Graph>{
+        match graph {
+            None => &mut self.default_graph,
+            Some(name) => self.named_graphs.get_mut(&name.as_string()).expect("Graph was checked to exist")
+        }
+    }
+
+    fn unknown_graph(name: &ResourceNode) -> RDFError{
+        RDFError::new(ErrorKind::UnknownGraph, format!("Graph {} doesn't exist.", name.as_string()))
+    }
+
+    ///
+    /// Lists all statements matching the pattern in the selected graphs, whereas `None` matches everything.
+    ///
+    /// # Parameters
+    ///
+    /// * `subject` - The subject to match or `None`
+    /// * `predicate` - The predicate to match or `None`
+    /// * `object` - The object to match or `None`
+    /// * `graph` - The graphs to match against
+    ///
+    /// # Returns
+    ///
+    /// All matching statements as `Quad` containing the graph they belong to.
+    ///
+    /// In union default graph mode, the default graph is the union of all named graphs,
+    /// thus `GraphPattern::AnyGraph` only returns the statements of the named graphs.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use rdf4rust::rdf::database::{Database, GraphPattern};
+    /// use rdf4rust::rdf::graph::{GraphType, Quad, Node, ResourceNode};
+    /// use rdf4rust::rdf::node_factory::{Literal, BlankNode, iri};
+    ///
+    /// let subject = ResourceNode::IRINode { iri: iri("http://example.com/s") };
+    /// let graph = ResourceNode::BNode { bnode: BlankNode::create_blank_node("g1") };
+    ///
+    /// let mut database = Database::new(GraphType::SimpleGraph);
+    /// database.add_quad(Quad::create(subject.clone(), iri("http://example.com/p"),
+    ///     Node::LiteralNode { literal: Literal::from("default") }, None));
+    /// database.add_quad(Quad::create(subject.clone(), iri("http://example.com/p"),
+    ///     Node::LiteralNode { literal: Literal::from("named") }, Some(graph.clone())));
+    ///
+    /// assert_eq!(2, database.quads_for_pattern(Some(&subject), None, None, &GraphPattern::AnyGraph).len());
+    /// assert_eq!(1, database.quads_for_pattern(None, Some(&iri("http://example.com/p")), None, &GraphPattern::DefaultGraph).len());
+    ///
+    /// let quads = database.quads_for_pattern(None, None, None, &GraphPattern::NamedGraph { name: graph.clone() });
+    /// assert!(quads[0].get_graph() == &Some(graph));
+    ///
+    /// // the default graph is the union of the named graphs
+    /// database.set_union_default_graph(true);
+    /// let quads = database.quads_for_pattern(None, None, None, &GraphPattern::DefaultGraph);
+    /// assert_eq!(1, quads.len());
+    /// assert!(quads[0].get_graph().is_none());
+    /// ```
+    ///
+    pub fn quads_for_pattern(&self, subject: Option<&ResourceNode>, predicate: Option<&IRIResource>, object: Option<&Node>, graph: &GraphPattern) -> Vec<Quad>{
+        let mut ret = Vec::new();
+        let mut add_matches = |graph_name: Option<ResourceNode>, g: &dyn Graph| {
+            for stmt in g.list_statements(subject.cloned(), predicate.cloned(), object.cloned()) {
+                ret.push(Quad::from_statement(stmt.clone(), graph_name.clone()));
+            }
+        };
+        match graph {
+            GraphPattern::AnyGraph | GraphPattern::DefaultGraph if !self.union_default_graph => {
+                add_matches(None, self.default_graph.as_ref());
+            }
+            GraphPattern::DefaultGraph => {
+                //the union of all named graphs, each statement only once
+                let mut stmts = HashSet::new();
+                for g in self.named_graphs.values() {
+                    stmts.extend(g.list_statements(subject.cloned(), predicate.cloned(), object.cloned()));
+                }
+                ret.extend(stmts.into_iter().map(|stmt| Quad::from_statement(stmt.clone(), None)));
+                return ret
+            }
+            _ => {}
+        }
+        match graph {
+            GraphPattern::AnyGraph | GraphPattern::NamedGraphs => {
+                for (name, g) in &self.named_graphs {
+                    add_matches(Some(Database::parse_graph_name(name)), g.as_ref());
                 }
             }
-            GraphType::IndexedGraph => {
-                Database{
-                    graph_type: GraphType::IndexedGraph,
-                    default_graph: Box::new(IndexedGraph::new()),
-                    named_graphs: HashMap::new()
+            GraphPattern::NamedGraph { name } => {
+                if let Some(g) = self.named_graphs.get(&name.as_string()) {
+                    add_matches(Some(name.clone()), g.as_ref());
                 }
             }
-            _ => Database{
-                graph_type: GraphType::SimpleGraph,
-                default_graph: Box::new(SimpleGraph::new()),
-                named_graphs: HashMap::new()
+            _ => {}
+        }
+        ret
+    }
+
+    ///
+    /// Canonicalizes the dataset with RDFC-1.0
+    ///
+    /// The stored default graph and all named graphs are canonicalized, independent of the union default graph mode.
+    ///
+    /// # Parameters
+    ///
+    /// * `canonicalizer` - The canonicalizer selecting the hash algorithm and the limits
+    ///
+    /// # Returns
+    ///
+    /// The canonical N-Quads with the issued blank node identifiers or an error if a limit is exceeded
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use rdf4rust::rdf::database::Database;
+    /// use rdf4rust::rdf::canonicalization::Canonicalizer;
+    /// use rdf4rust::rdf::graph::{GraphType, Quad, Node, ResourceNode};
+    /// use rdf4rust::rdf::node_factory::{Literal, BlankNode, iri};
+    ///
+    /// let p = iri("http://example.com/p");
+    /// let bnode = |id: &str| ResourceNode::BNode { bnode: BlankNode::create_blank_node(id) };
+    ///
+    /// let mut database = Database::new(GraphType::SimpleGraph);
+    /// database.add_quad(Quad::create(bnode("x"), p, Node::LiteralNode { literal: Literal::from("o") }, Some(bnode("g"))));
+    ///
+    /// let canonical = database.canonicalize(&Canonicalizer::new()).expect("Within limits");
+    /// assert_eq!("_:c14n0 <http://example.com/p> \"o\" _:c14n1 .\n", canonical.get_nquads());
+    /// ```
+    ///
+    pub fn canonicalize(&self, canonicalizer: &Canonicalizer) -> Result<CanonicalDataset, RDFError>{
+        canonicalizer.canonicalize(&self.stored_quads())
+    }
+
+    ///
+    /// Replaces all blank nodes of the dataset by skolem IRIs, blank node graph names as well
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use rdf4rust::rdf::database::{Database, GraphPattern};
+    /// use rdf4rust::rdf::skolem::Skolemizer;
+    /// use rdf4rust::rdf::graph::{GraphType, Quad, Node, ResourceNode};
+    /// use rdf4rust::rdf::node_factory::{Literal, BlankNode, iri};
+    ///
+    /// let p = iri("http://example.com/p");
+    /// let bnode = |id: &str| ResourceNode::BNode { bnode: BlankNode::create_blank_node(id) };
+    ///
+    /// let mut database = Database::new(GraphType::SimpleGraph);
+    /// database.add_quad(Quad::create(bnode("s"), p, Node::LiteralNode { literal: Literal::from("o") }, Some(bnode("g"))));
+    ///
+    /// let skolemizer = Skolemizer::new("https://example.com").unwrap();
+    /// database.skolemize(&skolemizer);
+    /// let quads = database.quads_for_pattern(None, None, None, &GraphPattern::AnyGraph);
+    /// assert_eq!("<https://example.com/.well-known/genid/s> <http://example.com/p> \"o\" <https://example.com/.well-known/genid/g> .", quads[0].to_string());
+    ///
+    /// database.unskolemize(&skolemizer);
+    /// assert!(database.get_named_graph("_:g").is_some());
+    /// let quads = database.quads_for_pattern(None, None, None, &GraphPattern::AnyGraph);
+    /// assert_eq!("_:s <http://example.com/p> \"o\" _:g .", quads[0].to_string());
+    /// ```
+    ///
+    pub fn skolemize(&mut self, skolemizer: &Skolemizer){
+        skolemizer.skolemize_graph(self.default_graph.as_mut());
+        for graph in self.named_graphs.values_mut() {
+            skolemizer.skolemize_graph(graph.as_mut());
+        }
+        self.rename_named_graphs(|name| skolemizer.skolemize_resource(name));
+    }
+
+    ///
+    /// Replaces all skolem IRIs of `skolemizer` in the dataset by blank nodes, graph names as well
+    ///
+    pub fn unskolemize(&mut self, skolemizer: &Skolemizer){
+        skolemizer.unskolemize_graph(self.default_graph.as_mut());
+        for graph in self.named_graphs.values_mut() {
+            skolemizer.unskolemize_graph(graph.as_mut());
+        }
+        self.rename_named_graphs(|name| skolemizer.unskolemize_resource(name));
+    }
+
+    ///
+    /// Renames all named graphs using `rename`, graphs getting the name of an existing graph are merged into it
+    ///
+    fn rename_named_graphs(&mut self, rename: impl Fn(ResourceNode) -> ResourceNode){
+        let keys: Vec<String> = self.named_graphs.keys().cloned().collect();
+        for key in keys {
+            let name = Database::parse_graph_name(&key);
+            let new_name = rename(name.clone());
+            if new_name == name {
+                continue;
+            }
+            if self.contains_named_graph(&new_name) {
+                self.merge_graph(Some(&name), Some(&new_name)).expect("Graph exists");
+                self.drop_named_graph(&name).expect("Graph exists");
+            } else {
+                self.rename_named_graph(&name, &new_name).expect("Graph exists and new name is unused");
             }
         }
+    }
+
+    ///
+    /// Lists the quads of the stored default graph and all named graphs, independent of the union default graph mode
+    ///
+    fn stored_quads(&self) -> Vec<Quad>{
+        let mut quads: Vec<Quad> = self.default_graph.list_statements(None, None, None).into_iter()
+            .map(|stmt| Quad::from_statement(stmt.clone(), None))
+            .collect();
+        for (name, g) in &self.named_graphs {
+            let graph_name = Database::parse_graph_name(name);
+            quads.extend(g.list_statements(None, None, None).into_iter().map(|stmt| Quad::from_statement(stmt.clone(), Some(graph_name.clone()))));
+        }
+        quads
+    }
 
+    ///
+    /// Converts the key of a named graph back to its name, blank nodes are stored as `_:id`.
+    ///
+    fn parse_graph_name(name: &str) -> ResourceNode{
+        if let Some(id) = name.strip_prefix("_:") {
+            return ResourceNode::BNode { bnode: BlankNode::create_blank_node(id) }
+        }
+        let iri = IRI::create_iri(&String::from(name)).expect("Graph names are valid IRIs");
+        ResourceNode::IRINode { iri: IRIResource::create_resource(iri) }
+    }
+
+    pub fn new(graph_type: GraphType)-> Self{
+        let graph_type = match graph_type {
+            GraphType::IndexedGraph => GraphType::IndexedGraph,
+            _ => GraphType::SimpleGraph
+        };
+        let mut database = Database{
+            graph_type,
+            default_graph: Box::new(SimpleGraph::new()),
+            named_graphs: HashMap::new(),
+            union_default_graph: false,
+            canonical_literals: false
+        };
+        database.default_graph = database.new_graph();
+        database
+    }
+
+    ///
+    /// Enables or disables the canonical literal option of all graphs of the database, see `SimpleGraph::with_canonical_literals`.
+    ///
+    /// Only graphs created afterwards are affected, so set it before adding statements.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use rdf4rust::rdf::database::Database;
+    /// use rdf4rust::rdf::graph::{GraphType, Statement, ResourceNode, Node};
+    /// use rdf4rust::rdf::node_factory::{Literal, iri};
+    /// use rdf4rust::rdf::xsd::XSD_INT;
+    ///
+    /// let int = |value: &str| Node::LiteralNode { literal: Literal::create_typed_literal(String::from(value), &XSD_INT) };
+    /// let stmt = |value: &str| Statement::create(ResourceNode::IRINode { iri: iri("http://example.com/sensor") }, iri("http://example.com/value"), int(value));
+    ///
+    /// let mut database = Database::new(GraphType::IndexedGraph).with_canonical_literals(true);
+    /// database.add_statement(Some(iri("http://example.com/g")), stmt("01"));
+    /// database.add_statement(Some(iri("http://example.com/g")), stmt("+1"));
+    /// assert_eq!(1, database.count());
+    ///
+    /// database.clear_graph(None).unwrap();
+    /// database.add_statement(None, stmt("01"));
+    /// database.add_statement(None, stmt("+1"));
+    /// assert_eq!(2, database.count());
+    /// ```
+    ///
+    pub fn with_canonical_literals(mut self, canonical_literals: bool) -> Self{
+        self.canonical_literals = canonical_literals;
+        self.default_graph = self.new_graph();
+        self
+    }
+
+    pub fn get_canonical_literals(&self) -> bool{
+        self.canonical_literals
     }
 
     pub fn get_default_graph(&self) -> &Box<dyn Graph> {
@@ -96,4 +605,75 @@ impl Database{
         self.named_graphs.get(name)
     }
 
-}
\ No newline at end of file
+}
+
+///
+/// The differences between two datasets, see `diff`
+///
+pub struct DatasetDiff{
+    added: Vec<Quad>,
+    removed: Vec<Quad>
+}
+
+impl DatasetDiff{
+
+    /// Gets the quads only in the new dataset, with the blank nodes of the new dataset
+    pub fn get_added(&self) -> &Vec<Quad>{
+        &self.added
+    }
+
+    /// Gets the quads only in the old dataset, with the blank nodes of the old dataset
+    pub fn get_removed(&self) -> &Vec<Quad>{
+        &self.removed
+    }
+
+    /// Checks if the datasets are isomorphic
+    pub fn is_empty(&self) -> bool{
+        self.added.is_empty() && self.removed.is_empty()
+    }
+}
+
+///
+/// Checks if both datasets are equal up to the renaming of blank nodes, including blank node graph names
+///
+/// Like `Database::canonicalize`, the stored default graphs are compared independent of the union default graph mode.
+///
+/// # Example
+///
+/// ```
+/// use rdf4rust::rdf::database::{is_isomorphic, Database};
+/// use rdf4rust::rdf::graph::{GraphType, Quad, Node, ResourceNode};
+/// use rdf4rust::rdf::node_factory::{Literal, BlankNode, iri};
+///
+/// let p = iri("http://example.com/p");
+/// let bnode = |id: &str| ResourceNode::BNode { bnode: BlankNode::create_blank_node(id) };
+/// let quad = |s: &str, g: &str| Quad::create(bnode(s), p.clone(), Node::LiteralNode { literal: Literal::from("o") }, Some(bnode(g)));
+///
+/// let mut a = Database::new(GraphType::SimpleGraph);
+/// a.add_quad(quad("s", "g"));
+/// let mut b = Database::new(GraphType::IndexedGraph);
+/// b.add_quad(quad("x", "y"));
+/// assert!(is_isomorphic(&a, &b));
+///
+/// // the subject is the graph name as well
+/// let mut c = Database::new(GraphType::SimpleGraph);
+/// c.add_quad(quad("x", "x"));
+/// assert!(!is_isomorphic(&a, &c));
+/// ```
+///
+pub fn is_isomorphic(a: &Database, b: &Database) -> bool{
+    a.count() == b.count() && diff(a, b).is_empty()
+}
+
+///
+/// Lists the quads added and removed from `old` to `new`, whereas blank nodes are matched up to isomorphism
+///
+/// Quads connected by blank nodes, also as graph names, are compared as a whole, see `graph::diff`.
+///
+pub fn diff(old: &Database, new: &Database) -> DatasetDiff{
+    let (added, removed) = diff_quads(&old.stored_quads(), &new.stored_quads());
+    DatasetDiff{
+        added,
+        removed
+    }
+}
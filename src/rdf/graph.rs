@@ -1,5 +1,7 @@
 use std::collections::{HashMap, HashSet};
-use crate::rdf::node_factory::{RDFNode, IRIResource, Literal, BlankNode};
+use crate::rdf::node_factory::{RDFNode, IRIResource, Literal, BlankNode, BlankNodeScope};
+use crate::rdf::canonicalization::diff_quads;
+use crate::rdf::xsd::rdf_base;
 use crate::io::reader::{parse_resolved_object, ParserError};
 use crate::util::iri::IRI;
 use crate::rdf::graph::ResourceNode::{BNode, IRINode};
@@ -14,13 +16,53 @@ pub trait Graph{
     fn count(&self)-> usize;
     fn list_all_statements(&self) -> &Vec<Statement>;
     fn add_statement(&mut self,stmt: Statement);
-    fn add_all_statements(&self,stmts: &Vec<Statement>);
-    fn remove_statement(&self,stmt: Statement);
-    fn remove_all_statements(&self,stmts: &Vec<Statement>);
+    fn add_all_statements(&mut self,stmts: &[Statement]);
+    fn remove_statement(&mut self,stmt: Statement);
+    fn remove_all_statements(&mut self,stmts: &[Statement]);
     fn list_statements_for_subject(&self,subject: ResourceNode) -> Vec<&Statement>;
     fn list_statements_for_object(&self,object: ResourceNode) -> Vec<&Statement>;
     fn list_statements_for_predicate(&self,predicate: ResourceNode) -> Vec<&Statement>;
     fn list_statements(&self, subject: Option<ResourceNode>, predicate: Option<IRIResource>, object: Option<Node>) -> Vec<&Statement>;
+
+    ///
+    /// Lists the statements whose subject or object is a quoted triple matching the pattern, whereas `None` matches everything
+    ///
+    /// Quoted triples can be matched as a whole by `list_statements` as well, this finds the statements about triples
+    /// only partially known, e.g. the annotations of all statements about a subject.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use rdf4rust::io::reader::{QuadIterator, Lang};
+    /// use rdf4rust::rdf::graph::{Graph, SimpleGraph, ResourceNode};
+    /// use rdf4rust::rdf::node_factory::iri;
+    ///
+    /// let input = "<< <http://example.com/alice> <http://xmlns.com/foaf/0.1/age> \"42\" >> <http://example.com/confidence> \"0.9\" .\n\
+    ///     << <http://example.com/bob> <http://xmlns.com/foaf/0.1/age> \"23\" >> <http://example.com/confidence> \"0.5\" .\n\
+    ///     <http://example.com/alice> <http://xmlns.com/foaf/0.1/age> \"42\" .\n";
+    /// let mut graph = SimpleGraph::new();
+    /// for quad in QuadIterator::new(input.as_bytes(), &Lang::NT) {
+    ///     graph.add_statement(quad.expect("Is valid N-Triples-star").into_statement().1);
+    /// }
+    ///
+    /// let alice = ResourceNode::IRINode { iri: iri("http://example.com/alice") };
+    /// let annotations = graph.list_statements_quoting(Some(alice), None, None);
+    /// assert_eq!(1, annotations.len());
+    /// assert_eq!("<< <http://example.com/alice> <http://xmlns.com/foaf/0.1/age> \"42\" >> <http://example.com/confidence> \"0.9\" .",
+    ///     annotations[0].to_string());
+    ///
+    /// // the quoted triple as a whole
+    /// let quoted = annotations[0].get_subject().clone();
+    /// assert_eq!(1, graph.list_statements(Some(quoted), None, None).len());
+    /// ```
+    ///
+    fn list_statements_quoting(&self, subject: Option<ResourceNode>, predicate: Option<IRIResource>, object: Option<Node>) -> Vec<&Statement>{
+        let quotes_match = |stmt: &&Statement| {
+            stmt.get_subject().as_triple().is_ok_and(|triple| triple.matches(&subject, &predicate, &object))
+                || stmt.get_object().as_triple().is_ok_and(|triple| triple.matches(&subject, &predicate, &object))
+        };
+        self.list_statements(None, None, None).into_iter().filter(quotes_match).collect()
+    }
     fn get_name(&self) -> Option<String>;
     fn load(&self);
     fn store(&self);
@@ -33,13 +75,19 @@ pub enum GraphType{
     FullIndexedGraph
 }
 
-#[derive(PartialOrd, PartialEq, Eq, Hash)]
+#[derive(Clone, PartialOrd, PartialEq, Eq, Hash)]
 pub enum ResourceNode{
     IRINode {
         iri: IRIResource
     },
     BNode{
         bnode:BlankNode
+    },
+    ///
+    /// A quoted triple of RDF-star, written `<< s p o >>`
+    ///
+    TripleNode{
+        triple: Box<Statement>
     }
 }
 
@@ -51,26 +99,32 @@ impl ResourceNode {
         match &self{
             ResourceNode::IRINode { iri } => {iri.as_string(false)}
             ResourceNode::BNode { bnode } => {bnode.as_string(true)}
+            ResourceNode::TripleNode { triple } => {triple.as_quoted_string()}
         }
     }
 
     pub fn is_bnode(&self)->bool{
         match &self{
-            ResourceNode::IRINode { .. } => {false}
             ResourceNode::BNode { .. } => {true}
+            _ => {false}
         }
     }
     pub fn is_uri(&self)->bool{
         match &self{
             ResourceNode::IRINode { .. } => {true}
-            ResourceNode::BNode { .. } => {false}
+            _ => {false}
         }
     }
 
+    pub fn is_triple(&self)->bool{
+        matches!(self, ResourceNode::TripleNode { .. })
+    }
+
     pub fn as_uri_resource(&self) -> Result<&IRIResource,&str> {
         match self {
             ResourceNode::IRINode { iri: uri } => { Ok(uri)}
             ResourceNode::BNode { .. } => { Err("Blank Node cannot be converted to URI resource.")}
+            ResourceNode::TripleNode { .. } => { Err("Quoted triple cannot be converted to URI resource.")}
         }
     }
 
@@ -78,11 +132,28 @@ impl ResourceNode {
         match self {
             ResourceNode::IRINode { .. } => { Err("URI Node cannot be converted to Blank Node.")}
             ResourceNode::BNode { bnode } => { Ok(bnode)}
+            ResourceNode::TripleNode { .. } => { Err("Quoted triple cannot be converted to Blank Node.")}
+        }
+    }
+
+    pub fn as_triple(&self) -> Result<&Statement,&str> {
+        match self {
+            ResourceNode::TripleNode { triple } => { Ok(triple)}
+            _ => { Err("Only a quoted triple can be converted to Statement.")}
+        }
+    }
+
+    /// The term in N-Triples syntax, e.g. `<http://example.com/s>`
+    fn as_term_string(&self) -> String {
+        match &self{
+            ResourceNode::IRINode { iri } => {iri.as_string(true)}
+            ResourceNode::BNode { bnode } => {bnode.as_string(true)}
+            ResourceNode::TripleNode { triple } => {triple.as_quoted_string()}
         }
     }
 }
 
-#[derive(PartialOrd, PartialEq, Eq, Hash)]
+#[derive(Clone, PartialOrd, PartialEq, Eq, Hash)]
 pub enum Node{
     IRINode {
         iri: IRIResource
@@ -92,6 +163,22 @@ pub enum Node{
     },
     BNode{
         bnode:BlankNode
+    },
+    ///
+    /// A quoted triple of RDF-star, written `<< s p o >>`
+    ///
+    TripleNode{
+        triple: Box<Statement>
+    }
+}
+
+impl From<ResourceNode> for Node{
+    fn from(node: ResourceNode) -> Self {
+        match node {
+            ResourceNode::IRINode { iri } => Node::IRINode { iri },
+            ResourceNode::BNode { bnode } => Node::BNode { bnode },
+            ResourceNode::TripleNode { triple } => Node::TripleNode { triple }
+        }
     }
 }
 
@@ -102,36 +189,39 @@ impl Node{
             Node::IRINode { iri } => {iri.as_string(false)}
             Node::LiteralNode { literal } => {literal.as_string(true)}
             Node::BNode { bnode } => {bnode.as_string(true)}
+            Node::TripleNode { triple } => {triple.as_quoted_string()}
         }
     }
 
     pub fn is_literal(&self)->bool{
         match &self{
-            Node::IRINode { .. } => {false}
             Node::LiteralNode { .. } => {true}
-            Node::BNode { .. } => {false}
+            _ => {false}
         }
     }
     pub fn is_bnode(&self)->bool{
         match &self{
-            Node::IRINode { .. } => {false}
-            Node::LiteralNode { .. } => {false}
             Node::BNode { .. } => {true}
+            _ => {false}
         }
     }
     pub fn is_uri(&self)->bool{
         match &self{
             Node::IRINode { .. } => {true}
-            Node::LiteralNode { .. } => {false}
-            Node::BNode { .. } => {false}
+            _ => {false}
         }
     }
 
+    pub fn is_triple(&self)->bool{
+        matches!(self, Node::TripleNode { .. })
+    }
+
     pub fn as_uri_resource(&self) -> Result<&IRIResource,&str> {
         match self {
             Node::IRINode { iri: uri } => { Ok(uri)}
             Node::LiteralNode { .. } => {Err("Cannot convert Literal to URI Resource")}
             Node::BNode { .. } => {Err("Cannot convert Blank node to URI Resource")}
+            Node::TripleNode { .. } => {Err("Cannot convert quoted triple to URI Resource")}
         }
     }
 
@@ -140,6 +230,7 @@ impl Node{
             Node::BNode { bnode } => {Ok(bnode)}
             Node::IRINode { .. } => { Err("Cannot convert URI Node to Blank Node")}
             Node::LiteralNode { .. } => {Err("Cannot convert Literal to Blank Node")}
+            Node::TripleNode { .. } => {Err("Cannot convert quoted triple to Blank Node")}
         }
     }
 
@@ -148,6 +239,46 @@ impl Node{
             Node::LiteralNode { literal } => {Ok(literal)}
             Node::IRINode { .. } => { Err("Cannot convert URI Node to Literal")}
             Node::BNode { .. } => {Err("Cannot convert Blank node to Literal")}
+            Node::TripleNode { .. } => {Err("Cannot convert quoted triple to Literal")}
+        }
+    }
+
+    pub fn as_triple(&self) -> Result<&Statement,&str> {
+        match self {
+            Node::TripleNode { triple } => {Ok(triple)}
+            _ => {Err("Only a quoted triple can be converted to Statement")}
+        }
+    }
+
+    ///
+    /// Maps a literal to its canonical form (see `Literal::canonicalize`), also within quoted triples.
+    /// IRIs and blank nodes are returned unchanged
+    ///
+    pub fn canonicalize(self) -> Node{
+        match self {
+            Node::LiteralNode { literal } => Node::LiteralNode { literal: literal.canonicalize() },
+            Node::TripleNode { triple } => Node::TripleNode { triple: Box::new(triple.canonicalize_literals()) },
+            node => node
+        }
+    }
+
+    /// The term in N-Triples syntax, e.g. `<http://example.com/o>`
+    pub(crate) fn as_term_string(&self) -> String{
+        match &self{
+            Node::IRINode { iri } => {iri.as_string(true)}
+            Node::LiteralNode { literal } => {literal.as_string(true)}
+            Node::BNode { bnode } => {bnode.as_string(true)}
+            Node::TripleNode { triple } => {triple.as_quoted_string()}
+        }
+    }
+
+    /// Converts the node to a subject, None for literals
+    pub fn to_resource(&self) -> Option<ResourceNode>{
+        match self {
+            Node::IRINode { iri } => Some(ResourceNode::IRINode { iri: iri.clone() }),
+            Node::BNode { bnode } => Some(ResourceNode::BNode { bnode: bnode.clone() }),
+            Node::TripleNode { triple } => Some(ResourceNode::TripleNode { triple: triple.clone() }),
+            Node::LiteralNode { .. } => None
         }
     }
 }
@@ -159,12 +290,12 @@ impl Node{
 ///
 /// A Statement is a Triple of subject, predicate and object
 ///
-/// * `subject` - `URIResource` or `BlankNode`
+/// * `subject` - `URIResource` or `BlankNode` or a quoted triple
 /// * `predicate` - `URIResource`
-/// * `object` - `URIResource` or `BlankNode` or `Literal`
+/// * `object` - `URIResource` or `BlankNode` or `Literal` or a quoted triple
 ///
 /// Hence Variables are not allowed, which makes the distinction to a plain `Triple` where all can be a `Variable` as well.
-#[derive(PartialOrd, PartialEq, Eq, Hash)]
+#[derive(Clone, PartialOrd, PartialEq, Eq, Hash)]
 pub struct Statement{
     subject: ResourceNode,
     predicate: IRIResource,
@@ -174,21 +305,7 @@ pub struct Statement{
 impl Display for Statement{
 
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
-        let subj = match &self.subject{
-            ResourceNode::IRINode { iri } => {iri.as_string(true)}
-            ResourceNode::BNode { bnode } => {bnode.as_string(true)}
-        };
-        let str = match &self.object{
-            Node::IRINode { iri } => {
-                format!("{} <{}> <{}> .", subj, self.predicate.get_iri(), iri.get_iri())}
-            Node::LiteralNode { literal } => {
-                format!("{} <{}> {} .", subj, self.predicate.get_iri(), literal.as_string(true))
-            }
-            Node::BNode { bnode } => {
-                format!("{} <{}> {} .", subj, self.predicate.get_iri(), bnode.as_string(true))
-            }
-        };
-        write!(f,"{}",str)
+        write!(f, "{} {} {} .", self.subject.as_term_string(), self.predicate.as_string(true), self.object.as_term_string())
     }
 
 }
@@ -217,6 +334,170 @@ impl Statement{
     pub fn get_object(&self)-> &Node{
         &self.object
     }
+
+    ///
+    /// Maps a literal object to its canonical form, see `Literal::canonicalize`
+    ///
+    pub fn canonicalize_literals(self) -> Statement{
+        let subject = match self.subject {
+            ResourceNode::TripleNode { triple } => ResourceNode::TripleNode { triple: Box::new(triple.canonicalize_literals()) },
+            subject => subject
+        };
+        Statement{
+            subject,
+            predicate: self.predicate,
+            object: self.object.canonicalize()
+        }
+    }
+
+    ///
+    /// Maps subject and object with `map` and the predicate with `map_predicate`, within quoted triples as well
+    ///
+    /// `map` is never called with a quoted triple and has to map resources to resources.
+    ///
+    pub(crate) fn map_terms(self, map: &dyn Fn(Node) -> Node, map_predicate: &dyn Fn(IRIResource) -> IRIResource) -> Statement{
+        let subject = match self.subject {
+            ResourceNode::TripleNode { triple } => ResourceNode::TripleNode { triple: Box::new(triple.map_terms(map, map_predicate)) },
+            subject => map(Node::from(subject)).to_resource().expect("Resources are mapped to resources")
+        };
+        let object = match self.object {
+            Node::TripleNode { triple } => Node::TripleNode { triple: Box::new(triple.map_terms(map, map_predicate)) },
+            object => map(object)
+        };
+        Statement{
+            subject,
+            predicate: map_predicate(self.predicate),
+            object
+        }
+    }
+
+    ///
+    /// The statement as quoted triple in N-Triples-star syntax, e.g. `<< <http://example.com/s> <http://example.com/p> "o" >>`
+    ///
+    pub fn as_quoted_string(&self) -> String{
+        format!("<< {} {} {} >>", self.subject.as_term_string(), self.predicate.as_string(true), self.object.as_term_string())
+    }
+
+    ///
+    /// Checks if the statement matches the pattern, whereas `None` matches everything
+    ///
+    pub fn matches(&self, subject: &Option<ResourceNode>, predicate: &Option<IRIResource>, object: &Option<Node>) -> bool{
+        (subject.is_none() || subject.as_ref() == Some(&self.subject)) &&
+            (predicate.is_none() || predicate.as_ref() == Some(&self.predicate)) &&
+            (object.is_none() || object.as_ref() == Some(&self.object))
+    }
+}
+
+///
+/// A `Statement` together with the graph it belongs to
+///
+/// * `graph` - The name of the graph (`URIResource` or `BlankNode`) or `None` if the statement belongs to the default graph
+///
+/// # Example
+///
+/// ```
+/// use rdf4rust::rdf::graph::{Quad, Node, ResourceNode};
+/// use rdf4rust::rdf::node_factory::{Literal, iri};
+///
+/// let quad = Quad::create(
+///     ResourceNode::IRINode { iri: iri("http://example.com/s") },
+///     iri("http://example.com/p"),
+///     Node::LiteralNode { literal: Literal::from("o") },
+///     Some(ResourceNode::IRINode { iri: iri("http://example.com/g") }));
+///
+/// assert_eq!("<http://example.com/s> <http://example.com/p> \"o\" <http://example.com/g> .", quad.to_string());
+/// ```
+///
+#[derive(Clone, PartialOrd, PartialEq, Eq, Hash)]
+pub struct Quad{
+    subject: ResourceNode,
+    predicate: IRIResource,
+    object: Node,
+    graph: Option<ResourceNode>
+}
+
+impl Display for Quad{
+
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let (subj, obj) = (self.subject.as_term_string(), self.object.as_term_string());
+        match &self.graph{
+            Some(graph) => write!(f, "{} {} {} {} .", subj, self.predicate.as_string(true), obj, graph.as_term_string()),
+            None => write!(f, "{} {} {} .", subj, self.predicate.as_string(true), obj)
+        }
+    }
+
+}
+
+impl Quad{
+
+    pub fn create(subject: ResourceNode,
+                  predicate: IRIResource,
+                  object: Node,
+                  graph: Option<ResourceNode>) -> Quad{
+        Quad{
+            subject,
+            predicate,
+            object,
+            graph
+        }
+    }
+
+    pub fn from_statement(stmt: Statement, graph: Option<ResourceNode>) -> Quad{
+        Quad::create(stmt.subject, stmt.predicate, stmt.object, graph)
+    }
+
+    ///
+    /// Splits the quad into its graph name and the `Statement`
+    ///
+    pub fn into_statement(self) -> (Option<ResourceNode>, Statement){
+        (self.graph, Statement::create(self.subject, self.predicate, self.object))
+    }
+
+    pub fn get_subject(&self)-> &ResourceNode{
+        &self.subject
+    }
+
+    pub fn get_predicate(&self)-> &IRIResource {
+        &self.predicate
+    }
+
+    pub fn get_object(&self)-> &Node{
+        &self.object
+    }
+
+    pub fn get_graph(&self)-> &Option<ResourceNode>{
+        &self.graph
+    }
+
+    ///
+    /// Replaces the blank nodes of subject, object and graph, whose IDs are taken as labels, by the blank nodes of `scope`
+    ///
+    pub fn scope_blank_nodes(self, scope: &BlankNodeScope) -> Quad{
+        self.map_terms(&|node| match node {
+            Node::BNode { bnode } => Node::BNode { bnode: scope.get_blank_node(&bnode.get_value()) },
+            node => node
+        }, &|predicate| predicate)
+    }
+
+    ///
+    /// Normalizes the IRIs of subject, predicate, object and graph, see `IRI::normalize`
+    ///
+    pub fn normalize_iris(self) -> Quad{
+        let normalize = |iri: IRIResource| IRIResource::create_resource(iri.get_iri().normalize());
+        self.map_terms(&|node| match node {
+            Node::IRINode { iri } => Node::IRINode { iri: normalize(iri) },
+            node => node
+        }, &normalize)
+    }
+
+    ///
+    /// Maps all terms like `Statement::map_terms`, the graph name with `map` as well
+    ///
+    pub(crate) fn map_terms(self, map: &dyn Fn(Node) -> Node, map_predicate: &dyn Fn(IRIResource) -> IRIResource) -> Quad{
+        let (graph, stmt) = self.into_statement();
+        let graph = graph.map(|graph| map(Node::from(graph)).to_resource().expect("Resources are mapped to resources"));
+        Quad::from_statement(stmt.map_terms(map, map_predicate), graph)
+    }
 }
 
 ///
@@ -231,8 +512,34 @@ impl Statement{
 ///
 /// Thus complexity is always O(N)
 ///
+/// # Canonical literals
+///
+/// If enabled with `with_canonical_literals`, literal objects are canonicalized on insert and in patterns,
+/// so statements with equal values are merged and found by any lexical form of the value.
+///
+/// ```
+/// use rdf4rust::rdf::graph::{Graph, SimpleGraph, Statement, Node, ResourceNode};
+/// use rdf4rust::rdf::node_factory::{Literal, iri};
+/// use rdf4rust::rdf::xsd::XSD_INT;
+///
+/// let int = |value: &str| Node::LiteralNode { literal: Literal::create_typed_literal(String::from(value), &XSD_INT) };
+/// let stmt = |value: &str| Statement::create(ResourceNode::IRINode { iri: iri("http://example.com/sensor") }, iri("http://example.com/value"), int(value));
+///
+/// let mut graph = SimpleGraph::new().with_canonical_literals(true);
+/// graph.add_statement(stmt("01"));
+/// graph.add_statement(stmt("+1"));
+/// assert_eq!(1, graph.count());
+/// assert_eq!(1, graph.list_statements(None, None, Some(int("001"))).len());
+///
+/// let mut graph = SimpleGraph::new();
+/// graph.add_statement(stmt("01"));
+/// graph.add_statement(stmt("+1"));
+/// assert_eq!(2, graph.count());
+/// ```
+///
 pub struct SimpleGraph{
-     statements: HashSet<Statement>
+     statements: HashSet<Statement>,
+     canonical_literals: bool
 }
 
 impl Graph for SimpleGraph{
@@ -245,19 +552,25 @@ impl Graph for SimpleGraph{
     }
 
     fn add_statement(&mut self, stmt: Statement) {
-       &self.statements.insert(stmt);
+       let stmt = self.prepare(stmt);
+       self.statements.insert(stmt);
     }
 
-    fn add_all_statements(&self, stmts: &Vec<Statement>) {
-        todo!()
+    fn add_all_statements(&mut self, stmts: &[Statement]) {
+        for stmt in stmts {
+            self.add_statement(stmt.clone());
+        }
     }
 
-    fn remove_statement(&self, stmt: Statement) {
-        todo!()
+    fn remove_statement(&mut self, stmt: Statement) {
+        let stmt = self.prepare(stmt);
+        self.statements.remove(&stmt);
     }
 
-    fn remove_all_statements(&self, stmts: &Vec<Statement>) {
-        todo!()
+    fn remove_all_statements(&mut self, stmts: &[Statement]) {
+        for stmt in stmts {
+            self.remove_statement(stmt.clone());
+        }
     }
 
     fn list_statements_for_subject(&self, subject: ResourceNode) -> Vec<&Statement> {
@@ -273,7 +586,8 @@ impl Graph for SimpleGraph{
     }
 
     fn list_statements(&self, subject: Option<ResourceNode>, predicate: Option<IRIResource>, object: Option<Node>) -> Vec<&Statement> {
-        todo!()
+        let object = if self.canonical_literals {object.map(Node::canonicalize)} else {object};
+        self.statements.iter().filter(|stmt| stmt.matches(&subject, &predicate, &object)).collect()
     }
 
     fn get_name(&self) -> Option<String> {
@@ -300,10 +614,27 @@ impl Graph for SimpleGraph{
 impl SimpleGraph{
     pub fn new()-> SimpleGraph{
         SimpleGraph{
-            statements: HashSet::new()
+            statements: HashSet::new(),
+            canonical_literals: false
         }
     }
 
+    ///
+    /// Enables the canonicalization of literal objects on insert and in patterns, disabled by default
+    ///
+    pub fn with_canonical_literals(mut self, canonical_literals: bool) -> Self{
+        self.canonical_literals = canonical_literals;
+        self
+    }
+
+    pub fn get_canonical_literals(&self) -> bool{
+        self.canonical_literals
+    }
+
+    fn prepare(&self, stmt: Statement) -> Statement{
+        if self.canonical_literals {stmt.canonicalize_literals()} else {stmt}
+    }
+
 }
 
 
@@ -331,7 +662,8 @@ impl SimpleGraph{
 pub struct IndexedGraph{
     statements: Vec<Statement>,
     spo: HashMap<String, HashSet<usize>>,
-    ops: HashMap<String, HashSet<usize>>
+    ops: HashMap<String, HashSet<usize>>,
+    canonical_literals: bool
 }
 
 impl IndexedGraph{
@@ -339,9 +671,65 @@ impl IndexedGraph{
         IndexedGraph{
             statements: Vec::new(),
             spo: HashMap::new(),
-            ops: HashMap::new()
+            ops: HashMap::new(),
+            canonical_literals: false
+        }
+    }
+
+    ///
+    /// Enables the canonicalization of literal objects on insert and in patterns, disabled by default, see `SimpleGraph`
+    ///
+    pub fn with_canonical_literals(mut self, canonical_literals: bool) -> Self{
+        self.canonical_literals = canonical_literals;
+        self
+    }
+
+    pub fn get_canonical_literals(&self) -> bool{
+        self.canonical_literals
+    }
+
+    fn prepare(&self, stmt: Statement) -> Statement{
+        if self.canonical_literals {stmt.canonicalize_literals()} else {stmt}
+    }
+
+    fn find_index(&self, stmt: &Statement) -> Option<usize>{
+        match self.spo.get(&stmt.subject.as_string()) {
+            Some(indices) => indices.iter().find(|&&x| &self.statements[x] == stmt).copied(),
+            None => None
+        }
+    }
+
+    fn unindex(&mut self, index: usize){
+        let subj_str = self.statements[index].subject.as_string();
+        let obj_str = self.statements[index].object.as_string();
+        if let Some(indices) = self.spo.get_mut(&subj_str) {
+            indices.remove(&index);
+            if indices.is_empty() {
+                self.spo.remove(&subj_str);
+            }
+        }
+        if let Some(indices) = self.ops.get_mut(&obj_str) {
+            indices.remove(&index);
+            if indices.is_empty() {
+                self.ops.remove(&obj_str);
+            }
         }
     }
+
+    fn remove_index(&mut self, index: usize){
+        //swap the last statement into the gap, so only its index entries have to be updated
+        let last = self.statements.len() - 1;
+        self.unindex(index);
+        if index != last {
+            self.unindex(last);
+            self.statements.swap(index, last);
+            let subj_str = self.statements[index].subject.as_string();
+            let obj_str = self.statements[index].object.as_string();
+            self.spo.entry(subj_str).or_default().insert(index);
+            self.ops.entry(obj_str).or_default().insert(index);
+        }
+        self.statements.pop();
+    }
 }
 
 impl Graph for IndexedGraph{
@@ -352,10 +740,14 @@ impl Graph for IndexedGraph{
     }
 
     fn list_all_statements(&self) -> &Vec<Statement> {
-        todo!()
+        &self.statements
     }
 
     fn add_statement(&mut self, stmt: Statement) {
+        let stmt = self.prepare(stmt);
+        if self.find_index(&stmt).is_some() {
+            return;
+        }
         let mut index = self.statements.len();
 
         let subj_str = (&stmt).subject.as_string();
@@ -374,16 +766,23 @@ impl Graph for IndexedGraph{
 
     }
 
-    fn add_all_statements(&self, stmts: &Vec<Statement>) {
-        todo!()
+    fn add_all_statements(&mut self, stmts: &[Statement]) {
+        for stmt in stmts {
+            self.add_statement(stmt.clone());
+        }
     }
 
-    fn remove_statement(&self, stmt: Statement) {
-        todo!()
+    fn remove_statement(&mut self, stmt: Statement) {
+        let stmt = self.prepare(stmt);
+        if let Some(index) = self.find_index(&stmt) {
+            self.remove_index(index);
+        }
     }
 
-    fn remove_all_statements(&self, stmts: &Vec<Statement>) {
-        todo!()
+    fn remove_all_statements(&mut self, stmts: &[Statement]) {
+        for stmt in stmts {
+            self.remove_statement(stmt.clone());
+        }
     }
 
     fn list_statements_for_subject(&self, subject: ResourceNode) -> Vec<&Statement> {
@@ -407,7 +806,22 @@ impl Graph for IndexedGraph{
     }
 
     fn list_statements(&self, subject: Option<ResourceNode>, predicate: Option<IRIResource>, object: Option<Node>) -> Vec<&Statement> {
-        todo!()
+        let object = if self.canonical_literals {object.map(Node::canonicalize)} else {object};
+        //use the SPO resp. OPS index if possible, otherwise iterate over all statements
+        let candidates: Vec<&Statement> = if let Some(subj) = &subject {
+            match self.spo.get(&subj.as_string()) {
+                Some(indices) => indices.iter().map(|&x| &self.statements[x]).collect(),
+                None => Vec::new()
+            }
+        } else if let Some(obj) = &object {
+            match self.ops.get(&obj.as_string()) {
+                Some(indices) => indices.iter().map(|&x| &self.statements[x]).collect(),
+                None => Vec::new()
+            }
+        } else {
+            self.statements.iter().collect()
+        };
+        candidates.into_iter().filter(|stmt| stmt.matches(&subject, &predicate, &object)).collect()
     }
 
     fn get_name(&self) -> Option<String> {
@@ -451,4 +865,153 @@ pub struct FullIndexedGraph{
     spo: HashMap<ResourceNode, HashSet<usize>>,
     ops: HashMap<IRIResource, HashSet<usize>>,
     pso: HashMap<Node, HashSet<usize>>
-}
\ No newline at end of file
+}
+///
+/// The differences between two graphs, see `diff`
+///
+pub struct GraphDiff{
+    added: Vec<Statement>,
+    removed: Vec<Statement>
+}
+
+impl GraphDiff{
+
+    /// Gets the statements only in the new graph, with the blank nodes of the new graph
+    pub fn get_added(&self) -> &Vec<Statement>{
+        &self.added
+    }
+
+    /// Gets the statements only in the old graph, with the blank nodes of the old graph
+    pub fn get_removed(&self) -> &Vec<Statement>{
+        &self.removed
+    }
+
+    /// Checks if the graphs are isomorphic
+    pub fn is_empty(&self) -> bool{
+        self.added.is_empty() && self.removed.is_empty()
+    }
+}
+
+fn graph_quads(graph: &dyn Graph) -> Vec<Quad>{
+    graph.list_statements(None, None, None).into_iter().map(|stmt| Quad::from_statement(stmt.clone(), None)).collect()
+}
+
+///
+/// Checks if both graphs are equal up to the renaming of blank nodes
+///
+/// Blank nodes are matched using the RDFC-1.0 canonicalization, which has no limit on its deep iterations here,
+/// so graphs with many indistinguishable blank nodes may take very long.
+///
+/// # Example
+///
+/// ```
+/// use rdf4rust::rdf::graph::{is_isomorphic, Graph, SimpleGraph, IndexedGraph, Statement, Node, ResourceNode};
+/// use rdf4rust::rdf::node_factory::{BlankNode, iri};
+///
+/// let knows = iri("http://xmlns.com/foaf/0.1/knows");
+/// let stmt = |s: &str, o: &str| Statement::create(ResourceNode::BNode { bnode: BlankNode::create_blank_node(s) },
+///     knows.clone(), Node::BNode { bnode: BlankNode::create_blank_node(o) });
+///
+/// let mut a = SimpleGraph::new();
+/// a.add_statement(stmt("alice", "bob"));
+/// let mut b = IndexedGraph::new();
+/// b.add_statement(stmt("x", "y"));
+/// assert!(is_isomorphic(&a, &b));
+///
+/// let mut c = SimpleGraph::new();
+/// c.add_statement(stmt("x", "x"));
+/// assert!(!is_isomorphic(&a, &c));
+/// ```
+///
+pub fn is_isomorphic(a: &dyn Graph, b: &dyn Graph) -> bool{
+    a.count() == b.count() && diff(a, b).is_empty()
+}
+
+///
+/// Lists the statements added and removed from `old` to `new`, whereas blank nodes are matched up to isomorphism
+///
+/// Statements connected by blank nodes are compared as a whole: if one of them changed, all of them are reported
+/// as removed from `old` and added to `new`. Statements without blank nodes are compared directly.
+///
+/// # Example
+///
+/// ```
+/// use rdf4rust::rdf::graph::{diff, Graph, SimpleGraph, Statement, Node, ResourceNode};
+/// use rdf4rust::rdf::node_factory::{Literal, BlankNode, RDFNode, iri};
+///
+/// let bnode = |id: &str| ResourceNode::BNode { bnode: BlankNode::create_blank_node(id) };
+/// let name = |s: ResourceNode, name: &str| Statement::create(s, iri("http://xmlns.com/foaf/0.1/name"), Node::LiteralNode { literal: Literal::from(name) });
+///
+/// let mut old = SimpleGraph::new();
+/// old.add_statement(name(bnode("a"), "Alice"));
+/// old.add_statement(name(bnode("b"), "Bob"));
+/// old.add_statement(name(ResourceNode::IRINode { iri: iri("http://example.com/carol") }, "Carol"));
+///
+/// let mut new = SimpleGraph::new();
+/// new.add_statement(name(bnode("x"), "Alice"));
+/// new.add_statement(name(bnode("y"), "Robert"));
+/// new.add_statement(name(ResourceNode::IRINode { iri: iri("http://example.com/carol") }, "Carol"));
+///
+/// let changes = diff(&old, &new);
+/// assert_eq!(1, changes.get_added().len());
+/// assert_eq!("Robert", changes.get_added()[0].get_object().as_literal().unwrap().get_value());
+/// assert_eq!(1, changes.get_removed().len());
+/// assert_eq!("Bob", changes.get_removed()[0].get_object().as_literal().unwrap().get_value());
+/// ```
+///
+pub fn diff(old: &dyn Graph, new: &dyn Graph) -> GraphDiff{
+    let (added, removed) = diff_quads(&graph_quads(old), &graph_quads(new));
+    GraphDiff{
+        added: added.into_iter().map(|quad| quad.into_statement().1).collect(),
+        removed: removed.into_iter().map(|quad| quad.into_statement().1).collect()
+    }
+}
+
+///
+/// Reads the members of the RDF list (`rdf:first`/`rdf:rest`) starting at `head`
+///
+/// # Returns
+///
+/// The members or None if the list is not well-formed, e.g. a cell has several `rdf:first` values or the list is cyclic
+///
+/// # Example
+///
+/// ```
+/// use rdf4rust::rdf::graph::{read_list, Graph, SimpleGraph, Statement, Node, ResourceNode};
+/// use rdf4rust::rdf::node_factory::{Literal, BlankNode, rdf};
+///
+/// let cell = |id: &str| ResourceNode::BNode { bnode: BlankNode::create_blank_node(id) };
+///
+/// let mut graph = SimpleGraph::new();
+/// graph.add_statement(Statement::create(cell("l0"), rdf("first"), Node::LiteralNode { literal: Literal::from("a") }));
+/// graph.add_statement(Statement::create(cell("l0"), rdf("rest"), Node::from(cell("l1"))));
+/// graph.add_statement(Statement::create(cell("l1"), rdf("first"), Node::LiteralNode { literal: Literal::from("b") }));
+/// graph.add_statement(Statement::create(cell("l1"), rdf("rest"), Node::IRINode { iri: rdf("nil") }));
+///
+/// assert_eq!(2, read_list(&graph, &Node::from(cell("l0"))).expect("Is a list").len());
+/// assert_eq!(0, read_list(&graph, &Node::IRINode { iri: rdf("nil") }).expect("Is the empty list").len());
+/// assert!(read_list(&graph, &Node::from(cell("l2"))).is_none());
+/// ```
+///
+pub fn read_list(graph: &dyn Graph, head: &Node) -> Option<Vec<Node>>{
+    let rdf = |local_name: &str| IRIResource::create_resource(IRI::create_iri(&(rdf_base() + local_name)).expect("Is valid IRI"));
+    let (first, rest, nil) = (rdf("first"), rdf("rest"), Node::IRINode { iri: rdf("nil") });
+    let mut items = Vec::new();
+    let mut visited = HashSet::new();
+    let mut node = head.clone();
+    while node != nil {
+        let cell = node.to_resource()?;
+        if !visited.insert(cell.clone()) {
+            return None
+        }
+        match (graph.list_statements(Some(cell.clone()), Some(first.clone()), None).as_slice(),
+               graph.list_statements(Some(cell), Some(rest.clone()), None).as_slice()) {
+            ([first], [rest]) => {
+                items.push(first.get_object().clone());
+                node = rest.get_object().clone();
+            }
+            _ => return None
+        }
+    }
+    Some(items)
+}
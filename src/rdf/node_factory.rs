@@ -3,13 +3,15 @@ use crate::rdf::xsd::*;
 use uuid::Uuid;
 use std::fmt;
 use std::ops::Add;
-use std::error::Error;
+use crate::error::{RDFError, ErrorKind};
 use std::collections::HashMap;
-use std::any::Any;
+use std::convert::TryFrom;
 use std::str::FromStr;
-use std::num::{NonZeroUsize, NonZeroIsize};
 use base64::DecodeError;
 use crate::util::hex_binary::{binary_to_string, string_to_binary};
+use crate::util::date_time::{DateTime, Date, Time, GYear, GYearMonth, GMonth, GDay, GMonthDay, Duration};
+use crate::util::decimal::{Decimal, BigInt, parse_integer};
+use crate::util::language_tag::{normalize_language_tag, lang_matches};
 
 
 ///
@@ -34,7 +36,7 @@ pub trait RDFNode {
     /// use rdf4rust::util::iri::IRI;
     ///
     /// // This should be true
-    /// let literal_node = Literal::create_byte_literal(8);
+    /// let literal_node = Literal::from(8i8);
     /// assert!(literal_node.is_literal());
     ///
     ///
@@ -65,7 +67,6 @@ pub trait RDFNode {
     ///
     /// ```
     /// use rdf4rust::rdf::node_factory::{Literal, IRIResource, Variable, BlankNode, RDFNode};
-    /// use rdf4rust::rdf::node_factory::RDFNode;
     /// use rdf4rust::util::iri::IRI;
     ///
     /// // This should be true
@@ -75,7 +76,7 @@ pub trait RDFNode {
     /// assert!(uri_node.is_iri());
     ///
     /// // Everything else should be false
-    /// let literal_node = Literal::create_byte_literal(8);
+    /// let literal_node = Literal::from(8i8);
     /// assert!(!literal_node.is_iri());
     ///
     /// let var_node = Variable::create_var("abc");
@@ -97,177 +98,415 @@ pub trait RDFNode {
     fn get_value(&self) -> String;
 }
 
-#[derive(PartialOrd, PartialEq, Eq, Hash)]
+///
+/// The base direction of a directional language-tagged string (RDF 1.2), written as `"value"@lang--ltr` resp. `--rtl`
+///
+#[derive(Clone, Copy, Debug, PartialOrd, PartialEq, Eq, Hash)]
+pub enum BaseDirection{
+    /// Left-to-right
+    Ltr,
+    /// Right-to-left
+    Rtl
+}
+
+impl fmt::Display for BaseDirection{
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self{
+            BaseDirection::Ltr => write!(f, "ltr"),
+            BaseDirection::Rtl => write!(f, "rtl")
+        }
+    }
+}
+
+impl FromStr for BaseDirection{
+    type Err = InvalidLiteralError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s{
+            "ltr" => Ok(BaseDirection::Ltr),
+            "rtl" => Ok(BaseDirection::Rtl),
+            _ => Err(InvalidLiteralError::new(ErrorKind::InvalidLiteral, format!("{} is not a valid base direction, expected ltr or rtl.", s)))
+        }
+    }
+}
+
+#[derive(Clone, PartialOrd, PartialEq, Eq, Hash)]
 pub struct Literal{
     value: String,
-    dtype: &'static XSDDataType,
-    lang: Option<String>
+    dtype: XSDDataTypeRef,
+    lang: Option<String>,
+    direction: Option<BaseDirection>
 }
 
 impl Literal{
 
-    fn as_object_generic<T: Any + FromStr>(&self) ->Option<Box<dyn Any>> {
-        self.as_object_generic_lc::<T>(false)
+    ///
+    /// Gets the value of the literal according to its datatype
+    ///
+    /// Literals with a language tag are always `LiteralValue::LangString` resp. `LiteralValue::DirLangString`.
+    /// Restrictions of built-in types (see `XSDDataType::create_restriction`) use the value of the type they restrict,
+    /// literals of unknown datatypes are `LiteralValue::Other`.
+    ///
+    /// # Returns
+    ///
+    /// The value or an InvalidLiteralError if the literal is ill-typed, i.e. its value isn't valid for its datatype
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use rdf4rust::rdf::node_factory::{Literal, LiteralValue};
+    /// use rdf4rust::rdf::xsd::{XSD_INT, XSD_ANY_URI, XSD_BASE64_BINARY};
+    /// use rdf4rust::util::decimal::BigInt;
+    ///
+    /// let int = Literal::create_typed_literal(String::from("+042"), &XSD_INT);
+    /// assert_eq!(LiteralValue::Integer(BigInt::from(42)), int.value().unwrap());
+    ///
+    /// match Literal::create_typed_literal(String::from("aGVsbG8="), &XSD_BASE64_BINARY).value() {
+    ///     Ok(LiteralValue::Binary(bytes)) => assert_eq!(b"hello".to_vec(), bytes),
+    ///     _ => panic!("Should be binary")
+    /// }
+    ///
+    /// assert_eq!(LiteralValue::AnyUri(String::from("../relative")), Literal::create_typed_literal(String::from("../relative"), &XSD_ANY_URI).value().unwrap());
+    /// assert!(Literal::create_typed_literal(String::from("2147483648"), &XSD_INT).value().is_err());
+    /// ```
+    ///
+    pub fn value(&self) -> Result<LiteralValue, InvalidLiteralError>{
+        if let Some(lang) = &self.lang{
+            return Ok(match self.direction{
+                Some(direction) => LiteralValue::DirLangString(self.value.clone(), lang.clone(), direction),
+                None => LiteralValue::LangString(self.value.clone(), lang.clone())
+            })
+        }
+        let ill_typed = || InvalidLiteralError::new(ErrorKind::InvalidLiteral, format!("Literal value \"{}\" is not valid for datatype <{}>.", self.value, self.dtype.get_value()));
+        if !self.dtype.is_valid(&self.value){
+            return Err(ill_typed())
+        }
+        let value = self.value.as_str();
+        let name = match self.dtype.get_primitive().get_value().strip_prefix("http://www.w3.org/2001/XMLSchema#"){
+            Some(name) => name,
+            None => return Ok(LiteralValue::Other(self.value.clone()))
+        };
+        Ok(match name{
+            "string" | "normalizedString" | "token" | "language" | "Name" | "NCName" | "NMTOKEN" => LiteralValue::String(self.value.clone()),
+            "boolean" => LiteralValue::Boolean(value == "true" || value == "1"),
+            "decimal" => LiteralValue::Decimal(value.parse::<Decimal>().map_err(|_| ill_typed())?),
+            "double" => LiteralValue::Double(parse_double(value).ok_or_else(ill_typed)?),
+            "float" => LiteralValue::Float(parse_double(value).ok_or_else(ill_typed)? as f32),
+            "dateTime" | "dateTimeStamp" => LiteralValue::DateTime(value.parse::<DateTime>().map_err(|_| ill_typed())?),
+            "date" => LiteralValue::Date(value.parse::<Date>().map_err(|_| ill_typed())?),
+            "time" => LiteralValue::Time(value.parse::<Time>().map_err(|_| ill_typed())?),
+            "gYear" => LiteralValue::GYear(value.parse::<GYear>().map_err(|_| ill_typed())?),
+            "gYearMonth" => LiteralValue::GYearMonth(value.parse::<GYearMonth>().map_err(|_| ill_typed())?),
+            "gMonth" => LiteralValue::GMonth(value.parse::<GMonth>().map_err(|_| ill_typed())?),
+            "gDay" => LiteralValue::GDay(value.parse::<GDay>().map_err(|_| ill_typed())?),
+            "gMonthDay" => LiteralValue::GMonthDay(value.parse::<GMonthDay>().map_err(|_| ill_typed())?),
+            "duration" | "dayTimeDuration" | "yearMonthDuration" => LiteralValue::Duration(value.parse::<Duration>().map_err(|_| ill_typed())?),
+            "hexBinary" => LiteralValue::Binary(string_to_binary(value).ok_or_else(ill_typed)?),
+            "base64Binary" => LiteralValue::Binary(base64::decode(value.replace(' ', "")).map_err(|_| ill_typed())?),
+            "anyURI" => LiteralValue::AnyUri(self.value.clone()),
+            _ if self.dtype.is_integer_type() => LiteralValue::Integer(parse_integer(value).map_err(|_| ill_typed())?),
+            _ => LiteralValue::Other(self.value.clone())
+        })
     }
 
-    fn as_object_generic_lc<T: Any + FromStr>(&self, lower_case: bool) ->Option<Box<dyn Any>>{
-        let tmp = if lower_case {
-            self.value.to_lowercase().parse::<T>()
-        }else{
-            self.value.parse::<T>()
+    ///
+    /// Maps the literal to the canonical lexical form of its value, keeping its datatype
+    ///
+    /// Canonical forms follow XSD 1.1, e.g. `1` for decimals without fraction digits, `1.0E2` for doubles and floats,
+    /// uppercase hexBinary and dateTimes without `24:00:00`. Timezones are kept, UTC is written as `Z`.
+    /// Strings, anyURIs, literals of unknown types and ill-typed literals are returned unchanged.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use rdf4rust::rdf::node_factory::{Literal, RDFNode};
+    /// use rdf4rust::rdf::xsd::{XSD_INT, XSD_DOUBLE, XSD_BOOLEAN, XSD_DATE_TIME, XSD_HEX_BINARY};
+    ///
+    /// let canonical = |value: &str, dtype| Literal::create_typed_literal(String::from(value), dtype).canonicalize().get_value().to_string();
+    /// assert_eq!("1", canonical("+001", &XSD_INT));
+    /// assert_eq!("1.5E2", canonical("150", &XSD_DOUBLE));
+    /// assert_eq!("-0.0E0", canonical("-0", &XSD_DOUBLE));
+    /// assert_eq!("true", canonical("1", &XSD_BOOLEAN));
+    /// assert_eq!("2000-01-02T00:00:00Z", canonical("2000-01-01T24:00:00+00:00", &XSD_DATE_TIME));
+    /// assert_eq!("0FB7", canonical("0fb7", &XSD_HEX_BINARY));
+    /// assert_eq!("nope", canonical("nope", &XSD_INT));
+    /// ```
+    ///
+    pub fn canonicalize(&self) -> Literal{
+        let value = match self.value(){
+            Ok(value) => value,
+            Err(_) => return self.clone()
         };
-        if let Ok(val) = tmp{
-            Some(Box::new(val))
-        }else{
-            None
-        }
+        let canonical = match value{
+            LiteralValue::Boolean(val) => val.to_string(),
+            LiteralValue::Integer(val) => val.to_string(),
+            LiteralValue::Decimal(val) => val.to_string(),
+            LiteralValue::Double(val) => canonical_double(val.is_nan(), format!("{:E}", val)),
+            LiteralValue::Float(val) => canonical_double(val.is_nan(), format!("{:E}", val)),
+            LiteralValue::DateTime(val) => val.to_string(),
+            LiteralValue::Date(val) => val.to_string(),
+            LiteralValue::Time(val) => val.to_string(),
+            LiteralValue::GYear(val) => val.to_string(),
+            LiteralValue::GYearMonth(val) => val.to_string(),
+            LiteralValue::GMonth(val) => val.to_string(),
+            LiteralValue::GDay(val) => val.to_string(),
+            LiteralValue::GMonthDay(val) => val.to_string(),
+            LiteralValue::Duration(val) => val.to_string(),
+            LiteralValue::Binary(val) if self.dtype.get_primitive() == &*XSD_HEX_BINARY => binary_to_string(&val),
+            LiteralValue::Binary(val) => base64::encode(&val),
+            LiteralValue::String(_) | LiteralValue::LangString(_, _) | LiteralValue::DirLangString(_, _, _)
+                | LiteralValue::AnyUri(_) | LiteralValue::Other(_) => return self.clone()
+        };
+        Literal{ value: canonical, dtype: self.dtype.clone(), lang: None, direction: None }
     }
 
-    pub fn as_object(&self) -> Option<Box<dyn Any>>{
-        if self.dtype.is_xsd_string(){
-            return Some(Box::new(String::from(&self.value)))
+    ///
+    /// Checks if both literals have the same value, even if their lexical forms or datatypes differ
+    ///
+    /// Integers and decimals are compared numerically, as are all types derived from them, e.g. `"01"^^xsd:int` equals `"1.0"^^xsd:decimal`.
+    /// Doubles and floats are only equal to values of the same type, NaN is not equal to itself.
+    /// dateTimes are compared on the timeline.
+    /// Ill-typed literals and literals of unknown types are only equal to identical literals.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use rdf4rust::rdf::node_factory::Literal;
+    /// use rdf4rust::rdf::xsd::{XSD_INT, XSD_DECIMAL, XSD_DOUBLE, XSD_DATE_TIME};
+    ///
+    /// let literal = |value: &str, dtype| Literal::create_typed_literal(String::from(value), dtype);
+    /// assert!(literal("01", &XSD_INT).value_eq(&literal("1.0", &XSD_DECIMAL)));
+    /// assert!(!literal("1", &XSD_INT).value_eq(&literal("1", &XSD_DOUBLE)));
+    /// assert!(literal("2002-04-02T12:00:00-01:00", &XSD_DATE_TIME).value_eq(&literal("2002-04-02T17:00:00+04:00", &XSD_DATE_TIME)));
+    /// assert!(!Literal::from("chat").value_eq(&Literal::create_lang_literal(String::from("chat"), String::from("fr")).unwrap()));
+    /// ```
+    ///
+    pub fn value_eq(&self, other: &Literal) -> bool{
+        let (value, other_value) = match (self.value(), other.value()){
+            (Ok(value), Ok(other_value)) => (value, other_value),
+            _ => return self == other
+        };
+        match (value, other_value){
+            (LiteralValue::Integer(int), LiteralValue::Decimal(dec)) | (LiteralValue::Decimal(dec), LiteralValue::Integer(int)) => Decimal::from(int) == dec,
+            //hexBinary and base64Binary resp. unknown types have distinct value spaces
+            (LiteralValue::Binary(val), LiteralValue::Binary(other_val)) => val == other_val && self.dtype.get_primitive() == other.dtype.get_primitive(),
+            (LiteralValue::Other(_), LiteralValue::Other(_)) => self == other,
+            (value, other_value) => value == other_value
         }
-        match self.dtype.get_value().as_str(){
-            "http://www.w3.org/2001/XMLSchema#unsignedByte" =>  {return self.as_object_generic::<u8>();}
-            "http://www.w3.org/2001/XMLSchema#unsignedShort" =>  {return self.as_object_generic::<u16>();}
-            "http://www.w3.org/2001/XMLSchema#unsignedInt" =>  {return self.as_object_generic::<u32>();}
-            "http://www.w3.org/2001/XMLSchema#unsignedLong" =>  {return self.as_object_generic::<u64>();}
-            "http://www.w3.org/2001/XMLSchema#byte" =>  {return self.as_object_generic::<i8>();}
-            "http://www.w3.org/2001/XMLSchema#short" =>  {return self.as_object_generic::<i16>();}
-            "http://www.w3.org/2001/XMLSchema#int" =>  {return self.as_object_generic::<i32>();}
-            "http://www.w3.org/2001/XMLSchema#long" =>  {return self.as_object_generic::<i64>();}
-            "http://www.w3.org/2001/XMLSchema#integer" =>  {return self.as_object_generic::<i64>();}
-            "http://www.w3.org/2001/XMLSchema#nonNegativeInteger" =>  {return self.as_object_generic::<usize>();}
-            "http://www.w3.org/2001/XMLSchema#nonPositiveInteger" =>  {return self.as_object_generic::<isize>();}
-            "http://www.w3.org/2001/XMLSchema#positiveInteger" =>  {return self.as_object_generic::<NonZeroUsize>();}
-            "http://www.w3.org/2001/XMLSchema#negativeInteger" =>  {return self.as_object_generic::<NonZeroIsize>();}
-            "http://www.w3.org/2001/XMLSchema#decimal" =>  {return self.as_object_generic_lc::<f64>(true);}
-            //TODO f32 doesn't allow INF and NaN
-            "http://www.w3.org/2001/XMLSchema#float" =>  {return self.as_object_generic_lc::<f32>(true);}
-            "http://www.w3.org/2001/XMLSchema#double" =>  {return self.as_object_generic_lc::<f64>(true);}
-            "http://www.w3.org/2001/XMLSchema#boolean" =>  {return self.as_object_generic::<bool>();}
-            "http://www.w3.org/2001/XMLSchema#anyType" =>  {return Some(Box::new(String::from(&self.value)));}
-            "http://www.w3.org/2001/XMLSchema#anySimpleType" =>  {return Some(Box::new(String::from(&self.value)));}
-            "http://www.w3.org/2001/XMLSchema#anyUri" =>  {return Some(Box::new(IRI::create_iri(self.get_value())));}
-            "http://www.w3.org/2001/XMLSchema#hexBinary" =>  {
-                return match string_to_binary(self.value.as_str()){
-                    None => {None}
-                    Some(val) => {return Some(Box::new(val))}
-                }
-            }
-            "http://www.w3.org/2001/XMLSchema#base64Binary" =>  {
-                return match base64::decode(self.value.as_str()){
-                    Ok(val) => Some(Box::new(val)),
-                    Err(_) => None
-                }
-            }
-            //TODO date, time etc.
+    }
 
-            _ => {}
+    ///
+    /// Gets the value of a `xsd:integer` literal, or a literal of a type derived from it, without precision loss
+    ///
+    /// # Returns
+    ///
+    /// The value or None if the literal is of another type or its value is not valid for its type
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use rdf4rust::rdf::node_factory::Literal;
+    /// use rdf4rust::rdf::xsd::{XSD_INTEGER, XSD_BYTE};
+    /// use rdf4rust::util::decimal::BigInt;
+    ///
+    /// let literal = Literal::create_typed_literal(String::from("123456789012345678901234567890"), &XSD_INTEGER);
+    /// let value = literal.as_integer().unwrap();
+    /// assert_eq!("246913578024691357802469135780", (&value * BigInt::from(2)).to_string());
+    ///
+    /// assert!(Literal::create_typed_literal(String::from("300"), &XSD_BYTE).as_integer().is_none());
+    /// ```
+    ///
+    pub fn as_integer(&self) -> Option<BigInt>{
+        if !self.dtype.is_integer_type() || !self.dtype.is_valid(&self.value){
+            return None
         }
-        Some(Box::new(String::from(&self.value)))
+        parse_integer(&self.value).ok()
     }
 
-    pub fn get_datatype(&self) -> &XSDDataType{
-        &self.dtype
-    }
-    pub fn get_lang(&self) -> &Option<String> {
-        &self.lang
+    ///
+    /// Gets the value of a `xsd:decimal` literal, or a literal of a type derived from it (including all integer types), without precision loss
+    ///
+    /// # Returns
+    ///
+    /// The value or None if the literal is of another type or its value is not valid for its type
+    ///
+    pub fn as_decimal(&self) -> Option<Decimal>{
+        if !self.dtype.is_decimal_type() || !self.dtype.is_valid(&self.value){
+            return None
+        }
+        self.value.parse::<Decimal>().ok()
     }
 
-    pub fn get_value(&self) -> &String {
-        &self.value
+    fn parse_if_type<T: FromStr>(&self, dtypes: &[&str]) -> Option<T>{
+        if dtypes.contains(&self.dtype.get_value().as_str()){
+            self.value.parse::<T>().ok()
+        }else{
+            None
+        }
     }
 
-    pub fn create_literal(value: String) -> Literal{
-        Literal::create_typed_literal(value.to_string(), &XSD_STRING)
+    ///
+    /// Gets the value of a `xsd:dateTime` or `xsd:dateTimeStamp` literal
+    ///
+    /// # Returns
+    ///
+    /// The parsed DateTime or None if the literal is of another type or its value is not valid
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use rdf4rust::rdf::node_factory::Literal;
+    /// use rdf4rust::rdf::xsd::XSD_DATE_TIME;
+    /// use rdf4rust::util::date_time::{Duration, Timezone};
+    ///
+    /// let literal = Literal::create_typed_literal(String::from("2002-10-10T12:00:00-05:00"), &XSD_DATE_TIME);
+    /// let date_time = literal.as_date_time().unwrap();
+    /// assert_eq!(17, date_time.to_timezone(Timezone::utc()).unwrap().get_hour());
+    ///
+    /// let later = date_time.add_duration(&"P1DT12H".parse::<Duration>().unwrap()).unwrap();
+    /// assert!(later > date_time);
+    /// assert_eq!("2002-10-12T00:00:00-05:00", later.to_string());
+    ///
+    /// assert!(Literal::from("2002-10-10T12:00:00").as_date_time().is_none());
+    /// ```
+    ///
+    pub fn as_date_time(&self) -> Option<DateTime>{
+        let date_time = self.parse_if_type::<DateTime>(&["http://www.w3.org/2001/XMLSchema#dateTime", "http://www.w3.org/2001/XMLSchema#dateTimeStamp"])?;
+        if date_time.get_timezone().is_none() && self.dtype.get_value() == "http://www.w3.org/2001/XMLSchema#dateTimeStamp"{
+            return None
+        }
+        Some(date_time)
     }
 
-    pub fn create_base64_binary_literal(value: &[u8]) -> Literal{
-        Literal::create_typed_literal(base64::encode(value), &XSD_HEX_BINARY)
+    /// Gets the value of a `xsd:date` literal, None if the literal is of another type or invalid
+    pub fn as_date(&self) -> Option<Date>{
+        self.parse_if_type(&["http://www.w3.org/2001/XMLSchema#date"])
     }
 
-    pub fn create_hex_binary_literal(value: &[u8]) -> Literal{
-        let hex_string =  binary_to_string(value);
-        Literal::create_typed_literal(hex_string, &XSD_BASE64_BINARY)
+    /// Gets the value of a `xsd:time` literal, None if the literal is of another type or invalid
+    pub fn as_time(&self) -> Option<Time>{
+        self.parse_if_type(&["http://www.w3.org/2001/XMLSchema#time"])
     }
 
-    pub fn create_any_type_literal(value: String) -> Literal{
-        Literal::create_typed_literal(value.to_string(), &XSD_ANY_TYPE)
+    /// Gets the value of a `xsd:gYear` literal, None if the literal is of another type or invalid
+    pub fn as_g_year(&self) -> Option<GYear>{
+        self.parse_if_type(&["http://www.w3.org/2001/XMLSchema#gYear"])
     }
 
-    pub fn create_any_simple_type_literal(value: String) -> Literal{
-        Literal::create_typed_literal(value.to_string(), &XSD_ANY_SIMPLE_TYPE)
+    /// Gets the value of a `xsd:gYearMonth` literal, None if the literal is of another type or invalid
+    pub fn as_g_year_month(&self) -> Option<GYearMonth>{
+        self.parse_if_type(&["http://www.w3.org/2001/XMLSchema#gYearMonth"])
     }
 
-    pub fn create_unsigned_long_literal(value: u64) -> Literal{
-        Literal::create_typed_literal(value.to_string(), &XSD_UNSIGNED_LONG)
+    /// Gets the value of a `xsd:gMonth` literal, None if the literal is of another type or invalid
+    pub fn as_g_month(&self) -> Option<GMonth>{
+        self.parse_if_type(&["http://www.w3.org/2001/XMLSchema#gMonth"])
     }
 
-    pub fn create_unsigned_integer_literal(value: u32) -> Literal{
-        Literal::create_typed_literal(value.to_string(), &XSD_UNSIGNED_INT)
+    /// Gets the value of a `xsd:gDay` literal, None if the literal is of another type or invalid
+    pub fn as_g_day(&self) -> Option<GDay>{
+        self.parse_if_type(&["http://www.w3.org/2001/XMLSchema#gDay"])
     }
 
-    pub fn create_unsigned_short_literal(value: u16) -> Literal{
-        Literal::create_typed_literal(value.to_string(), &XSD_UNSIGNED_SHORT)
+    /// Gets the value of a `xsd:gMonthDay` literal, None if the literal is of another type or invalid
+    pub fn as_g_month_day(&self) -> Option<GMonthDay>{
+        self.parse_if_type(&["http://www.w3.org/2001/XMLSchema#gMonthDay"])
     }
 
-    pub fn create_unsigned_byte_literal(value: u8) -> Literal{
-        Literal::create_typed_literal(value.to_string(), &XSD_UNSIGNED_BYTE)
+    ///
+    /// Gets the value of a `xsd:duration`, `xsd:dayTimeDuration` or `xsd:yearMonthDuration` literal
+    ///
+    /// # Returns
+    ///
+    /// The parsed Duration or None if the literal is of another type or its value is not valid for its type
+    ///
+    pub fn as_duration(&self) -> Option<Duration>{
+        match self.dtype.get_value().as_str(){
+            "http://www.w3.org/2001/XMLSchema#duration" => self.value.parse::<Duration>().ok(),
+            "http://www.w3.org/2001/XMLSchema#dayTimeDuration" => Duration::parse_day_time(&self.value).ok(),
+            "http://www.w3.org/2001/XMLSchema#yearMonthDuration" => Duration::parse_year_month(&self.value).ok(),
+            _ => None
+        }
     }
 
-    pub fn create_integer_literal(value: i64) -> Literal{
-        Literal::create_typed_literal(value.to_string(), &XSD_INTEGER)
+    pub fn get_datatype(&self) -> &XSDDataType{
+        &self.dtype
     }
 
-    pub fn create_long_literal(value: i64) -> Literal{
-        Literal::create_typed_literal(value.to_string(), &XSD_LONG)
+    ///
+    /// Gets the language tag, normalized to the case conventions of BCP 47 (see `util::language_tag::normalize_language_tag`)
+    ///
+    pub fn get_lang(&self) -> &Option<String> {
+        &self.lang
     }
 
-    pub fn create_int_literal(value: i32) -> Literal{
-        Literal::create_typed_literal(value.to_string(), &XSD_INT)
+    /// Gets the base direction of a directional language-tagged string
+    pub fn get_direction(&self) -> Option<BaseDirection> {
+        self.direction
     }
 
-    pub fn create_short_literal(value: i16) -> Literal{
-        Literal::create_typed_literal(value.to_string(), &XSD_SHORT)
+    ///
+    /// Checks if the language tag matches the language range like the SPARQL function `langMatches`
+    ///
+    /// Literals without a language tag have the empty tag, which only matches the empty range.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use rdf4rust::rdf::node_factory::Literal;
+    ///
+    /// let literal = Literal::create_lang_literal(String::from("Grüezi"), String::from("de-ch")).unwrap();
+    /// assert!(literal.lang_matches("de"));
+    /// assert!(literal.lang_matches("*"));
+    /// assert!(!literal.lang_matches("en"));
+    /// assert!(!Literal::from("Hello").lang_matches("*"));
+    /// ```
+    ///
+    pub fn lang_matches(&self, range: &str) -> bool {
+        lang_matches(self.lang.as_deref().unwrap_or(""), range)
     }
 
-    pub fn create_byte_literal(value: i8) -> Literal{
-        Literal::create_typed_literal(value.to_string(), &XSD_BYTE)
+    pub fn get_value(&self) -> &String {
+        &self.value
     }
 
-    pub fn create_positive_integer_literal(value: usize) -> Literal{
-        Literal::create_typed_literal(value.to_string(), &XSD_POSITIVE_INTEGER)
-    }
-    pub fn create_negative_integer_literal(value: isize) -> Literal{
-        Literal::create_typed_literal(value.to_string(), &XSD_NEGATIVE_INTEGER)
-    }
-    pub fn create_non_negative_integer_literal(value: usize) -> Literal{
-        Literal::create_typed_literal(value.to_string(), &XSD_NON_NEGATIVE_INTEGER)
-    }
-    pub fn create_non_positive_integer_literal(value: isize) -> Literal{
-        Literal::create_typed_literal(value.to_string(), &XSD_NON_POSITIVE_INTEGER)
+    pub fn create_base64_binary_literal(value: &[u8]) -> Literal{
+        Literal::create_typed_literal(base64::encode(value), &XSD_BASE64_BINARY)
     }
 
-    pub fn create_double_literal(value: f64) -> Literal{
-        Literal::create_typed_literal(value.to_string(), &XSD_DOUBLE)
-    }
-    pub fn create_float_literal(value: f32) -> Literal{
-        Literal::create_typed_literal(value.to_string(), &XSD_FLOAT)
+    pub fn create_hex_binary_literal(value: &[u8]) -> Literal{
+        let hex_string =  binary_to_string(value);
+        Literal::create_typed_literal(hex_string, &XSD_HEX_BINARY)
     }
 
-    pub fn create_decimal_literal(value: f64) -> Literal{
-        Literal::create_typed_literal(value.to_string(), &XSD_DECIMAL)
+    pub fn create_any_type_literal(value: String) -> Literal{
+        Literal::create_typed_literal(value.to_string(), &XSD_ANY_TYPE)
     }
 
-    pub fn create_boolean_literal(value: bool) -> Literal{
-        Literal::create_typed_literal(value.to_string(), &XSD_BOOLEAN)
+    pub fn create_any_simple_type_literal(value: String) -> Literal{
+        Literal::create_typed_literal(value.to_string(), &XSD_ANY_SIMPLE_TYPE)
     }
 
     pub fn create_any_uri_literal(value: IRI) -> Literal{
         Literal::create_typed_literal(value.as_string(), &XSD_ANY_URI)
     }
 
+    /// Creates a `xsd:dayTimeDuration` literal, fails if the duration has a year or month component
+    pub fn create_day_time_duration_literal(value: &Duration) -> Result<Literal, InvalidLiteralError>{
+        if !value.is_day_time(){
+            return Err(InvalidLiteralError::new(ErrorKind::InvalidLiteral, format!("{} is not a dayTimeDuration.", value)))
+        }
+        Ok(Literal::create_typed_literal(value.to_string(), &XSD_DAY_TIME_DURATION))
+    }
+
+    /// Creates a `xsd:yearMonthDuration` literal, fails if the duration has a day or time component
+    pub fn create_year_month_duration_literal(value: &Duration) -> Result<Literal, InvalidLiteralError>{
+        if !value.is_year_month(){
+            return Err(InvalidLiteralError::new(ErrorKind::InvalidLiteral, format!("{} is not a yearMonthDuration.", value)))
+        }
+        Ok(Literal::create_typed_literal(value.to_string(), &XSD_YEAR_MONTH_DURATION))
+    }
+
     ///
     /// Creates a typed literal using a `XSDDataType`
     ///
@@ -285,84 +524,173 @@ impl Literal{
     /// ```
     /// use rdf4rust::rdf::node_factory::RDFNode;
     /// use rdf4rust::rdf::node_factory::Literal;
-    /// use rdf4rust::rdf::xsd::{xsd_any_uri, xsd_int, xsd_string};
+    /// use rdf4rust::rdf::xsd::{XSD_ANY_URI, XSD_INT, XSD_STRING};
     ///
     /// let uri_literal = Literal::create_typed_literal(String::from("http://my-uri.com/Person1"), &XSD_ANY_URI);
     /// let int_literal = Literal::create_typed_literal(String::from("12345"), &XSD_INT);
     /// let simple_string_literal = Literal::create_typed_literal(String::from("my-value"), &XSD_STRING);
     ///
     /// //Note that if the XSDDataType is not xsd_string the quotation boolean in as_string(quotation) will be ignored.
-    /// assert_eq!("\"http://my-uri.com/Person1\"^^<http://www.w3.org/2001/XMLSchema#anyUri>", uri_literal.as_string(false).as_str());
-    /// assert_eq!("\"12345\"^^<http://www.w3.org/2001/XMLSchema#int>", uri_literal.as_string(false).as_str());
+    /// assert_eq!("\"http://my-uri.com/Person1\"^^<http://www.w3.org/2001/XMLSchema#anyURI>", uri_literal.as_string(false).as_str());
+    /// assert_eq!("\"12345\"^^<http://www.w3.org/2001/XMLSchema#int>", int_literal.as_string(false).as_str());
     /// assert_eq!("my-value", simple_string_literal.as_string(false).as_str());
     /// assert_eq!("\"my-value\"", simple_string_literal.as_string(true).as_str());
     /// ```
     ///
     pub fn create_typed_literal(value: String, dtype: &'static XSDDataType) -> Literal{
-        //TODO https://www.w3.org/TR/2012/REC-xmlschema11-2-20120405/datatypes.html# valid accordingly use regex?
+        Literal::create_typed_literal_from_ref(value, XSDDataTypeRef::Static(dtype))
+    }
+
+    ///
+    /// Creates a typed literal using a shared or static `XSDDataTypeRef`, e.g. as returned by `XSDDataType::get_or_default`
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use rdf4rust::rdf::node_factory::{Literal, RDFNode};
+    /// use rdf4rust::rdf::xsd::XSDDataType;
+    /// use rdf4rust::util::iri::IRI;
+    ///
+    /// let dtype = XSDDataType::get_or_default(IRI::create_iri(&String::from("http://example.com/datatypes#Custom")).unwrap());
+    /// let literal = Literal::create_typed_literal_from_ref(String::from("value"), dtype);
+    /// assert_eq!("\"value\"^^<http://example.com/datatypes#Custom>", literal.as_string(false).as_str());
+    /// ```
+    ///
+    pub fn create_typed_literal_from_ref(value: String, dtype: XSDDataTypeRef) -> Literal{
         Literal{
             value,
             dtype,
-            lang: None
+            lang: None,
+            direction: None
         }
     }
 
     ///
-    /// Creates a string literal with the specified language code
+    /// Creates a typed literal using a `XSDDataType`, but only if `value` is valid for `dtype`
+    ///
+    /// # Parameters
+    ///
+    /// * `value` - The value of the literal represented as String
+    /// * `dtype` - The `XSDDataType` type the value represents
+    ///
+    /// # Returns
+    ///
+    /// A Literal of type `dtype` or an InvalidLiteralError if `value` isn't in the lexical space of `dtype` or violates its facets
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use rdf4rust::rdf::node_factory::Literal;
+    /// use rdf4rust::rdf::xsd::{XSD_INT, XSD_UNSIGNED_BYTE};
+    ///
+    /// assert!(Literal::create_checked_typed_literal(String::from("12345"), &XSD_INT).is_ok());
+    /// assert!(Literal::create_checked_typed_literal(String::from("twelve"), &XSD_INT).is_err());
+    /// assert!(Literal::create_checked_typed_literal(String::from("256"), &XSD_UNSIGNED_BYTE).is_err());
+    /// ```
+    ///
+    pub fn create_checked_typed_literal(value: String, dtype: &'static XSDDataType) -> Result<Literal, InvalidLiteralError>{
+        if !dtype.is_valid(&value){
+            return Err(InvalidLiteralError::new(ErrorKind::InvalidLiteral, format!("Literal value \"{}\" is not valid for datatype <{}>.", value, dtype.get_value())))
+        }
+        Ok(Literal::create_typed_literal(value, dtype))
+    }
+
+    ///
+    /// Creates a language-tagged string literal
     ///
     /// # Parameters
     ///
     /// * `value` - The string value of the literal
-    /// * `lang` - The language tag
+    /// * `lang` - The BCP 47 language tag, its case is normalized, e.g. `en-us` becomes `en-US`
     ///
     /// # Returns
     ///
-    /// A Literal of type xsd:string annotated with a language tag
-    /// or if `lang` is empty returns a plain xsd:string literal
+    /// A Literal of type rdf:langString annotated with the language tag, or if `lang` is empty a plain xsd:string literal.
+    /// Fails if `lang` is no well-formed language tag.
     ///
     /// # Example
     ///
     /// ```
     /// use rdf4rust::rdf::node_factory::RDFNode;
     /// use rdf4rust::rdf::node_factory::Literal;
+    /// use rdf4rust::rdf::xsd::RDF_LANG_STRING;
     ///
-    /// let lang_literal = Literal::create_lang_literal(String::from("my-value"), String::from("en"));
-    /// let simple_string_literal = Literal::create_lang_literal(String::from("my-value"), String::from(""));
+    /// let lang_literal = Literal::create_lang_literal(String::from("my-value"), String::from("en-us")).unwrap();
+    /// let simple_string_literal = Literal::create_lang_literal(String::from("my-value"), String::from("")).unwrap();
     ///
-    /// assert_eq!("\"my-value\"@en", lang_literal.as_string(false).as_str());
+    /// assert_eq!("\"my-value\"@en-US", lang_literal.as_string(false).as_str());
+    /// assert_eq!(&*RDF_LANG_STRING, lang_literal.get_datatype());
+    /// assert!(lang_literal == Literal::create_lang_literal(String::from("my-value"), String::from("EN-US")).unwrap());
     /// assert_eq!("my-value", simple_string_literal.as_string(false).as_str());
     /// assert_eq!("\"my-value\"", simple_string_literal.as_string(true).as_str());
+    ///
+    /// assert!(Literal::create_lang_literal(String::from("my-value"), String::from("en_US")).is_err());
     /// ```
     ///
-    pub fn create_lang_literal(value: String, lang: String) -> Literal{
+    pub fn create_lang_literal(value: String, lang: String) -> Result<Literal, InvalidLiteralError>{
         if lang.is_empty(){
-            Literal{
-                value,
-                dtype: &XSD_STRING,
-                lang: None
-            }
-        }else {
-            Literal {
-                value,
-                dtype: &XSD_STRING,
-                lang: Some(lang)
-            }
+            return Ok(Literal::create_typed_literal(value, &XSD_STRING))
         }
+        Ok(Literal{
+            value,
+            dtype: XSDDataTypeRef::Static(&RDF_LANG_STRING),
+            lang: Some(normalize_language_tag(&lang)?),
+            direction: None
+        })
     }
 
-    fn parse_number(literal: &str) -> Result<Literal, InvalidLiteralError>{
-        if literal.contains(".") {
-            return if let Some(val) = literal.parse::<f64>().ok() {
-                Ok(Literal::create_decimal_literal(val))
-            } else {
-                Err(InvalidLiteralError::new(String::from("Literal is not valid.")))
-            }
+    ///
+    /// Creates a directional language-tagged string literal (RDF 1.2) of type rdf:dirLangString
+    ///
+    /// # Parameters
+    ///
+    /// * `value` - The string value of the literal
+    /// * `lang` - The BCP 47 language tag, its case is normalized
+    /// * `direction` - The base direction of the text
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use rdf4rust::rdf::node_factory::{Literal, RDFNode, BaseDirection};
+    /// use rdf4rust::rdf::xsd::RDF_DIR_LANG_STRING;
+    ///
+    /// let literal = Literal::create_dir_lang_literal(String::from("مرحبا"), String::from("AR"), BaseDirection::Rtl).unwrap();
+    /// assert_eq!("\"مرحبا\"@ar--rtl", literal.as_string(false));
+    /// assert_eq!(&*RDF_DIR_LANG_STRING, literal.get_datatype());
+    /// assert!(Literal::create_dir_lang_literal(String::from("hi"), String::from(""), BaseDirection::Ltr).is_err());
+    /// ```
+    ///
+    pub fn create_dir_lang_literal(value: String, lang: String, direction: BaseDirection) -> Result<Literal, InvalidLiteralError>{
+        Ok(Literal{
+            value,
+            dtype: XSDDataTypeRef::Static(&RDF_DIR_LANG_STRING),
+            lang: Some(normalize_language_tag(&lang)?),
+            direction: Some(direction)
+        })
+    }
+
+    /// Parses a language tag with an optional base direction, e.g. `en` or `en--ltr`
+    fn parse_lang_tag(val: String, tag: &str) -> Result<Literal, InvalidLiteralError>{
+        match tag.split_once("--"){
+            Some((lang, direction)) => Literal::create_dir_lang_literal(val, String::from(lang), direction.parse()?),
+            None if tag.is_empty() => Err(InvalidLiteralError::new(ErrorKind::InvalidLiteral, String::from("Language tag must not be empty."))),
+            None => Literal::create_lang_literal(val, String::from(tag))
         }
-        if let Some(val) = literal.parse::<i64>().ok() {
-            Ok(Literal::create_integer_literal(val))
+    }
+
+    fn parse_number(literal: &str) -> Result<Literal, InvalidLiteralError>{
+        // the lexical form is kept as is, the value types only validate it
+        let (valid, dtype): (bool, &'static XSDDataType) = if literal.contains(['e', 'E']) {
+            (XSD_DOUBLE.is_valid(literal), &XSD_DOUBLE)
+        } else if literal.contains('.') {
+            (literal.parse::<Decimal>().is_ok(), &XSD_DECIMAL)
         } else {
-            Err(InvalidLiteralError::new(String::from("Literal is not valid.")))
+            (parse_integer(literal).is_ok(), &XSD_INTEGER)
+        };
+        if !valid {
+            return Err(InvalidLiteralError::new(ErrorKind::InvalidLiteral, format!("Literal {} is not a valid number.", literal)))
         }
+        Ok(Literal::create_typed_literal(String::from(literal), dtype))
     }
 
     fn parse_prefixed_datatype(val: String, literal: &str, prefix_mapping: &HashMap<String, String>) -> Result<Literal, InvalidLiteralError> {
@@ -372,62 +700,62 @@ impl Literal{
             if let Some(uri) = prefix_mapping.get(prefix){
                 let iri = match IRI::create_iri(&String::from(uri).add(suffix)){
                     Ok(val) => val,
-                    Err(err) => return Err(InvalidLiteralError::new(err.msg))
+                    Err(err) => return Err(InvalidLiteralError::new(ErrorKind::InvalidLiteral, String::from("Datatype is not a valid IRI.")).with_source(err))
                 };
                 //TODO get XSD type or return new type
-                Ok(Literal::create_typed_literal(val, XSDDataType::get_or_default(iri)))
+                Ok(Literal::create_typed_literal_from_ref(val, XSDDataType::get_or_default(iri)))
             }
             else{
-                Err(InvalidLiteralError::new(String::from("Unresolved prefix mapping for prefix: ").add(prefix)))
+                Err(InvalidLiteralError::new(ErrorKind::UnknownPrefix, String::from("Unresolved prefix mapping for prefix: ").add(prefix)))
             }
         }
         else{
-            Err(InvalidLiteralError::new(String::from("Unknown Error occurred")))
+            Err(InvalidLiteralError::new(ErrorKind::InvalidLiteral, String::from("Prefixed datatype has to be of form prefix:suffix.")))
         }
     }
 
     fn parse_datatype(val: String, literal: &str) -> Result<Literal, InvalidLiteralError>{
         let iri = match IRI::create_iri(&String::from(&literal[..literal.len()-1])){
             Ok(iri) => {iri}
-            Err(err) => {return Err(InvalidLiteralError::new(err.msg))}
+            Err(err) => {return Err(InvalidLiteralError::new(ErrorKind::InvalidLiteral, String::from("Datatype is not a valid IRI.")).with_source(err))}
         };
-        Ok(Literal::create_typed_literal(val, XSDDataType::get_or_default(iri)))
+        Ok(Literal::create_typed_literal_from_ref(val, XSDDataType::get_or_default(iri)))
     }
 
     fn parse_complex_literal(literal: &str, starts_with: &str, prefix_mapping: &HashMap<String, String>) -> Result<Literal, InvalidLiteralError>{
         //swABCsw@?||^^
         let end_of_str = match literal.rfind(starts_with) {
             Some(val) => val,
-            None => return Result::Err(InvalidLiteralError::new(String::from("Literal starts with ").add(starts_with).add(" but doesn't close.")))
+            None => return Result::Err(InvalidLiteralError::new(ErrorKind::InvalidLiteral, String::from("Literal starts with ").add(starts_with).add(" but doesn't close.")))
         };
-        let val = String::from(&literal[starts_with.len()..end_of_str]);
+        let val = unescape_string(&literal[starts_with.len()..end_of_str])?;
         //Check if it has a language tag or Datatype tag
         if literal[end_of_str + 1..].starts_with("@") {
             if literal[end_of_str + 2..].contains("^^") {
-                return Result::Err(InvalidLiteralError::new(String::from("Literal cannot contain language tag as well as datatype.")))
+                return Result::Err(InvalidLiteralError::new(ErrorKind::InvalidLiteral, String::from("Literal cannot contain language tag as well as datatype.")))
             }
-            Ok(Literal::create_lang_literal(val, String::from(&literal[end_of_str + 2..])))
+            Literal::parse_lang_tag(val, &literal[end_of_str + 2..])
         } else if literal[end_of_str + 1..].starts_with("^^") {
             // datatype
             if literal[end_of_str + 3..].starts_with("<"){
-                if !literal.ends_with(">") {return Err(InvalidLiteralError::new(String::from("Literal DataType doesn't end on >")))}
+                if !literal.ends_with(">") {return Err(InvalidLiteralError::new(ErrorKind::InvalidLiteral, String::from("Literal DataType doesn't end on >")))}
                 Literal::parse_datatype(val, &literal[end_of_str + 4..])
             }
             else {
                 Literal::parse_prefixed_datatype(val, &literal[end_of_str+3..], &prefix_mapping)
             }
         } else {
-            Ok(Literal::create_literal(val))
+            Ok(Literal::from(val))
         }
     }
 
     pub fn parse_literal(literal: &str, prefix_mapping: &HashMap<String, String>) -> Result<Literal, InvalidLiteralError>{
         //true|false|number|quotes " ' ''' | @ | ^^<> | ^^prefix:suffix
         if literal == "true"{
-            return Ok(Literal::create_boolean_literal(true));
+            return Ok(Literal::from(true));
         }
         else if literal == "false"{
-            return Ok(Literal::create_boolean_literal(false));
+            return Ok(Literal::from(false));
         }
 
         let mut starts_with="";
@@ -450,6 +778,309 @@ impl Literal{
 
 }
 
+///
+/// The value of a `Literal`, see `Literal::value`
+///
+/// Literals of xsd:integer and all types derived from it are `Integer`, the string types derived from xsd:string are `String`.
+///
+/// Literals can also be created from and converted to Rust values directly with `From` and `TryFrom`.
+///
+/// # Example
+///
+/// ```
+/// use rdf4rust::rdf::node_factory::{Literal, LiteralValue, RDFNode};
+/// use rdf4rust::rdf::xsd::{XSD_BYTE, XSD_DOUBLE};
+/// use std::convert::TryFrom;
+///
+/// let byte = Literal::from(8i8);
+/// assert_eq!(&*XSD_BYTE, byte.get_datatype());
+/// assert_eq!(8, i64::try_from(&byte).unwrap());
+/// assert!(bool::try_from(&byte).is_err());
+///
+/// let inf = Literal::from(f64::INFINITY);
+/// assert_eq!("\"INF\"^^<http://www.w3.org/2001/XMLSchema#double>", inf.as_string(true));
+/// assert_eq!(Some(f64::INFINITY), inf.value().unwrap().as_f64());
+///
+/// assert_eq!(1.5, f64::try_from(&Literal::create_typed_literal(String::from("1.50"), &XSD_DOUBLE)).unwrap());
+/// assert_eq!("hello", String::try_from(&Literal::from("hello")).unwrap());
+/// ```
+///
+#[derive(Clone, Debug, PartialEq)]
+pub enum LiteralValue{
+    String(String),
+    /// A string with its language tag
+    LangString(String, String),
+    /// A string with its language tag and base direction
+    DirLangString(String, String, BaseDirection),
+    Boolean(bool),
+    Integer(BigInt),
+    Decimal(Decimal),
+    Double(f64),
+    Float(f32),
+    DateTime(DateTime),
+    Date(Date),
+    Time(Time),
+    GYear(GYear),
+    GYearMonth(GYearMonth),
+    GMonth(GMonth),
+    GDay(GDay),
+    GMonthDay(GMonthDay),
+    Duration(Duration),
+    /// The decoded octets of xsd:hexBinary and xsd:base64Binary
+    Binary(Vec<u8>),
+    /// An xsd:anyURI, which may be a relative reference
+    AnyUri(String),
+    /// The lexical value of a literal with a datatype unknown to rdf4rust
+    Other(String)
+}
+
+impl LiteralValue{
+
+    /// Gets numeric values (integer, decimal, double and float) as f64, None for all other values
+    pub fn as_f64(&self) -> Option<f64>{
+        match self{
+            LiteralValue::Integer(val) => Some(Decimal::from(val.clone()).to_f64()),
+            LiteralValue::Decimal(val) => Some(val.to_f64()),
+            LiteralValue::Double(val) => Some(*val),
+            LiteralValue::Float(val) => Some(*val as f64),
+            _ => None
+        }
+    }
+}
+
+///
+/// Decodes the escape sequences (`\t`, `\n`, `\"`, `\u00E9`, ...) of a quoted string in N-Triples or Turtle
+///
+fn unescape_string(value: &str) -> Result<String, InvalidLiteralError>{
+    if !value.contains('\\'){
+        return Ok(String::from(value))
+    }
+    let invalid = |msg: String| InvalidLiteralError::new(ErrorKind::InvalidLiteral, msg);
+    let mut ret = String::with_capacity(value.len());
+    let mut chars = value.chars();
+    while let Some(c) = chars.next(){
+        if c != '\\'{
+            ret.push(c);
+            continue;
+        }
+        match chars.next(){
+            Some('t') => ret.push('\t'),
+            Some('b') => ret.push('\u{8}'),
+            Some('n') => ret.push('\n'),
+            Some('r') => ret.push('\r'),
+            Some('f') => ret.push('\u{C}'),
+            Some(c) if c == '"' || c == '\'' || c == '\\' => ret.push(c),
+            Some(c) if c == 'u' || c == 'U' => {
+                let len = if c == 'u' {4} else {8};
+                let hex: String = chars.by_ref().take(len).collect();
+                let decoded = if hex.len() == len && hex.chars().all(|h| h.is_ascii_hexdigit()) {
+                    u32::from_str_radix(&hex, 16).ok().and_then(char::from_u32)
+                } else {
+                    None
+                };
+                match decoded{
+                    Some(decoded) => ret.push(decoded),
+                    None => return Err(invalid(format!("\\{}{} is not a valid escape sequence.", c, hex)))
+                }
+            }
+            Some(c) => return Err(invalid(format!("\\{} is not a valid escape sequence.", c))),
+            None => return Err(invalid(String::from("String ends with an unterminated escape sequence.")))
+        }
+    }
+    Ok(ret)
+}
+
+///
+/// Escapes a string for quoting in N-Triples as required by canonical N-Triples (RDF 1.2)
+///
+/// `"`, `\` and the control characters with a short escape (`\n`, `\t`, ...) use it, all other control characters `\uXXXX`.
+///
+pub(crate) fn escape_string(value: &str) -> String{
+    let mut ret = String::with_capacity(value.len());
+    for c in value.chars(){
+        match c{
+            '"' => ret.push_str("\\\""),
+            '\\' => ret.push_str("\\\\"),
+            '\n' => ret.push_str("\\n"),
+            '\r' => ret.push_str("\\r"),
+            '\t' => ret.push_str("\\t"),
+            '\u{8}' => ret.push_str("\\b"),
+            '\u{C}' => ret.push_str("\\f"),
+            '\u{0}'..='\u{1F}' | '\u{7F}' => ret.push_str(&format!("\\u{:04X}", c as u32)),
+            c => ret.push(c)
+        }
+    }
+    ret
+}
+
+fn parse_double(value: &str) -> Option<f64>{
+    match value{
+        "INF" | "+INF" => Some(f64::INFINITY),
+        "-INF" => Some(f64::NEG_INFINITY),
+        "NaN" => Some(f64::NAN),
+        _ => value.parse::<f64>().ok()
+    }
+}
+
+/// Adds the fraction digit XSD requires for canonical doubles to the `{:E}` format, e.g. `1E2` becomes `1.0E2`
+fn canonical_double(is_nan: bool, formatted: String) -> String{
+    if is_nan{
+        return String::from("NaN")
+    }
+    match formatted.as_str(){
+        "inf" => String::from("INF"),
+        "-inf" => String::from("-INF"),
+        _ => match formatted.split_once('E'){
+            Some((mantissa, exponent)) if !mantissa.contains('.') => format!("{}.0E{}", mantissa, exponent),
+            _ => formatted
+        }
+    }
+}
+
+fn format_double(value: f64) -> String{
+    if value.is_nan(){
+        String::from("NaN")
+    }else if value.is_infinite(){
+        String::from(if value > 0.0 {"INF"} else {"-INF"})
+    }else{
+        value.to_string()
+    }
+}
+
+fn not_convertible(literal: &Literal, target: &str) -> InvalidLiteralError{
+    InvalidLiteralError::new(ErrorKind::InvalidLiteral, format!("Literal {} can't be converted to {}.", literal.as_string(true), target))
+}
+
+impl TryFrom<&Literal> for bool{
+    type Error = InvalidLiteralError;
+
+    fn try_from(literal: &Literal) -> Result<Self, Self::Error> {
+        match literal.value()?{
+            LiteralValue::Boolean(val) => Ok(val),
+            _ => Err(not_convertible(literal, "bool"))
+        }
+    }
+}
+
+impl TryFrom<&Literal> for f64{
+    type Error = InvalidLiteralError;
+
+    /// Converts all numeric literals, possibly losing precision
+    fn try_from(literal: &Literal) -> Result<Self, Self::Error> {
+        literal.value()?.as_f64().ok_or_else(|| not_convertible(literal, "f64"))
+    }
+}
+
+impl TryFrom<&Literal> for f32{
+    type Error = InvalidLiteralError;
+
+    /// Converts all numeric literals, possibly losing precision
+    fn try_from(literal: &Literal) -> Result<Self, Self::Error> {
+        match literal.value()?{
+            LiteralValue::Float(val) => Ok(val),
+            val => val.as_f64().map(|val| val as f32).ok_or_else(|| not_convertible(literal, "f32"))
+        }
+    }
+}
+
+impl TryFrom<&Literal> for String{
+    type Error = InvalidLiteralError;
+
+    /// Converts literals of xsd:string (and its derived types), language-tagged strings and xsd:anyURI
+    fn try_from(literal: &Literal) -> Result<Self, Self::Error> {
+        match literal.value()?{
+            LiteralValue::String(val) | LiteralValue::LangString(val, _) | LiteralValue::DirLangString(val, _, _) | LiteralValue::AnyUri(val) => Ok(val),
+            _ => Err(not_convertible(literal, "String"))
+        }
+    }
+}
+
+impl TryFrom<&Literal> for Decimal{
+    type Error = InvalidLiteralError;
+
+    /// Converts xsd:decimal and integer literals
+    fn try_from(literal: &Literal) -> Result<Self, Self::Error> {
+        match literal.value()?{
+            LiteralValue::Decimal(val) => Ok(val),
+            LiteralValue::Integer(val) => Ok(Decimal::from(val)),
+            _ => Err(not_convertible(literal, "Decimal"))
+        }
+    }
+}
+
+/// Implements `TryFrom<&Literal>` for integer types, failing if the literal isn't an integer or the value doesn't fit
+macro_rules! try_from_integer_literal {
+    ($($target:ty),*) => {
+        $(
+            impl TryFrom<&Literal> for $target{
+                type Error = InvalidLiteralError;
+
+                fn try_from(literal: &Literal) -> Result<Self, Self::Error> {
+                    match literal.value()?{
+                        LiteralValue::Integer(val) => <$target>::try_from(&val).map_err(|_| not_convertible(literal, stringify!($target))),
+                        _ => Err(not_convertible(literal, stringify!($target)))
+                    }
+                }
+            }
+        )*
+    };
+}
+
+try_from_integer_literal!(i8, i16, i32, i64, i128, isize, u8, u16, u32, u64, u128, usize);
+
+/// Implements `TryFrom<&Literal>` for the value types which belong to exactly one `LiteralValue` variant
+macro_rules! try_from_value_literal {
+    ($($variant:ident => $target:ty),*) => {
+        $(
+            impl TryFrom<&Literal> for $target{
+                type Error = InvalidLiteralError;
+
+                fn try_from(literal: &Literal) -> Result<Self, Self::Error> {
+                    match literal.value()?{
+                        LiteralValue::$variant(val) => Ok(val),
+                        _ => Err(not_convertible(literal, stringify!($target)))
+                    }
+                }
+            }
+        )*
+    };
+}
+
+try_from_value_literal!(Integer => BigInt, DateTime => DateTime, Date => Date, Time => Time, GYear => GYear, GYearMonth => GYearMonth,
+    GMonth => GMonth, GDay => GDay, GMonthDay => GMonthDay, Duration => Duration, Binary => Vec<u8>);
+
+/// Implements `From<T> for Literal` using the `Display` implementation of `T` as lexical value
+macro_rules! literal_from_display {
+    ($($source:ty => $dtype:ident),*) => {
+        $(
+            impl From<$source> for Literal{
+                fn from(value: $source) -> Self {
+                    Literal::create_typed_literal(value.to_string(), &$dtype)
+                }
+            }
+        )*
+    };
+}
+
+literal_from_display!(String => XSD_STRING, &str => XSD_STRING, bool => XSD_BOOLEAN,
+    i8 => XSD_BYTE, i16 => XSD_SHORT, i32 => XSD_INT, i64 => XSD_LONG,
+    u8 => XSD_UNSIGNED_BYTE, u16 => XSD_UNSIGNED_SHORT, u32 => XSD_UNSIGNED_INT, u64 => XSD_UNSIGNED_LONG,
+    BigInt => XSD_INTEGER, Decimal => XSD_DECIMAL,
+    DateTime => XSD_DATE_TIME, Date => XSD_DATE, Time => XSD_TIME, GYear => XSD_G_YEAR, GYearMonth => XSD_G_YEAR_MONTH,
+    GMonth => XSD_G_MONTH, GDay => XSD_G_DAY, GMonthDay => XSD_G_MONTH_DAY, Duration => XSD_DURATION);
+
+impl From<f64> for Literal{
+    fn from(value: f64) -> Self {
+        Literal::create_typed_literal(format_double(value), &XSD_DOUBLE)
+    }
+}
+
+impl From<f32> for Literal{
+    fn from(value: f32) -> Self {
+        Literal::create_typed_literal(format_double(value as f64), &XSD_FLOAT)
+    }
+}
+
 impl RDFNode for Literal{
 
     fn is_literal(&self) -> bool {
@@ -469,20 +1100,23 @@ impl RDFNode for Literal{
     }
 
     fn as_string(&self, quoting: bool) -> String {
-        if self.dtype.is_xsd_string(){
-            if let Some(lang) = &self.lang {
-                // language is set so append
-                return  String::from("\"").add(&self.value).add("\"@").add(&self.lang.as_ref().expect("checked"));
+        if let Some(lang) = &self.lang {
+            // language is set so append it and the direction if any
+            return match self.direction {
+                Some(direction) => format!("\"{}\"@{}--{}", escape_string(&self.value), lang, direction),
+                None => String::from("\"").add(&escape_string(&self.value)).add("\"@").add(lang)
             }
+        }
+        if self.dtype.is_xsd_string(){
             //language is not set, so check if quoting is true and quote if so
             return if quoting {
-                String::from("\"").add(&self.value).add("\"")
+                String::from("\"").add(&escape_string(&self.value)).add("\"")
             } else {
                 String::from(&self.value)
             }
         }
         //datatype is not string, so add datatype.
-        return String::from("\"").add(&self.value).add("\"^^<").add(&self.dtype.get_value()).add(">");
+        String::from("\"").add(&escape_string(&self.value)).add("\"^^<").add(self.dtype.get_value()).add(">")
     }
 
     fn get_value(&self) -> String {
@@ -491,7 +1125,7 @@ impl RDFNode for Literal{
 
 }
 
-#[derive(Ord, PartialOrd, PartialEq, Eq, Hash)]
+#[derive(Clone, Ord, PartialOrd, PartialEq, Eq, Hash)]
 pub struct BlankNode{
     id: String
 }
@@ -520,6 +1154,69 @@ impl BlankNode{
 
 }
 
+///
+/// Allocates the blank nodes of a single document or load call.
+///
+/// Blank node labels like `_:b0` are only meaningful within the document using them, so each scope maps a label
+/// to a fresh ID which is the same for every use of the label within the scope, but differs from the IDs of all other scopes.
+/// A preserving scope keeps the labels as IDs instead, e.g. to write a single file back with its original labels.
+///
+/// Scopes don't store the labels they have seen, so they can be cloned and shared between threads.
+///
+/// # Example
+///
+/// ```
+/// use rdf4rust::rdf::node_factory::{BlankNodeScope, RDFNode};
+///
+/// let first = BlankNodeScope::new();
+/// let second = BlankNodeScope::new();
+/// assert!(first.get_blank_node("b0") == first.get_blank_node("b0"));
+/// assert!(first.get_blank_node("b0") != first.get_blank_node("b1"));
+/// assert!(first.get_blank_node("b0") != second.get_blank_node("b0"));
+///
+/// assert_eq!("b0", BlankNodeScope::preserving().get_blank_node("b0").get_value());
+/// ```
+///
+#[derive(Clone, Copy, Debug)]
+pub struct BlankNodeScope{
+    namespace: Option<Uuid>
+}
+
+impl BlankNodeScope{
+
+    /// Creates a new scope, whose IDs differ from those of all other scopes
+    pub fn new() -> Self{
+        BlankNodeScope{
+            namespace: Some(Uuid::new_v4())
+        }
+    }
+
+    /// Creates a scope keeping the labels as IDs
+    pub fn preserving() -> Self{
+        BlankNodeScope{
+            namespace: None
+        }
+    }
+
+    pub fn is_preserving(&self) -> bool{
+        self.namespace.is_none()
+    }
+
+    /// Gets the blank node for `label` (without `_:`) within this scope
+    pub fn get_blank_node(&self, label: &str) -> BlankNode{
+        match &self.namespace{
+            Some(namespace) => BlankNode{ id: Uuid::new_v5(namespace, label.as_bytes()).to_simple().to_string() },
+            None => BlankNode::create_blank_node(label)
+        }
+    }
+}
+
+impl Default for BlankNodeScope{
+    fn default() -> Self {
+        BlankNodeScope::new()
+    }
+}
+
 impl RDFNode for BlankNode{
     fn is_literal(&self) -> bool {
         false
@@ -546,7 +1243,7 @@ impl RDFNode for BlankNode{
     }
 }
 
-#[derive(PartialOrd, PartialEq, Eq, Hash)]
+#[derive(Clone, PartialOrd, PartialEq, Eq, Hash)]
 pub struct IRIResource {
     iri: IRI,
 }
@@ -636,22 +1333,37 @@ impl RDFNode for Variable{
     }
 }
 
-#[derive(Debug)]
-pub struct InvalidLiteralError {
-    pub msg: String
-}
+///
+/// Error which occurred while creating or parsing a literal
+///
+pub type InvalidLiteralError = RDFError;
 
-impl Error for InvalidLiteralError{
+///
+/// Creates the resource of a valid IRI, mainly for constants and examples
+///
+/// # Panics
+///
+/// If `iri` isn't a valid IRI, use `IRI::create_iri` for IRIs which need to be checked
+///
+/// # Example
+///
+/// ```
+/// use rdf4rust::rdf::node_factory::{iri, rdf};
+///
+/// assert_eq!("http://example.com/alice", iri("http://example.com/alice").get_iri().to_string());
+/// assert!(iri("http://www.w3.org/1999/02/22-rdf-syntax-ns#type") == rdf("type"));
+/// ```
+///
+pub fn iri(iri: &str) -> IRIResource{
+    IRIResource::create_resource(IRI::create_iri(&String::from(iri)).expect("Is valid IRI"))
 }
 
-impl InvalidLiteralError{
-    pub fn new(msg: String) -> InvalidLiteralError {
-        InvalidLiteralError{msg}
-    }
+/// Creates the resource of `local_name` in the RDF vocabulary
+pub fn rdf(local_name: &str) -> IRIResource{
+    iri(&(rdf_base() + local_name))
 }
 
-impl fmt::Display for InvalidLiteralError {
-    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
-        write!(f,"{}",self.msg)
-    }
-}
\ No newline at end of file
+/// Creates the resource of `local_name` in the RDFS vocabulary
+pub fn rdfs(local_name: &str) -> IRIResource{
+    iri(&(rdfs_base() + local_name))
+}
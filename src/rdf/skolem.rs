@@ -0,0 +1,233 @@
+//!
+//! Skolemization of blank nodes according to RDF 1.1 Concepts section 3.5.
+//!
+//! Blank nodes are replaced by IRIs of the form `{authority}/.well-known/genid/{id}`, which can be published and referenced
+//! from outside. Unskolemization replaces IRIs of that form by blank nodes again, restoring the original IDs.
+//!
+
+use crate::rdf::graph::{Graph, Quad, Node, ResourceNode, Statement};
+use crate::rdf::node_factory::{BlankNode, IRIResource, RDFNode};
+use crate::util::iri::{IRI, IRIInvalidError, percent_encode, percent_decode};
+use crate::error::ErrorKind;
+
+/// The path of skolem IRIs below the authority
+pub const GENID_PATH: &str = "/.well-known/genid/";
+
+///
+/// Converts blank nodes to skolem IRIs below an authority base and back
+///
+/// The ID of the blank node is percent-encoded as last path segment, so `unskolemize_iri` restores the same blank node.
+///
+/// # Example
+///
+/// ```
+/// use rdf4rust::rdf::skolem::Skolemizer;
+/// use rdf4rust::rdf::node_factory::{BlankNode, RDFNode};
+///
+/// let skolemizer = Skolemizer::new("https://example.com/").expect("Is valid authority");
+/// let iri = skolemizer.skolemize_blank_node(&BlankNode::create_blank_node("b0"));
+/// assert_eq!("https://example.com/.well-known/genid/b0", iri.get_value());
+///
+/// let bnode = skolemizer.unskolemize_iri(&iri).expect("Is a skolem IRI");
+/// assert_eq!("b0", bnode.get_value());
+///
+/// // IRIs of other authorities are kept
+/// let other = Skolemizer::new("https://example.org").unwrap().skolemize_blank_node(&bnode);
+/// assert!(skolemizer.unskolemize_iri(&other).is_none());
+///
+/// assert!(Skolemizer::new("urn:example").is_err());
+/// ```
+///
+#[derive(Clone, Debug)]
+pub struct Skolemizer{
+    base: String
+}
+
+impl Skolemizer{
+
+    ///
+    /// Creates a skolemizer minting IRIs below `authority`, e.g. `https://example.com`
+    ///
+    /// # Parameters
+    ///
+    /// * `authority` - An absolute IRI with an authority, optionally with a path, but without query or fragment
+    ///
+    /// # Returns
+    ///
+    /// The skolemizer or an error of kind `ErrorKind::InvalidIri` if `authority` is not valid
+    ///
+    pub fn new(authority: &str) -> Result<Skolemizer, IRIInvalidError>{
+        let iri = IRI::create_iri(&String::from(authority))?;
+        if iri.get_authority().is_none() || iri.get_query().is_some() || iri.get_fragment().is_some() {
+            return Err(IRIInvalidError::new(ErrorKind::InvalidIri, format!("<{}> is not a valid authority base, expected an IRI with authority and without query or fragment.", authority)))
+        }
+        Ok(Skolemizer{
+            base: format!("{}{}", authority.trim_end_matches('/'), GENID_PATH)
+        })
+    }
+
+    /// Gets the prefix of all skolem IRIs, e.g. `https://example.com/.well-known/genid/`
+    pub fn get_base(&self) -> &str{
+        &self.base
+    }
+
+    /// Checks if `iri` is a skolem IRI minted by this skolemizer
+    pub fn is_skolem_iri(&self, iri: &IRIResource) -> bool{
+        iri.get_value().starts_with(&self.base)
+    }
+
+    /// Replaces `bnode` by its skolem IRI
+    pub fn skolemize_blank_node(&self, bnode: &BlankNode) -> IRIResource{
+        let iri = IRI::create_iri(&format!("{}{}", self.base, percent_encode(&bnode.get_value())))
+            .expect("Base is valid and ID is percent-encoded");
+        IRIResource::create_resource(iri)
+    }
+
+    ///
+    /// Replaces a skolem IRI by its blank node
+    ///
+    /// If the last path segment is not a valid blank node label (e.g. the IRI was minted elsewhere),
+    /// the ID is derived from the whole IRI, so the same IRI always results in the same blank node.
+    ///
+    /// # Returns
+    ///
+    /// The blank node or None if `iri` is not a skolem IRI of this skolemizer
+    ///
+    pub fn unskolemize_iri(&self, iri: &IRIResource) -> Option<BlankNode>{
+        let value = iri.get_value();
+        let id = value.strip_prefix(&self.base)?;
+        match percent_decode(id) {
+            Ok(id) if is_blank_node_label(&id) => Some(BlankNode::create_blank_node(&id)),
+            _ => Some(BlankNode::generate_from_string(&value))
+        }
+    }
+
+    /// Replaces all blank nodes of `quad`, including a blank node graph name and within quoted triples, by skolem IRIs
+    pub fn skolemize_quad(&self, quad: Quad) -> Quad{
+        quad.map_terms(&|node| self.skolemize_node(node), &|predicate| predicate)
+    }
+
+    /// Replaces all skolem IRIs of `quad`, including the graph name, by blank nodes
+    pub fn unskolemize_quad(&self, quad: Quad) -> Quad{
+        quad.map_terms(&|node| self.unskolemize_node(node), &|predicate| predicate)
+    }
+
+    ///
+    /// Replaces all blank nodes of `graph` by skolem IRIs
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use rdf4rust::rdf::skolem::Skolemizer;
+    /// use rdf4rust::rdf::graph::{Graph, SimpleGraph, Statement, Node, ResourceNode};
+    /// use rdf4rust::rdf::node_factory::{BlankNode, iri};
+    ///
+    /// let knows = iri("http://xmlns.com/foaf/0.1/knows");
+    /// let mut graph = SimpleGraph::new();
+    /// graph.add_statement(Statement::create(ResourceNode::BNode { bnode: BlankNode::create_blank_node("alice") },
+    ///     knows, Node::BNode { bnode: BlankNode::create_blank_node("bob") }));
+    ///
+    /// let skolemizer = Skolemizer::new("https://example.com").unwrap();
+    /// skolemizer.skolemize_graph(&mut graph);
+    /// assert_eq!("<https://example.com/.well-known/genid/alice> <http://xmlns.com/foaf/0.1/knows> <https://example.com/.well-known/genid/bob> .",
+    ///     graph.list_statements(None, None, None)[0].to_string());
+    ///
+    /// skolemizer.unskolemize_graph(&mut graph);
+    /// assert_eq!("_:alice <http://xmlns.com/foaf/0.1/knows> _:bob .", graph.list_statements(None, None, None)[0].to_string());
+    /// ```
+    ///
+    pub fn skolemize_graph(&self, graph: &mut dyn Graph){
+        replace_statements(graph, |stmt| self.skolemize_statement(stmt));
+    }
+
+    /// Replaces all skolem IRIs of `graph` by blank nodes
+    pub fn unskolemize_graph(&self, graph: &mut dyn Graph){
+        replace_statements(graph, |stmt| self.unskolemize_statement(stmt));
+    }
+
+    fn skolemize_statement(&self, stmt: Statement) -> Statement{
+        stmt.map_terms(&|node| self.skolemize_node(node), &|predicate| predicate)
+    }
+
+    fn unskolemize_statement(&self, stmt: Statement) -> Statement{
+        stmt.map_terms(&|node| self.unskolemize_node(node), &|predicate| predicate)
+    }
+
+    fn skolemize_node(&self, node: Node) -> Node{
+        match node {
+            Node::BNode { bnode } => Node::IRINode { iri: self.skolemize_blank_node(&bnode) },
+            node => node
+        }
+    }
+
+    fn unskolemize_node(&self, node: Node) -> Node{
+        match node {
+            Node::IRINode { iri } => match self.unskolemize_iri(&iri) {
+                Some(bnode) => Node::BNode { bnode },
+                None => Node::IRINode { iri }
+            },
+            node => node
+        }
+    }
+
+    pub(crate) fn skolemize_resource(&self, resource: ResourceNode) -> ResourceNode{
+        match resource {
+            ResourceNode::BNode { bnode } => ResourceNode::IRINode { iri: self.skolemize_blank_node(&bnode) },
+            resource => resource
+        }
+    }
+
+    pub(crate) fn unskolemize_resource(&self, resource: ResourceNode) -> ResourceNode{
+        match resource {
+            ResourceNode::IRINode { iri } => match self.unskolemize_iri(&iri) {
+                Some(bnode) => ResourceNode::BNode { bnode },
+                None => ResourceNode::IRINode { iri }
+            },
+            resource => resource
+        }
+    }
+}
+
+///
+/// Skolemization applied inline while parsing, see `ParseOptions::with_skolemization`
+///
+#[derive(Clone, Debug)]
+pub enum Skolemization{
+    /// Replaces the blank nodes of the input by skolem IRIs
+    Skolemize(Skolemizer),
+    /// Replaces the skolem IRIs of the input by blank nodes
+    Unskolemize(Skolemizer)
+}
+
+impl Skolemization{
+
+    pub fn apply(&self, quad: Quad) -> Quad{
+        match self {
+            Skolemization::Skolemize(skolemizer) => skolemizer.skolemize_quad(quad),
+            Skolemization::Unskolemize(skolemizer) => skolemizer.unskolemize_quad(quad)
+        }
+    }
+}
+
+/// Replaces each statement of `graph` by its mapping, unchanged statements are kept
+fn replace_statements(graph: &mut dyn Graph, map: impl Fn(Statement) -> Statement){
+    let mut removed = Vec::new();
+    let mut added = Vec::new();
+    for stmt in graph.list_statements(None, None, None) {
+        let mapped = map(stmt.clone());
+        if &mapped != stmt {
+            removed.push(stmt.clone());
+            added.push(mapped);
+        }
+    }
+    graph.remove_all_statements(&removed);
+    graph.add_all_statements(&added);
+}
+
+/// Checks if `id` can be written as blank node label in N-Triples, Turtle and N-Quads
+fn is_blank_node_label(id: &str) -> bool{
+    !id.is_empty()
+        && id.chars().all(|c| c.is_alphanumeric() || c == '_' || c == '-' || c == '.')
+        && !id.starts_with(['-', '.'])
+        && !id.ends_with('.')
+}
@@ -0,0 +1,89 @@
+//!
+//! Terms of patterns, which are either variables or nodes, as used by rules and SPARQL queries,
+//! and the scanning of terms in their textual form.
+//!
+
+use crate::rdf::graph::Node;
+use crate::rdf::node_factory::IRIResource;
+use std::fmt;
+
+///
+/// A term of a pattern, either a variable or a fixed node
+///
+/// # Example
+///
+/// ```
+/// use rdf4rust::rdf::term::Term;
+/// use rdf4rust::rdf::node_factory::iri;
+///
+/// let name = Term::iri(iri("http://example.com/name"));
+/// assert_eq!("<http://example.com/name>", name.to_string());
+/// assert!(!name.is_variable());
+/// assert_eq!("?x", Term::var("x").to_string());
+/// ```
+///
+#[derive(Clone, PartialEq, Eq, Hash)]
+pub enum Term{
+    Variable(String),
+    Constant(Node)
+}
+
+impl Term{
+
+    /// Creates the variable `name`, given without the leading `?`
+    pub fn var(name: &str) -> Term{
+        Term::Variable(String::from(name))
+    }
+
+    /// Creates a constant IRI
+    pub fn iri(iri: IRIResource) -> Term{
+        Term::Constant(Node::IRINode { iri })
+    }
+
+    pub fn is_variable(&self) -> bool{
+        matches!(self, Term::Variable(_))
+    }
+}
+
+impl fmt::Display for Term{
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Term::Variable(name) => write!(f, "?{}", name),
+            Term::Constant(node) => write!(f, "{}", node.as_term_string())
+        }
+    }
+}
+
+/// The length of a word, which ends before whitespace or a delimiter
+pub(crate) fn word_length(text: &str) -> usize{
+    text.find(|c: char| c.is_whitespace() || "()[],".contains(c)).unwrap_or(text.len())
+}
+
+/// The position after the quote closing the string `text` starts with, None if it isn't closed
+pub(crate) fn closing_quote(text: &str) -> Option<usize>{
+    let quote = text.chars().next()?;
+    let mut escaped = false;
+    for (i, c) in text.char_indices().skip(1) {
+        if escaped {
+            escaped = false;
+        } else if c == '\\' {
+            escaped = true;
+        } else if c == quote {
+            return Some(i + 1)
+        }
+    }
+    None
+}
+
+/// The length of a quoted literal with its language tag or datatype
+pub(crate) fn literal_length(text: &str) -> usize{
+    let end = closing_quote(text).unwrap_or(text.len());
+    let rest = &text[end..];
+    if let Some(lang) = rest.strip_prefix('@') {
+        end + 1 + lang.find(|c: char| !(c.is_alphanumeric() || c == '-')).unwrap_or(lang.len())
+    } else if let Some(datatype) = rest.strip_prefix("^^") {
+        end + 2 + if datatype.starts_with('<') {datatype.find('>').map_or(datatype.len(), |i| i + 1)} else {word_length(datatype)}
+    } else {
+        end
+    }
+}
@@ -1,17 +1,49 @@
 
 
 use crate::util::iri::IRI;
+use crate::util::date_time::{DateTime, Date, Time, GYear, GYearMonth, GMonth, GDay, GMonthDay, Duration};
+use crate::util::decimal::Decimal;
 use std::collections::HashMap;
-use std::sync::Mutex;
-use std::borrow::Borrow;
+use std::sync::{Arc, RwLock};
+use std::ops::Deref;
+use std::cmp::Ordering;
+use std::fmt;
+use std::hash::{Hash, Hasher};
+use regex::Regex;
 
+/// Local names of xsd:integer and all built-in types derived from it
+const INTEGER_TYPES: [&str; 13] = ["integer", "nonNegativeInteger", "nonPositiveInteger", "positiveInteger", "negativeInteger",
+	"long", "int", "short", "byte", "unsignedLong", "unsignedInt", "unsignedShort", "unsignedByte"];
+
+const NAME_START_CHARS: &str = r":A-Z_a-z\x{C0}-\x{D6}\x{D8}-\x{F6}\x{F8}-\x{2FF}\x{370}-\x{37D}\x{37F}-\x{1FFF}\x{200C}-\x{200D}\x{2070}-\x{218F}\x{2C00}-\x{2FEF}\x{3001}-\x{D7FF}\x{F900}-\x{FDCF}\x{FDF0}-\x{FFFD}\x{10000}-\x{EFFFF}";
+const NAME_CHARS: &str = r"\-.0-9\x{B7}\x{300}-\x{36F}\x{203F}-\x{2040}";
+
+lazy_static! {
+	static ref INTEGER_PATTERN: Regex = Regex::new(r"^[+-]?[0-9]+$").unwrap();
+	static ref DECIMAL_PATTERN: Regex = Regex::new(r"^[+-]?([0-9]+(\.[0-9]*)?|\.[0-9]+)$").unwrap();
+	static ref FLOAT_PATTERN: Regex = Regex::new(r"^([+-]?([0-9]+(\.[0-9]*)?|\.[0-9]+)([eE][+-]?[0-9]+)?|[+-]?INF|NaN)$").unwrap();
+	static ref HEX_BINARY_PATTERN: Regex = Regex::new(r"^([0-9a-fA-F]{2})*$").unwrap();
+	static ref BASE64_BINARY_PATTERN: Regex = Regex::new(r"^(([A-Za-z0-9+/] ?){4})*(([A-Za-z0-9+/] ?){3}[A-Za-z0-9+/]|([A-Za-z0-9+/] ?){2}[AEIMQUYcgkosw048] ?=|[A-Za-z0-9+/] ?[AQgw] ?= ?=)?$").unwrap();
+	static ref LANGUAGE_PATTERN: Regex = Regex::new(r"^[a-zA-Z]{1,8}(-[a-zA-Z0-9]{1,8})*$").unwrap();
+	static ref NAME_PATTERN: Regex = Regex::new(&format!("^[{}][{}{}]*$", NAME_START_CHARS, NAME_START_CHARS, NAME_CHARS)).unwrap();
+	static ref NMTOKEN_PATTERN: Regex = Regex::new(&format!("^[{}{}]+$", NAME_START_CHARS, NAME_CHARS)).unwrap();
+	static ref REFERENCE_BASE: IRI = IRI::create_iri(&String::from("http://example.org/")).unwrap();
+}
 
 lazy_static! {
-	static ref ADDITIONAL_TYPES: Mutex<HashMap<String, &'static XSDDataType>> = Mutex::new(HashMap::new());
+	static ref BUILTIN_TYPES: HashMap<&'static str, &'static XSDDataType> =
+		get_builtin_types().into_iter().map(|xsd| (xsd.dtype.as_str(), xsd)).collect();
+	static ref REGISTRY: RwLock<HashMap<String, Arc<XSDDataType>>> = RwLock::new(HashMap::new());
 }
 
 lazy_static! {
 	pub static ref XSD_STRING: XSDDataType = xsd_string();
+	pub static ref XSD_NORMALIZED_STRING: XSDDataType = xsd_normalized_string();
+	pub static ref XSD_TOKEN: XSDDataType = xsd_token();
+	pub static ref XSD_LANGUAGE: XSDDataType = xsd_language();
+	pub static ref XSD_NAME: XSDDataType = xsd_name();
+	pub static ref XSD_NC_NAME: XSDDataType = xsd_nc_name();
+	pub static ref XSD_NM_TOKEN: XSDDataType = xsd_nm_token();
 	pub static ref XSD_UNSIGNED_BYTE: XSDDataType = xsd_unsigned_byte();
 	pub static ref XSD_UNSIGNED_SHORT: XSDDataType = xsd_unsigned_short();
 	pub static ref XSD_UNSIGNED_INT: XSDDataType = xsd_unsigned_int();
@@ -21,7 +53,7 @@ lazy_static! {
 	pub static ref XSD_INT: XSDDataType = xsd_int();
 	pub static ref XSD_LONG: XSDDataType = xsd_long();
 	pub static ref XSD_INTEGER: XSDDataType = xsd_integer();
-	pub static ref XSD_NON_NEGATIVE_INTEGER: XSDDataType = xsd_non_positive_integer();
+	pub static ref XSD_NON_NEGATIVE_INTEGER: XSDDataType = xsd_non_negative_integer();
 	pub static ref XSD_NON_POSITIVE_INTEGER: XSDDataType = xsd_non_positive_integer();
 	pub static ref XSD_POSITIVE_INTEGER: XSDDataType = xsd_positive_integer();
 	pub static ref XSD_NEGATIVE_INTEGER: XSDDataType = xsd_negative_integer();
@@ -35,7 +67,10 @@ lazy_static! {
 	pub static ref XSD_HEX_BINARY: XSDDataType = xsd_hex_binary();
 	pub static ref XSD_BASE64_BINARY: XSDDataType = xsd_base64binary();
 	pub static ref XSD_DURATION: XSDDataType = xsd_duration();
+	pub static ref XSD_DAY_TIME_DURATION: XSDDataType = xsd_day_time_duration();
+	pub static ref XSD_YEAR_MONTH_DURATION: XSDDataType = xsd_year_month_duration();
 	pub static ref XSD_DATE_TIME: XSDDataType = xsd_date_time();
+	pub static ref XSD_DATE_TIME_STAMP: XSDDataType = xsd_date_time_stamp();
 	pub static ref XSD_TIME: XSDDataType = xsd_time();
 	pub static ref XSD_DATE: XSDDataType = xsd_date();
 	pub static ref XSD_G_YEAR_MONTH: XSDDataType = xsd_g_year_month();
@@ -43,234 +78,570 @@ lazy_static! {
 	pub static ref XSD_G_MONTH: XSDDataType = xsd_g_month();
 	pub static ref XSD_G_DAY: XSDDataType = xsd_g_day();
 	pub static ref XSD_G_MONTH_DAY: XSDDataType = xsd_g_month_day();
+	pub static ref RDF_LANG_STRING: XSDDataType = rdf_lang_string();
+	pub static ref RDF_DIR_LANG_STRING: XSDDataType = rdf_dir_lang_string();
+}
+
+///
+/// Gets all built-in XSD data types which are supported by rdf4rust, including rdf:langString and rdf:dirLangString.
+///
+/// Those are always known to `XSDDataType::get_or_default` and can't be replaced using `XSDDataType::register`
+///
+pub fn get_builtin_types() -> Vec<&'static XSDDataType>{
+	vec![&*XSD_STRING, &*XSD_NORMALIZED_STRING, &*XSD_TOKEN, &*XSD_LANGUAGE, &*XSD_NAME, &*XSD_NC_NAME, &*XSD_NM_TOKEN,
+		 &*XSD_UNSIGNED_BYTE, &*XSD_UNSIGNED_SHORT, &*XSD_UNSIGNED_INT, &*XSD_UNSIGNED_LONG,
+		 &*XSD_BYTE, &*XSD_SHORT, &*XSD_INT, &*XSD_LONG, &*XSD_INTEGER,
+		 &*XSD_NON_NEGATIVE_INTEGER, &*XSD_NON_POSITIVE_INTEGER, &*XSD_POSITIVE_INTEGER, &*XSD_NEGATIVE_INTEGER,
+		 &*XSD_DECIMAL, &*XSD_FLOAT, &*XSD_DOUBLE, &*XSD_BOOLEAN,
+		 &*XSD_ANY_TYPE, &*XSD_ANY_SIMPLE_TYPE, &*XSD_ANY_URI, &*XSD_HEX_BINARY, &*XSD_BASE64_BINARY,
+		 &*XSD_DURATION, &*XSD_DAY_TIME_DURATION, &*XSD_YEAR_MONTH_DURATION,
+		 &*XSD_DATE_TIME, &*XSD_DATE_TIME_STAMP, &*XSD_TIME, &*XSD_DATE,
+		 &*XSD_G_YEAR_MONTH, &*XSD_G_YEAR, &*XSD_G_MONTH, &*XSD_G_DAY, &*XSD_G_MONTH_DAY,
+		 &*RDF_LANG_STRING, &*RDF_DIR_LANG_STRING]
 }
 
 /// Base URL for XSD =  http://www.w3.org/2001/XMLSchema#
 pub fn xsd_base() -> String {
     String::from("http://www.w3.org/2001/XMLSchema#")
 }
+/// Base URL for RDF = http://www.w3.org/1999/02/22-rdf-syntax-ns#
+pub fn rdf_base() -> String {
+    String::from("http://www.w3.org/1999/02/22-rdf-syntax-ns#")
+}
+/// Base URL for RDFS = http://www.w3.org/2000/01/rdf-schema#
+pub fn rdfs_base() -> String {
+    String::from("http://www.w3.org/2000/01/rdf-schema#")
+}
+/// RDF Type Language-Tagged String = http://www.w3.org/1999/02/22-rdf-syntax-ns#langString, the datatype of all literals with a language tag.
+/// No value is valid on its own, as the tag is part of the value.
+pub fn rdf_lang_string() -> XSDDataType {
+    XSDDataType::create_from_string(rdf_base() + "langString", |_| false).expect("")
+}
+/// RDF Type Directional Language-Tagged String = http://www.w3.org/1999/02/22-rdf-syntax-ns#dirLangString, the datatype of literals with a language tag and a base direction (RDF 1.2)
+pub fn rdf_dir_lang_string() -> XSDDataType {
+    XSDDataType::create_from_string(rdf_base() + "dirLangString", |_| false).expect("")
+}
 /// XSD Type String = http://www.w3.org/2001/XMLSchema#string
 pub fn xsd_string() -> XSDDataType {
-    XSDDataType::create_from_string(xsd_base() + "string", |s| true).expect("")
+    XSDDataType::create_from_string(xsd_base() + "string", |s| is_xml_string(&s)).expect("")
+}
+/// XSD Type Normalized String = http://www.w3.org/2001/XMLSchema#normalizedString, a string without tabs and line breaks
+pub fn xsd_normalized_string() -> XSDDataType {
+    XSDDataType::create_from_string(xsd_base() + "normalizedString", |s| is_normalized_string(&s)).expect("")
+}
+/// XSD Type Token = http://www.w3.org/2001/XMLSchema#token, a normalized string without leading, trailing or double spaces
+pub fn xsd_token() -> XSDDataType {
+    XSDDataType::create_from_string(xsd_base() + "token", |s| is_token(&s)).expect("")
+}
+/// XSD Type Language = http://www.w3.org/2001/XMLSchema#language
+pub fn xsd_language() -> XSDDataType {
+    XSDDataType::create_from_string(xsd_base() + "language", |s| LANGUAGE_PATTERN.is_match(&s)).expect("")
+}
+/// XSD Type Name = http://www.w3.org/2001/XMLSchema#Name, an XML name
+pub fn xsd_name() -> XSDDataType {
+    XSDDataType::create_from_string(xsd_base() + "Name", |s| NAME_PATTERN.is_match(&s)).expect("")
+}
+/// XSD Type NCName = http://www.w3.org/2001/XMLSchema#NCName, an XML name without colons
+pub fn xsd_nc_name() -> XSDDataType {
+    XSDDataType::create_from_string(xsd_base() + "NCName", |s| NAME_PATTERN.is_match(&s) && !s.contains(':')).expect("")
+}
+/// XSD Type NMTOKEN = http://www.w3.org/2001/XMLSchema#NMTOKEN
+pub fn xsd_nm_token() -> XSDDataType {
+    XSDDataType::create_from_string(xsd_base() + "NMTOKEN", |s| NMTOKEN_PATTERN.is_match(&s)).expect("")
 }
 /// XSD Type Unsigned Byte = http://www.w3.org/2001/XMLSchema#unsignedByte
 pub fn xsd_unsigned_byte() -> XSDDataType {
-    XSDDataType::create_from_string(xsd_base() +"unsignedByte", |s| true).expect("")
+    xsd_integer_type("unsignedByte", Some("0"), Some("255"))
 }
 /// XSD Type Unsigned Short = http://www.w3.org/2001/XMLSchema#unsignedShort
 pub fn xsd_unsigned_short() -> XSDDataType{
-	XSDDataType::create_from_string(xsd_base() +"unsignedShort", |s| true).expect("")
+	xsd_integer_type("unsignedShort", Some("0"), Some("65535"))
 }
 /// XSD Type Unsigned Int = http://www.w3.org/2001/XMLSchema#unsignedInt
 pub fn xsd_unsigned_int() -> XSDDataType{
-	XSDDataType::create_from_string(xsd_base() +"unsignedInt", |s| true).expect("")
+	xsd_integer_type("unsignedInt", Some("0"), Some("4294967295"))
 }
 /// XSD Type Unsigned Long = http://www.w3.org/2001/XMLSchema#unsignedLong
 pub fn xsd_unsigned_long() -> XSDDataType{
-	XSDDataType::create_from_string(xsd_base() +"unsignedLong", |s| true).expect("")
+	xsd_integer_type("unsignedLong", Some("0"), Some("18446744073709551615"))
 }
 /// XSD Type Byte = http://www.w3.org/2001/XMLSchema#byte
 pub fn xsd_byte() -> XSDDataType{
-	XSDDataType::create_from_string(xsd_base() +"byte", |s| true).expect("")
+	xsd_integer_type("byte", Some("-128"), Some("127"))
 }
 /// XSD Type Short = http://www.w3.org/2001/XMLSchema#short
 pub fn xsd_short() -> XSDDataType{
-	XSDDataType::create_from_string(xsd_base() +"short", |s| true).expect("")
+	xsd_integer_type("short", Some("-32768"), Some("32767"))
 }
 /// XSD Type Int = http://www.w3.org/2001/XMLSchema#int
 pub fn xsd_int() -> XSDDataType{
-	XSDDataType::create_from_string(xsd_base() +"int", |s| true).expect("")
+	xsd_integer_type("int", Some("-2147483648"), Some("2147483647"))
 }
 /// XSD Type Long = http://www.w3.org/2001/XMLSchema#long
 pub fn xsd_long() -> XSDDataType{
-	XSDDataType::create_from_string(xsd_base() +"long", |s| true).expect("")
+	xsd_integer_type("long", Some("-9223372036854775808"), Some("9223372036854775807"))
 }
-/// XSD Type Integer = http://www.w3.org/2001/XMLSchema#int
+/// XSD Type Integer = http://www.w3.org/2001/XMLSchema#integer
 ///
-/// Will be internally used as i64 due to semantics
+/// Will use rdf4rust::util::decimal::BigInt for the value
 pub fn xsd_integer() -> XSDDataType{
-	XSDDataType::create_from_string(xsd_base() +"integer", |s| true).expect("")
+	xsd_integer_type("integer", None, None)
 }
 /// XSD Type Non negative Integer = http://www.w3.org/2001/XMLSchema#nonNegativeInteger
 ///
-/// Will be internally used as usize
+/// Will use rdf4rust::util::decimal::BigInt for the value
 pub fn xsd_non_negative_integer() -> XSDDataType{
-	XSDDataType::create_from_string(xsd_base() +"nonNegativeInteger", |s| true).expect("")
+	xsd_integer_type("nonNegativeInteger", Some("0"), None)
 }
 /// XSD Type Non positive Integer = http://www.w3.org/2001/XMLSchema#nonPositiveInteger
 ///
-/// Will be internally used as isize
+/// Will use rdf4rust::util::decimal::BigInt for the value
 pub fn xsd_non_positive_integer() -> XSDDataType{
-	XSDDataType::create_from_string(xsd_base() +"nonPositiveInteger", |s| true).expect("")
+	xsd_integer_type("nonPositiveInteger", None, Some("0"))
 }
 /// XSD Type Positive Integer = http://www.w3.org/2001/XMLSchema#positiveInteger
 ///
-/// Will be internally used as NonZeroUsize
+/// Will use rdf4rust::util::decimal::BigInt for the value
 pub fn xsd_positive_integer() -> XSDDataType{
-	XSDDataType::create_from_string(xsd_base() +"positiveInteger", |s| true).expect("")
+	xsd_integer_type("positiveInteger", Some("1"), None)
 }
 /// XSD Type Negative Integer = http://www.w3.org/2001/XMLSchema#negativeInteger
 ///
-/// Will be internally used as NonZeroIsize
+/// Will use rdf4rust::util::decimal::BigInt for the value
 pub fn xsd_negative_integer() -> XSDDataType{
-	XSDDataType::create_from_string(xsd_base() +"negativeInteger", |s| true).expect("")
+	xsd_integer_type("negativeInteger", None, Some("-1"))
 }
 /// XSD Type Decimal = http://www.w3.org/2001/XMLSchema#decimal
 ///
-/// Will be internally used as f64
+/// Will use rdf4rust::util::decimal::Decimal for the value
 pub fn xsd_decimal() -> XSDDataType{
-	XSDDataType::create_from_string(xsd_base() +"decimal", |s| true).expect("")
+	XSDDataType::create_from_string(xsd_base() +"decimal", |s| DECIMAL_PATTERN.is_match(&s)).expect("")
 }
 /// XSD Type Float = http://www.w3.org/2001/XMLSchema#float
 ///
 /// will be internally used as f32
 pub fn xsd_float() -> XSDDataType{
-	XSDDataType::create_from_string(xsd_base() +"float", |s| true).expect("")
+	XSDDataType::create_from_string(xsd_base() +"float", |s| FLOAT_PATTERN.is_match(&s)).expect("")
 }
 /// XSD Type Double = http://www.w3.org/2001/XMLSchema#double
 ///
 /// will be internally used as f64
 pub fn xsd_double() -> XSDDataType{
-	XSDDataType::create_from_string(xsd_base() +"double", |s| true).expect("")
+	XSDDataType::create_from_string(xsd_base() +"double", |s| FLOAT_PATTERN.is_match(&s)).expect("")
 }
 /// XSD Type Boolean = http://www.w3.org/2001/XMLSchema#boolean
 pub fn xsd_boolean() -> XSDDataType{
-	XSDDataType::create_from_string(xsd_base() +"boolean", |s| s == "true" || s=="false").expect("")
+	XSDDataType::create_from_string(xsd_base() +"boolean", |s| s == "true" || s == "false" || s == "1" || s == "0").expect("")
 }
 /// XSD Type Super Type = http://www.w3.org/2001/XMLSchema#anyType
 ///
 /// Will use String for this value
 pub fn xsd_any_type() -> XSDDataType{
-	XSDDataType::create_from_string(xsd_base() +"anyType", |s| true).expect("")
+	XSDDataType::create_from_string(xsd_base() +"anyType", |_| true).expect("")
 }
 /// XSD Type Super Type for Simple Types = http://www.w3.org/2001/XMLSchema#anySimpleType
 ///
 /// Will use String for this value
 pub fn xsd_any_simple_type() -> XSDDataType{
-	XSDDataType::create_from_string(xsd_base() +"anySimpleType", |s| true).expect("")
+	XSDDataType::create_from_string(xsd_base() +"anySimpleType", |_| true).expect("")
 }
-/// XSD URI Type = http://www.w3.org/2001/XMLSchema#anyUri
+/// XSD URI Type = http://www.w3.org/2001/XMLSchema#anyURI, an absolute or relative IRI reference
 ///
 /// Will use rdf4rust::util::iri::IRI for the value
 pub fn xsd_any_uri() -> XSDDataType{
-	XSDDataType::create_from_string(xsd_base() +"anyUri", |s| true).expect("")
+	XSDDataType::create_from_string(xsd_base() +"anyURI", |s| REFERENCE_BASE.resolve(&s).is_ok()).expect("")
 }
 /// XSD Binary Type in Base64 format = http://www.w3.org/2001/XMLSchema#base64Binary
 ///
 /// Will use &[u8] for this value
 pub fn xsd_base64binary() -> XSDDataType{
-	XSDDataType::create_from_string(xsd_base() +"base64Binary", |s| true).expect("")
+	XSDDataType::create_from_string(xsd_base() +"base64Binary", |s| BASE64_BINARY_PATTERN.is_match(&s)).expect("")
 }
 /// XSD Binary Type in HEX format = http://www.w3.org/2001/XMLSchema#hexBinary
 ///
 /// Will use &[u8] for this value
 pub fn xsd_hex_binary() -> XSDDataType{
-	XSDDataType::create_from_string(xsd_base() +"hexBinary", |s| true).expect("")
+	XSDDataType::create_from_string(xsd_base() +"hexBinary", |s| HEX_BINARY_PATTERN.is_match(&s)).expect("")
 }
 /// XSD Type Duration = http://www.w3.org/2001/XMLSchema#duration
+///
+/// Will use rdf4rust::util::date_time::Duration for the value
 pub fn xsd_duration() -> XSDDataType{
-	XSDDataType::create_from_string(xsd_base() +"duration", |s| true).expect("")
+	XSDDataType::create_from_string(xsd_base() +"duration", |s| s.parse::<Duration>().is_ok()).expect("")
+}
+/// XSD Type Day Time Duration = http://www.w3.org/2001/XMLSchema#dayTimeDuration
+///
+/// Will use rdf4rust::util::date_time::Duration for the value
+pub fn xsd_day_time_duration() -> XSDDataType{
+	XSDDataType::create_from_string(xsd_base() +"dayTimeDuration", |s| Duration::parse_day_time(&s).is_ok()).expect("")
+}
+/// XSD Type Year Month Duration = http://www.w3.org/2001/XMLSchema#yearMonthDuration
+///
+/// Will use rdf4rust::util::date_time::Duration for the value
+pub fn xsd_year_month_duration() -> XSDDataType{
+	XSDDataType::create_from_string(xsd_base() +"yearMonthDuration", |s| Duration::parse_year_month(&s).is_ok()).expect("")
 }
 /// XSD Type Date time = http://www.w3.org/2001/XMLSchema#dateTime
+///
+/// Will use rdf4rust::util::date_time::DateTime for the value
 pub fn xsd_date_time() -> XSDDataType{
-	XSDDataType::create_from_string(xsd_base() +"dateTime", |s| true).expect("")
+	XSDDataType::create_from_string(xsd_base() +"dateTime", |s| s.parse::<DateTime>().is_ok()).expect("")
+}
+/// XSD Type Date time stamp = http://www.w3.org/2001/XMLSchema#dateTimeStamp, a dateTime with timezone
+///
+/// Will use rdf4rust::util::date_time::DateTime for the value
+pub fn xsd_date_time_stamp() -> XSDDataType{
+	XSDDataType::create_from_string(xsd_base() +"dateTimeStamp", |s| match s.parse::<DateTime>() {
+		Ok(date_time) => date_time.get_timezone().is_some(),
+		Err(_) => false
+	}).expect("")
 }
 /// XSD Type time = http://www.w3.org/2001/XMLSchema#time
+///
+/// Will use rdf4rust::util::date_time::Time for the value
 pub fn xsd_time() -> XSDDataType{
-	XSDDataType::create_from_string(xsd_base() +"time", |s| true).expect("")
+	XSDDataType::create_from_string(xsd_base() +"time", |s| s.parse::<Time>().is_ok()).expect("")
 }
 /// XSD Type date = http://www.w3.org/2001/XMLSchema#date
+///
+/// Will use rdf4rust::util::date_time::Date for the value
 pub fn xsd_date() -> XSDDataType{
-	XSDDataType::create_from_string(xsd_base() +"date", |s| true).expect("")
+	XSDDataType::create_from_string(xsd_base() +"date", |s| s.parse::<Date>().is_ok()).expect("")
 }
 /// XSD Type gYearMonth = http://www.w3.org/2001/XMLSchema#gYearMonth
+///
+/// Will use rdf4rust::util::date_time::GYearMonth for the value
 pub fn xsd_g_year_month() -> XSDDataType{
-	XSDDataType::create_from_string(xsd_base() +"gYearMonth", |s| true).expect("")
+	XSDDataType::create_from_string(xsd_base() +"gYearMonth", |s| s.parse::<GYearMonth>().is_ok()).expect("")
 }
 /// XSD Type gYear = http://www.w3.org/2001/XMLSchema#gYear
+///
+/// Will use rdf4rust::util::date_time::GYear for the value
 pub fn xsd_g_year() -> XSDDataType{
-	XSDDataType::create_from_string(xsd_base() +"gYear", |s| true).expect("")
+	XSDDataType::create_from_string(xsd_base() +"gYear", |s| s.parse::<GYear>().is_ok()).expect("")
 }
-/// XSD Type gMonthDay = http://www.w3.org/2001/XMLSchema#gMonthDay --MM--DD --MM--DD(Z+-)0digit|1[0-3]:0[0..5]digit
+/// XSD Type gMonthDay = http://www.w3.org/2001/XMLSchema#gMonthDay
+///
+/// Will use rdf4rust::util::date_time::GMonthDay for the value
 pub fn xsd_g_month_day() -> XSDDataType{
-	XSDDataType::create_from_string(xsd_base() +"gMonthDay", |s| true).expect("")
+	XSDDataType::create_from_string(xsd_base() +"gMonthDay", |s| s.parse::<GMonthDay>().is_ok()).expect("")
 }
 /// XSD Type gDay = http://www.w3.org/2001/XMLSchema#gDay
+///
+/// Will use rdf4rust::util::date_time::GDay for the value
 pub fn xsd_g_day() -> XSDDataType{
-	XSDDataType::create_from_string(xsd_base() +"gDay", |s| true).expect("")
+	XSDDataType::create_from_string(xsd_base() +"gDay", |s| s.parse::<GDay>().is_ok()).expect("")
 }
 /// XSD Type gMonth = http://www.w3.org/2001/XMLSchema#gMonth
+///
+/// Will use rdf4rust::util::date_time::GMonth for the value
 pub fn xsd_g_month() -> XSDDataType{
-	XSDDataType::create_from_string(xsd_base() +"gMonth", |s| true).expect("")
+	XSDDataType::create_from_string(xsd_base() +"gMonth", |s| s.parse::<GMonth>().is_ok()).expect("")
+}
+
+fn xsd_integer_type(name: &str, min_inclusive: Option<&str>, max_inclusive: Option<&str>) -> XSDDataType{
+	let mut facets = XSDFacets::default();
+	if let Some(min) = min_inclusive{
+		facets = facets.with_min_inclusive(String::from(min));
+	}
+	if let Some(max) = max_inclusive{
+		facets = facets.with_max_inclusive(String::from(max));
+	}
+	XSDDataType::create_from_string(xsd_base() + name, |s| INTEGER_PATTERN.is_match(&s)).expect("")
+		.with_facets(facets)
+}
+
+///
+/// The constraining facets of a `XSDDataType`, restricting its value space.
+///
+/// Bounds are given as lexical values of the data type they are used with.
+/// The order of values is the one of their value space, e.g. numerical for numbers, and the timeline for `xsd:dateTime`.
+/// The length facets count characters for strings, and octets for `xsd:hexBinary` and `xsd:base64Binary`.
+///
+/// # Example
+///
+/// ```
+/// use rdf4rust::rdf::xsd::XSDFacets;
+///
+/// let facets = XSDFacets::default()
+///     .with_min_inclusive(String::from("1"))
+///     .with_max_exclusive(String::from("10"));
+///
+/// assert_eq!(&Some(String::from("1")), facets.get_min_inclusive());
+/// assert_eq!(&None, facets.get_length());
+/// ```
+///
+#[derive(Clone, Debug, Default, PartialOrd, PartialEq, Eq, Hash)]
+pub struct XSDFacets{
+	min_inclusive: Option<String>,
+	max_inclusive: Option<String>,
+	min_exclusive: Option<String>,
+	max_exclusive: Option<String>,
+	length: Option<usize>,
+	min_length: Option<usize>,
+	max_length: Option<usize>,
+}
+
+impl XSDFacets{
+
+	pub fn with_min_inclusive(mut self, min_inclusive: String) -> XSDFacets{
+		self.min_inclusive = Some(min_inclusive);
+		self
+	}
+
+	pub fn with_max_inclusive(mut self, max_inclusive: String) -> XSDFacets{
+		self.max_inclusive = Some(max_inclusive);
+		self
+	}
+
+	pub fn with_min_exclusive(mut self, min_exclusive: String) -> XSDFacets{
+		self.min_exclusive = Some(min_exclusive);
+		self
+	}
+
+	pub fn with_max_exclusive(mut self, max_exclusive: String) -> XSDFacets{
+		self.max_exclusive = Some(max_exclusive);
+		self
+	}
+
+	pub fn with_length(mut self, length: usize) -> XSDFacets{
+		self.length = Some(length);
+		self
+	}
+
+	pub fn with_min_length(mut self, min_length: usize) -> XSDFacets{
+		self.min_length = Some(min_length);
+		self
+	}
+
+	pub fn with_max_length(mut self, max_length: usize) -> XSDFacets{
+		self.max_length = Some(max_length);
+		self
+	}
+
+	pub fn get_min_inclusive(&self) -> &Option<String>{
+		&self.min_inclusive
+	}
+
+	pub fn get_max_inclusive(&self) -> &Option<String>{
+		&self.max_inclusive
+	}
+
+	pub fn get_min_exclusive(&self) -> &Option<String>{
+		&self.min_exclusive
+	}
+
+	pub fn get_max_exclusive(&self) -> &Option<String>{
+		&self.max_exclusive
+	}
+
+	pub fn get_length(&self) -> &Option<usize>{
+		&self.length
+	}
+
+	pub fn get_min_length(&self) -> &Option<usize>{
+		&self.min_length
+	}
+
+	pub fn get_max_length(&self) -> &Option<usize>{
+		&self.max_length
+	}
+
+	fn is_satisfied(&self, dtype: &XSDDataType, value: &str) -> bool{
+		let in_order = |bound: &Option<String>, accepted: &[Ordering]| match bound{
+			Some(bound) => match dtype.compare_lexical(value, bound){
+				Some(order) => accepted.contains(&order),
+				None => false
+			},
+			None => true
+		};
+		if !(in_order(&self.min_inclusive, &[Ordering::Greater, Ordering::Equal])
+			&& in_order(&self.max_inclusive, &[Ordering::Less, Ordering::Equal])
+			&& in_order(&self.min_exclusive, &[Ordering::Greater])
+			&& in_order(&self.max_exclusive, &[Ordering::Less])){
+			return false
+		}
+		if self.length.is_none() && self.min_length.is_none() && self.max_length.is_none(){
+			return true
+		}
+		let length = match dtype.lexical_length(value){
+			Some(length) => length,
+			None => return false
+		};
+		self.length.is_none_or(|l| length == l)
+			&& self.min_length.is_none_or(|l| length >= l)
+			&& self.max_length.is_none_or(|l| length <= l)
+	}
 }
 
 ///
 /// The XSD Data type - usually http://www.w3.org/2001/XMLSchema# and the datatype name
 ///
 /// For distinction between the different types have a look at https://www.eclipse.org/modeling/emf/docs/xsd/dW/os-schema2/os-schema2-3-2.html
-#[derive(PartialOrd, PartialEq, Eq, Hash)]
+///
+/// Data types are identified by their IRI, two data types with the same IRI are considered equal.
 pub struct XSDDataType{
     dtype: String,
 	validation: fn(String) -> bool,
+	base: Option<&'static XSDDataType>,
+	facets: XSDFacets,
 }
 
-impl XSDDataType{
+impl PartialEq for XSDDataType{
+	fn eq(&self, other: &Self) -> bool {
+		self.dtype == other.dtype
+	}
+}
 
-	pub fn get_or_default(iri: IRI) -> &'static XSDDataType{
-		return match iri.as_string().as_str() {
-			"http://www.w3.org/2001/XMLSchema#string" => { &XSD_STRING as &XSDDataType}
-			"http://www.w3.org/2001/XMLSchema#unsignedByte" => { &XSD_UNSIGNED_BYTE as &XSDDataType }
-			"http://www.w3.org/2001/XMLSchema#unsignedShort" => { &XSD_UNSIGNED_SHORT as &XSDDataType}
-			"http://www.w3.org/2001/XMLSchema#unsignedInt" => { &XSD_UNSIGNED_INT as &XSDDataType }
-			"http://www.w3.org/2001/XMLSchema#unsignedLong" => { &XSD_UNSIGNED_LONG as &XSDDataType }
-			"http://www.w3.org/2001/XMLSchema#byte" => { &XSD_BYTE as &XSDDataType }
-			"http://www.w3.org/2001/XMLSchema#short" => { &XSD_SHORT as &XSDDataType }
-			"http://www.w3.org/2001/XMLSchema#int" => { &XSD_UNSIGNED_SHORT as &XSDDataType }
-			"http://www.w3.org/2001/XMLSchema#long" => { &XSD_LONG as &XSDDataType }
-			"http://www.w3.org/2001/XMLSchema#integer" => { &XSD_UNSIGNED_SHORT as &XSDDataType }
-			"http://www.w3.org/2001/XMLSchema#nonNegativeInteger" => { &XSD_UNSIGNED_SHORT as &XSDDataType }
-			"http://www.w3.org/2001/XMLSchema#nonPositiveInteger" => { &XSD_UNSIGNED_SHORT as &XSDDataType }
-			"http://www.w3.org/2001/XMLSchema#positiveInteger" => { &XSD_UNSIGNED_SHORT as &XSDDataType }
-			"http://www.w3.org/2001/XMLSchema#negativeInteger" => { &XSD_UNSIGNED_SHORT as &XSDDataType }
-			"http://www.w3.org/2001/XMLSchema#decimal" => { &XSD_UNSIGNED_SHORT as &XSDDataType }
-			"http://www.w3.org/2001/XMLSchema#float" => { &XSD_UNSIGNED_SHORT as &XSDDataType }
-			"http://www.w3.org/2001/XMLSchema#double" => { &XSD_UNSIGNED_SHORT as &XSDDataType }
-			"http://www.w3.org/2001/XMLSchema#boolean" => { &XSD_UNSIGNED_SHORT as &XSDDataType }
-			"http://www.w3.org/2001/XMLSchema#anyType" => { &XSD_UNSIGNED_SHORT as &XSDDataType }
-			"http://www.w3.org/2001/XMLSchema#anySimpleType" => { &XSD_UNSIGNED_SHORT as &XSDDataType }
-			"http://www.w3.org/2001/XMLSchema#anyUri" => { &XSD_UNSIGNED_SHORT as &XSDDataType }
-			"http://www.w3.org/2001/XMLSchema#hexBinary" => { &XSD_UNSIGNED_SHORT as &XSDDataType }
-			"http://www.w3.org/2001/XMLSchema#base64Binary" => { &XSD_UNSIGNED_SHORT as &XSDDataType }
-			"http://www.w3.org/2001/XMLSchema#duration" => {&XSD_UNSIGNED_SHORT as &XSDDataType}
-			"http://www.w3.org/2001/XMLSchema#dateTime" => {&XSD_UNSIGNED_SHORT as &XSDDataType}
-			"http://www.w3.org/2001/XMLSchema#time" => {&XSD_UNSIGNED_SHORT as &XSDDataType}
-			"http://www.w3.org/2001/XMLSchema#date" => {&XSD_UNSIGNED_SHORT as &XSDDataType}
-			"http://www.w3.org/2001/XMLSchema#gYearMonth" => {&XSD_UNSIGNED_SHORT as &XSDDataType}
-			"http://www.w3.org/2001/XMLSchema#gYear" => {&XSD_UNSIGNED_SHORT as &XSDDataType}
-			"http://www.w3.org/2001/XMLSchema#gMonth" => {&XSD_UNSIGNED_SHORT as &XSDDataType}
-			"http://www.w3.org/2001/XMLSchema#gDay" => {&XSD_UNSIGNED_SHORT as &XSDDataType}
-			"http://www.w3.org/2001/XMLSchema#gMonthDay" => {&XSD_UNSIGNED_SHORT as &XSDDataType}
-			//TODO this could create a mem leak, as this will be static and duplocates, use lazy static mut hashmap and check if iri is contained
-			_ => {
-				if let Some(&xsd) =  ADDITIONAL_TYPES.lock().unwrap().get(&iri.as_string()){
-					return xsd
-				}
-				let xsd: &'static XSDDataType = XSDDataType::create(&iri, |s| true).borrow();
-				ADDITIONAL_TYPES.lock().unwrap().insert(String::from(iri.as_string()), &xsd);
-				let xsd = ADDITIONAL_TYPES.lock().unwrap().get(&iri.as_string()).expect("");
-				return xsd
-			}
+impl Eq for XSDDataType{}
+
+impl PartialOrd for XSDDataType{
+	fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+		self.dtype.partial_cmp(&other.dtype)
+	}
+}
+
+impl Hash for XSDDataType{
+	fn hash<H: Hasher>(&self, state: &mut H) {
+		self.dtype.hash(state)
+	}
+}
+
+impl fmt::Debug for XSDDataType{
+	fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+		write!(f, "<{}>", self.dtype)
+	}
+}
+
+///
+/// A reference to a data type as held by literals, either a static one like the built-in types
+/// or a shared one, e.g. a registered custom type or the default type of an unknown IRI.
+///
+/// Dereferences to the `XSDDataType`, equality is the one of data types, i.e. by IRI.
+///
+#[derive(Clone)]
+pub enum XSDDataTypeRef{
+	Static(&'static XSDDataType),
+	Shared(Arc<XSDDataType>)
+}
+
+impl Deref for XSDDataTypeRef{
+	type Target = XSDDataType;
+
+	fn deref(&self) -> &XSDDataType {
+		match self {
+			XSDDataTypeRef::Static(xsd) => xsd,
+			XSDDataTypeRef::Shared(xsd) => xsd
 		}
 	}
+}
+
+impl From<&'static XSDDataType> for XSDDataTypeRef{
+	fn from(xsd: &'static XSDDataType) -> Self {
+		XSDDataTypeRef::Static(xsd)
+	}
+}
+
+impl PartialEq for XSDDataTypeRef{
+	fn eq(&self, other: &Self) -> bool {
+		**self == **other
+	}
+}
 
-	//TODO fn create_static_ref() -> &'static XSDDataType
+impl Eq for XSDDataTypeRef{}
 
-	fn create_static(iri: &IRI, valid: fn(String)->bool) -> XSDDataType {
-		let ret:XSDDataType = XSDDataType{
-			dtype: iri.as_string(),
-			validation: valid
-		};
-		ret
+impl PartialOrd for XSDDataTypeRef{
+	fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+		(**self).partial_cmp(&**other)
+	}
+}
+
+impl Hash for XSDDataTypeRef{
+	fn hash<H: Hasher>(&self, state: &mut H) {
+		(**self).hash(state)
+	}
+}
+
+impl fmt::Debug for XSDDataTypeRef{
+	fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+		(**self).fmt(f)
+	}
+}
+
+impl XSDDataType{
+
+	///
+	/// Gets the built-in or registered data type of `iri`.
+	///
+	/// If no data type is registered for `iri`, a new data type without lexical restrictions is returned.
+	/// Built-in types are looked up without locking, so this is cheap for the common XSD types.
+	///
+	/// # Example
+	///
+	/// ```
+	/// use rdf4rust::util::iri::IRI;
+	/// use rdf4rust::rdf::xsd::{XSDDataType, XSD_INT};
+	///
+	/// let int = XSDDataType::get_or_default(IRI::create_iri(&String::from("http://www.w3.org/2001/XMLSchema#int")).unwrap());
+	/// assert_eq!(&XSD_INT as &XSDDataType, &*int);
+	///
+	/// let custom = XSDDataType::get_or_default(IRI::create_iri(&String::from("http://example.com/datatypes#Custom")).unwrap());
+	/// assert!(custom.is_valid("anything"));
+	/// assert!(XSDDataType::get_registered("http://example.com/datatypes#Custom").is_none());
+	/// ```
+	///
+	pub fn get_or_default(iri: IRI) -> XSDDataTypeRef{
+		let iri_str = iri.as_string();
+		XSDDataType::get_registered(&iri_str)
+			.unwrap_or_else(|| XSDDataTypeRef::Shared(Arc::new(XSDDataType::create(&iri, |_| true))))
+	}
+
+	///
+	/// Gets the built-in or registered data type of `iri` if there is one.
+	///
+	pub fn get_registered(iri: &str) -> Option<XSDDataTypeRef>{
+		if let Some(&xsd) = BUILTIN_TYPES.get(iri){
+			return Some(XSDDataTypeRef::Static(xsd))
+		}
+		REGISTRY.read().unwrap().get(iri).map(|xsd| XSDDataTypeRef::Shared(Arc::clone(xsd)))
+	}
+
+	///
+	/// Registers a custom data type, so literals parsed afterwards will use it for validation.
+	///
+	/// A previously registered custom data type with the same IRI will be replaced, literals already using it keep the old one.
+	///
+	/// # Returns
+	///
+	/// The registered data type, or None if `dtype` uses the IRI of a built-in type
+	///
+	/// # Example
+	///
+	/// ```
+	/// use rdf4rust::util::iri::IRI;
+	/// use rdf4rust::rdf::xsd::{XSDDataType, XSDFacets, XSD_INTEGER};
+	///
+	/// let iri = IRI::create_iri(&String::from("http://example.com/datatypes#Percentage")).unwrap();
+	/// let facets = XSDFacets::default()
+	///     .with_min_inclusive(String::from("0"))
+	///     .with_max_inclusive(String::from("100"));
+	/// let percentage = XSDDataType::register(XSDDataType::create_restriction(&iri, &XSD_INTEGER, facets)).unwrap();
+	///
+	/// assert!(percentage.is_valid("42"));
+	/// assert!(!percentage.is_valid("101"));
+	/// assert!(!percentage.is_valid("4.2"));
+	/// assert_eq!(percentage, XSDDataType::get_or_default(iri));
+	///
+	/// assert!(XSDDataType::register(XSDDataType::create(&IRI::create_iri(&String::from("http://www.w3.org/2001/XMLSchema#int")).unwrap(), |_| true)).is_none());
+	/// ```
+	///
+	pub fn register(dtype: XSDDataType) -> Option<XSDDataTypeRef>{
+		if BUILTIN_TYPES.contains_key(dtype.dtype.as_str()){
+			return None
+		}
+		let xsd = Arc::new(dtype);
+		REGISTRY.write().unwrap().insert(xsd.dtype.clone(), Arc::clone(&xsd));
+		Some(XSDDataTypeRef::Shared(xsd))
 	}
 
     ///
@@ -290,7 +661,7 @@ impl XSDDataType{
     ///
     /// // Our validation simply says a valid value of MyDatatype is not empty
 	/// if let Some(dtype) = XSDDataType::create_from_string(String::from("http://my-example-datatypes.com/datatypes#MyDatatype"),
-	/// 	|s: String| !s.is_empty()
+	///     |s: String| !s.is_empty()
 	/// ){
     ///     //use dtype here
     /// }else{
@@ -300,11 +671,7 @@ impl XSDDataType{
     /// ```
     ///
     pub fn create_from_string(uri: String, validation: fn(String) -> bool) -> Option<XSDDataType>{
-        if let Some(xsd_uri) = IRI::create_iri(&uri).ok(){
-            Some(XSDDataType::create(&xsd_uri, validation))
-        }else {
-            None
-        }
+        IRI::create_iri(&uri).ok().map(|xsd_uri| XSDDataType::create(&xsd_uri, validation))
     }
 
     ///
@@ -321,10 +688,11 @@ impl XSDDataType{
     /// use rdf4rust::util::iri::IRI;
     /// use rdf4rust::rdf::xsd::XSDDataType;
     ///
-    /// if let Some(uri) = IRI::create_iri(&String::from("http://my-example-datatypes.com/datatypes#MyDatatype")){
-    /// 	// Our validation simply says a valid value of MyDatatype is not empty
-    ///     let dtype = XSDDataType::create(uri, |s: String| !s.is_empty() );
-    ///     //use dtype here
+    /// if let Ok(uri) = IRI::create_iri(&String::from("http://my-example-datatypes.com/datatypes#MyDatatype")){
+    ///     // Our validation simply says a valid value of MyDatatype is not empty
+    ///     let dtype = XSDDataType::create(&uri, |s: String| !s.is_empty() );
+    ///     assert!(dtype.is_valid("value"));
+    ///     assert!(!dtype.is_valid(""));
     /// }else{
     ///     //uri wasn't valid
     /// }
@@ -333,10 +701,132 @@ impl XSDDataType{
     pub fn create(uri : &IRI, validation: fn(String) -> bool) -> XSDDataType{
         XSDDataType{
             dtype: uri.as_string(),
-			validation
+			validation,
+			base: None,
+			facets: XSDFacets::default()
         }
     }
 
+	///
+	/// Creates a DataType which restricts the values of `base` using `facets`
+	///
+	/// # Parameters
+	///
+	/// * `uri` - The URI representing the data type
+	/// * `base` - The data type whose lexical space and value order will be used
+	/// * `facets` - The facets restricting the values of `base`
+	///
+	pub fn create_restriction(uri: &IRI, base: &'static XSDDataType, facets: XSDFacets) -> XSDDataType{
+		XSDDataType{
+			dtype: uri.as_string(),
+			validation: |_| true,
+			base: Some(base),
+			facets
+		}
+	}
+
+	///
+	/// Sets the facets of this data type, replacing the existing ones
+	///
+	pub fn with_facets(mut self, facets: XSDFacets) -> XSDDataType{
+		self.facets = facets;
+		self
+	}
+
+	pub fn get_facets(&self) -> &XSDFacets{
+		&self.facets
+	}
+
+	///
+	/// Gets the data type this data type is a restriction of, None for built-in and unrestricted data types
+	///
+	pub fn get_base(&self) -> Option<&'static XSDDataType>{
+		self.base
+	}
+
+	///
+	/// Checks if `value` is in the lexical space of this data type and its value satisfies all facets.
+	///
+	/// # Example
+	///
+	/// ```
+	/// use rdf4rust::rdf::xsd::{XSD_BYTE, XSD_DATE_TIME, XSD_NON_NEGATIVE_INTEGER, XSD_BOOLEAN};
+	///
+	/// assert!(XSD_BYTE.is_valid("-128"));
+	/// assert!(!XSD_BYTE.is_valid("128"));
+	/// assert!(!XSD_BYTE.is_valid("1.0"));
+	/// assert!(XSD_NON_NEGATIVE_INTEGER.is_valid("+0"));
+	/// assert!(!XSD_NON_NEGATIVE_INTEGER.is_valid("-1"));
+	/// assert!(XSD_DATE_TIME.is_valid("2002-10-10T12:00:00-05:00"));
+	/// assert!(!XSD_DATE_TIME.is_valid("2002-02-30T12:00:00"));
+	/// assert!(XSD_BOOLEAN.is_valid("1"));
+	/// assert!(!XSD_BOOLEAN.is_valid("yes"));
+	/// ```
+	///
+	pub fn is_valid(&self, value: &str) -> bool{
+		if let Some(base) = self.base{
+			if !base.is_valid(value){
+				return false
+			}
+		}
+		(self.validation)(String::from(value)) && self.facets.is_satisfied(self, value)
+	}
+
+	///
+	/// Checks if this data type is xsd:integer or derived from it, so its values are `BigInt`s
+	///
+	/// # Example
+	///
+	/// ```
+	/// use rdf4rust::rdf::xsd::{XSD_INTEGER, XSD_UNSIGNED_BYTE, XSD_DECIMAL};
+	///
+	/// assert!(XSD_INTEGER.is_integer_type());
+	/// assert!(XSD_UNSIGNED_BYTE.is_integer_type());
+	/// assert!(!XSD_DECIMAL.is_integer_type());
+	/// assert!(XSD_UNSIGNED_BYTE.is_decimal_type());
+	/// ```
+	///
+	pub fn is_integer_type(&self) -> bool{
+		INTEGER_TYPES.iter().any(|name| self.get_primitive().dtype == xsd_base() + name)
+	}
+
+	///
+	/// Checks if this data type is xsd:decimal or derived from it (including all integer types), so its values are `Decimal`s
+	///
+	pub fn is_decimal_type(&self) -> bool{
+		self.get_primitive().dtype == XSD_DECIMAL.dtype || self.is_integer_type()
+	}
+
+	///
+	/// Gets the data type this data type restricts in the end, itself if it isn't a restriction
+	///
+	pub fn get_primitive(&self) -> &XSDDataType{
+		match self.base{
+			Some(base) => base.get_primitive(),
+			None => self
+		}
+	}
+
+	fn compare_lexical(&self, a: &str, b: &str) -> Option<Ordering>{
+		match self.get_primitive().dtype.trim_start_matches("http://www.w3.org/2001/XMLSchema#"){
+			name if name == "decimal" || INTEGER_TYPES.contains(&name) => a.parse::<Decimal>().ok()?.partial_cmp(&b.parse::<Decimal>().ok()?),
+			"float" | "double" => parse_float(a)?.partial_cmp(&parse_float(b)?),
+			"dateTime" | "dateTimeStamp" => a.parse::<DateTime>().ok()?.partial_cmp(&b.parse::<DateTime>().ok()?),
+			"date" => a.parse::<Date>().ok()?.partial_cmp(&b.parse::<Date>().ok()?),
+			"time" => a.parse::<Time>().ok()?.partial_cmp(&b.parse::<Time>().ok()?),
+			"duration" | "dayTimeDuration" | "yearMonthDuration" => a.parse::<Duration>().ok()?.partial_cmp(&b.parse::<Duration>().ok()?),
+			_ => None
+		}
+	}
+
+	fn lexical_length(&self, value: &str) -> Option<usize>{
+		match self.get_primitive().dtype.trim_start_matches("http://www.w3.org/2001/XMLSchema#"){
+			"hexBinary" => Some(value.len() / 2),
+			"base64Binary" => base64::decode(value.replace(' ', "")).ok().map(|bytes| bytes.len()),
+			_ => Some(value.chars().count())
+		}
+	}
+
     ///
     /// Shortcut to check if the XSDDataType is `xsd:string`
     ///
@@ -355,7 +845,7 @@ impl XSDDataType{
     /// ```
     ///
     pub fn is_xsd_string(&self) -> bool{
-        self.dtype == xsd_string().dtype
+        self.dtype == XSD_STRING.dtype
     }
 
     ///
@@ -370,13 +860,38 @@ impl XSDDataType{
     /// ```
     /// use rdf4rust::rdf::xsd::xsd_boolean;
     ///
-    /// let uri = xsd_boolean().get_value();
+    /// let uri = xsd_boolean();
     ///
-    /// assert_eq!("http://www.w3.org/2001/XMLSchema#boolean", uri.as_string());
+    /// assert_eq!("http://www.w3.org/2001/XMLSchema#boolean", uri.get_value());
     /// ```
     ///
     pub fn get_value(&self) -> &String {
         &self.dtype
     }
 
-}
\ No newline at end of file
+}
+
+fn is_xml_char(c: char) -> bool{
+	c == '\t' || c == '\n' || c == '\r' || (c >= ' ' && c != '\u{FFFE}' && c != '\u{FFFF}')
+}
+
+fn is_xml_string(value: &str) -> bool{
+	value.chars().all(is_xml_char)
+}
+
+fn is_normalized_string(value: &str) -> bool{
+	is_xml_string(value) && !value.contains(['\t', '\n', '\r'])
+}
+
+fn is_token(value: &str) -> bool{
+	is_normalized_string(value) && !value.starts_with(' ') && !value.ends_with(' ') && !value.contains("  ")
+}
+
+fn parse_float(value: &str) -> Option<f64>{
+	match value{
+		"INF" | "+INF" => Some(f64::INFINITY),
+		"-INF" => Some(f64::NEG_INFINITY),
+		"NaN" => Some(f64::NAN),
+		_ => value.parse::<f64>().ok()
+	}
+}
@@ -0,0 +1,4 @@
+pub mod rules;
+pub mod builtins;
+pub mod rdfs;
+pub mod owl;
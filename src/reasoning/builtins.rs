@@ -0,0 +1,434 @@
+//!
+//! Builtins which can be used in the bodies of rules besides triple patterns, e.g. `lessThan(?age 18)`.
+//!
+//! Tests like `lessThan` or `regex` only keep the solutions they hold for.
+//! Functions like `sum` compute a value from their other arguments and bind it to their last argument,
+//! if that is already bound they keep the solution if it has the computed value.
+//! A builtin is evaluated as soon as all its (other) arguments are bound.
+//!
+//! Comparisons and arithmetic work on the values of literals (see `Literal::value`), e.g. `"01"^^xsd:int` equals `1.0`.
+//! Numbers are promoted from integers to decimals to doubles, strings, booleans, dateTimes, dates, times and durations
+//! are compared with values of the same type. Tests of values which can't be compared, e.g. a string and a number, fail.
+//!
+
+use crate::error::{RDFError, ErrorKind};
+use crate::rdf::graph::Node;
+use crate::rdf::node_factory::{Literal, LiteralValue, RDFNode};
+use crate::rdf::term::Term;
+use crate::util::decimal::{BigInt, Decimal, DIVISION_SCALE};
+use regex::Regex;
+use std::cmp::Ordering;
+use std::fmt;
+use std::hash::{Hash, Hasher};
+use std::ops::RangeInclusive;
+
+///
+/// The builtins available in rules, named as in Jena rules
+///
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum Builtin{
+    /// `equal(?a ?b)` - both values are equal, literals are compared by value
+    Equal,
+    /// `notEqual(?a ?b)` - the values are not equal
+    NotEqual,
+    /// `lessThan(?a ?b)`
+    LessThan,
+    /// `greaterThan(?a ?b)`
+    GreaterThan,
+    /// `le(?a ?b)` - less than or equal
+    Le,
+    /// `ge(?a ?b)` - greater than or equal
+    Ge,
+    /// `sum(?a ?b ?c)` - ?c is ?a + ?b
+    Sum,
+    /// `difference(?a ?b ?c)` - ?c is ?a - ?b
+    Difference,
+    /// `product(?a ?b ?c)` - ?c is ?a * ?b
+    Product,
+    /// `quotient(?a ?b ?c)` - ?c is ?a / ?b, integers are divided as decimals
+    Quotient,
+    /// `min(?a ?b ?c)` - ?c is the smaller number of ?a and ?b
+    Min,
+    /// `max(?a ?b ?c)` - ?c is the larger number of ?a and ?b
+    Max,
+    /// `strConcat(?a ... ?c)` - ?c is the string concatenating the lexical forms of all other arguments
+    StrConcat,
+    /// `strlen(?a ?c)` - ?c is the number of characters of the lexical form of ?a
+    Strlen,
+    /// `regex(?a "pattern")` or `regex(?a "pattern" "flags")` - the lexical form of the literal ?a matches the pattern
+    Regex,
+    /// `isLiteral(?a)`
+    IsLiteral,
+    /// `notLiteral(?a)`
+    NotLiteral,
+    /// `isIRI(?a)`
+    IsIRI,
+    /// `isBNode(?a)`
+    IsBNode,
+    /// `notBNode(?a)`
+    NotBNode
+}
+
+impl Builtin{
+
+    pub const ALL: [Builtin; 20] = [Builtin::Equal, Builtin::NotEqual, Builtin::LessThan, Builtin::GreaterThan, Builtin::Le, Builtin::Ge,
+        Builtin::Sum, Builtin::Difference, Builtin::Product, Builtin::Quotient, Builtin::Min, Builtin::Max,
+        Builtin::StrConcat, Builtin::Strlen, Builtin::Regex,
+        Builtin::IsLiteral, Builtin::NotLiteral, Builtin::IsIRI, Builtin::IsBNode, Builtin::NotBNode];
+
+    /// Gets the name used in the rule syntax, e.g. `lessThan`
+    pub fn get_name(&self) -> &'static str{
+        match self {
+            Builtin::Equal => "equal",
+            Builtin::NotEqual => "notEqual",
+            Builtin::LessThan => "lessThan",
+            Builtin::GreaterThan => "greaterThan",
+            Builtin::Le => "le",
+            Builtin::Ge => "ge",
+            Builtin::Sum => "sum",
+            Builtin::Difference => "difference",
+            Builtin::Product => "product",
+            Builtin::Quotient => "quotient",
+            Builtin::Min => "min",
+            Builtin::Max => "max",
+            Builtin::StrConcat => "strConcat",
+            Builtin::Strlen => "strlen",
+            Builtin::Regex => "regex",
+            Builtin::IsLiteral => "isLiteral",
+            Builtin::NotLiteral => "notLiteral",
+            Builtin::IsIRI => "isIRI",
+            Builtin::IsBNode => "isBNode",
+            Builtin::NotBNode => "notBNode"
+        }
+    }
+
+    /// Gets the builtin named `name`, None if there is no such builtin
+    pub fn from_name(name: &str) -> Option<Builtin>{
+        Builtin::ALL.iter().find(|builtin| builtin.get_name() == name).copied()
+    }
+
+    /// Checks if the builtin computes the value of its last argument instead of testing its arguments
+    pub fn is_function(&self) -> bool{
+        matches!(self, Builtin::Sum | Builtin::Difference | Builtin::Product | Builtin::Quotient | Builtin::Min | Builtin::Max
+            | Builtin::StrConcat | Builtin::Strlen)
+    }
+
+    /// The allowed numbers of arguments
+    fn arity(&self) -> RangeInclusive<usize>{
+        match self {
+            Builtin::Equal | Builtin::NotEqual | Builtin::LessThan | Builtin::GreaterThan | Builtin::Le | Builtin::Ge | Builtin::Strlen => 2..=2,
+            Builtin::Sum | Builtin::Difference | Builtin::Product | Builtin::Quotient | Builtin::Min | Builtin::Max => 3..=3,
+            Builtin::StrConcat => 2..=usize::MAX,
+            Builtin::Regex => 2..=3,
+            Builtin::IsLiteral | Builtin::NotLiteral | Builtin::IsIRI | Builtin::IsBNode | Builtin::NotBNode => 1..=1
+        }
+    }
+}
+
+impl fmt::Display for Builtin{
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}", self.get_name())
+    }
+}
+
+///
+/// A builtin applied to terms, part of the body of a `Rule`
+///
+/// # Example
+///
+/// ```
+/// use rdf4rust::reasoning::builtins::{Builtin, BuiltinCall};
+/// use rdf4rust::reasoning::rules::Term;
+/// use rdf4rust::rdf::graph::Node;
+/// use rdf4rust::rdf::node_factory::Literal;
+///
+/// let adult = BuiltinCall::new(Builtin::Ge, vec![Term::var("age"), Term::Constant(Node::LiteralNode { literal: Literal::from(18) })]).unwrap();
+/// assert_eq!("ge(?age \"18\"^^<http://www.w3.org/2001/XMLSchema#int>)", adult.to_string());
+///
+/// assert!(BuiltinCall::new(Builtin::Sum, vec![Term::var("a"), Term::var("b")]).is_err());
+/// assert!(BuiltinCall::new(Builtin::Regex, vec![Term::var("a"), Term::Constant(Node::LiteralNode { literal: Literal::from("(") })]).is_err());
+/// ```
+///
+#[derive(Clone)]
+pub struct BuiltinCall{
+    builtin: Builtin,
+    arguments: Vec<Term>,
+    /// The compiled pattern of a regex with constant pattern and flags
+    regex: Option<Regex>
+}
+
+impl PartialEq for BuiltinCall{
+    fn eq(&self, other: &Self) -> bool {
+        self.builtin == other.builtin && self.arguments == other.arguments
+    }
+}
+
+impl Eq for BuiltinCall{}
+
+impl Hash for BuiltinCall{
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.builtin.hash(state);
+        self.arguments.hash(state);
+    }
+}
+
+impl fmt::Display for BuiltinCall{
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}({})", self.builtin, self.arguments.iter().map(|argument| argument.to_string()).collect::<Vec<_>>().join(" "))
+    }
+}
+
+impl BuiltinCall{
+
+    ///
+    /// Applies `builtin` to `arguments`
+    ///
+    /// # Returns
+    ///
+    /// The call or an error of kind `ErrorKind::InvalidRule` if the number of arguments doesn't fit the builtin
+    /// or the constant pattern of a regex is invalid
+    ///
+    pub fn new(builtin: Builtin, arguments: Vec<Term>) -> Result<BuiltinCall, RDFError>{
+        if !builtin.arity().contains(&arguments.len()) {
+            return Err(RDFError::new(ErrorKind::InvalidRule, format!("Builtin {} can't be applied to {} arguments.", builtin, arguments.len())))
+        }
+        let mut regex = None;
+        if builtin == Builtin::Regex {
+            let constants: Option<Vec<&Node>> = arguments[1..].iter().map(|argument| match argument {
+                Term::Constant(node) => Some(node),
+                Term::Variable(_) => None
+            }).collect();
+            if let Some(constants) = constants {
+                match compile_regex(&constants) {
+                    Some(compiled) => regex = Some(compiled),
+                    None => return Err(RDFError::new(ErrorKind::InvalidRule, format!("{} is not a valid regular expression.",
+                        constants.iter().map(|node| node.as_term_string()).collect::<Vec<_>>().join(" "))))
+                }
+            }
+        }
+        Ok(BuiltinCall{
+            builtin,
+            arguments,
+            regex
+        })
+    }
+
+    pub fn get_builtin(&self) -> Builtin{
+        self.builtin
+    }
+
+    pub fn get_arguments(&self) -> &Vec<Term>{
+        &self.arguments
+    }
+
+    /// The arguments which have to be bound before the builtin can be evaluated
+    pub(crate) fn get_inputs(&self) -> &[Term]{
+        if self.builtin.is_function() {&self.arguments[..self.arguments.len() - 1]} else {&self.arguments}
+    }
+
+    /// The argument a function binds, None for tests
+    pub(crate) fn get_result(&self) -> Option<&Term>{
+        if self.builtin.is_function() {self.arguments.last()} else {None}
+    }
+
+    /// Checks if a test holds for the values of its arguments
+    pub(crate) fn test(&self, arguments: &[Node]) -> bool{
+        match self.builtin {
+            Builtin::Equal => equal(&arguments[0], &arguments[1]),
+            Builtin::NotEqual => !equal(&arguments[0], &arguments[1]),
+            Builtin::LessThan => compare(&arguments[0], &arguments[1]) == Some(Ordering::Less),
+            Builtin::GreaterThan => compare(&arguments[0], &arguments[1]) == Some(Ordering::Greater),
+            Builtin::Le => matches!(compare(&arguments[0], &arguments[1]), Some(Ordering::Less) | Some(Ordering::Equal)),
+            Builtin::Ge => matches!(compare(&arguments[0], &arguments[1]), Some(Ordering::Greater) | Some(Ordering::Equal)),
+            Builtin::Regex => {
+                let text = match &arguments[0] {
+                    Node::LiteralNode { literal } => literal.get_value(),
+                    _ => return false
+                };
+                match &self.regex {
+                    Some(regex) => regex.is_match(text),
+                    None => compile_regex(&arguments[1..].iter().collect::<Vec<_>>()).is_some_and(|regex| regex.is_match(text))
+                }
+            }
+            Builtin::IsLiteral => arguments[0].is_literal(),
+            Builtin::NotLiteral => !arguments[0].is_literal(),
+            Builtin::IsIRI => arguments[0].is_uri(),
+            Builtin::IsBNode => arguments[0].is_bnode(),
+            Builtin::NotBNode => !arguments[0].is_bnode(),
+            _ => false
+        }
+    }
+
+    /// Computes the value of a function from the values of its inputs, None if it is undefined for them
+    pub(crate) fn compute(&self, inputs: &[Node]) -> Option<Literal>{
+        match self.builtin {
+            Builtin::Sum | Builtin::Difference | Builtin::Product | Builtin::Quotient =>
+                arithmetic(self.builtin, Number::of(&inputs[0])?, Number::of(&inputs[1])?),
+            Builtin::Min | Builtin::Max => {
+                let ordering = Number::compare(Number::of(&inputs[0])?, Number::of(&inputs[1])?)?;
+                let first = (ordering == Ordering::Greater) == (self.builtin == Builtin::Max);
+                inputs[if first {0} else {1}].as_literal().ok().cloned()
+            }
+            Builtin::StrConcat => Some(Literal::from(inputs.iter().map(lexical_form).collect::<Option<String>>()?)),
+            Builtin::Strlen => Some(Literal::from(BigInt::from(lexical_form(&inputs[0])?.chars().count()))),
+            _ => None
+        }
+    }
+}
+
+/// Compiles the pattern and the optional flags (`i`, `m`, `s` and `x`) of a regex
+fn compile_regex(arguments: &[&Node]) -> Option<Regex>{
+    let string = |node: &Node| match node {
+        Node::LiteralNode { literal } => Some(literal.get_value().clone()),
+        _ => None
+    };
+    let pattern = string(arguments[0])?;
+    let flags = match arguments.get(1) {
+        Some(flags) => string(flags)?,
+        None => String::new()
+    };
+    regex(&pattern, &flags)
+}
+
+/// Compiles a regular expression with flags like in SPARQL, None if the pattern or a flag is invalid
+pub(crate) fn regex(pattern: &str, flags: &str) -> Option<Regex>{
+    if !flags.chars().all(|flag| "imsx".contains(flag)) {
+        return None
+    }
+    if flags.is_empty() {
+        Regex::new(pattern).ok()
+    } else {
+        Regex::new(&format!("(?{}){}", flags, pattern)).ok()
+    }
+}
+
+/// The lexical form of a literal or the IRI, None for blank nodes and quoted triples
+fn lexical_form(node: &Node) -> Option<String>{
+    match node {
+        Node::LiteralNode { literal } => Some(literal.get_value().clone()),
+        Node::IRINode { iri } => Some(iri.get_value()),
+        _ => None
+    }
+}
+
+/// A numeric value, integers and decimals are exact
+enum Number{
+    Integer(BigInt),
+    Decimal(Decimal),
+    Double(f64)
+}
+
+impl Number{
+
+    /// Gets the value of a numeric literal, None for other nodes and ill-typed literals
+    fn of(node: &Node) -> Option<Number>{
+        match node.as_literal().ok()?.value().ok()? {
+            LiteralValue::Integer(value) => Some(Number::Integer(value)),
+            LiteralValue::Decimal(value) => Some(Number::Decimal(value)),
+            LiteralValue::Double(value) => Some(Number::Double(value)),
+            LiteralValue::Float(value) => Some(Number::Double(value as f64)),
+            _ => None
+        }
+    }
+
+    fn to_decimal(&self) -> Decimal{
+        match self {
+            Number::Integer(value) => Decimal::from(value.clone()),
+            Number::Decimal(value) => value.clone(),
+            Number::Double(_) => unreachable!("Doubles are never promoted to decimals")
+        }
+    }
+
+    fn to_f64(&self) -> f64{
+        match self {
+            Number::Integer(value) => Decimal::from(value.clone()).to_f64(),
+            Number::Decimal(value) => value.to_f64(),
+            Number::Double(value) => *value
+        }
+    }
+
+    /// Promotes both numbers to the same type
+    fn promote(a: Number, b: Number) -> (Number, Number){
+        match (a, b) {
+            (Number::Integer(a), Number::Integer(b)) => (Number::Integer(a), Number::Integer(b)),
+            (a @ Number::Double(_), b) | (a, b @ Number::Double(_)) => (Number::Double(a.to_f64()), Number::Double(b.to_f64())),
+            (a, b) => (Number::Decimal(a.to_decimal()), Number::Decimal(b.to_decimal()))
+        }
+    }
+
+    fn compare(a: Number, b: Number) -> Option<Ordering>{
+        match Number::promote(a, b) {
+            (Number::Integer(a), Number::Integer(b)) => Some(a.cmp(&b)),
+            (Number::Decimal(a), Number::Decimal(b)) => Some(a.cmp(&b)),
+            (a, b) => a.to_f64().partial_cmp(&b.to_f64())
+        }
+    }
+}
+
+/// Applies `builtin` (sum, difference, product or quotient) to two numeric literals, None for other nodes
+pub(crate) fn calculate(builtin: Builtin, a: &Node, b: &Node) -> Option<Literal>{
+    arithmetic(builtin, Number::of(a)?, Number::of(b)?)
+}
+
+/// Checks if `node` is a literal of a numeric datatype with a valid value
+pub(crate) fn is_numeric(node: &Node) -> bool{
+    Number::of(node).is_some()
+}
+
+fn arithmetic(builtin: Builtin, a: Number, b: Number) -> Option<Literal>{
+    Some(match Number::promote(a, b) {
+        (Number::Integer(a), Number::Integer(b)) => match builtin {
+            Builtin::Sum => Literal::from(a + b),
+            Builtin::Difference => Literal::from(a - b),
+            Builtin::Product => Literal::from(a * b),
+            _ => Literal::from(Decimal::from(a).checked_div(&Decimal::from(b), DIVISION_SCALE)?)
+        },
+        (Number::Decimal(a), Number::Decimal(b)) => match builtin {
+            Builtin::Sum => Literal::from(a + b),
+            Builtin::Difference => Literal::from(a - b),
+            Builtin::Product => Literal::from(a * b),
+            _ => Literal::from(a.checked_div(&b, DIVISION_SCALE)?)
+        },
+        (a, b) => {
+            let (a, b) = (a.to_f64(), b.to_f64());
+            Literal::from(match builtin {
+                Builtin::Sum => a + b,
+                Builtin::Difference => a - b,
+                Builtin::Product => a * b,
+                _ => a / b
+            })
+        }
+    })
+}
+
+/// Checks if both nodes are equal, numbers are compared after promotion and other literals by value
+pub(crate) fn equal(a: &Node, b: &Node) -> bool{
+    if let (Some(a), Some(b)) = (Number::of(a), Number::of(b)) {
+        return Number::compare(a, b) == Some(Ordering::Equal)
+    }
+    match (a, b) {
+        (Node::LiteralNode { literal: a }, Node::LiteralNode { literal: b }) => a.value_eq(b),
+        _ => a == b
+    }
+}
+
+/// Orders two literals by value, None if they are not comparable
+pub(crate) fn compare(a: &Node, b: &Node) -> Option<Ordering>{
+    if let (Some(a), Some(b)) = (Number::of(a), Number::of(b)) {
+        return Number::compare(a, b)
+    }
+    let (a, b) = match (a, b) {
+        (Node::LiteralNode { literal: a }, Node::LiteralNode { literal: b }) => (a.value().ok()?, b.value().ok()?),
+        _ => return None
+    };
+    match (a, b) {
+        (LiteralValue::String(a), LiteralValue::String(b)) => Some(a.cmp(&b)),
+        (LiteralValue::LangString(a, a_lang), LiteralValue::LangString(b, b_lang)) if a_lang == b_lang => Some(a.cmp(&b)),
+        (LiteralValue::Boolean(a), LiteralValue::Boolean(b)) => Some(a.cmp(&b)),
+        (LiteralValue::DateTime(a), LiteralValue::DateTime(b)) => a.partial_cmp(&b),
+        (LiteralValue::Date(a), LiteralValue::Date(b)) => a.partial_cmp(&b),
+        (LiteralValue::Time(a), LiteralValue::Time(b)) => a.partial_cmp(&b),
+        (LiteralValue::Duration(a), LiteralValue::Duration(b)) => a.partial_cmp(&b),
+        _ => None
+    }
+}
@@ -0,0 +1,411 @@
+//!
+//! OWL 2 RL reasoning according to the OWL 2 RL/RDF rules of OWL 2 Profiles section 4.3.
+//!
+//! The rules are applied by the forward chaining `Reasoner`, the rules with `false` as conclusion are constraints
+//! reporting an `Inconsistency`. Rules iterating over lists (e.g. `owl:intersectionOf` or `owl:propertyChainAxiom`)
+//! are compiled into one rule per list found in the ontology, so lists added after creating the reasoner are not considered.
+//!
+//! Not supported are the rules dt-type2, dt-eq and dt-diff, which need statements with literal subjects,
+//! and eq-ref, which is disabled by default, as it makes every resource `owl:sameAs` itself.
+//!
+//! # Example
+//!
+//! ```
+//! use rdf4rust::reasoning::owl::OwlRlReasoner;
+//! use rdf4rust::rdf::graph::{Graph, SimpleGraph, Statement, Node, ResourceNode};
+//! use rdf4rust::rdf::node_factory::{IRIResource, iri};
+//!
+//! let ex = |s: &str| iri(&format!("http://example.com/{}", s));
+//! let owl = |s: &str| iri(&format!("http://www.w3.org/2002/07/owl#{}", s));
+//! let rdf_type = iri("http://www.w3.org/1999/02/22-rdf-syntax-ns#type");
+//! let stmt = |s: IRIResource, p: IRIResource, o: IRIResource| Statement::create(ResourceNode::IRINode { iri: s }, p, Node::IRINode { iri: o });
+//!
+//! let mut graph = SimpleGraph::new();
+//! graph.add_statement(stmt(ex("ancestorOf"), rdf_type.clone(), owl("TransitiveProperty")));
+//! graph.add_statement(stmt(ex("ancestorOf"), owl("inverseOf"), ex("descendantOf")));
+//! graph.add_statement(stmt(ex("alice"), ex("ancestorOf"), ex("bob")));
+//! graph.add_statement(stmt(ex("bob"), ex("ancestorOf"), ex("carol")));
+//! graph.add_statement(stmt(ex("carol"), owl("sameAs"), ex("caroline")));
+//!
+//! let reasoner = OwlRlReasoner::new();
+//! let derived = reasoner.derive(&graph).expect("Is consistent");
+//! assert!(derived.contains(&stmt(ex("caroline"), ex("descendantOf"), ex("alice"))));
+//!
+//! // individuals of disjoint classes make the graph inconsistent
+//! graph.add_statement(stmt(ex("Person"), owl("disjointWith"), ex("Place")));
+//! graph.add_statement(stmt(ex("bob"), rdf_type.clone(), ex("Person")));
+//! graph.add_statement(stmt(ex("bob"), rdf_type.clone(), ex("Place")));
+//! let inconsistencies = reasoner.derive(&graph).err().expect("Is inconsistent");
+//! assert_eq!(1, inconsistencies.len());
+//! assert_eq!("cax-dw", inconsistencies[0].get_rule());
+//! assert_eq!(3, inconsistencies[0].get_statements().len());
+//! ```
+//!
+
+use crate::reasoning::rules::{Reasoner, Rule, Term, TriplePattern, Inconsistency, derived_statements, merge_graphs, add_to_database};
+use crate::reasoning::rdfs::{datatype_axioms, statement};
+use crate::rdf::database::{Database, GraphPattern};
+use crate::rdf::graph::{read_list, Graph, Node, ResourceNode, Statement};
+use crate::rdf::node_factory::{iri, rdf, rdfs, IRIResource, Literal};
+use crate::rdf::xsd::XSD_NON_NEGATIVE_INTEGER;
+
+/// Base URL for OWL = http://www.w3.org/2002/07/owl#
+pub fn owl_base() -> String {
+    String::from("http://www.w3.org/2002/07/owl#")
+}
+
+/// Subject, predicate and object of a pattern, see `term`
+type PatternTemplate = [&'static str; 3];
+
+/// The rules of OWL 2 RL/RDF which don't depend on lists, as name, body and head.
+/// Digits are `xsd:nonNegativeInteger` literals, an empty head marks a constraint.
+const RULES: &[(&str, &[PatternTemplate], &[PatternTemplate])] = &[
+    //equality
+    ("eq-sym", &[["?x", "owl:sameAs", "?y"]], &[["?y", "owl:sameAs", "?x"]]),
+    ("eq-trans", &[["?x", "owl:sameAs", "?y"], ["?y", "owl:sameAs", "?z"]], &[["?x", "owl:sameAs", "?z"]]),
+    ("eq-rep-s", &[["?s", "owl:sameAs", "?s2"], ["?s", "?p", "?o"]], &[["?s2", "?p", "?o"]]),
+    ("eq-rep-p", &[["?p", "owl:sameAs", "?p2"], ["?s", "?p", "?o"]], &[["?s", "?p2", "?o"]]),
+    ("eq-rep-o", &[["?o", "owl:sameAs", "?o2"], ["?s", "?p", "?o"]], &[["?s", "?p", "?o2"]]),
+    ("eq-diff1", &[["?x", "owl:sameAs", "?y"], ["?x", "owl:differentFrom", "?y"]], &[]),
+    //properties
+    ("prp-dom", &[["?p", "rdfs:domain", "?c"], ["?x", "?p", "?y"]], &[["?x", "rdf:type", "?c"]]),
+    ("prp-rng", &[["?p", "rdfs:range", "?c"], ["?x", "?p", "?y"]], &[["?y", "rdf:type", "?c"]]),
+    ("prp-fp", &[["?p", "rdf:type", "owl:FunctionalProperty"], ["?x", "?p", "?y1"], ["?x", "?p", "?y2"]], &[["?y1", "owl:sameAs", "?y2"]]),
+    ("prp-ifp", &[["?p", "rdf:type", "owl:InverseFunctionalProperty"], ["?x1", "?p", "?y"], ["?x2", "?p", "?y"]], &[["?x1", "owl:sameAs", "?x2"]]),
+    ("prp-irp", &[["?p", "rdf:type", "owl:IrreflexiveProperty"], ["?x", "?p", "?x"]], &[]),
+    ("prp-symp", &[["?p", "rdf:type", "owl:SymmetricProperty"], ["?x", "?p", "?y"]], &[["?y", "?p", "?x"]]),
+    ("prp-asyp", &[["?p", "rdf:type", "owl:AsymmetricProperty"], ["?x", "?p", "?y"], ["?y", "?p", "?x"]], &[]),
+    ("prp-trp", &[["?p", "rdf:type", "owl:TransitiveProperty"], ["?x", "?p", "?y"], ["?y", "?p", "?z"]], &[["?x", "?p", "?z"]]),
+    ("prp-spo1", &[["?p1", "rdfs:subPropertyOf", "?p2"], ["?x", "?p1", "?y"]], &[["?x", "?p2", "?y"]]),
+    ("prp-eqp1", &[["?p1", "owl:equivalentProperty", "?p2"], ["?x", "?p1", "?y"]], &[["?x", "?p2", "?y"]]),
+    ("prp-eqp2", &[["?p1", "owl:equivalentProperty", "?p2"], ["?x", "?p2", "?y"]], &[["?x", "?p1", "?y"]]),
+    ("prp-pdw", &[["?p1", "owl:propertyDisjointWith", "?p2"], ["?x", "?p1", "?y"], ["?x", "?p2", "?y"]], &[]),
+    ("prp-inv1", &[["?p1", "owl:inverseOf", "?p2"], ["?x", "?p1", "?y"]], &[["?y", "?p2", "?x"]]),
+    ("prp-inv2", &[["?p1", "owl:inverseOf", "?p2"], ["?x", "?p2", "?y"]], &[["?y", "?p1", "?x"]]),
+    ("prp-npa1", &[["?x", "owl:sourceIndividual", "?i1"], ["?x", "owl:assertionProperty", "?p"], ["?x", "owl:targetIndividual", "?i2"], ["?i1", "?p", "?i2"]], &[]),
+    ("prp-npa2", &[["?x", "owl:sourceIndividual", "?i"], ["?x", "owl:assertionProperty", "?p"], ["?x", "owl:targetValue", "?lt"], ["?i", "?p", "?lt"]], &[]),
+    //classes
+    ("cls-nothing2", &[["?x", "rdf:type", "owl:Nothing"]], &[]),
+    ("cls-com", &[["?c1", "owl:complementOf", "?c2"], ["?x", "rdf:type", "?c1"], ["?x", "rdf:type", "?c2"]], &[]),
+    ("cls-svf1", &[["?x", "owl:someValuesFrom", "?y"], ["?x", "owl:onProperty", "?p"], ["?u", "?p", "?v"], ["?v", "rdf:type", "?y"]], &[["?u", "rdf:type", "?x"]]),
+    ("cls-svf2", &[["?x", "owl:someValuesFrom", "owl:Thing"], ["?x", "owl:onProperty", "?p"], ["?u", "?p", "?v"]], &[["?u", "rdf:type", "?x"]]),
+    ("cls-avf", &[["?x", "owl:allValuesFrom", "?y"], ["?x", "owl:onProperty", "?p"], ["?u", "rdf:type", "?x"], ["?u", "?p", "?v"]], &[["?v", "rdf:type", "?y"]]),
+    ("cls-hv1", &[["?x", "owl:hasValue", "?y"], ["?x", "owl:onProperty", "?p"], ["?u", "rdf:type", "?x"]], &[["?u", "?p", "?y"]]),
+    ("cls-hv2", &[["?x", "owl:hasValue", "?y"], ["?x", "owl:onProperty", "?p"], ["?u", "?p", "?y"]], &[["?u", "rdf:type", "?x"]]),
+    ("cls-maxc1", &[["?x", "owl:maxCardinality", "0"], ["?x", "owl:onProperty", "?p"], ["?u", "rdf:type", "?x"], ["?u", "?p", "?y"]], &[]),
+    ("cls-maxc2", &[["?x", "owl:maxCardinality", "1"], ["?x", "owl:onProperty", "?p"], ["?u", "rdf:type", "?x"], ["?u", "?p", "?y1"], ["?u", "?p", "?y2"]], &[["?y1", "owl:sameAs", "?y2"]]),
+    ("cls-maxqc1", &[["?x", "owl:maxQualifiedCardinality", "0"], ["?x", "owl:onProperty", "?p"], ["?x", "owl:onClass", "?c"], ["?u", "rdf:type", "?x"], ["?u", "?p", "?y"], ["?y", "rdf:type", "?c"]], &[]),
+    ("cls-maxqc2", &[["?x", "owl:maxQualifiedCardinality", "0"], ["?x", "owl:onProperty", "?p"], ["?x", "owl:onClass", "owl:Thing"], ["?u", "rdf:type", "?x"], ["?u", "?p", "?y"]], &[]),
+    ("cls-maxqc3", &[["?x", "owl:maxQualifiedCardinality", "1"], ["?x", "owl:onProperty", "?p"], ["?x", "owl:onClass", "?c"], ["?u", "rdf:type", "?x"],
+        ["?u", "?p", "?y1"], ["?y1", "rdf:type", "?c"], ["?u", "?p", "?y2"], ["?y2", "rdf:type", "?c"]], &[["?y1", "owl:sameAs", "?y2"]]),
+    ("cls-maxqc4", &[["?x", "owl:maxQualifiedCardinality", "1"], ["?x", "owl:onProperty", "?p"], ["?x", "owl:onClass", "owl:Thing"], ["?u", "rdf:type", "?x"],
+        ["?u", "?p", "?y1"], ["?u", "?p", "?y2"]], &[["?y1", "owl:sameAs", "?y2"]]),
+    //class axioms
+    ("cax-sco", &[["?c1", "rdfs:subClassOf", "?c2"], ["?x", "rdf:type", "?c1"]], &[["?x", "rdf:type", "?c2"]]),
+    ("cax-eqc1", &[["?c1", "owl:equivalentClass", "?c2"], ["?x", "rdf:type", "?c1"]], &[["?x", "rdf:type", "?c2"]]),
+    ("cax-eqc2", &[["?c1", "owl:equivalentClass", "?c2"], ["?x", "rdf:type", "?c2"]], &[["?x", "rdf:type", "?c1"]]),
+    ("cax-dw", &[["?c1", "owl:disjointWith", "?c2"], ["?x", "rdf:type", "?c1"], ["?x", "rdf:type", "?c2"]], &[]),
+    //schema vocabulary
+    ("scm-cls", &[["?c", "rdf:type", "owl:Class"]],
+        &[["?c", "rdfs:subClassOf", "?c"], ["?c", "owl:equivalentClass", "?c"], ["?c", "rdfs:subClassOf", "owl:Thing"], ["owl:Nothing", "rdfs:subClassOf", "?c"]]),
+    ("scm-sco", &[["?c1", "rdfs:subClassOf", "?c2"], ["?c2", "rdfs:subClassOf", "?c3"]], &[["?c1", "rdfs:subClassOf", "?c3"]]),
+    ("scm-eqc1", &[["?c1", "owl:equivalentClass", "?c2"]], &[["?c1", "rdfs:subClassOf", "?c2"], ["?c2", "rdfs:subClassOf", "?c1"]]),
+    ("scm-eqc2", &[["?c1", "rdfs:subClassOf", "?c2"], ["?c2", "rdfs:subClassOf", "?c1"]], &[["?c1", "owl:equivalentClass", "?c2"]]),
+    ("scm-op", &[["?p", "rdf:type", "owl:ObjectProperty"]], &[["?p", "rdfs:subPropertyOf", "?p"], ["?p", "owl:equivalentProperty", "?p"]]),
+    ("scm-dp", &[["?p", "rdf:type", "owl:DatatypeProperty"]], &[["?p", "rdfs:subPropertyOf", "?p"], ["?p", "owl:equivalentProperty", "?p"]]),
+    ("scm-spo", &[["?p1", "rdfs:subPropertyOf", "?p2"], ["?p2", "rdfs:subPropertyOf", "?p3"]], &[["?p1", "rdfs:subPropertyOf", "?p3"]]),
+    ("scm-eqp1", &[["?p1", "owl:equivalentProperty", "?p2"]], &[["?p1", "rdfs:subPropertyOf", "?p2"], ["?p2", "rdfs:subPropertyOf", "?p1"]]),
+    ("scm-eqp2", &[["?p1", "rdfs:subPropertyOf", "?p2"], ["?p2", "rdfs:subPropertyOf", "?p1"]], &[["?p1", "owl:equivalentProperty", "?p2"]]),
+    ("scm-dom1", &[["?p", "rdfs:domain", "?c1"], ["?c1", "rdfs:subClassOf", "?c2"]], &[["?p", "rdfs:domain", "?c2"]]),
+    ("scm-dom2", &[["?p2", "rdfs:domain", "?c"], ["?p1", "rdfs:subPropertyOf", "?p2"]], &[["?p1", "rdfs:domain", "?c"]]),
+    ("scm-rng1", &[["?p", "rdfs:range", "?c1"], ["?c1", "rdfs:subClassOf", "?c2"]], &[["?p", "rdfs:range", "?c2"]]),
+    ("scm-rng2", &[["?p2", "rdfs:range", "?c"], ["?p1", "rdfs:subPropertyOf", "?p2"]], &[["?p1", "rdfs:range", "?c"]]),
+    ("scm-hv", &[["?c1", "owl:hasValue", "?i"], ["?c1", "owl:onProperty", "?p1"], ["?c2", "owl:hasValue", "?i"], ["?c2", "owl:onProperty", "?p2"],
+        ["?p1", "rdfs:subPropertyOf", "?p2"]], &[["?c1", "rdfs:subClassOf", "?c2"]]),
+    ("scm-svf1", &[["?c1", "owl:someValuesFrom", "?y1"], ["?c1", "owl:onProperty", "?p"], ["?c2", "owl:someValuesFrom", "?y2"], ["?c2", "owl:onProperty", "?p"],
+        ["?y1", "rdfs:subClassOf", "?y2"]], &[["?c1", "rdfs:subClassOf", "?c2"]]),
+    ("scm-svf2", &[["?c1", "owl:someValuesFrom", "?y"], ["?c1", "owl:onProperty", "?p1"], ["?c2", "owl:someValuesFrom", "?y"], ["?c2", "owl:onProperty", "?p2"],
+        ["?p1", "rdfs:subPropertyOf", "?p2"]], &[["?c1", "rdfs:subClassOf", "?c2"]]),
+    ("scm-avf1", &[["?c1", "owl:allValuesFrom", "?y1"], ["?c1", "owl:onProperty", "?p"], ["?c2", "owl:allValuesFrom", "?y2"], ["?c2", "owl:onProperty", "?p"],
+        ["?y1", "rdfs:subClassOf", "?y2"]], &[["?c1", "rdfs:subClassOf", "?c2"]]),
+    ("scm-avf2", &[["?c1", "owl:allValuesFrom", "?y"], ["?c1", "owl:onProperty", "?p1"], ["?c2", "owl:allValuesFrom", "?y"], ["?c2", "owl:onProperty", "?p2"],
+        ["?p1", "rdfs:subPropertyOf", "?p2"]], &[["?c2", "rdfs:subClassOf", "?c1"]])
+];
+
+/// The annotation properties of rule prp-ap
+const ANNOTATION_PROPERTIES: &[&str] = &["rdfs:label", "rdfs:comment", "rdfs:seeAlso", "rdfs:isDefinedBy", "owl:deprecated", "owl:versionInfo",
+    "owl:priorVersion", "owl:backwardCompatibleWith", "owl:incompatibleWith"];
+
+///
+/// Creates reasoners for the OWL 2 RL/RDF rules
+///
+/// # Example
+///
+/// ```
+/// use rdf4rust::reasoning::owl::OwlRlReasoner;
+/// use rdf4rust::rdf::database::{Database, GraphPattern};
+/// use rdf4rust::rdf::graph::{GraphType, Quad, Node, ResourceNode};
+/// use rdf4rust::rdf::node_factory::iri;
+///
+/// let resource = |s: &str| ResourceNode::IRINode { iri: iri(s) };
+/// let node = |s: &str| Node::IRINode { iri: iri(s) };
+/// let ontology = resource("http://example.com/ontology");
+/// let inferred = resource("http://example.com/inferred");
+///
+/// let mut database = Database::new(GraphType::SimpleGraph);
+/// database.add_quad(Quad::create(resource("http://example.com/knows"), iri("http://www.w3.org/1999/02/22-rdf-syntax-ns#type"),
+///     node("http://www.w3.org/2002/07/owl#SymmetricProperty"), Some(ontology)));
+/// database.add_quad(Quad::create(resource("http://example.com/alice"), iri("http://example.com/knows"), node("http://example.com/bob"), None));
+///
+/// let derived = OwlRlReasoner::new().materialize_database(&mut database, &GraphPattern::AnyGraph, Some(&inferred)).expect("Is consistent");
+/// assert!(derived > 0);
+/// let quads = database.quads_for_pattern(Some(&resource("http://example.com/bob")), Some(&iri("http://example.com/knows")), None,
+///     &GraphPattern::NamedGraph { name: inferred });
+/// assert_eq!(1, quads.len());
+/// ```
+///
+#[derive(Clone, Debug, Default)]
+pub struct OwlRlReasoner{
+    reflexive_equality: bool
+}
+
+impl OwlRlReasoner{
+
+    pub fn new() -> OwlRlReasoner{
+        OwlRlReasoner{
+            reflexive_equality: false
+        }
+    }
+
+    /// Enables rule eq-ref, which makes every subject, predicate and object `owl:sameAs` itself, disabled by default
+    pub fn with_reflexive_equality(mut self, reflexive_equality: bool) -> Self{
+        self.reflexive_equality = reflexive_equality;
+        self
+    }
+
+    pub fn get_reflexive_equality(&self) -> bool{
+        self.reflexive_equality
+    }
+
+    ///
+    /// Creates the forward chaining reasoner for `ontology`
+    ///
+    /// The rules working on lists (cls-int1, cls-int2, cls-uni, cls-oo, prp-spo2, prp-key, eq-diff2, eq-diff3, prp-adp, cax-adc,
+    /// scm-int and scm-uni) are compiled for each list in `ontology`, the other rules are independent of it.
+    ///
+    pub fn reasoner(&self, ontology: &dyn Graph) -> Reasoner{
+        let mut rules: Vec<Rule> = RULES.iter()
+            .map(|(name, body, head)| Rule::new(name, patterns(body), patterns(head)).expect("OWL 2 RL rules are valid"))
+            .collect();
+        if self.reflexive_equality {
+            rules.push(Rule::new("eq-ref", patterns(&[["?s", "?p", "?o"]]),
+                patterns(&[["?s", "owl:sameAs", "?s"], ["?p", "owl:sameAs", "?p"], ["?o", "owl:sameAs", "?o"]])).expect("OWL 2 RL rules are valid"));
+        }
+        let mut axioms = vec![statement(owl("Thing"), rdf("type"), owl("Class")), statement(owl("Nothing"), rdf("type"), owl("Class"))];
+        axioms.extend(ANNOTATION_PROPERTIES.iter().map(|&property| statement(expand(property), rdf("type"), owl("AnnotationProperty"))));
+        axioms.extend(datatype_axioms());
+        compile_list_rules(ontology, &mut rules, &mut axioms);
+        Reasoner::new(rules).with_axioms(axioms)
+    }
+
+    ///
+    /// Checks if `graph` is consistent
+    ///
+    /// # Returns
+    ///
+    /// The inconsistencies found in the closure of `graph`, empty if it is consistent
+    ///
+    pub fn check(&self, graph: &dyn Graph) -> Vec<Inconsistency>{
+        match self.derive(graph) {
+            Ok(_) => Vec::new(),
+            Err(inconsistencies) => inconsistencies
+        }
+    }
+
+    ///
+    /// Computes all statements entailed by `graph` which are not contained in it
+    ///
+    /// # Returns
+    ///
+    /// The derived statements or the inconsistencies, if `graph` is inconsistent
+    ///
+    pub fn derive(&self, graph: &dyn Graph) -> Result<Vec<Statement>, Vec<Inconsistency>>{
+        let reasoner = self.reasoner(graph);
+        let closure = reasoner.closure(graph);
+        let mut inconsistencies = reasoner.check(&closure);
+        inconsistencies.extend(ill_typed_literals(&closure));
+        if inconsistencies.is_empty() {
+            Ok(derived_statements(graph, &closure))
+        } else {
+            Err(inconsistencies)
+        }
+    }
+
+    ///
+    /// Adds all statements entailed by `source` which are not contained in it to `target`
+    ///
+    /// # Returns
+    ///
+    /// The number of derived statements or the inconsistencies, in which case nothing is added
+    ///
+    pub fn materialize(&self, source: &dyn Graph, target: &mut dyn Graph) -> Result<usize, Vec<Inconsistency>>{
+        let derived = self.derive(source)?;
+        target.add_all_statements(&derived);
+        Ok(derived.len())
+    }
+
+    ///
+    /// Adds all statements entailed by the graphs of `database` matching `source` to the graph `target`
+    ///
+    /// # Parameters
+    ///
+    /// * `database` - The dataset to reason over
+    /// * `source` - The graphs whose merge is the ontology and the data, e.g. `GraphPattern::AnyGraph` for all graphs
+    /// * `target` - The name of the graph receiving the derived statements or None for the default graph
+    ///
+    /// # Returns
+    ///
+    /// The number of derived statements or the inconsistencies, in which case nothing is added
+    ///
+    pub fn materialize_database(&self, database: &mut Database, source: &GraphPattern, target: Option<&ResourceNode>) -> Result<usize, Vec<Inconsistency>>{
+        let derived = self.derive(&merge_graphs(database, source))?;
+        add_to_database(database, &derived, target);
+        Ok(derived.len())
+    }
+}
+
+fn owl(local_name: &str) -> IRIResource{
+    iri(&(owl_base() + local_name))
+}
+
+/// Expands a prefixed name of the RDF, RDFS or OWL vocabulary
+fn expand(name: &str) -> IRIResource{
+    match name.split_once(':') {
+        Some(("rdf", local_name)) => rdf(local_name),
+        Some(("rdfs", local_name)) => rdfs(local_name),
+        Some(("owl", local_name)) => owl(local_name),
+        _ => panic!("Unknown prefix in {}", name)
+    }
+}
+
+fn term(term: &str) -> Term{
+    match term.strip_prefix('?') {
+        Some(name) => Term::var(name),
+        None if term.chars().all(|c| c.is_ascii_digit()) =>
+            Term::Constant(Node::LiteralNode { literal: Literal::create_typed_literal(String::from(term), &XSD_NON_NEGATIVE_INTEGER) }),
+        None => Term::iri(expand(term))
+    }
+}
+
+fn patterns(patterns: &[PatternTemplate]) -> Vec<TriplePattern>{
+    patterns.iter().map(|[s, p, o]| TriplePattern::new(term(s), term(p), term(o))).collect()
+}
+
+fn pattern(subject: Term, predicate: Term, object: Term) -> TriplePattern{
+    TriplePattern::new(subject, predicate, object)
+}
+
+fn type_of(subject: Term, class: &Node) -> TriplePattern{
+    pattern(subject, Term::iri(rdf("type")), Term::Constant(class.clone()))
+}
+
+/// Adds a rule unless it is invalid, which happens for empty lists
+fn push_rule(rules: &mut Vec<Rule>, name: &str, body: Vec<TriplePattern>, head: Vec<TriplePattern>){
+    if let Ok(rule) = Rule::new(name, body, head) {
+        rules.push(rule);
+    }
+}
+
+/// The objects of `subject predicate ?o`
+fn objects<'a>(graph: &'a dyn Graph, subject: &ResourceNode, predicate: IRIResource) -> Vec<&'a Node>{
+    graph.list_statements(Some(subject.clone()), Some(predicate), None).into_iter().map(|stmt| stmt.get_object()).collect()
+}
+
+/// The lists which are objects of `predicate`, together with their subject
+fn lists(ontology: &dyn Graph, predicate: &str) -> Vec<(ResourceNode, Vec<Node>)>{
+    ontology.list_statements(None, Some(expand(predicate)), None).into_iter()
+        .filter_map(|stmt| read_list(ontology, stmt.get_object()).map(|items| (stmt.get_subject().clone(), items)))
+        .collect()
+}
+
+/// The pairs of distinct members of `items`
+fn pairs(items: &[Node]) -> Vec<(&Node, &Node)>{
+    let mut pairs = Vec::new();
+    for (i, first) in items.iter().enumerate() {
+        for second in &items[i + 1..] {
+            if first != second {
+                pairs.push((first, second));
+            }
+        }
+    }
+    pairs
+}
+
+/// Compiles the rules iterating over lists for each list in `ontology`
+fn compile_list_rules(ontology: &dyn Graph, rules: &mut Vec<Rule>, axioms: &mut Vec<Statement>){
+    let sub_class_of = |sub: &Node, sup: &Node| sub.to_resource().map(|sub| Statement::create(sub, rdfs("subClassOf"), sup.clone()));
+    for (class, members) in lists(ontology, "owl:intersectionOf") {
+        let class = Node::from(class);
+        push_rule(rules, "cls-int1", members.iter().map(|member| type_of(Term::var("y"), member)).collect(), vec![type_of(Term::var("y"), &class)]);
+        push_rule(rules, "cls-int2", vec![type_of(Term::var("y"), &class)], members.iter().map(|member| type_of(Term::var("y"), member)).collect());
+        axioms.extend(members.iter().filter_map(|member| sub_class_of(&class, member)));
+    }
+    for (class, members) in lists(ontology, "owl:unionOf") {
+        let class = Node::from(class);
+        for member in &members {
+            push_rule(rules, "cls-uni", vec![type_of(Term::var("y"), member)], vec![type_of(Term::var("y"), &class)]);
+        }
+        axioms.extend(members.iter().filter_map(|member| sub_class_of(member, &class)));
+    }
+    for (class, members) in lists(ontology, "owl:oneOf") {
+        axioms.extend(members.iter().filter_map(|member| member.to_resource())
+            .map(|member| Statement::create(member, rdf("type"), Node::from(class.clone()))));
+    }
+    for (property, chain) in lists(ontology, "owl:propertyChainAxiom") {
+        let var = |i: usize| Term::var(&format!("u{}", i));
+        let body = chain.iter().enumerate().map(|(i, link)| pattern(var(i), Term::Constant(link.clone()), var(i + 1))).collect();
+        push_rule(rules, "prp-spo2", body, vec![pattern(var(0), Term::Constant(Node::from(property)), var(chain.len()))]);
+    }
+    for (class, keys) in lists(ontology, "owl:hasKey") {
+        let class = Node::from(class);
+        let mut body = vec![type_of(Term::var("x"), &class), type_of(Term::var("y"), &class)];
+        for (i, key) in keys.iter().enumerate() {
+            body.push(pattern(Term::var("x"), Term::Constant(key.clone()), Term::var(&format!("z{}", i))));
+            body.push(pattern(Term::var("y"), Term::Constant(key.clone()), Term::var(&format!("z{}", i))));
+        }
+        push_rule(rules, "prp-key", body, vec![pattern(Term::var("x"), Term::iri(owl("sameAs")), Term::var("y"))]);
+    }
+    let is_a = |subject: &ResourceNode, class: &str| objects(ontology, subject, rdf("type")).contains(&&Node::IRINode { iri: owl(class) });
+    let same_as = |first: &Node, second: &Node| vec![pattern(Term::Constant(first.clone()), Term::iri(owl("sameAs")), Term::Constant(second.clone()))];
+    for (_, members) in lists(ontology, "owl:distinctMembers") {
+        for (first, second) in pairs(&members) {
+            push_rule(rules, "eq-diff3", same_as(first, second), vec![]);
+        }
+    }
+    for (subject, members) in lists(ontology, "owl:members") {
+        if is_a(&subject, "AllDifferent") {
+            for (first, second) in pairs(&members) {
+                push_rule(rules, "eq-diff2", same_as(first, second), vec![]);
+            }
+        }
+        if is_a(&subject, "AllDisjointProperties") {
+            for (first, second) in pairs(&members) {
+                push_rule(rules, "prp-adp", vec![pattern(Term::var("x"), Term::Constant(first.clone()), Term::var("y")),
+                    pattern(Term::var("x"), Term::Constant(second.clone()), Term::var("y"))], vec![]);
+            }
+        }
+        if is_a(&subject, "AllDisjointClasses") {
+            for (first, second) in pairs(&members) {
+                push_rule(rules, "cax-adc", vec![type_of(Term::var("x"), first), type_of(Term::var("x"), second)], vec![]);
+            }
+        }
+    }
+}
+
+/// The inconsistencies of rule dt-not-type, literals whose value is not valid for their datatype
+fn ill_typed_literals(graph: &dyn Graph) -> Vec<Inconsistency>{
+    graph.list_statements(None, None, None).into_iter()
+        .filter(|stmt| matches!(stmt.get_object(), Node::LiteralNode { literal } if literal.value().is_err()))
+        .map(|stmt| Inconsistency::new("dt-not-type", vec![stmt.clone()]))
+        .collect()
+}
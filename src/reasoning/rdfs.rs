@@ -0,0 +1,220 @@
+//!
+//! RDFS entailment according to RDF 1.1 Semantics section 9.2.
+//!
+//! The entailment rules rdfs2 – rdfs13 and rdf1 are `Rule`s for the forward chaining `Reasoner`,
+//! rdfs1 (`D rdf:type rdfs:Datatype` for every recognized datatype `D`) is a set of axioms, as its premise is not a triple.
+//! Statements with a literal subject (e.g. the range of a literal valued property) can't be represented and are not derived.
+//!
+//! # Example
+//!
+//! ```
+//! use rdf4rust::reasoning::rdfs::{self, RdfsRule};
+//! use rdf4rust::rdf::graph::{Graph, SimpleGraph, Statement, Node, ResourceNode};
+//! use rdf4rust::rdf::node_factory::{IRIResource, iri};
+//!
+//! let ex = |s: &str| iri(&format!("http://example.com/{}", s));
+//! let stmt = |s: IRIResource, p: IRIResource, o: IRIResource| Statement::create(ResourceNode::IRINode { iri: s }, p, Node::IRINode { iri: o });
+//! let rdf_type = iri("http://www.w3.org/1999/02/22-rdf-syntax-ns#type");
+//! let sub_class_of = iri("http://www.w3.org/2000/01/rdf-schema#subClassOf");
+//! let domain = iri("http://www.w3.org/2000/01/rdf-schema#domain");
+//!
+//! let mut graph = rdfs::reasoner(&[RdfsRule::Rdfs2, RdfsRule::Rdfs9, RdfsRule::Rdfs11], false)
+//!     .infer(Box::new(SimpleGraph::new()));
+//! graph.add_statement(stmt(ex("Student"), sub_class_of.clone(), ex("Person")));
+//! graph.add_statement(stmt(ex("Person"), sub_class_of.clone(), ex("Agent")));
+//! graph.add_statement(stmt(ex("enrolledIn"), domain.clone(), ex("Student")));
+//! graph.add_statement(stmt(ex("alice"), ex("enrolledIn"), ex("course1")));
+//!
+//! let types = |graph: &dyn Graph| graph.list_statements(Some(ResourceNode::IRINode { iri: ex("alice") }), Some(rdf_type.clone()), None).len();
+//! assert_eq!(3, types(&graph));
+//! assert!(graph.is_derived(&stmt(ex("Student"), sub_class_of.clone(), ex("Agent"))));
+//!
+//! // the derived types of alice are retracted with the statement they depend on
+//! graph.remove_statement(stmt(ex("alice"), ex("enrolledIn"), ex("course1")));
+//! assert_eq!(0, types(&graph));
+//!
+//! // materialize the complete RDFS closure including the axiomatic triples, so rdfs:domain rdfs:domain rdf:Property applies
+//! let mut inferred = SimpleGraph::new();
+//! rdfs::reasoner(&RdfsRule::ALL, true).materialize(graph.get_base_graph(), &mut inferred);
+//! assert_eq!(1, inferred.list_statements(Some(ResourceNode::IRINode { iri: ex("enrolledIn") }), Some(rdf_type.clone()),
+//!     Some(Node::IRINode { iri: iri("http://www.w3.org/1999/02/22-rdf-syntax-ns#Property") })).len());
+//! ```
+//!
+
+use crate::reasoning::rules::{Reasoner, Rule, Term, TriplePattern};
+use crate::rdf::graph::{Node, ResourceNode, Statement};
+use crate::rdf::node_factory::{iri, rdf, rdfs, IRIResource};
+use crate::rdf::xsd::get_builtin_types;
+
+pub use crate::rdf::xsd::rdfs_base;
+
+///
+/// The RDFS entailment rules, named as in RDF 1.1 Semantics
+///
+/// Leaving out `Rdf1`, `Rdfs4a`, `Rdfs4b` and `Rdfs1` avoids the many trivial statements typing every resource
+/// as `rdfs:Resource`, every predicate as `rdf:Property` and every datatype as `rdfs:Datatype`.
+///
+#[derive(Clone, Copy, PartialEq, Eq, Hash, Debug)]
+pub enum RdfsRule{
+    /// `?x ?a ?y` entails `?a rdf:type rdf:Property`
+    Rdf1,
+    /// Every recognized datatype `D` is of `rdf:type rdfs:Datatype`
+    Rdfs1,
+    /// `?a rdfs:domain ?x` and `?y ?a ?z` entail `?y rdf:type ?x`
+    Rdfs2,
+    /// `?a rdfs:range ?x` and `?y ?a ?z` entail `?z rdf:type ?x`
+    Rdfs3,
+    /// `?x ?a ?y` entails `?x rdf:type rdfs:Resource`
+    Rdfs4a,
+    /// `?x ?a ?y` entails `?y rdf:type rdfs:Resource`
+    Rdfs4b,
+    /// `rdfs:subPropertyOf` is transitive
+    Rdfs5,
+    /// Every property is a subproperty of itself
+    Rdfs6,
+    /// `?a rdfs:subPropertyOf ?b` and `?x ?a ?y` entail `?x ?b ?y`
+    Rdfs7,
+    /// Every class is a subclass of `rdfs:Resource`
+    Rdfs8,
+    /// `?x rdfs:subClassOf ?y` and `?z rdf:type ?x` entail `?z rdf:type ?y`
+    Rdfs9,
+    /// Every class is a subclass of itself
+    Rdfs10,
+    /// `rdfs:subClassOf` is transitive
+    Rdfs11,
+    /// Every container membership property is a subproperty of `rdfs:member`
+    Rdfs12,
+    /// Every datatype is a subclass of `rdfs:Literal`
+    Rdfs13
+}
+
+impl RdfsRule{
+
+    pub const ALL: [RdfsRule; 15] = [RdfsRule::Rdf1, RdfsRule::Rdfs1, RdfsRule::Rdfs2, RdfsRule::Rdfs3, RdfsRule::Rdfs4a, RdfsRule::Rdfs4b,
+        RdfsRule::Rdfs5, RdfsRule::Rdfs6, RdfsRule::Rdfs7, RdfsRule::Rdfs8, RdfsRule::Rdfs9, RdfsRule::Rdfs10, RdfsRule::Rdfs11,
+        RdfsRule::Rdfs12, RdfsRule::Rdfs13];
+
+    /// Gets the name of the rule, e.g. `rdfs4a`
+    pub fn get_name(&self) -> &'static str{
+        match self {
+            RdfsRule::Rdf1 => "rdf1",
+            RdfsRule::Rdfs1 => "rdfs1",
+            RdfsRule::Rdfs2 => "rdfs2",
+            RdfsRule::Rdfs3 => "rdfs3",
+            RdfsRule::Rdfs4a => "rdfs4a",
+            RdfsRule::Rdfs4b => "rdfs4b",
+            RdfsRule::Rdfs5 => "rdfs5",
+            RdfsRule::Rdfs6 => "rdfs6",
+            RdfsRule::Rdfs7 => "rdfs7",
+            RdfsRule::Rdfs8 => "rdfs8",
+            RdfsRule::Rdfs9 => "rdfs9",
+            RdfsRule::Rdfs10 => "rdfs10",
+            RdfsRule::Rdfs11 => "rdfs11",
+            RdfsRule::Rdfs12 => "rdfs12",
+            RdfsRule::Rdfs13 => "rdfs13"
+        }
+    }
+
+    ///
+    /// The rule for the `Reasoner`
+    ///
+    /// # Returns
+    ///
+    /// The rule or None for `Rdfs1`, which is applied as axioms, see `datatype_axioms`
+    ///
+    pub fn to_rule(&self) -> Option<Rule>{
+        let var = Term::var;
+        let rdf_type = || Term::iri(rdf("type"));
+        let sub_class_of = || Term::iri(rdfs("subClassOf"));
+        let sub_property_of = || Term::iri(rdfs("subPropertyOf"));
+        let pattern = TriplePattern::new;
+        let (body, head) = match self {
+            RdfsRule::Rdf1 => (vec![pattern(var("x"), var("a"), var("y"))], pattern(var("a"), rdf_type(), Term::iri(rdf("Property")))),
+            RdfsRule::Rdfs1 => return None,
+            RdfsRule::Rdfs2 => (vec![pattern(var("a"), Term::iri(rdfs("domain")), var("x")), pattern(var("y"), var("a"), var("z"))],
+                pattern(var("y"), rdf_type(), var("x"))),
+            RdfsRule::Rdfs3 => (vec![pattern(var("a"), Term::iri(rdfs("range")), var("x")), pattern(var("y"), var("a"), var("z"))],
+                pattern(var("z"), rdf_type(), var("x"))),
+            RdfsRule::Rdfs4a => (vec![pattern(var("x"), var("a"), var("y"))], pattern(var("x"), rdf_type(), Term::iri(rdfs("Resource")))),
+            RdfsRule::Rdfs4b => (vec![pattern(var("x"), var("a"), var("y"))], pattern(var("y"), rdf_type(), Term::iri(rdfs("Resource")))),
+            RdfsRule::Rdfs5 => (vec![pattern(var("x"), sub_property_of(), var("y")), pattern(var("y"), sub_property_of(), var("z"))],
+                pattern(var("x"), sub_property_of(), var("z"))),
+            RdfsRule::Rdfs6 => (vec![pattern(var("x"), rdf_type(), Term::iri(rdf("Property")))], pattern(var("x"), sub_property_of(), var("x"))),
+            RdfsRule::Rdfs7 => (vec![pattern(var("a"), sub_property_of(), var("b")), pattern(var("x"), var("a"), var("y"))],
+                pattern(var("x"), var("b"), var("y"))),
+            RdfsRule::Rdfs8 => (vec![pattern(var("x"), rdf_type(), Term::iri(rdfs("Class")))], pattern(var("x"), sub_class_of(), Term::iri(rdfs("Resource")))),
+            RdfsRule::Rdfs9 => (vec![pattern(var("x"), sub_class_of(), var("y")), pattern(var("z"), rdf_type(), var("x"))],
+                pattern(var("z"), rdf_type(), var("y"))),
+            RdfsRule::Rdfs10 => (vec![pattern(var("x"), rdf_type(), Term::iri(rdfs("Class")))], pattern(var("x"), sub_class_of(), var("x"))),
+            RdfsRule::Rdfs11 => (vec![pattern(var("x"), sub_class_of(), var("y")), pattern(var("y"), sub_class_of(), var("z"))],
+                pattern(var("x"), sub_class_of(), var("z"))),
+            RdfsRule::Rdfs12 => (vec![pattern(var("x"), rdf_type(), Term::iri(rdfs("ContainerMembershipProperty")))],
+                pattern(var("x"), sub_property_of(), Term::iri(rdfs("member")))),
+            RdfsRule::Rdfs13 => (vec![pattern(var("x"), rdf_type(), Term::iri(rdfs("Datatype")))], pattern(var("x"), sub_class_of(), Term::iri(rdfs("Literal"))))
+        };
+        Some(Rule::new(self.get_name(), body, vec![head]).expect("RDFS rules are valid"))
+    }
+}
+
+///
+/// Creates a reasoner for RDFS entailment
+///
+/// # Parameters
+///
+/// * `rules` - The entailment rules to apply, `RdfsRule::ALL` for the complete RDFS entailment
+/// * `axiomatic_triples` - Whether to add the RDF and RDFS axiomatic triples, see `axiomatic_triples`
+///
+pub fn reasoner(rules: &[RdfsRule], axiomatic_triples: bool) -> Reasoner{
+    let mut axioms = Vec::new();
+    if rules.contains(&RdfsRule::Rdfs1) {
+        axioms.extend(datatype_axioms());
+    }
+    if axiomatic_triples {
+        axioms.extend(self::axiomatic_triples());
+    }
+    Reasoner::new(rules.iter().filter_map(RdfsRule::to_rule).collect()).with_axioms(axioms)
+}
+
+///
+/// The statements of rule rdfs1, typing every built-in datatype (see `get_builtin_types`) as `rdfs:Datatype`
+///
+pub fn datatype_axioms() -> Vec<Statement>{
+    get_builtin_types().into_iter()
+        .map(|dtype| statement(iri(dtype.get_value()), rdf("type"), rdfs("Datatype")))
+        .collect()
+}
+
+///
+/// The RDF and RDFS axiomatic triples
+///
+/// The triples for the infinitely many container membership properties `rdf:_1`, `rdf:_2`, ... are left out.
+///
+pub fn axiomatic_triples() -> Vec<Statement>{
+    let mut axioms: Vec<Statement> = ["type", "subject", "predicate", "object", "first", "rest", "value"].iter()
+        .map(|property| statement(rdf(property), rdf("type"), rdf("Property")))
+        .collect();
+    axioms.push(statement(rdf("nil"), rdf("type"), rdf("List")));
+    let domains = [(rdf("type"), rdfs("Resource")), (rdfs("domain"), rdf("Property")), (rdfs("range"), rdf("Property")),
+        (rdfs("subPropertyOf"), rdf("Property")), (rdfs("subClassOf"), rdfs("Class")), (rdf("subject"), rdf("Statement")),
+        (rdf("predicate"), rdf("Statement")), (rdf("object"), rdf("Statement")), (rdfs("member"), rdfs("Resource")),
+        (rdf("first"), rdf("List")), (rdf("rest"), rdf("List")), (rdfs("seeAlso"), rdfs("Resource")),
+        (rdfs("isDefinedBy"), rdfs("Resource")), (rdfs("comment"), rdfs("Resource")), (rdfs("label"), rdfs("Resource")),
+        (rdf("value"), rdfs("Resource"))];
+    axioms.extend(domains.iter().map(|(property, class)| statement(property.clone(), rdfs("domain"), class.clone())));
+    let ranges = [(rdf("type"), rdfs("Class")), (rdfs("domain"), rdfs("Class")), (rdfs("range"), rdfs("Class")),
+        (rdfs("subPropertyOf"), rdf("Property")), (rdfs("subClassOf"), rdfs("Class")), (rdf("subject"), rdfs("Resource")),
+        (rdf("predicate"), rdfs("Resource")), (rdf("object"), rdfs("Resource")), (rdfs("member"), rdfs("Resource")),
+        (rdf("first"), rdfs("Resource")), (rdf("rest"), rdf("List")), (rdfs("seeAlso"), rdfs("Resource")),
+        (rdfs("isDefinedBy"), rdfs("Resource")), (rdfs("comment"), rdfs("Literal")), (rdfs("label"), rdfs("Literal")),
+        (rdf("value"), rdfs("Resource"))];
+    axioms.extend(ranges.iter().map(|(property, class)| statement(property.clone(), rdfs("range"), class.clone())));
+    let sub_classes = [(rdf("Alt"), rdfs("Container")), (rdf("Bag"), rdfs("Container")), (rdf("Seq"), rdfs("Container")),
+        (rdfs("ContainerMembershipProperty"), rdf("Property")), (rdfs("Datatype"), rdfs("Class"))];
+    axioms.extend(sub_classes.iter().map(|(sub, sup)| statement(sub.clone(), rdfs("subClassOf"), sup.clone())));
+    axioms.push(statement(rdfs("isDefinedBy"), rdfs("subPropertyOf"), rdfs("seeAlso")));
+    axioms
+}
+
+pub(crate) fn statement(subject: IRIResource, predicate: IRIResource, object: IRIResource) -> Statement{
+    Statement::create(ResourceNode::IRINode { iri: subject }, predicate, Node::IRINode { iri: object })
+}
@@ -0,0 +1,1178 @@
+//!
+//! Forward chaining of rules over graphs.
+//!
+//! A `Rule` derives the triples of its head for every solution of its body. Bodies are evaluated as joins over
+//! `Graph::list_statements`, always continuing with the pattern with the most bound terms.
+//!
+//! The closure is computed semi-naively: only newly derived statements are joined against the closure,
+//! so each combination of premises is considered once instead of once per iteration.
+//! An `InferenceGraph` keeps the closure up to date when statements are added (by propagating the new statements)
+//! or removed (by deleting everything derived from them and rederiving what still has another derivation).
+//!
+//! Rules without head are constraints: each solution of their body is an `Inconsistency`.
+//!
+//! Besides triple patterns, bodies may contain builtins (see `reasoning::builtins`), e.g. to compare literals.
+//! Rules can also be written in a syntax similar to Jena rules and parsed with `parse_rules`:
+//!
+//! ```text
+//! @prefix ex: <http://example.com/>
+//!
+//! [adult: (?p ex:age ?age) ge(?age 18) -> (?p rdf:type ex:Adult)]
+//! [nextYear: (?p ex:age ?age) sum(?age 1 ?next) -> (?p ex:ageNextYear ?next)]
+//! [noMinors: (?p rdf:type ex:Adult) (?p ex:age ?age) lessThan(?age 18) -> false]
+//! ```
+//!
+//! Rules of that syntax start with an optional name followed by a colon, the body and the head are separated by `->`.
+//! Terms are written as in Turtle: variables like `?x`, IRIs like `<http://example.com/a>`, prefixed names like `ex:a`,
+//! blank nodes like `_:b` and literals like `"chat"@fr`, `"1"^^xsd:int`, `42`, `1.5` or `true`.
+//! `@prefix` declares prefixes, `rdf`, `rdfs`, `owl` and `xsd` are predefined. Comments start with `#` or `//`.
+//! A head `false` makes the rule a constraint. Rules computing new values with builtins like `sum` may not terminate.
+//!
+
+use crate::error::{RDFError, ErrorKind};
+use crate::io::reader::parse_object;
+use crate::rdf::database::{Database, GraphPattern};
+use crate::rdf::graph::{Graph, IndexedGraph, Node, Quad, ResourceNode, Statement};
+use crate::rdf::node_factory::{IRIResource, Literal};
+pub use crate::rdf::term::Term;
+use crate::rdf::term::{closing_quote, literal_length, word_length};
+use crate::reasoning::builtins::{self, Builtin, BuiltinCall};
+use crate::util::iri::IRI;
+use std::collections::{HashMap, HashSet};
+use std::error::Error;
+use std::fmt;
+use std::rc::Rc;
+use std::str::FromStr;
+
+/// The values of the bound variables, borrowed from the rule and the matched statements
+type Bindings<'a> = Vec<(&'a str, Value<'a>)>;
+
+///
+/// A node bound to a variable, which is either a subject, a predicate or an object of a statement,
+/// or a literal computed by a builtin
+///
+#[derive(Clone)]
+enum Value<'a>{
+    Resource(&'a ResourceNode),
+    Predicate(&'a IRIResource),
+    Node(&'a Node),
+    Literal(Rc<Literal>)
+}
+
+impl<'a> Value<'a>{
+
+    fn as_iri(&self) -> Option<&'a IRIResource>{
+        match *self {
+            Value::Resource(ResourceNode::IRINode { iri }) | Value::Predicate(iri) | Value::Node(Node::IRINode { iri }) => Some(iri),
+            _ => None
+        }
+    }
+
+    fn into_node(self) -> Node{
+        match self {
+            Value::Resource(resource) => Node::from(resource.clone()),
+            Value::Predicate(iri) => Node::IRINode { iri: iri.clone() },
+            Value::Node(node) => node.clone(),
+            Value::Literal(literal) => Node::LiteralNode { literal: Literal::clone(&literal) }
+        }
+    }
+
+    /// Converts the value to a subject, None for literals
+    fn into_resource(self) -> Option<ResourceNode>{
+        match self {
+            Value::Resource(resource) => Some(resource.clone()),
+            Value::Predicate(iri) => Some(ResourceNode::IRINode { iri: iri.clone() }),
+            Value::Node(node) => node.to_resource(),
+            Value::Literal(_) => None
+        }
+    }
+}
+
+impl PartialEq for Value<'_>{
+    fn eq(&self, other: &Self) -> bool {
+        match (self, other) {
+            (Value::Node(a), Value::Node(b)) => a == b,
+            (Value::Resource(a), Value::Resource(b)) => a == b,
+            _ => match (self.as_iri(), other.as_iri()) {
+                (Some(a), Some(b)) => a == b,
+                (None, None) => self.clone().into_node() == other.clone().into_node(),
+                _ => false
+            }
+        }
+    }
+}
+
+impl Term{
+
+    /// The node of the term, None if it is an unbound variable
+    fn resolve<'a>(&'a self, bindings: &Bindings<'a>) -> Option<Value<'a>>{
+        match self {
+            Term::Variable(name) => bindings.iter().find(|(var, _)| var == name).map(|(_, value)| value.clone()),
+            Term::Constant(node) => Some(Value::Node(node))
+        }
+    }
+
+    /// Binds the term to `value`, false if it is already bound to another value
+    fn unify<'a>(&'a self, value: Value<'a>, bindings: &mut Bindings<'a>) -> bool{
+        match self.resolve(bindings) {
+            Some(bound) => bound == value,
+            None => {
+                if let Term::Variable(name) = self {
+                    bindings.push((name, value));
+                }
+                true
+            }
+        }
+    }
+}
+
+///
+/// A triple whose terms may be variables
+///
+#[derive(Clone, PartialEq, Eq, Hash)]
+pub struct TriplePattern{
+    subject: Term,
+    predicate: Term,
+    object: Term
+}
+
+impl fmt::Display for TriplePattern{
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "({} {} {})", self.subject, self.predicate, self.object)
+    }
+}
+
+impl TriplePattern{
+
+    pub fn new(subject: Term, predicate: Term, object: Term) -> TriplePattern{
+        TriplePattern{
+            subject,
+            predicate,
+            object
+        }
+    }
+
+    pub fn get_subject(&self) -> &Term{
+        &self.subject
+    }
+
+    pub fn get_predicate(&self) -> &Term{
+        &self.predicate
+    }
+
+    pub fn get_object(&self) -> &Term{
+        &self.object
+    }
+
+    fn terms(&self) -> [&Term; 3]{
+        [&self.subject, &self.predicate, &self.object]
+    }
+
+    /// Extends `bindings` so the pattern matches `stmt`, None if it can't match
+    fn unify<'a>(&'a self, stmt: &'a Statement, bindings: &Bindings<'a>) -> Option<Bindings<'a>>{
+        let mut bindings = bindings.clone();
+        let matched = self.subject.unify(Value::Resource(stmt.get_subject()), &mut bindings)
+            && self.predicate.unify(Value::Predicate(stmt.get_predicate()), &mut bindings)
+            && self.object.unify(Value::Node(stmt.get_object()), &mut bindings);
+        if matched {Some(bindings)} else {None}
+    }
+
+    ///
+    /// Replaces the variables by their bindings
+    ///
+    /// # Returns
+    ///
+    /// The statement or None if the result is no valid statement, e.g. because the subject is a literal
+    ///
+    fn instantiate(&self, bindings: &Bindings) -> Option<Statement>{
+        let subject = self.subject.resolve(bindings)?.into_resource()?;
+        let predicate = self.predicate.resolve(bindings)?.as_iri()?.clone();
+        Some(Statement::create(subject, predicate, self.object.resolve(bindings)?.into_node()))
+    }
+
+    /// Ranks how selective the pattern is with `bindings`, bound subjects and objects can be looked up in an index
+    fn selectivity(&self, bindings: &Bindings) -> usize{
+        let bound = |term: &Term| term.resolve(bindings).is_some() as usize;
+        2 * bound(&self.subject) + bound(&self.predicate) + 2 * bound(&self.object)
+    }
+
+    /// The statements of `graph` which may match the pattern with `bindings`
+    fn candidates<'a>(&self, graph: &'a dyn Graph, bindings: &Bindings) -> Vec<&'a Statement>{
+        let subject = match self.subject.resolve(bindings) {
+            Some(value) => match value.into_resource() {
+                Some(subject) => Some(subject),
+                None => return Vec::new()
+            },
+            None => None
+        };
+        let predicate = match self.predicate.resolve(bindings) {
+            Some(value) => match value.as_iri() {
+                Some(iri) => Some(iri.clone()),
+                None => return Vec::new()
+            },
+            None => None
+        };
+        graph.list_statements(subject, predicate, self.object.resolve(bindings).map(Value::into_node))
+    }
+}
+
+///
+/// An inference rule deriving the statements of its head for every solution of its body
+///
+/// All variables of the head have to occur in the body. A rule without head is a constraint, which derives nothing,
+/// but reports an `Inconsistency` for every solution of its body, see `Reasoner::check`.
+///
+/// # Example
+///
+/// ```
+/// use rdf4rust::reasoning::rules::{Rule, Term, TriplePattern};
+/// use rdf4rust::rdf::node_factory::iri;
+///
+/// let term = |s: &str| Term::iri(iri(s));
+/// let grandparent = Rule::new("grandparent",
+///     vec![TriplePattern::new(Term::var("x"), term("http://example.com/parent"), Term::var("y")),
+///          TriplePattern::new(Term::var("y"), term("http://example.com/parent"), Term::var("z"))],
+///     vec![TriplePattern::new(Term::var("x"), term("http://example.com/grandparent"), Term::var("z"))]).expect("Is a valid rule");
+/// assert_eq!("[grandparent: (?x <http://example.com/parent> ?y) (?y <http://example.com/parent> ?z) -> (?x <http://example.com/grandparent> ?z)]",
+///     grandparent.to_string());
+///
+/// // ?z is not bound by the body
+/// assert!(Rule::new("unsafe",
+///     vec![TriplePattern::new(Term::var("x"), term("http://example.com/parent"), Term::var("y"))],
+///     vec![TriplePattern::new(Term::var("x"), term("http://example.com/grandparent"), Term::var("z"))]).is_err());
+/// ```
+///
+#[derive(Clone, PartialEq, Eq, Hash)]
+pub struct Rule{
+    name: String,
+    body: Vec<TriplePattern>,
+    builtins: Vec<BuiltinCall>,
+    head: Vec<TriplePattern>
+}
+
+impl fmt::Display for Rule{
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let patterns = |patterns: &Vec<TriplePattern>| patterns.iter().map(|pattern| pattern.to_string()).collect::<Vec<_>>().join(" ");
+        let head = if self.is_constraint() {String::from("false")} else {patterns(&self.head)};
+        let builtins: String = self.builtins.iter().map(|builtin| format!(" {}", builtin)).collect();
+        write!(f, "[{}: {}{} -> {}]", self.name, patterns(&self.body), builtins, head)
+    }
+}
+
+impl FromStr for Rule{
+    type Err = RDFError;
+
+    /// Parses a single rule, see `parse_rules`
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let mut rules = parse_rules(s)?;
+        if rules.len() != 1 {
+            return Err(RDFError::new(ErrorKind::Syntax, format!("Expected exactly one rule, but found {}.", rules.len())))
+        }
+        Ok(rules.remove(0))
+    }
+}
+
+impl Rule{
+
+    ///
+    /// Creates a rule
+    ///
+    /// # Parameters
+    ///
+    /// * `name` - The name of the rule, e.g. `rdfs9`
+    /// * `body` - The premises, which have to match the graph
+    /// * `head` - The conclusions derived for each solution of `body`, empty for a constraint
+    ///
+    /// # Returns
+    ///
+    /// The rule or an error of kind `ErrorKind::InvalidRule` if the body is empty or a variable of the head doesn't occur in the body
+    ///
+    pub fn new(name: &str, body: Vec<TriplePattern>, head: Vec<TriplePattern>) -> Result<Rule, RDFError>{
+        Rule::create(name, body, Vec::new(), head)
+    }
+
+    ///
+    /// Creates a rule whose body contains builtins besides the triple patterns
+    ///
+    /// # Parameters
+    ///
+    /// * `name` - The name of the rule
+    /// * `body` - The triple patterns of the premises
+    /// * `builtins` - The builtins, which are evaluated as soon as the triple patterns bind their arguments
+    /// * `head` - The conclusions derived for each solution of `body`, empty for a constraint
+    ///
+    /// # Returns
+    ///
+    /// The rule or an error of kind `ErrorKind::InvalidRule` if the body has no triple pattern,
+    /// a variable of the head doesn't occur in the body or a builtin has an argument which is never bound
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use rdf4rust::reasoning::rules::{Rule, Term, TriplePattern};
+    /// use rdf4rust::reasoning::builtins::{Builtin, BuiltinCall};
+    /// use rdf4rust::rdf::node_factory::iri;
+    ///
+    /// let ex = |s: &str| Term::iri(iri(&format!("http://example.com/{}", s)));
+    /// let total = Rule::create("total",
+    ///     vec![TriplePattern::new(Term::var("x"), ex("net"), Term::var("net")), TriplePattern::new(Term::var("x"), ex("tax"), Term::var("tax"))],
+    ///     vec![BuiltinCall::new(Builtin::Sum, vec![Term::var("net"), Term::var("tax"), Term::var("total")]).unwrap()],
+    ///     vec![TriplePattern::new(Term::var("x"), ex("total"), Term::var("total"))]).expect("Is a valid rule");
+    /// assert_eq!("[total: (?x <http://example.com/net> ?net) (?x <http://example.com/tax> ?tax) sum(?net ?tax ?total) -> (?x <http://example.com/total> ?total)]",
+    ///     total.to_string());
+    ///
+    /// // ?tax is neither bound by a triple pattern nor by another builtin
+    /// assert!(Rule::create("invalid", vec![TriplePattern::new(Term::var("x"), ex("net"), Term::var("net"))],
+    ///     vec![BuiltinCall::new(Builtin::Sum, vec![Term::var("net"), Term::var("tax"), Term::var("total")]).unwrap()],
+    ///     vec![TriplePattern::new(Term::var("x"), ex("total"), Term::var("total"))]).is_err());
+    /// ```
+    ///
+    pub fn create(name: &str, body: Vec<TriplePattern>, builtins: Vec<BuiltinCall>, head: Vec<TriplePattern>) -> Result<Rule, RDFError>{
+        if body.is_empty() {
+            return Err(RDFError::new(ErrorKind::InvalidRule, format!("Rule {} has no triple pattern in its body, use axioms for unconditional statements.", name)))
+        }
+        let mut bound: HashSet<&Term> = body.iter().flat_map(|pattern| pattern.terms()).filter(|term| term.is_variable()).collect();
+        //functions bind their result once their inputs are bound, possibly by other functions
+        let mut pending: Vec<&BuiltinCall> = builtins.iter().collect();
+        while let Some(i) = pending.iter().position(|builtin| builtin.get_inputs().iter().all(|term| !term.is_variable() || bound.contains(term))) {
+            if let Some(result) = pending.swap_remove(i).get_result() {
+                bound.insert(result);
+            }
+        }
+        if let Some(builtin) = pending.first() {
+            return Err(RDFError::new(ErrorKind::InvalidRule, format!("Builtin {} of rule {} has arguments which are not bound by its body.", builtin, name)))
+        }
+        if let Some(unbound) = head.iter().flat_map(|pattern| pattern.terms()).find(|term| term.is_variable() && !bound.contains(term)) {
+            return Err(RDFError::new(ErrorKind::InvalidRule, format!("Variable {} of the head of rule {} doesn't occur in its body.", unbound, name)))
+        }
+        Ok(Rule{
+            name: String::from(name),
+            body,
+            builtins,
+            head
+        })
+    }
+
+    pub fn get_name(&self) -> &str{
+        &self.name
+    }
+
+    pub fn get_body(&self) -> &Vec<TriplePattern>{
+        &self.body
+    }
+
+    /// Gets the builtins of the body
+    pub fn get_builtins(&self) -> &Vec<BuiltinCall>{
+        &self.builtins
+    }
+
+    pub fn get_head(&self) -> &Vec<TriplePattern>{
+        &self.head
+    }
+
+    /// Checks if the rule is a constraint, i.e. it has no head
+    pub fn is_constraint(&self) -> bool{
+        self.head.is_empty()
+    }
+
+    /// The inconsistencies reported by a constraint for `graph`
+    fn violations(&self, graph: &dyn Graph) -> Vec<Inconsistency>{
+        let mut solutions = Vec::new();
+        join(graph, self.body.iter().collect(), self.builtins.iter().collect(), Bindings::new(), &mut solutions);
+        let mut inconsistencies: Vec<Inconsistency> = Vec::new();
+        for bindings in solutions {
+            let mut statements: Vec<Statement> = self.body.iter().filter_map(|pattern| pattern.instantiate(&bindings)).collect();
+            statements.sort_by_key(|stmt| stmt.to_string());
+            statements.dedup();
+            //symmetric bodies match the same statements several times
+            if !inconsistencies.iter().any(|inconsistency| inconsistency.statements == statements) {
+                inconsistencies.push(Inconsistency{
+                    rule: self.name.clone(),
+                    statements
+                });
+            }
+        }
+        inconsistencies
+    }
+
+    /// The statements derived by a body solution which uses `fact` for at least one pattern
+    fn consequences<'a>(&'a self, graph: &'a dyn Graph, fact: &'a Statement) -> Vec<Statement>{
+        let mut solutions = Vec::new();
+        for (i, pattern) in self.body.iter().enumerate() {
+            if let Some(bindings) = pattern.unify(fact, &Bindings::new()) {
+                let rest: Vec<&TriplePattern> = self.body.iter().enumerate().filter(|(j, _)| *j != i).map(|(_, pattern)| pattern).collect();
+                join(graph, rest, self.builtins.iter().collect(), bindings, &mut solutions);
+            }
+        }
+        solutions.iter()
+            .flat_map(|bindings| self.head.iter().filter_map(move |pattern| pattern.instantiate(bindings)))
+            .collect()
+    }
+
+    /// Checks if the rule derives `stmt` in one step from `graph`
+    fn derives<'a>(&'a self, graph: &'a dyn Graph, stmt: &'a Statement) -> bool{
+        self.head.iter().any(|pattern| match pattern.unify(stmt, &Bindings::new()) {
+            Some(bindings) => {
+                let mut solutions = Vec::new();
+                join(graph, self.body.iter().collect(), self.builtins.iter().collect(), bindings, &mut solutions);
+                !solutions.is_empty()
+            }
+            None => false
+        })
+    }
+}
+
+///
+/// A solution of the body of a constraint, which shows that a graph is inconsistent
+///
+/// # Example
+///
+/// ```
+/// use rdf4rust::reasoning::rules::{Reasoner, Rule, Term, TriplePattern};
+/// use rdf4rust::rdf::graph::{Graph, SimpleGraph, Statement, Node, ResourceNode};
+/// use rdf4rust::rdf::node_factory::iri;
+///
+/// let ex = |s: &str| iri(&format!("http://example.com/{}", s));
+/// let stmt = |s: &str, p: &str, o: &str| Statement::create(ResourceNode::IRINode { iri: ex(s) }, ex(p), Node::IRINode { iri: ex(o) });
+/// let irreflexive = Rule::new("irreflexive", vec![TriplePattern::new(Term::var("x"), Term::iri(ex("parent")), Term::var("x"))], vec![]).unwrap();
+/// assert_eq!("[irreflexive: (?x <http://example.com/parent> ?x) -> false]", irreflexive.to_string());
+///
+/// let mut graph = Reasoner::new(vec![irreflexive]).infer(Box::new(SimpleGraph::new()));
+/// graph.add_statement(stmt("alice", "parent", "bob"));
+/// assert!(graph.list_inconsistencies().is_empty());
+///
+/// graph.add_statement(stmt("bob", "parent", "bob"));
+/// let inconsistencies = graph.list_inconsistencies();
+/// assert_eq!("irreflexive", inconsistencies[0].get_rule());
+/// assert!(inconsistencies[0].get_statements() == &vec![stmt("bob", "parent", "bob")]);
+/// ```
+///
+#[derive(Clone, PartialEq, Eq, Hash)]
+pub struct Inconsistency{
+    rule: String,
+    statements: Vec<Statement>
+}
+
+impl Inconsistency{
+
+    ///
+    /// Creates an inconsistency, which was not found by a constraint, e.g. an ill-typed literal
+    ///
+    /// # Parameters
+    ///
+    /// * `rule` - The name of the violated rule
+    /// * `statements` - The statements which are inconsistent together
+    ///
+    pub fn new(rule: &str, statements: Vec<Statement>) -> Inconsistency{
+        Inconsistency{
+            rule: String::from(rule),
+            statements
+        }
+    }
+
+    /// Gets the name of the violated constraint
+    pub fn get_rule(&self) -> &str{
+        &self.rule
+    }
+
+    /// Gets the statements matched by the body of the constraint
+    pub fn get_statements(&self) -> &Vec<Statement>{
+        &self.statements
+    }
+}
+
+impl fmt::Display for Inconsistency{
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "Rule {} is violated by {}", self.rule, self.statements.iter().map(|stmt| stmt.to_string()).collect::<Vec<_>>().join(" "))
+    }
+}
+
+impl fmt::Debug for Inconsistency{
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}", self)
+    }
+}
+
+impl Error for Inconsistency{}
+
+impl From<Inconsistency> for RDFError{
+    fn from(inconsistency: Inconsistency) -> Self {
+        RDFError::new(ErrorKind::Inconsistent, inconsistency.to_string()).with_source(inconsistency)
+    }
+}
+
+/// The prefixes which can be used in rules without declaration
+const DEFAULT_PREFIXES: [(&str, &str); 4] = [
+    ("rdf", "http://www.w3.org/1999/02/22-rdf-syntax-ns#"),
+    ("rdfs", "http://www.w3.org/2000/01/rdf-schema#"),
+    ("owl", "http://www.w3.org/2002/07/owl#"),
+    ("xsd", "http://www.w3.org/2001/XMLSchema#")
+];
+
+///
+/// Parses rules written in a syntax similar to Jena rules, see the module documentation
+///
+/// # Parameters
+///
+/// * `rules` - The rules and prefix declarations
+///
+/// # Returns
+///
+/// The rules in the order they are written, rules without name are named by their position, e.g. `rule2`.
+/// On failure an error located in `rules`, of kind `ErrorKind::Syntax` for malformed rules, `ErrorKind::InvalidRule`
+/// for invalid rules (see `Rule::create`) or the kind of an invalid term, e.g. `ErrorKind::UnknownPrefix`
+///
+/// # Example
+///
+/// ```
+/// use rdf4rust::reasoning::rules::{parse_rules, Reasoner};
+/// use rdf4rust::rdf::graph::{Graph, SimpleGraph, Statement, Node, ResourceNode};
+/// use rdf4rust::rdf::node_factory::{Literal, iri, rdf};
+/// use rdf4rust::error::ErrorKind;
+///
+/// let rules = parse_rules(r#"
+///     @prefix ex: <http://example.com/> .
+///
+///     // people of age are adults
+///     [adult: (?p ex:age ?age) ge(?age 18) -> (?p rdf:type ex:Adult)]
+///     [nextYear: (?p ex:age ?age), sum(?age, 1, ?next) -> (?p ex:ageNextYear ?next)]
+///     [greeting: (?p ex:name ?name) strConcat("Hello " ?name ?greeting) -> (?p ex:greeting ?greeting)]
+///     [noMinors: (?p rdf:type ex:Adult) (?p ex:age ?age) lessThan(?age 18) -> false]
+/// "#).expect("Are valid rules");
+/// assert_eq!("[adult: (?p <http://example.com/age> ?age) ge(?age \"18\"^^<http://www.w3.org/2001/XMLSchema#integer>) -> \
+///     (?p <http://www.w3.org/1999/02/22-rdf-syntax-ns#type> <http://example.com/Adult>)]", rules[0].to_string());
+///
+/// let ex = |s: &str| iri(&format!("http://example.com/{}", s));
+/// let resource = |s: &str| ResourceNode::IRINode { iri: ex(s) };
+/// let mut graph = Reasoner::new(rules).infer(Box::new(SimpleGraph::new()));
+/// graph.add_statement(Statement::create(resource("alice"), ex("age"), Node::LiteralNode { literal: Literal::from(42) }));
+/// graph.add_statement(Statement::create(resource("alice"), ex("name"), Node::LiteralNode { literal: Literal::from("Alice") }));
+/// graph.add_statement(Statement::create(resource("bob"), ex("age"), Node::LiteralNode { literal: Literal::from(17) }));
+///
+/// let objects = |s: &str, p: &str| graph.list_statements(Some(resource(s)), Some(ex(p)), None).iter()
+///     .map(|stmt| stmt.get_object().as_string()).collect::<Vec<_>>();
+/// assert_eq!(vec!["\"43\"^^<http://www.w3.org/2001/XMLSchema#integer>"], objects("alice", "ageNextYear"));
+/// assert_eq!(vec!["\"Hello Alice\""], objects("alice", "greeting"));
+/// assert_eq!(1, graph.list_statements(None, None, Some(Node::IRINode { iri: ex("Adult") })).len());
+/// assert!(graph.list_inconsistencies().is_empty());
+///
+/// graph.add_statement(Statement::create(resource("bob"), rdf("type"), Node::IRINode { iri: ex("Adult") }));
+/// assert_eq!("noMinors", graph.list_inconsistencies()[0].get_rule());
+///
+/// let err = parse_rules("[broken: (?a ex:p ?b) -> (?a ex:q ?b)]").err().expect("ex is not declared");
+/// assert_eq!(ErrorKind::UnknownPrefix, err.get_kind());
+/// assert_eq!(Some(14), err.get_column());
+/// ```
+///
+pub fn parse_rules(rules: &str) -> Result<Vec<Rule>, RDFError>{
+    RuleParser{
+        text: rules,
+        pos: 0,
+        prefixes: DEFAULT_PREFIXES.iter().map(|(prefix, namespace)| (String::from(*prefix), String::from(*namespace))).collect()
+    }.rules()
+}
+
+/// A recursive descent parser of the rule syntax
+struct RuleParser<'a>{
+    text: &'a str,
+    pos: usize,
+    prefixes: HashMap<String, String>
+}
+
+impl<'a> RuleParser<'a>{
+
+    fn rules(&mut self) -> Result<Vec<Rule>, RDFError>{
+        let mut rules = Vec::new();
+        while let Some(c) = self.peek() {
+            if c == '[' {
+                let rule = self.rule(rules.len() + 1)?;
+                rules.push(rule);
+            } else if self.text[self.pos..].starts_with("@prefix") {
+                self.pos += "@prefix".len();
+                self.prefix()?;
+            } else {
+                return Err(self.unexpected("'[' or '@prefix'"))
+            }
+        }
+        Ok(rules)
+    }
+
+    /// Parses a prefix declaration after `@prefix`, e.g. `ex: <http://example.com/> .`
+    fn prefix(&mut self) -> Result<(), RDFError>{
+        self.skip();
+        let pos = self.pos;
+        let prefix = match self.token().strip_suffix(':') {
+            Some(prefix) if !prefix.contains(':') => prefix,
+            _ => return Err(self.error(ErrorKind::Syntax, String::from("Expected a prefix name followed by ':'."), pos))
+        };
+        self.skip();
+        let pos = self.pos;
+        let namespace = match self.token().strip_prefix('<').and_then(|iri| iri.strip_suffix('>')) {
+            Some(namespace) => namespace,
+            None => return Err(self.error(ErrorKind::Syntax, format!("Expected the IRI of prefix {}.", prefix), pos))
+        };
+        if let Err(err) = IRI::create_iri(&String::from(namespace)) {
+            return Err(self.locate(err, pos))
+        }
+        self.prefixes.insert(String::from(prefix), String::from(namespace));
+        if self.peek() == Some('.') {
+            self.pos += 1;
+        }
+        Ok(())
+    }
+
+    /// Parses `[name: body -> head]`, `index` is the position of the rule in the document
+    fn rule(&mut self, index: usize) -> Result<Rule, RDFError>{
+        let start = self.pos;
+        self.expect('[')?;
+        self.skip();
+        let pos = self.pos;
+        let name = match self.token().strip_suffix(':') {
+            Some(name) if !name.is_empty() && !name.contains(':') => String::from(name),
+            _ => {
+                self.pos = pos;
+                format!("rule{}", index)
+            }
+        };
+        let mut body = Vec::new();
+        let mut builtins = Vec::new();
+        loop {
+            match self.peek() {
+                Some('(') => body.push(self.pattern()?),
+                Some(_) => {
+                    let pos = self.pos;
+                    match self.token() {
+                        "->" => break,
+                        "" => return Err(self.unexpected("a triple pattern, a builtin or '->'")),
+                        name => builtins.push(self.builtin(name, pos)?)
+                    }
+                }
+                None => return Err(self.unexpected("'->'"))
+            }
+        }
+        let mut head = Vec::new();
+        self.skip();
+        let pos = self.pos;
+        if self.token() != "false" {
+            self.pos = pos;
+            while self.peek() == Some('(') {
+                head.push(self.pattern()?);
+            }
+            if head.is_empty() {
+                return Err(self.unexpected("a triple pattern or 'false'"))
+            }
+        }
+        self.expect(']')?;
+        Rule::create(&name, body, builtins, head).map_err(|err| self.locate(err, start))
+    }
+
+    /// Parses `(subject predicate object)`
+    fn pattern(&mut self) -> Result<TriplePattern, RDFError>{
+        self.expect('(')?;
+        let pattern = TriplePattern::new(self.term()?, self.term()?, self.term()?);
+        self.expect(')')?;
+        Ok(pattern)
+    }
+
+    /// Parses the arguments of the builtin `name` starting at `pos`
+    fn builtin(&mut self, name: &str, pos: usize) -> Result<BuiltinCall, RDFError>{
+        let builtin = match Builtin::from_name(name) {
+            Some(builtin) => builtin,
+            None => return Err(self.error(ErrorKind::Syntax, format!("{} is no builtin.", name), pos))
+        };
+        self.expect('(')?;
+        let mut arguments = Vec::new();
+        while self.peek() != Some(')') {
+            arguments.push(self.term()?);
+        }
+        self.pos += 1;
+        BuiltinCall::new(builtin, arguments).map_err(|err| self.locate(err, pos))
+    }
+
+    /// Parses a variable like `?x` or a node in Turtle syntax
+    fn term(&mut self) -> Result<Term, RDFError>{
+        self.skip();
+        let pos = self.pos;
+        let rest = &self.text[pos..];
+        if rest.starts_with(['"', '\'']) && closing_quote(rest).is_none() {
+            return Err(self.error(ErrorKind::Syntax, String::from("Literal is not closed."), pos))
+        }
+        let token = self.token();
+        if token.is_empty() {
+            return Err(self.unexpected("a term"))
+        }
+        if let Some(name) = token.strip_prefix('?') {
+            if name.is_empty() || !name.chars().all(|c| c.is_alphanumeric() || c == '_') {
+                return Err(self.error(ErrorKind::Syntax, format!("{} is not a valid variable.", token), pos))
+            }
+            return Ok(Term::var(name))
+        }
+        parse_object(token, &self.prefixes, false).map(Term::Constant).map_err(|err| self.locate(err, pos))
+    }
+
+    /// Reads the next token, e.g. `?x`, `<http://example.com/a>`, `"chat"@fr`, `->` or a builtin name
+    fn token(&mut self) -> &'a str{
+        let rest = &self.text[self.pos..];
+        let len = if rest.starts_with('<') {
+            rest.find('>').map_or(rest.len(), |end| end + 1)
+        } else if rest.starts_with(['"', '\'']) {
+            literal_length(rest)
+        } else {
+            word_length(rest)
+        };
+        self.pos += len;
+        &rest[..len]
+    }
+
+    /// Skips whitespace, commas and comments
+    fn skip(&mut self){
+        loop {
+            let rest = &self.text[self.pos..];
+            let trimmed = rest.trim_start_matches(|c: char| c.is_whitespace() || c == ',');
+            self.pos += rest.len() - trimmed.len();
+            if !(trimmed.starts_with('#') || trimmed.starts_with("//")) {
+                return
+            }
+            self.pos += trimmed.find('\n').unwrap_or(trimmed.len());
+        }
+    }
+
+    fn peek(&mut self) -> Option<char>{
+        self.skip();
+        self.text[self.pos..].chars().next()
+    }
+
+    fn expect(&mut self, expected: char) -> Result<(), RDFError>{
+        if self.peek() != Some(expected) {
+            return Err(self.unexpected(&format!("'{}'", expected)))
+        }
+        self.pos += expected.len_utf8();
+        Ok(())
+    }
+
+    fn unexpected(&mut self, expected: &str) -> RDFError{
+        self.skip();
+        let found = match self.text[self.pos..].split_whitespace().next() {
+            Some(found) => format!("'{}'", found),
+            None => String::from("the end of the rules")
+        };
+        self.error(ErrorKind::Syntax, format!("Expected {}, but found {}.", expected, found), self.pos)
+    }
+
+    fn error(&self, kind: ErrorKind, msg: String, pos: usize) -> RDFError{
+        self.locate(RDFError::new(kind, msg), pos)
+    }
+
+    /// Locates `err` at the byte position `pos` of the text
+    fn locate(&self, err: RDFError, pos: usize) -> RDFError{
+        let line_start = self.text[..pos].rfind('\n').map_or(0, |i| i + 1);
+        let line_end = self.text[pos..].find('\n').map_or(self.text.len(), |i| pos + i);
+        let line_no = self.text[..pos].matches('\n').count() + 1;
+        err.with_position(line_no, &self.text[line_start..line_end], pos - line_start).offset_by(line_start)
+    }
+}
+
+/// Collects all extensions of `bindings` which match all `patterns` in `graph` and satisfy all `builtins`
+fn join<'a>(graph: &'a dyn Graph, mut patterns: Vec<&'a TriplePattern>, mut builtins: Vec<&'a BuiltinCall>, mut bindings: Bindings<'a>, solutions: &mut Vec<Bindings<'a>>){
+    //builtins are evaluated as early as possible to prune the join
+    while let Some(i) = builtins.iter().position(|builtin| builtin.get_inputs().iter().all(|term| term.resolve(&bindings).is_some())) {
+        if !evaluate(builtins.swap_remove(i), &mut bindings) {
+            return
+        }
+    }
+    let next = match (0..patterns.len()).max_by_key(|&i| patterns[i].selectivity(&bindings)) {
+        Some(next) => patterns.swap_remove(next),
+        None => {
+            solutions.push(bindings);
+            return
+        }
+    };
+    for stmt in next.candidates(graph, &bindings) {
+        if let Some(extended) = next.unify(stmt, &bindings) {
+            join(graph, patterns.clone(), builtins.clone(), extended, solutions);
+        }
+    }
+}
+
+/// Evaluates a builtin whose inputs are bound, binding the result of a function if it is unbound
+fn evaluate<'a>(builtin: &'a BuiltinCall, bindings: &mut Bindings<'a>) -> bool{
+    let inputs: Vec<Node> = builtin.get_inputs().iter()
+        .map(|term| term.resolve(bindings).expect("Inputs are bound").into_node())
+        .collect();
+    match builtin.get_result() {
+        Some(result) => match (builtin.compute(&inputs), result.resolve(bindings)) {
+            (Some(literal), Some(bound)) => builtins::equal(&Node::LiteralNode { literal }, &bound.into_node()),
+            (Some(literal), None) => result.unify(Value::Literal(Rc::new(literal)), bindings),
+            (None, _) => false
+        },
+        None => builtin.test(&inputs)
+    }
+}
+
+/// The statements of `closure` which are not contained in `source`
+pub(crate) fn derived_statements(source: &dyn Graph, closure: &IndexedGraph) -> Vec<Statement>{
+    let asserted: HashSet<&Statement> = source.list_statements(None, None, None).into_iter().collect();
+    closure.list_all_statements().iter().filter(|stmt| !asserted.contains(stmt)).cloned().collect()
+}
+
+/// Merges the graphs of `database` matching `pattern` into a single graph
+pub(crate) fn merge_graphs(database: &Database, pattern: &GraphPattern) -> IndexedGraph{
+    let mut graph = IndexedGraph::new();
+    for quad in database.quads_for_pattern(None, None, None, pattern) {
+        graph.add_statement(quad.into_statement().1);
+    }
+    graph
+}
+
+pub(crate) fn add_to_database(database: &mut Database, stmts: &[Statement], graph: Option<&ResourceNode>){
+    for stmt in stmts {
+        database.add_quad(Quad::from_statement(stmt.clone(), graph.cloned()));
+    }
+}
+
+pub(crate) fn contains(graph: &dyn Graph, stmt: &Statement) -> bool{
+    !graph.list_statements(Some(stmt.get_subject().clone()), Some(stmt.get_predicate().clone()), Some(stmt.get_object().clone())).is_empty()
+}
+
+/// Adds everything derivable from the statements of `queue` to `closure`, which already contains them
+fn saturate(closure: &mut IndexedGraph, rules: &[Rule], mut queue: Vec<Statement>){
+    while let Some(fact) = queue.pop() {
+        for rule in rules.iter().filter(|rule| !rule.is_constraint()) {
+            for stmt in rule.consequences(closure, &fact) {
+                if !contains(closure, &stmt) {
+                    closure.add_statement(stmt.clone());
+                    queue.push(stmt);
+                }
+            }
+        }
+    }
+}
+
+///
+/// Computes the closure of a graph under a set of rules and axioms
+///
+/// Axioms are statements which hold unconditionally, e.g. the RDFS axiomatic triples.
+///
+/// # Example
+///
+/// ```
+/// use rdf4rust::reasoning::rules::{Reasoner, Rule, Term, TriplePattern};
+/// use rdf4rust::rdf::graph::{Graph, SimpleGraph, Statement, Node, ResourceNode};
+/// use rdf4rust::rdf::node_factory::iri;
+///
+/// let ex = |s: &str| iri(&format!("http://example.com/{}", s));
+/// let stmt = |s: &str, p: &str, o: &str| Statement::create(ResourceNode::IRINode { iri: ex(s) }, ex(p), Node::IRINode { iri: ex(o) });
+/// let ancestor = |s: &str, o: &str| TriplePattern::new(Term::var(s), Term::iri(ex("ancestor")), Term::var(o));
+/// let reasoner = Reasoner::new(vec![
+///     Rule::new("parent", vec![TriplePattern::new(Term::var("x"), Term::iri(ex("parent")), Term::var("y"))], vec![ancestor("x", "y")]).unwrap(),
+///     Rule::new("transitive", vec![ancestor("x", "y"), ancestor("y", "z")], vec![ancestor("x", "z")]).unwrap()]);
+///
+/// let mut source = SimpleGraph::new();
+/// source.add_statement(stmt("carol", "parent", "bob"));
+/// source.add_statement(stmt("bob", "parent", "alice"));
+///
+/// let mut target = SimpleGraph::new();
+/// assert_eq!(3, reasoner.materialize(&source, &mut target));
+/// assert_eq!(1, target.list_statements(None, None, Some(Node::IRINode { iri: ex("alice") }))
+///     .iter().filter(|stmt| stmt.get_subject() == &ResourceNode::IRINode { iri: ex("carol") }).count());
+/// ```
+///
+#[derive(Clone)]
+pub struct Reasoner{
+    rules: Vec<Rule>,
+    axioms: Vec<Statement>
+}
+
+impl Reasoner{
+
+    pub fn new(rules: Vec<Rule>) -> Reasoner{
+        Reasoner{
+            rules,
+            axioms: Vec::new()
+        }
+    }
+
+    /// Sets the statements which hold in every graph, none by default
+    pub fn with_axioms(mut self, axioms: Vec<Statement>) -> Self{
+        self.axioms = axioms;
+        self
+    }
+
+    pub fn get_rules(&self) -> &Vec<Rule>{
+        &self.rules
+    }
+
+    pub fn get_axioms(&self) -> &Vec<Statement>{
+        &self.axioms
+    }
+
+    ///
+    /// Computes all statements entailed by `source` which are not contained in it, including the axioms
+    ///
+    pub fn derive(&self, source: &dyn Graph) -> Vec<Statement>{
+        derived_statements(source, &self.closure(source))
+    }
+
+    /// Computes the statements of `source`, the axioms and everything they entail
+    pub(crate) fn closure(&self, source: &dyn Graph) -> IndexedGraph{
+        let mut closure = IndexedGraph::new();
+        let seeds: Vec<Statement> = source.list_statements(None, None, None).into_iter().cloned()
+            .chain(self.axioms.iter().cloned())
+            .collect();
+        closure.add_all_statements(&seeds);
+        saturate(&mut closure, &self.rules, seeds);
+        closure
+    }
+
+    ///
+    /// Adds all statements entailed by `source` which are not contained in it to `target`
+    ///
+    /// `target` may be a separate graph or another view of the same data, e.g. a named graph for inferences.
+    ///
+    /// # Returns
+    ///
+    /// The number of derived statements
+    ///
+    pub fn materialize(&self, source: &dyn Graph, target: &mut dyn Graph) -> usize{
+        let derived = self.derive(source);
+        target.add_all_statements(&derived);
+        derived.len()
+    }
+
+    ///
+    /// Adds all statements entailed by the graphs of `database` matching `source` to the graph `target`
+    ///
+    /// # Parameters
+    ///
+    /// * `database` - The dataset to reason over
+    /// * `source` - The graphs whose merge is the premise, e.g. `GraphPattern::AnyGraph` for all graphs
+    /// * `target` - The name of the graph receiving the derived statements or None for the default graph
+    ///
+    /// # Returns
+    ///
+    /// The number of derived statements
+    ///
+    pub fn materialize_database(&self, database: &mut Database, source: &GraphPattern, target: Option<&ResourceNode>) -> usize{
+        let derived = self.derive(&merge_graphs(database, source));
+        add_to_database(database, &derived, target);
+        derived.len()
+    }
+
+    ///
+    /// Evaluates the constraints against `graph`, which should contain all statements entailed by the rules, e.g. an `InferenceGraph`
+    ///
+    /// # Returns
+    ///
+    /// The inconsistencies, empty if `graph` is consistent
+    ///
+    pub fn check(&self, graph: &dyn Graph) -> Vec<Inconsistency>{
+        self.rules.iter().filter(|rule| rule.is_constraint()).flat_map(|rule| rule.violations(graph)).collect()
+    }
+
+    ///
+    /// Creates a virtual graph answering queries with the statements of `base` and all statements entailed by them
+    ///
+    pub fn infer(&self, base: Box<dyn Graph>) -> InferenceGraph{
+        InferenceGraph::new(base, self.clone())
+    }
+}
+
+///
+/// A graph containing the statements of a base graph and everything they entail
+///
+/// Statements added to or removed from the inference graph are added to resp. removed from the base graph,
+/// and the derived statements are updated incrementally:
+///
+/// * Added statements are joined with the closure and whatever they entail is added.
+/// * For removed statements everything derived from them is deleted first.
+///   Deleted statements with another derivation from the remaining closure are rederived afterwards.
+///
+/// Derived statements can't be removed, since they still follow from the base graph.
+///
+/// # Example
+///
+/// ```
+/// use rdf4rust::reasoning::rules::{Reasoner, Rule, Term, TriplePattern};
+/// use rdf4rust::rdf::graph::{Graph, SimpleGraph, Statement, Node, ResourceNode};
+/// use rdf4rust::rdf::node_factory::iri;
+///
+/// let ex = |s: &str| iri(&format!("http://example.com/{}", s));
+/// let stmt = |s: &str, p: &str, o: &str| Statement::create(ResourceNode::IRINode { iri: ex(s) }, ex(p), Node::IRINode { iri: ex(o) });
+/// let knows = |s: &str, o: &str| TriplePattern::new(Term::var(s), Term::iri(ex("knows")), Term::var(o));
+/// let symmetric = Rule::new("symmetric", vec![knows("x", "y")], vec![knows("y", "x")]).unwrap();
+///
+/// let mut graph = Reasoner::new(vec![symmetric]).infer(Box::new(SimpleGraph::new()));
+/// graph.add_statement(stmt("alice", "knows", "bob"));
+/// assert_eq!(2, graph.count());
+/// assert!(graph.is_derived(&stmt("bob", "knows", "alice")));
+///
+/// // asserting a derived statement keeps it when its derivation is removed
+/// graph.add_statement(stmt("bob", "knows", "alice"));
+/// graph.remove_statement(stmt("alice", "knows", "bob"));
+/// assert_eq!(2, graph.count());
+/// assert_eq!(1, graph.get_base_graph().count());
+///
+/// graph.remove_statement(stmt("bob", "knows", "alice"));
+/// assert_eq!(0, graph.count());
+/// ```
+///
+pub struct InferenceGraph{
+    base: Box<dyn Graph>,
+    closure: IndexedGraph,
+    reasoner: Reasoner,
+    axioms: HashSet<Statement>
+}
+
+impl InferenceGraph{
+
+    ///
+    /// Creates the inference graph of `base` and computes its closure
+    ///
+    pub fn new(base: Box<dyn Graph>, reasoner: Reasoner) -> InferenceGraph{
+        let mut closure = IndexedGraph::new();
+        let seeds: Vec<Statement> = base.list_statements(None, None, None).into_iter().cloned()
+            .chain(reasoner.axioms.iter().cloned())
+            .collect();
+        closure.add_all_statements(&seeds);
+        saturate(&mut closure, &reasoner.rules, seeds);
+        InferenceGraph{
+            base,
+            closure,
+            axioms: reasoner.axioms.iter().cloned().collect(),
+            reasoner
+        }
+    }
+
+    /// Gets the graph with the asserted statements
+    pub fn get_base_graph(&self) -> &dyn Graph{
+        self.base.as_ref()
+    }
+
+    pub fn get_reasoner(&self) -> &Reasoner{
+        &self.reasoner
+    }
+
+    /// Lists the statements which are entailed, but neither asserted in the base graph nor axioms
+    pub fn list_derived_statements(&self) -> Vec<&Statement>{
+        self.closure.list_all_statements().iter().filter(|stmt| self.is_derived(stmt)).collect()
+    }
+
+    /// Checks if `stmt` is entailed, but neither asserted in the base graph nor an axiom
+    pub fn is_derived(&self, stmt: &Statement) -> bool{
+        contains(&self.closure, stmt) && !self.is_asserted(stmt)
+    }
+
+    /// Evaluates the constraints of the reasoner against the closure, see `Reasoner::check`
+    pub fn list_inconsistencies(&self) -> Vec<Inconsistency>{
+        self.reasoner.check(&self.closure)
+    }
+
+    fn is_asserted(&self, stmt: &Statement) -> bool{
+        self.axioms.contains(stmt) || contains(self.base.as_ref(), stmt)
+    }
+
+    fn assert_all(&mut self, stmts: &[Statement]){
+        let mut queue = Vec::new();
+        for stmt in stmts {
+            self.base.add_statement(stmt.clone());
+            if !contains(&self.closure, stmt) {
+                self.closure.add_statement(stmt.clone());
+                queue.push(stmt.clone());
+            }
+        }
+        saturate(&mut self.closure, &self.reasoner.rules, queue);
+    }
+
+    fn retract(&mut self, stmt: Statement){
+        self.base.remove_statement(stmt.clone());
+        if self.is_asserted(&stmt) || !contains(&self.closure, &stmt) {
+            return
+        }
+        //delete everything derivable using the statement ...
+        let mut deleted = HashSet::new();
+        deleted.insert(stmt.clone());
+        let mut queue = vec![stmt];
+        while let Some(fact) = queue.pop() {
+            for rule in self.reasoner.rules.iter().filter(|rule| !rule.is_constraint()) {
+                for derived in rule.consequences(&self.closure, &fact) {
+                    if !deleted.contains(&derived) && contains(&self.closure, &derived) && !self.is_asserted(&derived) {
+                        deleted.insert(derived.clone());
+                        queue.push(derived);
+                    }
+                }
+            }
+        }
+        for stmt in &deleted {
+            self.closure.remove_statement(stmt.clone());
+        }
+        //... and rederive what follows from the rest
+        let rederived: Vec<Statement> = deleted.into_iter()
+            .filter(|stmt| self.reasoner.rules.iter().any(|rule| rule.derives(&self.closure, stmt)))
+            .collect();
+        self.closure.add_all_statements(&rederived);
+        saturate(&mut self.closure, &self.reasoner.rules, rederived);
+    }
+}
+
+impl Graph for InferenceGraph{
+    fn count(&self) -> usize {
+        self.closure.count()
+    }
+
+    fn list_all_statements(&self) -> &Vec<Statement> {
+        self.closure.list_all_statements()
+    }
+
+    fn add_statement(&mut self, stmt: Statement) {
+        self.assert_all(&[stmt]);
+    }
+
+    fn add_all_statements(&mut self, stmts: &[Statement]) {
+        self.assert_all(stmts);
+    }
+
+    fn remove_statement(&mut self, stmt: Statement) {
+        self.retract(stmt);
+    }
+
+    fn remove_all_statements(&mut self, stmts: &[Statement]) {
+        for stmt in stmts {
+            self.retract(stmt.clone());
+        }
+    }
+
+    fn list_statements_for_subject(&self, subject: ResourceNode) -> Vec<&Statement> {
+        self.closure.list_statements(Some(subject), None, None)
+    }
+
+    fn list_statements_for_object(&self, object: ResourceNode) -> Vec<&Statement> {
+        self.closure.list_statements(None, None, Some(Node::from(object)))
+    }
+
+    fn list_statements_for_predicate(&self, predicate: ResourceNode) -> Vec<&Statement> {
+        match predicate {
+            ResourceNode::IRINode { iri } => self.closure.list_statements(None, Some(iri), None),
+            _ => Vec::new()
+        }
+    }
+
+    fn list_statements(&self, subject: Option<ResourceNode>, predicate: Option<IRIResource>, object: Option<Node>) -> Vec<&Statement> {
+        self.closure.list_statements(subject, predicate, object)
+    }
+
+    fn get_name(&self) -> Option<String> {
+        None
+    }
+
+    fn load(&self) {
+        self.base.load()
+    }
+
+    fn store(&self) {
+        self.base.store()
+    }
+
+    fn print(&self) {
+        self.closure.print()
+    }
+}
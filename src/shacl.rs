@@ -0,0 +1,4 @@
+pub mod shapes;
+pub mod validation;
+pub mod sparql;
+pub mod rules;
@@ -0,0 +1,303 @@
+//!
+//! SHACL rules of the SHACL Advanced Features, see <https://www.w3.org/TR/shacl-af/#rules>.
+//!
+//! The values of `sh:rule` of a shape are `sh:TripleRule`s, whose `sh:subject`, `sh:predicate` and `sh:object` are
+//! node expressions, and `sh:SPARQLRule`s with a `CONSTRUCT` query in `sh:construct`. The rules of a shape are applied
+//! to its focus nodes in the order of `sh:order` if the focus node conforms to all `sh:condition` shapes.
+//! The inferred statements are added to the data graph, so rules see the inferences of the rules before them, and all
+//! rules are applied again until nothing new is inferred. Blank nodes of `CONSTRUCT` templates are the same for the
+//! same rule, focus node and solution, which keeps the iteration finite.
+//!
+//! Node expressions are `sh:this`, constant IRIs and literals and path expressions (`[ sh:path ... ]`) starting at
+//! the focus node.
+//!
+//! # Example
+//!
+//! ```
+//! use rdf4rust::shacl::shapes::ShapesGraph;
+//! use rdf4rust::shacl::rules::RuleEngine;
+//! use rdf4rust::io::reader::{QuadIterator, Lang};
+//! use rdf4rust::rdf::database::{Database, GraphPattern};
+//! use rdf4rust::rdf::graph::{GraphType, Node, ResourceNode};
+//! use rdf4rust::rdf::node_factory::iri;
+//!
+//! let input = r#"
+//! <http://example.com/RectangleShape> <http://www.w3.org/ns/shacl#targetClass> <http://example.com/Rectangle> <http://example.com/shapes> .
+//! <http://example.com/RectangleShape> <http://www.w3.org/ns/shacl#rule> _:square <http://example.com/shapes> .
+//! _:square <http://www.w3.org/1999/02/22-rdf-syntax-ns#type> <http://www.w3.org/ns/shacl#SPARQLRule> <http://example.com/shapes> .
+//! _:square <http://www.w3.org/ns/shacl#construct> "CONSTRUCT { $this a <http://example.com/Square> } WHERE { $this <http://example.com/width> ?w ; <http://example.com/height> ?h FILTER (?w = ?h) }" <http://example.com/shapes> .
+//! <http://example.com/SquareShape> <http://www.w3.org/ns/shacl#targetClass> <http://example.com/Square> <http://example.com/shapes> .
+//! <http://example.com/SquareShape> <http://www.w3.org/ns/shacl#rule> _:side <http://example.com/shapes> .
+//! _:side <http://www.w3.org/1999/02/22-rdf-syntax-ns#type> <http://www.w3.org/ns/shacl#TripleRule> <http://example.com/shapes> .
+//! _:side <http://www.w3.org/ns/shacl#subject> <http://www.w3.org/ns/shacl#this> <http://example.com/shapes> .
+//! _:side <http://www.w3.org/ns/shacl#predicate> <http://example.com/side> <http://example.com/shapes> .
+//! _:side <http://www.w3.org/ns/shacl#object> _:width <http://example.com/shapes> .
+//! _:width <http://www.w3.org/ns/shacl#path> <http://example.com/width> <http://example.com/shapes> .
+//! <http://example.com/a> <http://www.w3.org/1999/02/22-rdf-syntax-ns#type> <http://example.com/Rectangle> .
+//! <http://example.com/a> <http://example.com/width> "3"^^<http://www.w3.org/2001/XMLSchema#integer> .
+//! <http://example.com/a> <http://example.com/height> "3"^^<http://www.w3.org/2001/XMLSchema#integer> .
+//! "#;
+//! let mut database = Database::new(GraphType::IndexedGraph);
+//! for quad in QuadIterator::new(input.as_bytes(), &Lang::NQ) {
+//!     database.add_quad(quad.expect("Is valid N-Quads"));
+//! }
+//!
+//! let ex = |s: &str| iri(&format!("http://example.com/{}", s));
+//! let shapes = ShapesGraph::from_database(&database, &GraphPattern::NamedGraph { name: ResourceNode::IRINode { iri: ex("shapes") } })
+//!     .expect("Is a valid shapes graph");
+//! let inferred = ResourceNode::IRINode { iri: ex("inferred") };
+//! let count = RuleEngine::new(&shapes).materialize_database(&mut database, &GraphPattern::DefaultGraph, Some(&inferred));
+//! // ex:a a ex:Square and, as a square, ex:a ex:side 3
+//! assert_eq!(2, count);
+//! let sides = database.quads_for_pattern(None, Some(&ex("side")), None, &GraphPattern::NamedGraph { name: inferred });
+//! assert_eq!("3", sides[0].get_object().as_literal().expect("Is a literal").get_value());
+//! ```
+//!
+
+use crate::error::RDFError;
+use crate::rdf::database::{Database, GraphPattern};
+use crate::rdf::graph::{Graph, IndexedGraph, Node, ResourceNode, Statement};
+use crate::reasoning::rules::{add_to_database, contains, merge_graphs};
+use crate::shacl::shapes::{invalid, objects, sh, to_shape, Path, ShapesGraph};
+use crate::shacl::sparql::{is_deactivated, read_query, PRE_BOUND};
+use crate::shacl::validation::Validator;
+use crate::sparql::query::{Query, Solution};
+use std::cmp::Ordering;
+
+///
+/// A node expression of a triple rule, computing nodes from the focus node
+///
+#[derive(Clone)]
+pub enum NodeExpression{
+    /// `sh:this`, the focus node
+    This,
+    /// An IRI or literal
+    Constant(Node),
+    /// `[ sh:path ... ]`, the values of the path at the focus node
+    Path(Path)
+}
+
+impl NodeExpression{
+
+    /// Reads the node expression `node` of a rule of `shape`
+    fn parse(graph: &dyn Graph, shape: &ResourceNode, node: &Node) -> Result<NodeExpression, RDFError>{
+        match node {
+            Node::IRINode { iri } if iri == &sh("this") => Ok(NodeExpression::This),
+            Node::IRINode { .. } | Node::LiteralNode { .. } => Ok(NodeExpression::Constant(node.clone())),
+            Node::BNode { bnode } => match objects(graph, &ResourceNode::BNode { bnode: bnode.clone() }, "path").as_slice() {
+                [path] => Ok(NodeExpression::Path(Path::parse(graph, shape, path)?)),
+                _ => Err(invalid(shape, format!("The node expression {} is not supported.", node.as_string())))
+            },
+            _ => Err(invalid(shape, format!("{} is not a node expression.", node.as_string())))
+        }
+    }
+
+    /// Evaluates the expression for the focus node `focus`
+    pub fn evaluate(&self, data: &dyn Graph, focus: &Node) -> Vec<Node>{
+        match self {
+            NodeExpression::This => vec![focus.clone()],
+            NodeExpression::Constant(node) => vec![node.clone()],
+            NodeExpression::Path(path) => path.evaluate(data, focus)
+        }
+    }
+}
+
+///
+/// What a rule infers
+///
+#[derive(Clone)]
+pub enum RuleBody{
+    /// `sh:TripleRule`, a statement for each combination of the nodes of the expressions
+    Triple{subject: Box<NodeExpression>, predicate: Box<NodeExpression>, object: Box<NodeExpression>},
+    /// `sh:SPARQLRule`, the statements constructed by the query with `$this` pre-bound
+    Sparql(Query)
+}
+
+///
+/// A SHACL rule, a value of `sh:rule`
+///
+#[derive(Clone)]
+pub struct ShapeRule{
+    id: Node,
+    body: RuleBody,
+    conditions: Vec<ResourceNode>,
+    order: f64
+}
+
+impl ShapeRule{
+
+    /// Reads the rule `node` of `shape`, None if it is deactivated, the condition shapes are added to `queue`
+    pub(crate) fn read(graph: &dyn Graph, shape: &ResourceNode, node: &Node, queue: &mut Vec<ResourceNode>) -> Result<Option<ShapeRule>, RDFError>{
+        let resource = node.to_resource().ok_or_else(|| invalid(shape, format!("The value {} of sh:rule is not a node.", node.as_string())))?;
+        if is_deactivated(graph, shape, &resource)? {
+            return Ok(None)
+        }
+        let expression = |parameter: &str| match objects(graph, &resource, parameter).as_slice() {
+            [expression] => NodeExpression::parse(graph, shape, expression).map(Box::new),
+            _ => Err(invalid(shape, format!("The rule {} has not a single sh:{}.", node.as_string(), parameter)))
+        };
+        let body = match read_query(graph, shape, &resource, "construct", None, &PRE_BOUND)? {
+            Some(query) => RuleBody::Sparql(query),
+            None => RuleBody::Triple{ subject: expression("subject")?, predicate: expression("predicate")?, object: expression("object")? }
+        };
+        let mut conditions = Vec::new();
+        for condition in objects(graph, &resource, "condition") {
+            let condition = to_shape(shape, "condition", condition)?;
+            queue.push(condition.clone());
+            conditions.push(condition);
+        }
+        let order = match objects(graph, &resource, "order").as_slice() {
+            [] => 0.0,
+            [order] => order.as_literal().ok().and_then(|order| order.value().ok()).and_then(|order| order.as_f64())
+                .ok_or_else(|| invalid(shape, format!("The value {} of sh:order is not a number.", order.as_string())))?,
+            _ => return Err(invalid(shape, String::from("sh:order has several values.")))
+        };
+        Ok(Some(ShapeRule{ id: node.clone(), body, conditions, order }))
+    }
+
+    /// Gets the node of the rule in the shapes graph
+    pub fn get_id(&self) -> &Node{
+        &self.id
+    }
+
+    pub fn get_body(&self) -> &RuleBody{
+        &self.body
+    }
+
+    /// Gets the shapes of `sh:condition`, which the focus nodes have to conform to
+    pub fn get_conditions(&self) -> &Vec<ResourceNode>{
+        &self.conditions
+    }
+
+    /// Gets the value of `sh:order`, 0 by default
+    pub fn get_order(&self) -> f64{
+        self.order
+    }
+
+    /// The statements the rule infers for the focus node `focus`
+    fn apply(&self, data: &dyn Graph, focus: &Node) -> Vec<Statement>{
+        match &self.body {
+            RuleBody::Triple { subject, predicate, object } => {
+                let mut stmts = Vec::new();
+                let predicates: Vec<_> = predicate.evaluate(data, focus).into_iter()
+                    .filter_map(|predicate| match predicate {
+                        Node::IRINode { iri } => Some(iri),
+                        _ => None
+                    })
+                    .collect();
+                let objects = object.evaluate(data, focus);
+                for subject in subject.evaluate(data, focus).iter().filter_map(Node::to_resource) {
+                    for predicate in &predicates {
+                        for object in &objects {
+                            stmts.push(Statement::create(subject.clone(), predicate.clone(), object.clone()));
+                        }
+                    }
+                }
+                stmts
+            }
+            RuleBody::Sparql(query) => {
+                let mut bindings = Solution::new();
+                bindings.insert(String::from("this"), focus.clone());
+                query.construct_skolemized(data, &bindings, &format!("{} {}", self.id.as_string(), focus.as_string()))
+            }
+        }
+    }
+}
+
+///
+/// Applies the rules of the shapes of a shapes graph to data graphs
+///
+pub struct RuleEngine<'a>{
+    shapes: &'a ShapesGraph
+}
+
+impl<'a> RuleEngine<'a>{
+
+    pub fn new(shapes: &'a ShapesGraph) -> RuleEngine<'a>{
+        RuleEngine{ shapes }
+    }
+
+    pub fn get_shapes(&self) -> &ShapesGraph{
+        self.shapes
+    }
+
+    ///
+    /// Computes all statements inferred by the rules from `source` which are not contained in it
+    ///
+    /// # Returns
+    ///
+    /// The inferred statements in the order they were inferred
+    ///
+    pub fn derive(&self, source: &dyn Graph) -> Vec<Statement>{
+        let mut graph = IndexedGraph::new();
+        for stmt in source.list_statements(None, None, None) {
+            graph.add_statement(stmt.clone());
+        }
+        let validator = Validator::new(self.shapes);
+        let mut derived = Vec::new();
+        loop {
+            let count = derived.len();
+            for shape in self.shapes.get_shapes().iter().filter(|shape| !shape.is_deactivated() && !shape.get_rules().is_empty()) {
+                let mut rules: Vec<&ShapeRule> = shape.get_rules().iter().collect();
+                rules.sort_by(|a, b| a.order.partial_cmp(&b.order).unwrap_or(Ordering::Equal));
+                let mut focus_nodes: Vec<Node> = Vec::new();
+                for focus in shape.get_targets().iter().flat_map(|target| target.get_focus_nodes(&graph)) {
+                    if !focus_nodes.contains(&focus) {
+                        focus_nodes.push(focus);
+                    }
+                }
+                for rule in rules {
+                    for focus in &focus_nodes {
+                        if !rule.conditions.iter().all(|condition| validator.conforms(&graph, focus, condition)) {
+                            continue
+                        }
+                        for stmt in rule.apply(&graph, focus) {
+                            if !contains(&graph, &stmt) {
+                                graph.add_statement(stmt.clone());
+                                derived.push(stmt);
+                            }
+                        }
+                    }
+                }
+            }
+            if derived.len() == count {
+                return derived
+            }
+        }
+    }
+
+    ///
+    /// Adds all statements inferred from `source` which are not contained in it to `target`
+    ///
+    /// # Returns
+    ///
+    /// The number of inferred statements
+    ///
+    pub fn materialize(&self, source: &dyn Graph, target: &mut dyn Graph) -> usize{
+        let derived = self.derive(source);
+        let count = derived.len();
+        for stmt in derived {
+            target.add_statement(stmt);
+        }
+        count
+    }
+
+    ///
+    /// Adds all statements inferred from the graphs of `database` matching `source` to the graph `target`
+    ///
+    /// # Parameters
+    ///
+    /// * `database` - The dataset with the data graphs
+    /// * `source` - The graphs whose merge is the data graph, e.g. `GraphPattern::DefaultGraph`
+    /// * `target` - The name of the graph receiving the inferred statements or None for the default graph
+    ///
+    /// # Returns
+    ///
+    /// The number of inferred statements
+    ///
+    pub fn materialize_database(&self, database: &mut Database, source: &GraphPattern, target: Option<&ResourceNode>) -> usize{
+        let derived = self.derive(&merge_graphs(database, source));
+        add_to_database(database, &derived, target);
+        derived.len()
+    }
+}
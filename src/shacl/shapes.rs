@@ -0,0 +1,925 @@
+//!
+//! The shapes of a SHACL shapes graph, see <https://www.w3.org/TR/shacl/>.
+//!
+//! A `ShapesGraph` reads all shapes of a graph: the instances of `sh:NodeShape` and `sh:PropertyShape`,
+//! the subjects of targets and all shapes referenced by other shapes, e.g. with `sh:property` or `sh:node`.
+//! Shapes with `sh:path` are property shapes, all others are node shapes.
+//! The parameters of the SHACL Core constraint components are read into `Constraint`s,
+//! each value of a parameter like `sh:class` is a constraint on its own.
+//!
+
+use crate::error::{RDFError, ErrorKind};
+use crate::rdf::database::{Database, GraphPattern};
+use crate::rdf::graph::{read_list, Graph, Node, ResourceNode, Statement};
+use crate::rdf::node_factory::{iri, rdf, rdfs, BlankNode, IRIResource, Literal, RDFNode};
+use crate::reasoning::builtins;
+use crate::reasoning::rules::merge_graphs;
+use crate::shacl::rules::ShapeRule;
+use crate::shacl::sparql::{ComponentConstraint, ConstraintComponent, SparqlConstraint};
+use regex::Regex;
+use std::collections::{HashMap, HashSet};
+use std::convert::TryFrom;
+use std::fmt;
+
+/// Base URL for SHACL = http://www.w3.org/ns/shacl#
+pub fn sh_base() -> String {
+    String::from("http://www.w3.org/ns/shacl#")
+}
+
+pub(crate) fn sh(local_name: &str) -> IRIResource{
+    iri(&(sh_base() + local_name))
+}
+
+pub(crate) fn invalid(shape: &ResourceNode, msg: String) -> RDFError{
+    RDFError::new(ErrorKind::InvalidShape, format!("Shape {}: {}", shape.as_string(), msg))
+}
+
+pub(crate) fn objects<'a>(graph: &'a dyn Graph, subject: &ResourceNode, predicate: &str) -> Vec<&'a Node>{
+    graph.list_statements(Some(subject.clone()), Some(sh(predicate)), None).into_iter().map(|stmt| stmt.get_object()).collect()
+}
+
+/// Converts a node to the IRI a parameter requires
+pub(crate) fn to_iri(shape: &ResourceNode, parameter: &str, node: &Node) -> Result<IRIResource, RDFError>{
+    match node {
+        Node::IRINode { iri } => Ok(iri.clone()),
+        _ => Err(invalid(shape, format!("The value {} of sh:{} is not an IRI.", node.as_string(), parameter)))
+    }
+}
+
+/// Converts a node to the shape a parameter refers to
+pub(crate) fn to_shape(shape: &ResourceNode, parameter: &str, node: &Node) -> Result<ResourceNode, RDFError>{
+    node.to_resource().filter(|resource| !matches!(resource, ResourceNode::TripleNode { .. }))
+        .ok_or_else(|| invalid(shape, format!("The value {} of sh:{} is not a shape.", node.as_string(), parameter)))
+}
+
+/// Converts a node to the non-negative integer a parameter requires
+fn to_count(shape: &ResourceNode, parameter: &str, node: &Node) -> Result<usize, RDFError>{
+    node.as_literal().ok().and_then(Literal::as_integer).and_then(|count| usize::try_from(&count).ok())
+        .ok_or_else(|| invalid(shape, format!("The value {} of sh:{} is not a non-negative integer.", node.as_string(), parameter)))
+}
+
+/// Converts a node to the boolean a parameter requires
+pub(crate) fn to_bool(shape: &ResourceNode, parameter: &str, node: &Node) -> Result<bool, RDFError>{
+    node.as_literal().ok().and_then(|literal| bool::try_from(literal).ok())
+        .ok_or_else(|| invalid(shape, format!("The value {} of sh:{} is not a boolean.", node.as_string(), parameter)))
+}
+
+/// Reads the list a parameter requires
+pub(crate) fn to_list(graph: &dyn Graph, shape: &ResourceNode, parameter: &str, node: &Node) -> Result<Vec<Node>, RDFError>{
+    read_list(graph, node).ok_or_else(|| invalid(shape, format!("The value {} of sh:{} is not a well-formed list.", node.as_string(), parameter)))
+}
+
+/// The string of a literal or IRI, None for blank nodes and quoted triples
+pub(crate) fn string_value(node: &Node) -> Option<String>{
+    match node {
+        Node::LiteralNode { literal } => Some(literal.get_value().clone()),
+        Node::IRINode { iri } => Some(iri.get_value()),
+        _ => None
+    }
+}
+
+///
+/// A SHACL property path, leading from a focus node to its value nodes
+///
+/// Displays in the syntax of SPARQL property paths, e.g. `(<http://example.com/parent>)+`.
+///
+/// # Example
+///
+/// ```
+/// use rdf4rust::shacl::shapes::Path;
+/// use rdf4rust::rdf::graph::{Graph, SimpleGraph, Statement, Node, ResourceNode};
+/// use rdf4rust::rdf::node_factory::iri;
+///
+/// let ex = |s: &str| iri(&format!("http://example.com/{}", s));
+/// let node = |s: &str| Node::IRINode { iri: ex(s) };
+/// let mut graph = SimpleGraph::new();
+/// graph.add_statement(Statement::create(ResourceNode::IRINode { iri: ex("carol") }, ex("parent"), node("bob")));
+/// graph.add_statement(Statement::create(ResourceNode::IRINode { iri: ex("bob") }, ex("parent"), node("alice")));
+///
+/// let ancestors = Path::OneOrMore(Box::new(Path::Predicate(ex("parent"))));
+/// assert_eq!("(<http://example.com/parent>)+", ancestors.to_string());
+/// assert!(vec![node("bob"), node("alice")] == ancestors.evaluate(&graph, &node("carol")));
+///
+/// let descendants = Path::Inverse(Box::new(ancestors));
+/// assert!(vec![node("bob"), node("carol")] == descendants.evaluate(&graph, &node("alice")));
+/// ```
+///
+#[derive(Clone, PartialEq, Eq, Hash)]
+pub enum Path{
+    /// An IRI, the objects of the predicate
+    Predicate(IRIResource),
+    /// `sh:inversePath`, the subjects of the path
+    Inverse(Box<Path>),
+    /// A list of paths followed one after the other
+    Sequence(Vec<Path>),
+    /// `sh:alternativePath`, the values of any of the paths
+    Alternative(Vec<Path>),
+    /// `sh:zeroOrMorePath`
+    ZeroOrMore(Box<Path>),
+    /// `sh:oneOrMorePath`
+    OneOrMore(Box<Path>),
+    /// `sh:zeroOrOnePath`
+    ZeroOrOne(Box<Path>)
+}
+
+impl fmt::Display for Path{
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let join = |paths: &Vec<Path>, separator: &str| paths.iter().map(|path| path.to_string()).collect::<Vec<_>>().join(separator);
+        match self {
+            Path::Predicate(iri) => write!(f, "{}", iri.as_string(true)),
+            Path::Inverse(path) => write!(f, "^{}", path),
+            Path::Sequence(paths) => write!(f, "({})", join(paths, "/")),
+            Path::Alternative(paths) => write!(f, "({})", join(paths, "|")),
+            Path::ZeroOrMore(path) => write!(f, "({})*", path),
+            Path::OneOrMore(path) => write!(f, "({})+", path),
+            Path::ZeroOrOne(path) => write!(f, "({})?", path)
+        }
+    }
+}
+
+impl Path{
+
+    ///
+    /// Reads the path `node` of `shape` from a shapes graph
+    ///
+    /// # Returns
+    ///
+    /// The path or an error of kind `ErrorKind::InvalidShape` if `node` is no well-formed SHACL property path
+    ///
+    pub fn parse(graph: &dyn Graph, shape: &ResourceNode, node: &Node) -> Result<Path, RDFError>{
+        let ill_formed = || invalid(shape, format!("{} is not a well-formed property path.", node.as_string()));
+        let resource = match node {
+            Node::IRINode { iri } => return Ok(Path::Predicate(iri.clone())),
+            Node::BNode { bnode } => ResourceNode::BNode { bnode: bnode.clone() },
+            _ => return Err(ill_formed())
+        };
+        if !graph.list_statements(Some(resource.clone()), Some(rdf("first")), None).is_empty() {
+            let paths = read_list(graph, node).ok_or_else(ill_formed)?;
+            if paths.len() < 2 {
+                return Err(ill_formed())
+            }
+            return Ok(Path::Sequence(paths.iter().map(|path| Path::parse(graph, shape, path)).collect::<Result<_, _>>()?))
+        }
+        let nested: Vec<(&str, &Node)> = ["inversePath", "alternativePath", "zeroOrMorePath", "oneOrMorePath", "zeroOrOnePath"].iter()
+            .flat_map(|parameter| objects(graph, &resource, parameter).into_iter().map(move |path| (*parameter, path)))
+            .collect();
+        let (parameter, path) = match nested.as_slice() {
+            [nested] => *nested,
+            _ => return Err(ill_formed())
+        };
+        if parameter == "alternativePath" {
+            let paths = read_list(graph, path).ok_or_else(ill_formed)?;
+            if paths.len() < 2 {
+                return Err(ill_formed())
+            }
+            return Ok(Path::Alternative(paths.iter().map(|path| Path::parse(graph, shape, path)).collect::<Result<_, _>>()?))
+        }
+        let path = Box::new(Path::parse(graph, shape, path)?);
+        Ok(match parameter {
+            "inversePath" => Path::Inverse(path),
+            "zeroOrMorePath" => Path::ZeroOrMore(path),
+            "oneOrMorePath" => Path::OneOrMore(path),
+            _ => Path::ZeroOrOne(path)
+        })
+    }
+
+    ///
+    /// Gets the nodes reachable from `focus` in `graph`, each node once
+    ///
+    pub fn evaluate(&self, graph: &dyn Graph, focus: &Node) -> Vec<Node>{
+        self.step(graph, std::slice::from_ref(focus))
+    }
+
+    /// The nodes reachable from any of `nodes`
+    fn step(&self, graph: &dyn Graph, nodes: &[Node]) -> Vec<Node>{
+        let mut reached = Reached::default();
+        match self {
+            Path::Predicate(predicate) => {
+                for subject in nodes.iter().filter_map(Node::to_resource) {
+                    for stmt in graph.list_statements(Some(subject), Some(predicate.clone()), None) {
+                        reached.add(stmt.get_object().clone());
+                    }
+                }
+            }
+            Path::Inverse(path) => match path.as_ref() {
+                Path::Predicate(predicate) => {
+                    for object in nodes {
+                        for stmt in graph.list_statements(None, Some(predicate.clone()), Some(object.clone())) {
+                            reached.add(Node::from(stmt.get_subject().clone()));
+                        }
+                    }
+                }
+                path => return path.inverse().step(graph, nodes)
+            },
+            Path::Sequence(paths) => return paths.iter().fold(nodes.to_vec(), |nodes, path| path.step(graph, &nodes)),
+            Path::Alternative(paths) => {
+                for path in paths {
+                    path.step(graph, nodes).into_iter().for_each(|node| {reached.add(node);});
+                }
+            }
+            Path::ZeroOrMore(path) | Path::OneOrMore(path) => {
+                if let Path::ZeroOrMore(_) = self {
+                    nodes.iter().for_each(|node| {reached.add(node.clone());});
+                }
+                let mut frontier = path.step(graph, nodes);
+                while !frontier.is_empty() {
+                    let new: Vec<Node> = frontier.into_iter().filter(|node| reached.add(node.clone())).collect();
+                    frontier = path.step(graph, &new);
+                }
+            }
+            Path::ZeroOrOne(path) => {
+                nodes.iter().for_each(|node| {reached.add(node.clone());});
+                path.step(graph, nodes).into_iter().for_each(|node| {reached.add(node);});
+            }
+        }
+        reached.nodes
+    }
+
+    /// The path leading backwards, inverses are pushed down to the predicates
+    fn inverse(&self) -> Path{
+        match self {
+            Path::Predicate(_) => Path::Inverse(Box::new(self.clone())),
+            Path::Inverse(path) => path.as_ref().clone(),
+            Path::Sequence(paths) => Path::Sequence(paths.iter().rev().map(Path::inverse).collect()),
+            Path::Alternative(paths) => Path::Alternative(paths.iter().map(Path::inverse).collect()),
+            Path::ZeroOrMore(path) => Path::ZeroOrMore(Box::new(path.inverse())),
+            Path::OneOrMore(path) => Path::OneOrMore(Box::new(path.inverse())),
+            Path::ZeroOrOne(path) => Path::ZeroOrOne(Box::new(path.inverse()))
+        }
+    }
+
+    ///
+    /// Writes the path in its RDF form, using new blank nodes for all but predicate paths
+    ///
+    /// # Returns
+    ///
+    /// The node of the path, the statements describing it are added to `stmts`
+    ///
+    pub fn to_rdf(&self, stmts: &mut Vec<Statement>) -> Node{
+        let nested = |parameter: &str, path: &Path, stmts: &mut Vec<Statement>| {
+            let node = ResourceNode::BNode { bnode: BlankNode::generate_blank_node() };
+            let object = path.to_rdf(stmts);
+            stmts.push(Statement::create(node.clone(), sh(parameter), object));
+            Node::from(node)
+        };
+        match self {
+            Path::Predicate(iri) => Node::IRINode { iri: iri.clone() },
+            Path::Inverse(path) => nested("inversePath", path, stmts),
+            Path::ZeroOrMore(path) => nested("zeroOrMorePath", path, stmts),
+            Path::OneOrMore(path) => nested("oneOrMorePath", path, stmts),
+            Path::ZeroOrOne(path) => nested("zeroOrOnePath", path, stmts),
+            Path::Sequence(paths) => {
+                let items = paths.iter().map(|path| path.to_rdf(stmts)).collect();
+                write_list(items, stmts)
+            }
+            Path::Alternative(paths) => {
+                let node = ResourceNode::BNode { bnode: BlankNode::generate_blank_node() };
+                let items = paths.iter().map(|path| path.to_rdf(stmts)).collect();
+                let list = write_list(items, stmts);
+                stmts.push(Statement::create(node.clone(), sh("alternativePath"), list));
+                Node::from(node)
+            }
+        }
+    }
+}
+
+/// Nodes in the order they are reached, each node once
+#[derive(Default)]
+struct Reached{
+    nodes: Vec<Node>,
+    seen: HashSet<Node>
+}
+
+impl Reached{
+
+    /// Adds `node`, false if it was already reached
+    fn add(&mut self, node: Node) -> bool{
+        if !self.seen.insert(node.clone()) {
+            return false
+        }
+        self.nodes.push(node);
+        true
+    }
+}
+
+/// Writes `items` as RDF list with new blank nodes, returns the head of the list
+pub(crate) fn write_list(items: Vec<Node>, stmts: &mut Vec<Statement>) -> Node{
+    let mut head = Node::IRINode { iri: rdf("nil") };
+    for item in items.into_iter().rev() {
+        let cell = ResourceNode::BNode { bnode: BlankNode::generate_blank_node() };
+        stmts.push(Statement::create(cell.clone(), rdf("first"), item));
+        stmts.push(Statement::create(cell.clone(), rdf("rest"), head));
+        head = Node::from(cell);
+    }
+    head
+}
+
+///
+/// A target of a shape, selecting the focus nodes which are validated against it
+///
+#[derive(Clone, PartialEq, Eq, Hash)]
+pub enum Target{
+    /// `sh:targetNode`, the node itself
+    Node(Node),
+    /// `sh:targetClass` or an implicit class target, all SHACL instances of the class
+    Class(Node),
+    /// `sh:targetSubjectsOf`, all subjects of the predicate
+    SubjectsOf(IRIResource),
+    /// `sh:targetObjectsOf`, all objects of the predicate
+    ObjectsOf(IRIResource)
+}
+
+impl Target{
+
+    /// Gets the focus nodes selected in `data`, each node once
+    pub fn get_focus_nodes(&self, data: &dyn Graph) -> Vec<Node>{
+        let mut reached = Reached::default();
+        match self {
+            Target::Node(node) => {
+                reached.add(node.clone());
+            }
+            Target::Class(class) => {
+                for class in subclasses(data, class) {
+                    for stmt in data.list_statements(None, Some(rdf("type")), Some(class)) {
+                        reached.add(Node::from(stmt.get_subject().clone()));
+                    }
+                }
+            }
+            Target::SubjectsOf(predicate) => {
+                for stmt in data.list_statements(None, Some(predicate.clone()), None) {
+                    reached.add(Node::from(stmt.get_subject().clone()));
+                }
+            }
+            Target::ObjectsOf(predicate) => {
+                for stmt in data.list_statements(None, Some(predicate.clone()), None) {
+                    reached.add(stmt.get_object().clone());
+                }
+            }
+        }
+        reached.nodes
+    }
+}
+
+/// `class` and all its direct and indirect subclasses in `data`
+fn subclasses(data: &dyn Graph, class: &Node) -> Vec<Node>{
+    Path::ZeroOrMore(Box::new(Path::Inverse(Box::new(Path::Predicate(rdfs("subClassOf")))))).evaluate(data, class)
+}
+
+/// Checks if `node` is a SHACL instance of `class` in `data`, i.e. it has a type which is a subclass of `class`
+pub(crate) fn is_instance(data: &dyn Graph, node: &Node, class: &Node) -> bool{
+    let types = Path::Sequence(vec![Path::Predicate(rdf("type")), Path::ZeroOrMore(Box::new(Path::Predicate(rdfs("subClassOf"))))]);
+    types.evaluate(data, node).contains(class)
+}
+
+///
+/// The severity of a shape and the results it reports, `sh:Violation` by default
+///
+#[derive(Clone, PartialEq, Eq, Hash)]
+pub enum Severity{
+    Info,
+    Warning,
+    Violation,
+    /// Any other IRI used as `sh:severity`
+    Other(Box<IRIResource>)
+}
+
+impl Severity{
+
+    pub fn from_iri(iri: &IRIResource) -> Severity{
+        match iri.get_value().strip_prefix(&sh_base()) {
+            Some("Info") => Severity::Info,
+            Some("Warning") => Severity::Warning,
+            Some("Violation") => Severity::Violation,
+            _ => Severity::Other(Box::new(iri.clone()))
+        }
+    }
+
+    pub fn get_iri(&self) -> IRIResource{
+        match self {
+            Severity::Info => sh("Info"),
+            Severity::Warning => sh("Warning"),
+            Severity::Violation => sh("Violation"),
+            Severity::Other(iri) => iri.as_ref().clone()
+        }
+    }
+}
+
+///
+/// The values of `sh:nodeKind`
+///
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum NodeKind{
+    IRI,
+    BlankNode,
+    Literal,
+    BlankNodeOrIRI,
+    BlankNodeOrLiteral,
+    IRIOrLiteral
+}
+
+impl NodeKind{
+
+    /// Gets the node kind `sh:IRI`, `sh:BlankNode`, ... names, None for other IRIs
+    pub fn from_iri(iri: &IRIResource) -> Option<NodeKind>{
+        match iri.get_value().strip_prefix(&sh_base())? {
+            "IRI" => Some(NodeKind::IRI),
+            "BlankNode" => Some(NodeKind::BlankNode),
+            "Literal" => Some(NodeKind::Literal),
+            "BlankNodeOrIRI" => Some(NodeKind::BlankNodeOrIRI),
+            "BlankNodeOrLiteral" => Some(NodeKind::BlankNodeOrLiteral),
+            "IRIOrLiteral" => Some(NodeKind::IRIOrLiteral),
+            _ => None
+        }
+    }
+
+    /// Checks if `node` is of this kind
+    pub fn matches(&self, node: &Node) -> bool{
+        match self {
+            NodeKind::IRI => node.is_uri(),
+            NodeKind::BlankNode => node.is_bnode(),
+            NodeKind::Literal => node.is_literal(),
+            NodeKind::BlankNodeOrIRI => node.is_bnode() || node.is_uri(),
+            NodeKind::BlankNodeOrLiteral => node.is_bnode() || node.is_literal(),
+            NodeKind::IRIOrLiteral => node.is_uri() || node.is_literal()
+        }
+    }
+}
+
+///
+/// A constraint of a shape, i.e. a SHACL Core constraint component with the values of its parameters
+///
+#[derive(Clone)]
+pub enum Constraint{
+    /// `sh:class`
+    Class(Node),
+    /// `sh:datatype`
+    Datatype(IRIResource),
+    /// `sh:nodeKind`
+    NodeKind(NodeKind),
+    /// `sh:minCount`
+    MinCount(usize),
+    /// `sh:maxCount`
+    MaxCount(usize),
+    /// `sh:minExclusive`
+    MinExclusive(Node),
+    /// `sh:minInclusive`
+    MinInclusive(Node),
+    /// `sh:maxExclusive`
+    MaxExclusive(Node),
+    /// `sh:maxInclusive`
+    MaxInclusive(Node),
+    /// `sh:minLength`
+    MinLength(usize),
+    /// `sh:maxLength`
+    MaxLength(usize),
+    /// `sh:pattern` compiled with its `sh:flags`
+    Pattern(Regex),
+    /// `sh:languageIn`, the language ranges
+    LanguageIn(Vec<String>),
+    /// `sh:uniqueLang true`
+    UniqueLang,
+    /// `sh:equals`
+    Equals(IRIResource),
+    /// `sh:disjoint`
+    Disjoint(IRIResource),
+    /// `sh:lessThan`
+    LessThan(IRIResource),
+    /// `sh:lessThanOrEquals`
+    LessThanOrEquals(IRIResource),
+    /// `sh:not`
+    Not(ResourceNode),
+    /// `sh:and`
+    And(Vec<ResourceNode>),
+    /// `sh:or`
+    Or(Vec<ResourceNode>),
+    /// `sh:xone`
+    Xone(Vec<ResourceNode>),
+    /// `sh:node`
+    Node(ResourceNode),
+    /// `sh:property`
+    Property(ResourceNode),
+    /// `sh:qualifiedValueShape` with `sh:qualifiedMinCount`, `siblings` are the shapes conforming values must not conform to
+    /// if `sh:qualifiedValueShapesDisjoint` is true
+    QualifiedMinCount{shape: ResourceNode, count: usize, siblings: Vec<ResourceNode>},
+    /// `sh:qualifiedValueShape` with `sh:qualifiedMaxCount`
+    QualifiedMaxCount{shape: ResourceNode, count: usize, siblings: Vec<ResourceNode>},
+    /// `sh:closed true`, the properties allowed by `sh:property` shapes and `sh:ignoredProperties`
+    Closed(Vec<IRIResource>),
+    /// `sh:hasValue`
+    HasValue(Node),
+    /// `sh:in`
+    In(Vec<Node>),
+    /// `sh:sparql`, a SPARQL-based constraint
+    Sparql(Box<SparqlConstraint>),
+    /// A constraint of a SPARQL-based constraint component
+    Component(Box<ComponentConstraint>)
+}
+
+impl Constraint{
+
+    /// Gets the IRI of the constraint component, e.g. `sh:ClassConstraintComponent`
+    pub fn get_component(&self) -> IRIResource{
+        let name = match self {
+            Constraint::Class(_) => "ClassConstraintComponent",
+            Constraint::Datatype(_) => "DatatypeConstraintComponent",
+            Constraint::NodeKind(_) => "NodeKindConstraintComponent",
+            Constraint::MinCount(_) => "MinCountConstraintComponent",
+            Constraint::MaxCount(_) => "MaxCountConstraintComponent",
+            Constraint::MinExclusive(_) => "MinExclusiveConstraintComponent",
+            Constraint::MinInclusive(_) => "MinInclusiveConstraintComponent",
+            Constraint::MaxExclusive(_) => "MaxExclusiveConstraintComponent",
+            Constraint::MaxInclusive(_) => "MaxInclusiveConstraintComponent",
+            Constraint::MinLength(_) => "MinLengthConstraintComponent",
+            Constraint::MaxLength(_) => "MaxLengthConstraintComponent",
+            Constraint::Pattern(_) => "PatternConstraintComponent",
+            Constraint::LanguageIn(_) => "LanguageInConstraintComponent",
+            Constraint::UniqueLang => "UniqueLangConstraintComponent",
+            Constraint::Equals(_) => "EqualsConstraintComponent",
+            Constraint::Disjoint(_) => "DisjointConstraintComponent",
+            Constraint::LessThan(_) => "LessThanConstraintComponent",
+            Constraint::LessThanOrEquals(_) => "LessThanOrEqualsConstraintComponent",
+            Constraint::Not(_) => "NotConstraintComponent",
+            Constraint::And(_) => "AndConstraintComponent",
+            Constraint::Or(_) => "OrConstraintComponent",
+            Constraint::Xone(_) => "XoneConstraintComponent",
+            Constraint::Node(_) => "NodeConstraintComponent",
+            Constraint::Property(_) => "PropertyConstraintComponent",
+            Constraint::QualifiedMinCount { .. } => "QualifiedMinCountConstraintComponent",
+            Constraint::QualifiedMaxCount { .. } => "QualifiedMaxCountConstraintComponent",
+            Constraint::Closed(_) => "ClosedConstraintComponent",
+            Constraint::HasValue(_) => "HasValueConstraintComponent",
+            Constraint::In(_) => "InConstraintComponent",
+            Constraint::Sparql(_) => "SPARQLConstraintComponent",
+            Constraint::Component(constraint) => return constraint.get_component().clone()
+        };
+        sh(name)
+    }
+}
+
+///
+/// A node shape or property shape
+///
+#[derive(Clone)]
+pub struct Shape{
+    id: ResourceNode,
+    targets: Vec<Target>,
+    path: Option<Path>,
+    constraints: Vec<Constraint>,
+    severity: Severity,
+    messages: Vec<Node>,
+    rules: Vec<ShapeRule>,
+    deactivated: bool
+}
+
+impl Shape{
+
+    /// Gets the IRI or blank node of the shape in the shapes graph
+    pub fn get_id(&self) -> &ResourceNode{
+        &self.id
+    }
+
+    pub fn get_targets(&self) -> &Vec<Target>{
+        &self.targets
+    }
+
+    /// Gets the path of a property shape, None for node shapes
+    pub fn get_path(&self) -> Option<&Path>{
+        self.path.as_ref()
+    }
+
+    pub fn get_constraints(&self) -> &Vec<Constraint>{
+        &self.constraints
+    }
+
+    pub fn get_severity(&self) -> &Severity{
+        &self.severity
+    }
+
+    /// Gets the values of `sh:message`, which are copied to the results of the shape
+    pub fn get_messages(&self) -> &Vec<Node>{
+        &self.messages
+    }
+
+    /// Gets the rules of `sh:rule` which are not deactivated
+    pub fn get_rules(&self) -> &Vec<ShapeRule>{
+        &self.rules
+    }
+
+    /// Checks if the shape is deactivated with `sh:deactivated true`, deactivated shapes are ignored
+    pub fn is_deactivated(&self) -> bool{
+        self.deactivated
+    }
+
+    pub fn is_property_shape(&self) -> bool{
+        self.path.is_some()
+    }
+}
+
+///
+/// The shapes read from a shapes graph
+///
+/// # Example
+///
+/// ```
+/// use rdf4rust::shacl::shapes::{ShapesGraph, Constraint, Target};
+/// use rdf4rust::rdf::database::{Database, GraphPattern};
+/// use rdf4rust::rdf::graph::{GraphType, Quad, Node, ResourceNode};
+/// use rdf4rust::rdf::node_factory::{Literal, iri};
+/// use rdf4rust::error::ErrorKind;
+///
+/// let sh = |s: &str| iri(&format!("http://www.w3.org/ns/shacl#{}", s));
+/// let shapes_graph = ResourceNode::IRINode { iri: iri("http://example.com/shapes") };
+/// let shape = ResourceNode::IRINode { iri: iri("http://example.com/PersonShape") };
+///
+/// let mut database = Database::new(GraphType::IndexedGraph);
+/// database.add_quad(Quad::create(shape.clone(), sh("targetClass"), Node::IRINode { iri: iri("http://example.com/Person") }, Some(shapes_graph.clone())));
+/// database.add_quad(Quad::create(shape.clone(), sh("maxLength"), Node::LiteralNode { literal: Literal::from(10) }, Some(shapes_graph.clone())));
+///
+/// let shapes = ShapesGraph::from_database(&database, &GraphPattern::NamedGraph { name: shapes_graph.clone() }).expect("Is a valid shapes graph");
+/// let person = shapes.get_shape(&shape).expect("Is a shape");
+/// assert!(!person.is_property_shape());
+/// assert!(person.get_targets()[0] == Target::Class(Node::IRINode { iri: iri("http://example.com/Person") }));
+/// assert!(matches!(person.get_constraints()[0], Constraint::MaxLength(10)));
+///
+/// database.add_quad(Quad::create(shape, sh("maxLength"), Node::LiteralNode { literal: Literal::from("ten") }, Some(shapes_graph.clone())));
+/// let err = ShapesGraph::from_database(&database, &GraphPattern::NamedGraph { name: shapes_graph }).err().expect("Is ill-formed");
+/// assert_eq!(ErrorKind::InvalidShape, err.get_kind());
+/// ```
+///
+#[derive(Clone, Default)]
+pub struct ShapesGraph{
+    shapes: Vec<Shape>,
+    index: HashMap<ResourceNode, usize>,
+    components: Vec<ConstraintComponent>
+}
+
+impl ShapesGraph{
+
+    ///
+    /// Reads all shapes of `graph`
+    ///
+    /// # Returns
+    ///
+    /// The shapes or an error of kind `ErrorKind::InvalidShape` if a shape is ill-formed, e.g. has an invalid path
+    ///
+    pub fn from_graph(graph: &dyn Graph) -> Result<ShapesGraph, RDFError>{
+        let mut queue: Vec<ResourceNode> = Vec::new();
+        for class in ["NodeShape", "PropertyShape"].iter() {
+            queue.extend(graph.list_statements(None, Some(rdf("type")), Some(Node::IRINode { iri: sh(class) })).into_iter().map(|stmt| stmt.get_subject().clone()));
+        }
+        for target in ["targetNode", "targetClass", "targetSubjectsOf", "targetObjectsOf"].iter() {
+            queue.extend(graph.list_statements(None, Some(sh(target)), None).into_iter().map(|stmt| stmt.get_subject().clone()));
+        }
+        queue.reverse();
+        let mut shapes = ShapesGraph{ components: ConstraintComponent::read_all(graph)?, ..ShapesGraph::default() };
+        while let Some(id) = queue.pop() {
+            if !shapes.index.contains_key(&id) {
+                let shape = ShapeReader{ graph, id: &id, components: &shapes.components }.read(&mut queue)?;
+                shapes.index.insert(id, shapes.shapes.len());
+                shapes.shapes.push(shape);
+            }
+        }
+        Ok(shapes)
+    }
+
+    ///
+    /// Reads all shapes of the merge of the graphs of `database` matching `graphs`
+    ///
+    pub fn from_database(database: &Database, graphs: &GraphPattern) -> Result<ShapesGraph, RDFError>{
+        ShapesGraph::from_graph(&merge_graphs(database, graphs))
+    }
+
+    /// Gets all shapes in the order they were found
+    pub fn get_shapes(&self) -> &Vec<Shape>{
+        &self.shapes
+    }
+
+    pub fn get_shape(&self, id: &ResourceNode) -> Option<&Shape>{
+        self.index.get(id).map(|&i| &self.shapes[i])
+    }
+
+    /// Gets the SPARQL-based constraint components declared in the shapes graph
+    pub fn get_components(&self) -> &Vec<ConstraintComponent>{
+        &self.components
+    }
+}
+
+/// The names of parameters with the constraints created from their values
+type Parameters<T> = [(&'static str, fn(T) -> Constraint)];
+
+/// Reads a single shape from the shapes graph
+struct ShapeReader<'a>{
+    graph: &'a dyn Graph,
+    id: &'a ResourceNode,
+    components: &'a [ConstraintComponent]
+}
+
+impl<'a> ShapeReader<'a>{
+
+    fn values(&self, parameter: &str) -> Vec<&'a Node>{
+        objects(self.graph, self.id, parameter)
+    }
+
+    /// The single value of an optional parameter
+    fn value(&self, parameter: &str) -> Result<Option<&'a Node>, RDFError>{
+        match self.values(parameter).as_slice() {
+            [] => Ok(None),
+            [value] => Ok(Some(*value)),
+            _ => Err(invalid(self.id, format!("sh:{} has several values.", parameter)))
+        }
+    }
+
+    fn is_a(&self, class: IRIResource) -> bool{
+        !self.graph.list_statements(Some(self.id.clone()), Some(rdf("type")), Some(Node::IRINode { iri: class })).is_empty()
+    }
+
+    /// Reads the shape, adding the shapes it refers to to `queue`
+    fn read(&self, queue: &mut Vec<ResourceNode>) -> Result<Shape, RDFError>{
+        let id = self.id;
+        let mut targets: Vec<Target> = self.values("targetNode").into_iter().map(|node| Target::Node(node.clone())).collect();
+        targets.extend(self.values("targetClass").into_iter().map(|class| Target::Class(class.clone())));
+        if self.is_a(rdfs("Class")) && (self.is_a(sh("NodeShape")) || self.is_a(sh("PropertyShape"))) {
+            targets.push(Target::Class(Node::from(id.clone())));
+        }
+        for predicate in self.values("targetSubjectsOf") {
+            targets.push(Target::SubjectsOf(to_iri(id, "targetSubjectsOf", predicate)?));
+        }
+        for predicate in self.values("targetObjectsOf") {
+            targets.push(Target::ObjectsOf(to_iri(id, "targetObjectsOf", predicate)?));
+        }
+        let path = match self.value("path")? {
+            Some(path) => Some(Path::parse(self.graph, id, path)?),
+            None => None
+        };
+        let severity = match self.value("severity")? {
+            Some(severity) => Severity::from_iri(&to_iri(id, "severity", severity)?),
+            None => Severity::Violation
+        };
+        let deactivated = match self.value("deactivated")? {
+            Some(deactivated) => to_bool(id, "deactivated", deactivated)?,
+            None => false
+        };
+        let constraints = self.constraints(path.as_ref(), queue)?;
+        let mut rules = Vec::new();
+        for rule in self.values("rule") {
+            rules.extend(ShapeRule::read(self.graph, id, rule, queue)?);
+        }
+        Ok(Shape{
+            id: id.clone(),
+            targets,
+            path,
+            constraints,
+            severity,
+            messages: self.values("message").into_iter().cloned().collect(),
+            rules,
+            deactivated
+        })
+    }
+
+    fn constraints(&self, path: Option<&Path>, queue: &mut Vec<ResourceNode>) -> Result<Vec<Constraint>, RDFError>{
+        let id = self.id;
+        let mut constraints = Vec::new();
+        for class in self.values("class") {
+            constraints.push(Constraint::Class(class.clone()));
+        }
+        for datatype in self.values("datatype") {
+            constraints.push(Constraint::Datatype(to_iri(id, "datatype", datatype)?));
+        }
+        for kind in self.values("nodeKind") {
+            match NodeKind::from_iri(&to_iri(id, "nodeKind", kind)?) {
+                Some(kind) => constraints.push(Constraint::NodeKind(kind)),
+                None => return Err(invalid(id, format!("{} is not a node kind.", kind.as_string())))
+            }
+        }
+        let counts: &Parameters<usize> = &[("minCount", Constraint::MinCount), ("maxCount", Constraint::MaxCount),
+            ("minLength", Constraint::MinLength), ("maxLength", Constraint::MaxLength)];
+        for (parameter, constraint) in counts.iter() {
+            for count in self.values(parameter) {
+                constraints.push(constraint(to_count(id, parameter, count)?));
+            }
+        }
+        let ranges: &Parameters<Node> = &[("minExclusive", Constraint::MinExclusive), ("minInclusive", Constraint::MinInclusive),
+            ("maxExclusive", Constraint::MaxExclusive), ("maxInclusive", Constraint::MaxInclusive)];
+        for (parameter, constraint) in ranges.iter() {
+            for bound in self.values(parameter) {
+                constraints.push(constraint(bound.clone()));
+            }
+        }
+        let flags = match self.value("flags")? {
+            Some(flags) => string_value(flags).ok_or_else(|| invalid(id, String::from("sh:flags is not a string.")))?,
+            None => String::new()
+        };
+        for pattern in self.values("pattern") {
+            match string_value(pattern).and_then(|pattern| builtins::regex(&pattern, &flags)) {
+                Some(regex) => constraints.push(Constraint::Pattern(regex)),
+                None => return Err(invalid(id, format!("{} with flags \"{}\" is not a valid regular expression.", pattern.as_string(), flags)))
+            }
+        }
+        for ranges in self.values("languageIn") {
+            let ranges = to_list(self.graph, id, "languageIn", ranges)?.iter()
+                .map(|range| range.as_literal().map(|range| range.get_value().clone()).map_err(|_| invalid(id, String::from("sh:languageIn contains a non-literal."))))
+                .collect::<Result<_, _>>()?;
+            constraints.push(Constraint::LanguageIn(ranges));
+        }
+        for unique in self.values("uniqueLang") {
+            if to_bool(id, "uniqueLang", unique)? {
+                constraints.push(Constraint::UniqueLang);
+            }
+        }
+        let pairs: &Parameters<IRIResource> = &[("equals", Constraint::Equals), ("disjoint", Constraint::Disjoint),
+            ("lessThan", Constraint::LessThan), ("lessThanOrEquals", Constraint::LessThanOrEquals)];
+        for (parameter, constraint) in pairs.iter() {
+            for property in self.values(parameter) {
+                constraints.push(constraint(to_iri(id, parameter, property)?));
+            }
+        }
+        let shape = |parameter: &str, node: &Node, queue: &mut Vec<ResourceNode>| -> Result<ResourceNode, RDFError> {
+            let shape = to_shape(id, parameter, node)?;
+            queue.push(shape.clone());
+            Ok(shape)
+        };
+        for not in self.values("not") {
+            constraints.push(Constraint::Not(shape("not", not, queue)?));
+        }
+        let lists: &Parameters<Vec<ResourceNode>> = &[("and", Constraint::And), ("or", Constraint::Or), ("xone", Constraint::Xone)];
+        for (parameter, constraint) in lists.iter() {
+            for list in self.values(parameter) {
+                let shapes = to_list(self.graph, id, parameter, list)?.iter()
+                    .map(|member| shape(parameter, member, queue))
+                    .collect::<Result<_, _>>()?;
+                constraints.push(constraint(shapes));
+            }
+        }
+        for node in self.values("node") {
+            constraints.push(Constraint::Node(shape("node", node, queue)?));
+        }
+        for property in self.values("property") {
+            constraints.push(Constraint::Property(shape("property", property, queue)?));
+        }
+        for qualified in self.values("qualifiedValueShape") {
+            let qualified = shape("qualifiedValueShape", qualified, queue)?;
+            let disjoint = match self.value("qualifiedValueShapesDisjoint")? {
+                Some(disjoint) => to_bool(id, "qualifiedValueShapesDisjoint", disjoint)?,
+                None => false
+            };
+            let siblings = if disjoint {self.siblings(&qualified)} else {Vec::new()};
+            if let Some(count) = self.value("qualifiedMinCount")? {
+                constraints.push(Constraint::QualifiedMinCount{shape: qualified.clone(), count: to_count(id, "qualifiedMinCount", count)?, siblings: siblings.clone()});
+            }
+            if let Some(count) = self.value("qualifiedMaxCount")? {
+                constraints.push(Constraint::QualifiedMaxCount{shape: qualified, count: to_count(id, "qualifiedMaxCount", count)?, siblings});
+            }
+        }
+        for closed in self.values("closed") {
+            if to_bool(id, "closed", closed)? {
+                constraints.push(Constraint::Closed(self.allowed_properties()?));
+            }
+        }
+        for value in self.values("hasValue") {
+            constraints.push(Constraint::HasValue(value.clone()));
+        }
+        for list in self.values("in") {
+            constraints.push(Constraint::In(to_list(self.graph, id, "in", list)?));
+        }
+        for sparql in self.values("sparql") {
+            if let Some(sparql) = SparqlConstraint::read(self.graph, id, sparql, path)? {
+                constraints.push(Constraint::Sparql(Box::new(sparql)));
+            }
+        }
+        for component in self.components {
+            constraints.extend(component.instantiate(self.graph, id, path)?.into_iter().map(|constraint| Constraint::Component(Box::new(constraint))));
+        }
+        Ok(constraints)
+    }
+
+    /// The qualified value shapes of the other property shapes of the shapes having this shape as property
+    fn siblings(&self, qualified: &ResourceNode) -> Vec<ResourceNode>{
+        let mut siblings = Vec::new();
+        for parent in self.graph.list_statements(None, Some(sh("property")), Some(Node::from(self.id.clone()))) {
+            for sibling in objects(self.graph, parent.get_subject(), "property").into_iter().filter_map(Node::to_resource) {
+                for shape in objects(self.graph, &sibling, "qualifiedValueShape").into_iter().filter_map(Node::to_resource) {
+                    if &shape != qualified && !siblings.contains(&shape) {
+                        siblings.push(shape);
+                    }
+                }
+            }
+        }
+        siblings
+    }
+
+    /// The predicate paths of the property shapes and the values of `sh:ignoredProperties`
+    fn allowed_properties(&self) -> Result<Vec<IRIResource>, RDFError>{
+        let mut allowed = Vec::new();
+        for property in self.values("property").into_iter().filter_map(Node::to_resource) {
+            if let [Node::IRINode { iri }] = objects(self.graph, &property, "path").as_slice() {
+                allowed.push(iri.clone());
+            }
+        }
+        if let Some(ignored) = self.value("ignoredProperties")? {
+            for property in to_list(self.graph, self.id, "ignoredProperties", ignored)? {
+                allowed.push(to_iri(self.id, "ignoredProperties", &property)?);
+            }
+        }
+        Ok(allowed)
+    }
+}
@@ -0,0 +1,425 @@
+//!
+//! SHACL-SPARQL, see <https://www.w3.org/TR/shacl/#sparql-constraints>: SPARQL-based constraints (`sh:sparql`)
+//! and SPARQL-based constraint components declared as instances of `sh:ConstraintComponent`.
+//!
+//! The queries are evaluated over the data graph with the SPARQL subset of `Query`. The variables `$this`,
+//! `$currentShape`, `$value` and the parameters of components are pre-bound, `$PATH` is replaced by the path of the
+//! property shape in SPARQL syntax. As required by SHACL 5.2.1, queries must not contain `MINUS`, `VALUES` or `SERVICE`
+//! and must not bind a pre-bound variable with `AS`, otherwise the shapes graph is ill-formed. Prefixes are declared with `sh:prefixes`, whose values have `sh:declare` values
+//! with `sh:prefix` and `sh:namespace`. `{$name}` and `{?name}` in messages are replaced by the values of the variables.
+//!
+//! # Example
+//!
+//! ```
+//! use rdf4rust::shacl::shapes::ShapesGraph;
+//! use rdf4rust::shacl::validation::Validator;
+//! use rdf4rust::io::reader::{QuadIterator, Lang};
+//! use rdf4rust::rdf::database::{Database, GraphPattern};
+//! use rdf4rust::rdf::graph::{GraphType, Node, ResourceNode};
+//! use rdf4rust::rdf::node_factory::{RDFNode, iri};
+//!
+//! let shapes = r#"
+//! <http://example.com/shapes> <http://www.w3.org/ns/shacl#declare> _:ex <http://example.com/shapes> .
+//! _:ex <http://www.w3.org/ns/shacl#prefix> "ex" <http://example.com/shapes> .
+//! _:ex <http://www.w3.org/ns/shacl#namespace> "http://example.com/"^^<http://www.w3.org/2001/XMLSchema#anyURI> <http://example.com/shapes> .
+//! <http://example.com/PersonShape> <http://www.w3.org/ns/shacl#targetClass> <http://example.com/Person> <http://example.com/shapes> .
+//! <http://example.com/PersonShape> <http://www.w3.org/ns/shacl#sparql> _:noSelfParent <http://example.com/shapes> .
+//! _:noSelfParent <http://www.w3.org/ns/shacl#message> "{$this} is its own parent" <http://example.com/shapes> .
+//! _:noSelfParent <http://www.w3.org/ns/shacl#prefixes> <http://example.com/shapes> <http://example.com/shapes> .
+//! _:noSelfParent <http://www.w3.org/ns/shacl#select> "SELECT $this WHERE { $this ex:parent+ $this }" <http://example.com/shapes> .
+//! <http://example.com/alice> <http://www.w3.org/1999/02/22-rdf-syntax-ns#type> <http://example.com/Person> .
+//! <http://example.com/alice> <http://example.com/parent> <http://example.com/bob> .
+//! <http://example.com/bob> <http://example.com/parent> <http://example.com/alice> .
+//! "#;
+//! let mut database = Database::new(GraphType::IndexedGraph);
+//! for quad in QuadIterator::new(shapes.as_bytes(), &Lang::NQ) {
+//!     database.add_quad(quad.expect("Is valid N-Quads"));
+//! }
+//!
+//! let ex = |s: &str| iri(&format!("http://example.com/{}", s));
+//! let shapes_graph = ShapesGraph::from_database(&database, &GraphPattern::NamedGraph { name: ResourceNode::IRINode { iri: ex("shapes") } })
+//!     .expect("Is a valid shapes graph");
+//! let report = Validator::new(&shapes_graph).validate_database(&database, &GraphPattern::DefaultGraph);
+//! assert_eq!(1, report.get_results().len());
+//! let result = &report.get_results()[0];
+//! assert!(result.get_focus_node() == &Node::IRINode { iri: ex("alice") });
+//! assert_eq!("http://www.w3.org/ns/shacl#SPARQLConstraintComponent", result.get_source_constraint_component().get_value());
+//! assert_eq!("http://example.com/alice is its own parent", result.get_messages()[0].as_literal().expect("Is a literal").get_value());
+//! ```
+//!
+
+use crate::error::RDFError;
+use crate::rdf::graph::{Graph, Node, ResourceNode};
+use crate::rdf::node_factory::{rdf, IRIResource, Literal, RDFNode};
+use crate::shacl::shapes::{invalid, objects, sh, string_value, to_bool, to_iri, Path, Shape};
+use crate::shacl::validation::ValidationResult;
+use crate::sparql::query::{Query, QueryForm, Solution};
+use std::collections::HashMap;
+
+/// Reads the prefixes declared by the values of `sh:prefixes` of `node`
+fn read_prefixes(graph: &dyn Graph, shape: &ResourceNode, node: &ResourceNode) -> Result<HashMap<String, String>, RDFError>{
+    let mut prefixes: HashMap<String, String> = HashMap::new();
+    for ontology in objects(graph, node, "prefixes") {
+        let ontology = ontology.to_resource().ok_or_else(|| invalid(shape, format!("The value {} of sh:prefixes is not a node.", ontology.as_string())))?;
+        for declaration in objects(graph, &ontology, "declare") {
+            let ill_formed = || invalid(shape, format!("{} is not a well-formed prefix declaration.", declaration.as_string()));
+            let declaration = declaration.to_resource().ok_or_else(ill_formed)?;
+            let (prefix, namespace) = match (objects(graph, &declaration, "prefix").as_slice(), objects(graph, &declaration, "namespace").as_slice()) {
+                ([prefix], [namespace]) if prefix.is_literal() => (string_value(prefix).ok_or_else(ill_formed)?, string_value(namespace).ok_or_else(ill_formed)?),
+                _ => return Err(ill_formed())
+            };
+            if prefixes.get(&prefix).is_some_and(|declared| declared != &namespace) {
+                return Err(invalid(shape, format!("The prefix {} is declared with different namespaces.", prefix)))
+            }
+            prefixes.insert(prefix, namespace);
+        }
+    }
+    Ok(prefixes)
+}
+
+/// The variables which are potentially pre-bound in all queries, besides `$value` and the parameters of components
+pub(crate) const PRE_BOUND: [&str; 3] = ["this", "currentShape", "shapesGraph"];
+
+///
+/// Reads the query `parameter` of `node`, e.g. `sh:select`
+///
+/// `$PATH` is replaced by `path`, None if `node` has no such query.
+/// The query must be allowed with the variables `pre_bound` (see `Query::check_pre_binding`).
+///
+pub(crate) fn read_query(graph: &dyn Graph, shape: &ResourceNode, node: &ResourceNode, parameter: &str, path: Option<&Path>, pre_bound: &[&str]) -> Result<Option<Query>, RDFError>{
+    let text = match objects(graph, node, parameter).as_slice() {
+        [] => return Ok(None),
+        [text] if text.is_literal() => string_value(text).unwrap_or_default(),
+        _ => return Err(invalid(shape, format!("{} has not a single string as sh:{}.", node.as_string(), parameter)))
+    };
+    let text = match path {
+        Some(path) => text.replace("$PATH", &path.to_string()),
+        None if text.contains("$PATH") => return Err(invalid(shape, format!("The query of {} uses $PATH in a node shape.", node.as_string()))),
+        None => text
+    };
+    let query = Query::parse(&text, &read_prefixes(graph, shape, node)?)
+        .map_err(|err| invalid(shape, format!("The query of {} is not valid: {}", node.as_string(), err)))?;
+    let form_matches = match query.get_form() {
+        QueryForm::Select { .. } => parameter == "select",
+        QueryForm::Ask => parameter == "ask",
+        QueryForm::Construct(_) => parameter == "construct"
+    };
+    if !form_matches {
+        return Err(invalid(shape, format!("The value of sh:{} of {} is not a {} query.", parameter, node.as_string(), parameter.to_uppercase())))
+    }
+    query.check_pre_binding(pre_bound)
+        .map_err(|err| invalid(shape, format!("The query of {} is not valid: {}", node.as_string(), err)))?;
+    Ok(Some(query))
+}
+
+/// Checks if `node` is deactivated with `sh:deactivated true`
+pub(crate) fn is_deactivated(graph: &dyn Graph, shape: &ResourceNode, node: &ResourceNode) -> Result<bool, RDFError>{
+    match objects(graph, node, "deactivated").as_slice() {
+        [] => Ok(false),
+        [deactivated] => to_bool(shape, "deactivated", deactivated),
+        _ => Err(invalid(shape, format!("{} has several values of sh:deactivated.", node.as_string())))
+    }
+}
+
+/// The bindings of `$this` and `$currentShape`
+fn pre_bindings(shape: &Shape, focus: &Node) -> Solution{
+    let mut bindings = Solution::new();
+    bindings.insert(String::from("this"), focus.clone());
+    bindings.insert(String::from("currentShape"), Node::from(shape.get_id().clone()));
+    bindings
+}
+
+/// Replaces `{$name}` and `{?name}` in a message by the values of the variables
+fn substitute(message: &Node, solution: &Solution) -> Node{
+    let literal = match message {
+        Node::LiteralNode { literal } => literal,
+        _ => return message.clone()
+    };
+    let mut text = literal.get_value().clone();
+    for (variable, value) in solution {
+        let value = string_value(value).unwrap_or_else(|| value.as_string());
+        text = text.replace(&format!("{{${}}}", variable), &value).replace(&format!("{{?{}}}", variable), &value);
+    }
+    let literal = match literal.get_lang() {
+        Some(lang) => Literal::create_lang_literal(text.clone(), lang.clone()).unwrap_or_else(|_| Literal::from(text)),
+        None => Literal::from(text)
+    };
+    Node::LiteralNode { literal }
+}
+
+///
+/// Creates the result of a solution of a `SELECT` query
+///
+/// The value is the binding of `?value`, or the focus node at node shapes. A binding of `?path` replaces the path of
+/// the shape and a binding of `?message` the `messages`.
+///
+fn to_result(shape: &Shape, component: IRIResource, focus: &Node, solution: &Solution, messages: &[Node]) -> ValidationResult{
+    let value = solution.get("value").cloned().or_else(|| if shape.is_property_shape() {None} else {Some(focus.clone())});
+    let mut result = ValidationResult::new(shape, component, focus.clone(), value);
+    if let Some(Node::IRINode { iri }) = solution.get("path") {
+        result = result.with_path(Some(Path::Predicate(iri.clone())));
+    }
+    let messages: Vec<Node> = match solution.get("message") {
+        Some(message) => vec![message.clone()],
+        None => messages.iter().map(|message| substitute(message, solution)).collect()
+    };
+    if messages.is_empty() {result} else {result.with_messages(messages)}
+}
+
+/// Adds the pre-bound variables to a solution, which may have been projected away
+fn complete(mut solution: Solution, bindings: &Solution) -> Solution{
+    for (variable, value) in bindings {
+        solution.entry(variable.clone()).or_insert_with(|| value.clone());
+    }
+    solution
+}
+
+///
+/// A SPARQL-based constraint, a value of `sh:sparql` with a `SELECT` query
+///
+/// Each solution of the query for a focus node `$this` is a result.
+///
+#[derive(Clone)]
+pub struct SparqlConstraint{
+    id: Node,
+    query: Query,
+    messages: Vec<Node>
+}
+
+impl SparqlConstraint{
+
+    /// Reads the constraint `node` of `shape`, None if it is deactivated
+    pub(crate) fn read(graph: &dyn Graph, shape: &ResourceNode, node: &Node, path: Option<&Path>) -> Result<Option<SparqlConstraint>, RDFError>{
+        let resource = node.to_resource().ok_or_else(|| invalid(shape, format!("The value {} of sh:sparql is not a node.", node.as_string())))?;
+        if is_deactivated(graph, shape, &resource)? {
+            return Ok(None)
+        }
+        let query = read_query(graph, shape, &resource, "select", path, &PRE_BOUND)?
+            .ok_or_else(|| invalid(shape, format!("The SPARQL constraint {} has no sh:select.", node.as_string())))?;
+        Ok(Some(SparqlConstraint{
+            id: node.clone(),
+            query,
+            messages: objects(graph, &resource, "message").into_iter().cloned().collect()
+        }))
+    }
+
+    /// Gets the node of the constraint, which is the `sh:sourceConstraint` of its results
+    pub fn get_id(&self) -> &Node{
+        &self.id
+    }
+
+    pub fn get_query(&self) -> &Query{
+        &self.query
+    }
+
+    pub fn get_messages(&self) -> &Vec<Node>{
+        &self.messages
+    }
+
+    /// Validates `focus` against the constraint of `shape`
+    pub(crate) fn validate(&self, data: &dyn Graph, shape: &Shape, focus: &Node) -> Vec<ValidationResult>{
+        let bindings = pre_bindings(shape, focus);
+        self.query.select(data, &bindings).into_iter()
+            .map(|solution| to_result(shape, sh("SPARQLConstraintComponent"), focus, &complete(solution, &bindings), &self.messages)
+                .with_source_constraint(Some(self.id.clone())))
+            .collect()
+    }
+}
+
+///
+/// A parameter of a constraint component
+///
+#[derive(Clone, PartialEq, Eq, Hash)]
+pub struct Parameter{
+    path: IRIResource,
+    optional: bool
+}
+
+impl Parameter{
+
+    /// Gets the IRI of the parameter, which shapes use as predicate
+    pub fn get_path(&self) -> &IRIResource{
+        &self.path
+    }
+
+    /// Gets the name of the variable the value is bound to, the local name of the IRI
+    pub fn get_name(&self) -> String{
+        let iri = self.path.get_value();
+        String::from(iri.rsplit(['#', '/']).next().unwrap_or(&iri))
+    }
+
+    pub fn is_optional(&self) -> bool{
+        self.optional
+    }
+}
+
+///
+/// A SPARQL-based constraint component, an instance of `sh:ConstraintComponent` in the shapes graph
+///
+/// A shape which has values for all mandatory parameters has a constraint of the component for each combination of
+/// parameter values. Property shapes use `sh:propertyValidator`, node shapes `sh:nodeValidator` and both fall back to
+/// `sh:validator`. `ASK` validators are evaluated for each value node, `SELECT` validators report each solution.
+///
+#[derive(Clone)]
+pub struct ConstraintComponent{
+    id: IRIResource,
+    parameters: Vec<Parameter>,
+    validator: Option<ResourceNode>,
+    node_validator: Option<ResourceNode>,
+    property_validator: Option<ResourceNode>,
+    messages: Vec<Node>
+}
+
+impl ConstraintComponent{
+
+    /// Reads all constraint components of the shapes graph
+    pub(crate) fn read_all(graph: &dyn Graph) -> Result<Vec<ConstraintComponent>, RDFError>{
+        let mut components = Vec::new();
+        for stmt in graph.list_statements(None, Some(rdf("type")), Some(Node::IRINode { iri: sh("ConstraintComponent") })) {
+            let id = stmt.get_subject();
+            let iri = match id {
+                ResourceNode::IRINode { iri } => iri.clone(),
+                _ => return Err(invalid(id, String::from("Constraint components have to be IRIs.")))
+            };
+            let mut parameters = Vec::new();
+            for parameter in objects(graph, id, "parameter") {
+                let parameter = parameter.to_resource().ok_or_else(|| invalid(id, format!("The parameter {} is not a node.", parameter.as_string())))?;
+                let path = match objects(graph, &parameter, "path").as_slice() {
+                    [path] => to_iri(id, "path", path)?,
+                    _ => return Err(invalid(id, format!("The parameter {} has not a single IRI as sh:path.", parameter.as_string())))
+                };
+                let optional = match objects(graph, &parameter, "optional").as_slice() {
+                    [] => false,
+                    [optional] => to_bool(id, "optional", optional)?,
+                    _ => return Err(invalid(id, String::from("sh:optional has several values.")))
+                };
+                parameters.push(Parameter{ path, optional });
+            }
+            if parameters.is_empty() {
+                return Err(invalid(id, String::from("The constraint component has no parameters.")))
+            }
+            let validator = |parameter: &str| -> Result<Option<ResourceNode>, RDFError> {
+                match objects(graph, id, parameter).as_slice() {
+                    [] => Ok(None),
+                    [validator] => validator.to_resource().map(Some).ok_or_else(|| invalid(id, format!("The value of sh:{} is not a node.", parameter))),
+                    _ => Err(invalid(id, format!("sh:{} has several values.", parameter)))
+                }
+            };
+            components.push(ConstraintComponent{
+                id: iri,
+                parameters,
+                validator: validator("validator")?,
+                node_validator: validator("nodeValidator")?,
+                property_validator: validator("propertyValidator")?,
+                messages: objects(graph, id, "message").into_iter().cloned().collect()
+            });
+        }
+        Ok(components)
+    }
+
+    pub fn get_id(&self) -> &IRIResource{
+        &self.id
+    }
+
+    pub fn get_parameters(&self) -> &Vec<Parameter>{
+        &self.parameters
+    }
+
+    /// Creates the constraints of `shape` with path `path`, none if a mandatory parameter is missing or there is no validator
+    pub(crate) fn instantiate(&self, graph: &dyn Graph, shape: &ResourceNode, path: Option<&Path>) -> Result<Vec<ComponentConstraint>, RDFError>{
+        let mut combinations: Vec<Vec<(String, Node)>> = vec![Vec::new()];
+        for parameter in &self.parameters {
+            let values: Vec<Node> = graph.list_statements(Some(shape.clone()), Some(parameter.path.clone()), None).into_iter()
+                .map(|stmt| stmt.get_object().clone())
+                .collect();
+            if values.is_empty() {
+                if parameter.optional {
+                    continue
+                }
+                return Ok(Vec::new())
+            }
+            combinations = combinations.into_iter()
+                .flat_map(|combination| values.iter().map(move |value| {
+                    let mut combination = combination.clone();
+                    combination.push((parameter.get_name(), value.clone()));
+                    combination
+                }))
+                .collect();
+        }
+        let specific = if path.is_some() {&self.property_validator} else {&self.node_validator};
+        let validator = match specific.as_ref().or(self.validator.as_ref()) {
+            Some(validator) => validator,
+            None => return Ok(Vec::new())
+        };
+        let names: Vec<String> = self.parameters.iter().map(Parameter::get_name).collect();
+        let mut pre_bound: Vec<&str> = PRE_BOUND.iter().copied().chain(names.iter().map(String::as_str)).collect();
+        let query = match read_query(graph, shape, validator, "select", path, &pre_bound)? {
+            Some(query) => query,
+            None => {
+                //ASK validators get each value node as $value
+                pre_bound.push("value");
+                read_query(graph, shape, validator, "ask", path, &pre_bound)?
+                    .ok_or_else(|| invalid(shape, format!("The validator {} has neither sh:select nor sh:ask.", validator.as_string())))?
+            }
+        };
+        let mut messages: Vec<Node> = objects(graph, validator, "message").into_iter().cloned().collect();
+        if messages.is_empty() {
+            messages = self.messages.clone();
+        }
+        Ok(combinations.into_iter()
+            .map(|parameters| ComponentConstraint{ component: self.id.clone(), query: query.clone(), parameters, messages: messages.clone() })
+            .collect())
+    }
+}
+
+///
+/// A constraint of a SPARQL-based constraint component with the values of its parameters
+///
+#[derive(Clone)]
+pub struct ComponentConstraint{
+    component: IRIResource,
+    query: Query,
+    parameters: Vec<(String, Node)>,
+    messages: Vec<Node>
+}
+
+impl ComponentConstraint{
+
+    /// Gets the IRI of the constraint component
+    pub fn get_component(&self) -> &IRIResource{
+        &self.component
+    }
+
+    /// Gets the query of the validator
+    pub fn get_query(&self) -> &Query{
+        &self.query
+    }
+
+    /// Gets the names of the parameters with their values
+    pub fn get_parameters(&self) -> &Vec<(String, Node)>{
+        &self.parameters
+    }
+
+    /// Validates `focus` with its value nodes `values` against the constraint of `shape`
+    pub(crate) fn validate(&self, data: &dyn Graph, shape: &Shape, focus: &Node, values: &[Node]) -> Vec<ValidationResult>{
+        let mut bindings = pre_bindings(shape, focus);
+        bindings.extend(self.parameters.iter().cloned());
+        if let QueryForm::Ask = self.query.get_form() {
+            return values.iter()
+                .filter_map(|value| {
+                    bindings.insert(String::from("value"), value.clone());
+                    if self.query.ask(data, &bindings) {
+                        return None
+                    }
+                    Some(to_result(shape, self.component.clone(), focus, &bindings, &self.messages))
+                })
+                .collect()
+        }
+        if !shape.is_property_shape() {
+            bindings.insert(String::from("value"), focus.clone());
+        }
+        self.query.select(data, &bindings).into_iter()
+            .map(|solution| to_result(shape, self.component.clone(), focus, &complete(solution, &bindings), &self.messages))
+            .collect()
+    }
+}
@@ -0,0 +1,439 @@
+//!
+//! Validation of data graphs against the shapes of a `ShapesGraph` and the resulting validation report.
+//!
+//! The focus nodes of each shape are selected by its targets and validated against the constraints of the shape.
+//! Shapes referenced by `sh:node`, `sh:not`, `sh:and`, ... are checked for conformance of the value nodes.
+//! A node which is checked against a shape while it is already being checked against it (a recursive shape) conforms.
+//! SHACL instances (`sh:class`, `sh:targetClass`) follow `rdf:type` and `rdfs:subClassOf` statements of the data graph,
+//! no other entailment is applied.
+//!
+//! # Example
+//!
+//! ```
+//! use rdf4rust::shacl::shapes::ShapesGraph;
+//! use rdf4rust::shacl::validation::Validator;
+//! use rdf4rust::rdf::database::{Database, GraphPattern};
+//! use rdf4rust::rdf::graph::{GraphType, Quad, Node, ResourceNode};
+//! use rdf4rust::rdf::node_factory::{BlankNode, IRIResource, Literal, iri};
+//!
+//! let ex = |s: &str| iri(&format!("http://example.com/{}", s));
+//! let sh = |s: &str| iri(&format!("http://www.w3.org/ns/shacl#{}", s));
+//! let shapes = ResourceNode::IRINode { iri: ex("shapes") };
+//! let person_shape = ResourceNode::IRINode { iri: ex("PersonShape") };
+//! let age_shape = ResourceNode::BNode { bnode: BlankNode::generate_blank_node() };
+//!
+//! let mut database = Database::new(GraphType::IndexedGraph);
+//! let mut add = |s: ResourceNode, p: IRIResource, o: Node, g: Option<ResourceNode>| database.add_quad(Quad::create(s, p, o, g));
+//! // ex:PersonShape sh:targetClass ex:Person ; sh:property [ sh:path ex:age ; sh:maxCount 1 ; sh:datatype xsd:integer ] .
+//! add(person_shape.clone(), sh("targetClass"), Node::IRINode { iri: ex("Person") }, Some(shapes.clone()));
+//! add(person_shape.clone(), sh("property"), Node::from(age_shape.clone()), Some(shapes.clone()));
+//! add(age_shape.clone(), sh("path"), Node::IRINode { iri: ex("age") }, Some(shapes.clone()));
+//! add(age_shape.clone(), sh("maxCount"), Node::LiteralNode { literal: Literal::from(1) }, Some(shapes.clone()));
+//! add(age_shape.clone(), sh("datatype"), Node::IRINode { iri: iri("http://www.w3.org/2001/XMLSchema#integer") }, Some(shapes.clone()));
+//!
+//! let alice = ResourceNode::IRINode { iri: ex("alice") };
+//! add(alice.clone(), iri("http://www.w3.org/1999/02/22-rdf-syntax-ns#type"), Node::IRINode { iri: ex("Person") }, None);
+//! add(alice.clone(), ex("age"), Node::LiteralNode { literal: Literal::from("thirty") }, None);
+//!
+//! let shapes_graph = ShapesGraph::from_database(&database, &GraphPattern::NamedGraph { name: shapes }).expect("Is a valid shapes graph");
+//! let report = Validator::new(&shapes_graph).validate_database(&database, &GraphPattern::DefaultGraph);
+//! assert!(!report.conforms());
+//! let result = &report.get_results()[0];
+//! assert!(result.get_focus_node() == &Node::from(alice));
+//! assert!(result.get_value() == Some(&Node::LiteralNode { literal: Literal::from("thirty") }));
+//! assert!(result.get_source_shape() == &age_shape);
+//! assert!(result.get_source_constraint_component() == &sh("DatatypeConstraintComponent"));
+//!
+//! // the report as sh:ValidationReport graph
+//! report.add_to_database(&mut database, Some(&ResourceNode::IRINode { iri: ex("report") }));
+//! assert_eq!(10, database.quads_for_pattern(None, None, None, &GraphPattern::NamedGraph { name: ResourceNode::IRINode { iri: ex("report") } }).len());
+//! ```
+//!
+
+use crate::rdf::database::{Database, GraphPattern};
+use crate::rdf::graph::{Graph, Node, ResourceNode, Statement};
+use crate::rdf::node_factory::{rdf, BlankNode, IRIResource, Literal, RDFNode};
+use crate::reasoning::builtins::compare;
+use crate::reasoning::rules::{add_to_database, merge_graphs};
+use crate::shacl::shapes::{is_instance, sh, string_value, Constraint, Path, Severity, Shape, ShapesGraph};
+use std::cmp::Ordering;
+use std::collections::HashSet;
+use std::fmt;
+
+///
+/// A single result of a validation, i.e. a violated constraint
+///
+#[derive(Clone, PartialEq, Eq, Hash)]
+pub struct ValidationResult{
+    focus_node: Node,
+    path: Option<Path>,
+    value: Option<Node>,
+    source_shape: ResourceNode,
+    source_constraint_component: IRIResource,
+    source_constraint: Option<Node>,
+    severity: Severity,
+    messages: Vec<Node>
+}
+
+impl ValidationResult{
+
+    ///
+    /// Creates a result reported by `shape`
+    ///
+    /// # Parameters
+    ///
+    /// * `shape` - The shape whose constraint is violated, the path, severity and messages are taken from it
+    /// * `component` - The IRI of the violated constraint component
+    /// * `focus_node` - The focus node which was validated
+    /// * `value` - The value node violating the constraint, if there is a single one
+    ///
+    pub fn new(shape: &Shape, component: IRIResource, focus_node: Node, value: Option<Node>) -> ValidationResult{
+        ValidationResult{
+            focus_node,
+            path: shape.get_path().cloned(),
+            value,
+            source_shape: shape.get_id().clone(),
+            source_constraint_component: component,
+            source_constraint: None,
+            severity: shape.get_severity().clone(),
+            messages: shape.get_messages().clone()
+        }
+    }
+
+    /// Sets the path of the result, e.g. to the property of a closed shape
+    pub fn with_path(mut self, path: Option<Path>) -> Self{
+        self.path = path;
+        self
+    }
+
+    /// Sets the node of the SPARQL-based constraint which reported the result
+    pub fn with_source_constraint(mut self, constraint: Option<Node>) -> Self{
+        self.source_constraint = constraint;
+        self
+    }
+
+    /// Replaces the messages of the shape, e.g. by the messages of a SPARQL-based constraint
+    pub fn with_messages(mut self, messages: Vec<Node>) -> Self{
+        self.messages = messages;
+        self
+    }
+
+    pub fn get_focus_node(&self) -> &Node{
+        &self.focus_node
+    }
+
+    pub fn get_path(&self) -> Option<&Path>{
+        self.path.as_ref()
+    }
+
+    pub fn get_value(&self) -> Option<&Node>{
+        self.value.as_ref()
+    }
+
+    pub fn get_source_shape(&self) -> &ResourceNode{
+        &self.source_shape
+    }
+
+    pub fn get_source_constraint_component(&self) -> &IRIResource{
+        &self.source_constraint_component
+    }
+
+    /// Gets the node of the SPARQL-based constraint, None for other constraints
+    pub fn get_source_constraint(&self) -> Option<&Node>{
+        self.source_constraint.as_ref()
+    }
+
+    pub fn get_severity(&self) -> &Severity{
+        &self.severity
+    }
+
+    pub fn get_messages(&self) -> &Vec<Node>{
+        &self.messages
+    }
+
+    /// Writes the result as `sh:ValidationResult` with a new blank node, which is returned
+    fn to_statements(&self, stmts: &mut Vec<Statement>) -> ResourceNode{
+        let result = ResourceNode::BNode { bnode: BlankNode::generate_blank_node() };
+        stmts.push(Statement::create(result.clone(), rdf("type"), Node::IRINode { iri: sh("ValidationResult") }));
+        stmts.push(Statement::create(result.clone(), sh("focusNode"), self.focus_node.clone()));
+        if let Some(path) = &self.path {
+            let path = path.to_rdf(stmts);
+            stmts.push(Statement::create(result.clone(), sh("resultPath"), path));
+        }
+        if let Some(value) = &self.value {
+            stmts.push(Statement::create(result.clone(), sh("value"), value.clone()));
+        }
+        stmts.push(Statement::create(result.clone(), sh("resultSeverity"), Node::IRINode { iri: self.severity.get_iri() }));
+        stmts.push(Statement::create(result.clone(), sh("sourceConstraintComponent"), Node::IRINode { iri: self.source_constraint_component.clone() }));
+        stmts.push(Statement::create(result.clone(), sh("sourceShape"), Node::from(self.source_shape.clone())));
+        if let Some(constraint) = &self.source_constraint {
+            stmts.push(Statement::create(result.clone(), sh("sourceConstraint"), constraint.clone()));
+        }
+        for message in &self.messages {
+            stmts.push(Statement::create(result.clone(), sh("resultMessage"), message.clone()));
+        }
+        result
+    }
+}
+
+impl fmt::Display for ValidationResult{
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{} of shape {} at focus node {}", self.source_constraint_component.as_string(true), self.source_shape.as_string(), self.focus_node.as_string())?;
+        if let Some(path) = &self.path {
+            write!(f, " with path {}", path)?;
+        }
+        if let Some(value) = &self.value {
+            write!(f, " by value {}", value.as_string())?;
+        }
+        Ok(())
+    }
+}
+
+///
+/// The results of a validation
+///
+#[derive(Clone, Default, PartialEq, Eq)]
+pub struct ValidationReport{
+    results: Vec<ValidationResult>
+}
+
+impl ValidationReport{
+
+    pub fn new(results: Vec<ValidationResult>) -> ValidationReport{
+        ValidationReport{ results }
+    }
+
+    /// Checks if the data graph conforms to the shapes, i.e. there are no results of any severity
+    pub fn conforms(&self) -> bool{
+        self.results.is_empty()
+    }
+
+    pub fn get_results(&self) -> &Vec<ValidationResult>{
+        &self.results
+    }
+
+    ///
+    /// Writes the report as `sh:ValidationReport` graph with new blank nodes for the report, its results and their paths
+    ///
+    pub fn to_statements(&self) -> Vec<Statement>{
+        let report = ResourceNode::BNode { bnode: BlankNode::generate_blank_node() };
+        let mut stmts = vec![
+            Statement::create(report.clone(), rdf("type"), Node::IRINode { iri: sh("ValidationReport") }),
+            Statement::create(report.clone(), sh("conforms"), Node::LiteralNode { literal: Literal::from(self.conforms()) })
+        ];
+        for result in &self.results {
+            let result = result.to_statements(&mut stmts);
+            stmts.push(Statement::create(report.clone(), sh("result"), Node::from(result)));
+        }
+        stmts
+    }
+
+    /// Adds the `sh:ValidationReport` graph to `graph`
+    pub fn add_to_graph(&self, graph: &mut dyn Graph){
+        for stmt in self.to_statements() {
+            graph.add_statement(stmt);
+        }
+    }
+
+    ///
+    /// Adds the `sh:ValidationReport` graph to `database`
+    ///
+    /// # Parameters
+    ///
+    /// * `database` - The database the report is added to
+    /// * `graph` - The named graph the report is added to, None for the default graph
+    ///
+    pub fn add_to_database(&self, database: &mut Database, graph: Option<&ResourceNode>){
+        add_to_database(database, &self.to_statements(), graph);
+    }
+}
+
+///
+/// Validates data graphs against the shapes of a shapes graph
+///
+pub struct Validator<'a>{
+    shapes: &'a ShapesGraph
+}
+
+impl<'a> Validator<'a>{
+
+    pub fn new(shapes: &'a ShapesGraph) -> Validator<'a>{
+        Validator{ shapes }
+    }
+
+    pub fn get_shapes(&self) -> &ShapesGraph{
+        self.shapes
+    }
+
+    ///
+    /// Validates the focus nodes of all shapes with targets in `data`
+    ///
+    /// # Returns
+    ///
+    /// The report with the results in the order of the shapes and their constraints, each result once
+    ///
+    pub fn validate(&self, data: &dyn Graph) -> ValidationReport{
+        let mut context = Context{ shapes: self.shapes, data, visiting: HashSet::new() };
+        let mut results = Vec::new();
+        for shape in self.shapes.get_shapes().iter().filter(|shape| !shape.is_deactivated()) {
+            for target in shape.get_targets() {
+                for focus in target.get_focus_nodes(data) {
+                    context.validate_shape(shape, &focus, &mut results);
+                }
+            }
+        }
+        let mut seen = HashSet::new();
+        results.retain(|result| seen.insert(result.clone()));
+        ValidationReport::new(results)
+    }
+
+    ///
+    /// Validates the merge of the graphs of `database` matching `data`
+    ///
+    pub fn validate_database(&self, database: &Database, data: &GraphPattern) -> ValidationReport{
+        self.validate(&merge_graphs(database, data))
+    }
+
+    ///
+    /// Checks if `node` conforms to the shape `shape` in `data`, shapes which are not in the shapes graph have no constraints
+    ///
+    pub fn conforms(&self, data: &dyn Graph, node: &Node, shape: &ResourceNode) -> bool{
+        Context{ shapes: self.shapes, data, visiting: HashSet::new() }.conforms(node, shape)
+    }
+}
+
+/// The state of a validation
+struct Context<'a>{
+    shapes: &'a ShapesGraph,
+    data: &'a dyn Graph,
+    /// The pairs of shape and focus node currently being validated
+    visiting: HashSet<(ResourceNode, Node)>
+}
+
+impl<'a> Context<'a>{
+
+    fn conforms(&mut self, node: &Node, shape: &ResourceNode) -> bool{
+        let shape = match self.shapes.get_shape(shape) {
+            Some(shape) => shape,
+            None => return true
+        };
+        let mut results = Vec::new();
+        self.validate_shape(shape, node, &mut results);
+        results.is_empty()
+    }
+
+    fn validate_shape(&mut self, shape: &Shape, focus: &Node, results: &mut Vec<ValidationResult>){
+        if shape.is_deactivated() || !self.visiting.insert((shape.get_id().clone(), focus.clone())) {
+            return
+        }
+        let values = match shape.get_path() {
+            Some(path) => path.evaluate(self.data, focus),
+            None => vec![focus.clone()]
+        };
+        for constraint in shape.get_constraints() {
+            self.validate_constraint(shape, constraint, focus, &values, results);
+        }
+        self.visiting.remove(&(shape.get_id().clone(), focus.clone()));
+    }
+
+    fn validate_constraint(&mut self, shape: &Shape, constraint: &Constraint, focus: &Node, values: &[Node], results: &mut Vec<ValidationResult>){
+        let data = self.data;
+        let component = constraint.get_component();
+        let result = |value: Option<&Node>| ValidationResult::new(shape, component.clone(), focus.clone(), value.cloned());
+        let failing = |fails: &mut dyn FnMut(&Node) -> bool| values.iter().filter(|value| fails(value)).map(|value| result(Some(value))).collect::<Vec<_>>();
+        let range = |value: &Node, bound: &Node, allowed: &[Ordering]| !compare(value, bound).is_some_and(|ordering| allowed.contains(&ordering));
+        let length = |value: &Node| string_value(value).map(|string| string.chars().count());
+        let others = |property: &IRIResource| Path::Predicate(property.clone()).evaluate(data, focus);
+        let new = match constraint {
+            Constraint::Class(class) => failing(&mut |value| !is_instance(data, value, class)),
+            Constraint::Datatype(datatype) => failing(&mut |value| match value {
+                Node::LiteralNode { literal } => literal.get_datatype().get_value() != &datatype.get_value() || literal.value().is_err(),
+                _ => true
+            }),
+            Constraint::NodeKind(kind) => failing(&mut |value| !kind.matches(value)),
+            Constraint::MinCount(count) => if values.len() < *count {vec![result(None)]} else {Vec::new()},
+            Constraint::MaxCount(count) => if values.len() > *count {vec![result(None)]} else {Vec::new()},
+            Constraint::MinExclusive(bound) => failing(&mut |value| range(value, bound, &[Ordering::Greater])),
+            Constraint::MinInclusive(bound) => failing(&mut |value| range(value, bound, &[Ordering::Greater, Ordering::Equal])),
+            Constraint::MaxExclusive(bound) => failing(&mut |value| range(value, bound, &[Ordering::Less])),
+            Constraint::MaxInclusive(bound) => failing(&mut |value| range(value, bound, &[Ordering::Less, Ordering::Equal])),
+            Constraint::MinLength(min) => failing(&mut |value| length(value).is_none_or(|length| length < *min)),
+            Constraint::MaxLength(max) => failing(&mut |value| length(value).is_none_or(|length| length > *max)),
+            Constraint::Pattern(regex) => failing(&mut |value| !string_value(value).is_some_and(|string| regex.is_match(&string))),
+            Constraint::LanguageIn(ranges) => failing(&mut |value| match value {
+                Node::LiteralNode { literal } => !ranges.iter().any(|range| literal.get_lang().is_some() && literal.lang_matches(range)),
+                _ => true
+            }),
+            Constraint::UniqueLang => {
+                let mut counts: Vec<(String, usize)> = Vec::new();
+                for lang in values.iter().filter_map(|value| value.as_literal().ok()?.get_lang().as_ref()) {
+                    let lang = lang.to_lowercase();
+                    match counts.iter_mut().find(|(other, _)| other == &lang) {
+                        Some((_, count)) => *count += 1,
+                        None => counts.push((lang, 1))
+                    }
+                }
+                counts.iter().filter(|(_, count)| *count > 1).map(|_| result(None)).collect()
+            }
+            Constraint::Equals(property) => {
+                let others = others(property);
+                let mut new = failing(&mut |value| !others.contains(value));
+                new.extend(others.iter().filter(|other| !values.contains(other)).map(|other| result(Some(other))));
+                new
+            }
+            Constraint::Disjoint(property) => {
+                let others = others(property);
+                failing(&mut |value| others.contains(value))
+            }
+            Constraint::LessThan(property) | Constraint::LessThanOrEquals(property) => {
+                let others = others(property);
+                let allowed: &[Ordering] = if let Constraint::LessThan(_) = constraint {&[Ordering::Less]} else {&[Ordering::Less, Ordering::Equal]};
+                failing(&mut |value| others.iter().any(|other| range(value, other, allowed)))
+            }
+            Constraint::Not(negated) => failing(&mut |value| self.conforms(value, negated)),
+            Constraint::And(shapes) => failing(&mut |value| !shapes.iter().all(|shape| self.conforms(value, shape))),
+            Constraint::Or(shapes) => failing(&mut |value| !shapes.iter().any(|shape| self.conforms(value, shape))),
+            Constraint::Xone(shapes) => failing(&mut |value| shapes.iter().filter(|shape| self.conforms(value, shape)).count() != 1),
+            Constraint::Node(node) => failing(&mut |value| !self.conforms(value, node)),
+            Constraint::Property(property) => {
+                if let Some(property) = self.shapes.get_shape(property) {
+                    for value in values {
+                        self.validate_shape(property, value, results);
+                    }
+                }
+                Vec::new()
+            }
+            Constraint::QualifiedMinCount { shape: qualified, count, siblings } | Constraint::QualifiedMaxCount { shape: qualified, count, siblings } => {
+                let conforming = values.iter()
+                    .filter(|value| self.conforms(value, qualified) && !siblings.iter().any(|sibling| self.conforms(value, sibling)))
+                    .count();
+                let violated = match constraint {
+                    Constraint::QualifiedMinCount { .. } => conforming < *count,
+                    _ => conforming > *count
+                };
+                if violated {vec![result(None)]} else {Vec::new()}
+            }
+            Constraint::Closed(allowed) => {
+                let mut new = Vec::new();
+                for value in values {
+                    let subject = match value.to_resource() {
+                        Some(subject) => subject,
+                        None => continue
+                    };
+                    for stmt in data.list_statements(Some(subject), None, None) {
+                        if !allowed.contains(stmt.get_predicate()) {
+                            new.push(result(Some(stmt.get_object())).with_path(Some(Path::Predicate(stmt.get_predicate().clone()))));
+                        }
+                    }
+                }
+                new
+            }
+            Constraint::HasValue(expected) => if values.contains(expected) {Vec::new()} else {vec![result(None)]},
+            Constraint::In(members) => failing(&mut |value| !members.contains(value)),
+            Constraint::Sparql(sparql) => sparql.validate(data, shape, focus),
+            Constraint::Component(component) => component.validate(data, shape, focus, values)
+        };
+        results.extend(new);
+    }
+}
+
@@ -0,0 +1,788 @@
+//!
+//! A recursive descent parser of the SPARQL subset of `Query`.
+//!
+
+use crate::error::{ErrorKind, RDFError};
+use crate::io::reader::parse_object;
+use crate::rdf::graph::Node;
+use crate::rdf::node_factory::{rdf, BlankNode, IRIResource};
+use crate::rdf::term::{closing_quote, literal_length, Term};
+use crate::shacl::shapes::Path;
+use crate::sparql::query::{Expression, Function, Operator, Pattern, Query, QueryForm};
+use crate::util::iri::IRI;
+use std::collections::{HashMap, HashSet};
+
+/// Parses `query` with the predeclared `prefixes`
+pub(crate) fn parse(query: &str, prefixes: &HashMap<String, String>) -> Result<Query, RDFError>{
+    QueryParser{
+        text: query,
+        pos: 0,
+        prefixes: prefixes.clone(),
+        blank_nodes: 0,
+        template: false
+    }.query()
+}
+
+/// A recursive descent parser of queries
+struct QueryParser<'a>{
+    text: &'a str,
+    pos: usize,
+    prefixes: HashMap<String, String>,
+    /// The number of anonymous blank nodes so far
+    blank_nodes: usize,
+    /// Whether a `CONSTRUCT` template is parsed, where blank nodes are nodes and not variables
+    template: bool
+}
+
+impl<'a> QueryParser<'a>{
+
+    fn query(&mut self) -> Result<Query, RDFError>{
+        self.prologue()?;
+        let pos = self.pos;
+        let mut projections = Vec::new();
+        let (form, mut pattern) = if self.keyword("SELECT") {
+            let distinct = self.keyword("DISTINCT") || self.keyword("REDUCED");
+            let variables = if self.eat("*") {
+                None
+            } else {
+                let mut variables = Vec::new();
+                loop {
+                    if self.peek() == Some('(') {
+                        self.pos += 1;
+                        let expression = self.expression()?;
+                        self.expect_keyword("AS")?;
+                        let variable = self.variable()?;
+                        self.expect(")")?;
+                        projections.push(Pattern::Bind(expression, variable.clone()));
+                        variables.push(variable);
+                    } else if matches!(self.peek(), Some('?' | '$')) {
+                        variables.push(self.variable()?);
+                    } else {
+                        break
+                    }
+                }
+                if variables.is_empty() {
+                    return Err(self.unexpected("a variable or '*'"))
+                }
+                Some(variables)
+            };
+            self.keyword("WHERE");
+            (QueryForm::Select { distinct, variables }, self.group()?)
+        } else if self.keyword("ASK") {
+            self.keyword("WHERE");
+            (QueryForm::Ask, self.group()?)
+        } else if self.keyword("CONSTRUCT") {
+            if self.keyword("WHERE") {
+                let pattern = self.group()?;
+                let mut template = Vec::new();
+                for element in &pattern {
+                    match element {
+                        Pattern::Triple { subject, predicate, object } => template.push((subject.clone(), predicate.clone(), object.clone())),
+                        _ => return Err(self.error(ErrorKind::InvalidQuery, String::from("CONSTRUCT WHERE only allows triple patterns."), pos))
+                    }
+                }
+                (QueryForm::Construct(template), pattern)
+            } else {
+                (QueryForm::Construct(self.template()?), {
+                    self.expect_keyword("WHERE")?;
+                    self.group()?
+                })
+            }
+        } else if self.keyword("DESCRIBE") {
+            return Err(self.error(ErrorKind::InvalidQuery, String::from("DESCRIBE queries are not supported."), pos))
+        } else {
+            return Err(self.unexpected("SELECT, ASK or CONSTRUCT"))
+        };
+        let pos = self.pos;
+        if self.keyword("GROUP") || self.keyword("HAVING") {
+            return Err(self.error(ErrorKind::InvalidQuery, String::from("Aggregates are not supported."), pos))
+        }
+        let mut order = Vec::new();
+        if self.keyword("ORDER") {
+            self.expect_keyword("BY")?;
+            loop {
+                let descending = if self.keyword("DESC") {
+                    true
+                } else {
+                    self.keyword("ASC");
+                    false
+                };
+                let expression = match self.peek() {
+                    Some('?' | '$') => Expression::Variable(self.variable()?),
+                    Some(_) if !self.at_keyword("LIMIT") && !self.at_keyword("OFFSET") && !self.at_keyword("VALUES") => self.primary()?,
+                    _ => break
+                };
+                order.push((expression, descending));
+            }
+            if order.is_empty() {
+                return Err(self.unexpected("an order condition"))
+            }
+        }
+        let mut limit = None;
+        let mut offset = 0;
+        loop {
+            if self.keyword("LIMIT") {
+                limit = Some(self.integer()?);
+            } else if self.keyword("OFFSET") {
+                offset = self.integer()?;
+            } else {
+                break
+            }
+        }
+        if self.keyword("VALUES") {
+            let values = self.values()?;
+            pattern = vec![Pattern::Group(pattern), values];
+        }
+        pattern.extend(projections);
+        if self.peek().is_some() {
+            return Err(self.unexpected("the end of the query"))
+        }
+        Ok(Query::new(form, pattern, order, limit, offset))
+    }
+
+    /// Parses the `BASE` and `PREFIX` declarations
+    fn prologue(&mut self) -> Result<(), RDFError>{
+        loop {
+            let pos = self.pos;
+            if self.keyword("BASE") {
+                return Err(self.error(ErrorKind::InvalidQuery, String::from("BASE is not supported, IRIs have to be absolute."), pos))
+            }
+            if !self.keyword("PREFIX") {
+                return Ok(())
+            }
+            self.skip();
+            let pos = self.pos;
+            let prefix = match self.word().strip_suffix(':') {
+                Some(prefix) if !prefix.contains(':') => prefix,
+                _ => return Err(self.error(ErrorKind::Syntax, String::from("Expected a prefix name followed by ':'."), pos))
+            };
+            self.skip();
+            let pos = self.pos;
+            let namespace = match self.iri_token() {
+                Some(namespace) => &namespace[1..namespace.len() - 1],
+                None => return Err(self.error(ErrorKind::Syntax, format!("Expected the IRI of prefix {}.", prefix), pos))
+            };
+            if let Err(err) = IRI::create_iri(&String::from(namespace)) {
+                return Err(self.locate(err, pos))
+            }
+            self.prefixes.insert(String::from(prefix), String::from(namespace));
+        }
+    }
+
+    /// Parses the triples of a `CONSTRUCT` template in braces
+    fn template(&mut self) -> Result<Vec<(Term, Term, Term)>, RDFError>{
+        self.expect("{")?;
+        self.template = true;
+        let mut patterns = Vec::new();
+        while self.peek() != Some('}') {
+            self.triples(&mut patterns)?;
+            if !self.eat(".") {
+                break
+            }
+        }
+        self.template = false;
+        self.expect("}")?;
+        patterns.into_iter()
+            .map(|pattern| match pattern {
+                Pattern::Triple { subject, predicate, object } => Ok((subject, predicate, object)),
+                _ => Err(self.error(ErrorKind::InvalidQuery, String::from("Templates can't contain property paths."), self.pos))
+            })
+            .collect()
+    }
+
+    /// Parses a group graph pattern in braces
+    fn group(&mut self) -> Result<Vec<Pattern>, RDFError>{
+        self.expect("{")?;
+        let mut patterns = Vec::new();
+        loop {
+            let pos = self.pos;
+            match self.peek() {
+                Some('}') => break,
+                Some('{') => {
+                    let mut groups = vec![self.group()?];
+                    while self.keyword("UNION") {
+                        groups.push(self.group()?);
+                    }
+                    patterns.push(if groups.len() == 1 {Pattern::Group(groups.remove(0))} else {Pattern::Union(groups)});
+                }
+                Some('.') => self.pos += 1,
+                Some(_) if self.keyword("OPTIONAL") => patterns.push(Pattern::Optional(self.group()?)),
+                Some(_) if self.keyword("MINUS") => patterns.push(Pattern::Minus(self.group()?)),
+                Some(_) if self.keyword("FILTER") => patterns.push(Pattern::Filter(self.constraint()?)),
+                Some(_) if self.keyword("VALUES") => patterns.push(self.values()?),
+                Some(_) if self.keyword("BIND") => {
+                    self.expect("(")?;
+                    let expression = self.expression()?;
+                    self.expect_keyword("AS")?;
+                    let pos = self.pos;
+                    let variable = self.variable()?;
+                    self.expect(")")?;
+                    if in_scope(&patterns).contains(&variable) {
+                        return Err(self.error(ErrorKind::InvalidQuery, format!("?{} is already bound when it's bound by BIND.", variable), pos))
+                    }
+                    patterns.push(Pattern::Bind(expression, variable));
+                }
+                Some(_) if self.keyword("SELECT") => return Err(self.error(ErrorKind::InvalidQuery, String::from("Subqueries are not supported."), pos)),
+                Some(_) if self.keyword("GRAPH") || self.keyword("SERVICE") => {
+                    return Err(self.error(ErrorKind::InvalidQuery, format!("{} is not supported.", self.text[pos..self.pos].trim().to_uppercase()), pos))
+                }
+                Some(_) => self.triples(&mut patterns)?,
+                None => return Err(self.unexpected("'}'"))
+            }
+        }
+        self.pos += 1;
+        Ok(patterns)
+    }
+
+    /// Parses the triples of a subject with its property list, `;` and `,` abbreviations included
+    fn triples(&mut self, patterns: &mut Vec<Pattern>) -> Result<(), RDFError>{
+        let subject = if self.eat("[") {
+            let subject = self.anonymous();
+            if self.peek() != Some(']') {
+                self.properties(&subject, patterns)?;
+            }
+            self.expect("]")?;
+            if matches!(self.peek(), Some('.' | '}') | None) {
+                return Ok(())
+            }
+            subject
+        } else {
+            self.term()?
+        };
+        self.properties(&subject, patterns)
+    }
+
+    /// Parses a property list of `subject`
+    fn properties(&mut self, subject: &Term, patterns: &mut Vec<Pattern>) -> Result<(), RDFError>{
+        loop {
+            let predicate = match self.peek() {
+                Some('?' | '$') => Term::Variable(self.variable()?),
+                _ => match self.path()? {
+                    Path::Predicate(iri) => Term::iri(iri),
+                    path if !self.template => {
+                        loop {
+                            let object = self.object(patterns)?;
+                            patterns.push(Pattern::Path { subject: subject.clone(), path: path.clone(), object });
+                            if !self.eat(",") {
+                                break
+                            }
+                        }
+                        if self.next_property()? {continue} else {return Ok(())}
+                    }
+                    _ => return Err(self.error(ErrorKind::InvalidQuery, String::from("Templates can't contain property paths."), self.pos))
+                }
+            };
+            loop {
+                let object = self.object(patterns)?;
+                patterns.push(Pattern::Triple { subject: subject.clone(), predicate: predicate.clone(), object });
+                if !self.eat(",") {
+                    break
+                }
+            }
+            if !self.next_property()? {
+                return Ok(())
+            }
+        }
+    }
+
+    /// Skips the `;` between properties, true if another property follows
+    fn next_property(&mut self) -> Result<bool, RDFError>{
+        if !self.eat(";") {
+            return Ok(false)
+        }
+        while self.eat(";") {}
+        Ok(!matches!(self.peek(), Some('.' | '}' | ']') | None))
+    }
+
+    /// Parses an object, which may be a blank node property list adding its triples to `patterns`
+    fn object(&mut self, patterns: &mut Vec<Pattern>) -> Result<Term, RDFError>{
+        if !self.eat("[") {
+            return self.term()
+        }
+        let object = self.anonymous();
+        if self.peek() != Some(']') {
+            self.properties(&object, patterns)?;
+        }
+        self.expect("]")?;
+        Ok(object)
+    }
+
+    /// A new anonymous blank node, a variable unless in a template
+    fn anonymous(&mut self) -> Term{
+        self.blank_nodes += 1;
+        let label = format!("anon{}", self.blank_nodes);
+        if self.template {
+            Term::Constant(Node::BNode { bnode: BlankNode::create_blank_node(&label) })
+        } else {
+            Term::Variable(format!("_:{}", label))
+        }
+    }
+
+    /// Parses a property path, `a` included
+    fn path(&mut self) -> Result<Path, RDFError>{
+        let mut alternatives = vec![self.sequence()?];
+        while self.eat("|") {
+            alternatives.push(self.sequence()?);
+        }
+        Ok(if alternatives.len() == 1 {alternatives.remove(0)} else {Path::Alternative(alternatives)})
+    }
+
+    fn sequence(&mut self) -> Result<Path, RDFError>{
+        let mut paths = vec![self.path_element()?];
+        while self.eat("/") {
+            paths.push(self.path_element()?);
+        }
+        Ok(if paths.len() == 1 {paths.remove(0)} else {Path::Sequence(paths)})
+    }
+
+    /// Parses a possibly inverse and repeated path primary
+    fn path_element(&mut self) -> Result<Path, RDFError>{
+        let inverse = self.eat("^");
+        let pos = self.pos;
+        let primary = match self.peek() {
+            Some('(') => {
+                self.pos += 1;
+                let path = self.path()?;
+                self.expect(")")?;
+                path
+            }
+            Some('!') => return Err(self.error(ErrorKind::InvalidQuery, String::from("Negated property sets are not supported."), pos)),
+            _ => Path::Predicate(self.iri()?)
+        };
+        let rest = &self.text[self.pos..];
+        let path = if rest.starts_with('*') {
+            self.pos += 1;
+            Path::ZeroOrMore(Box::new(primary))
+        } else if rest.starts_with('+') {
+            self.pos += 1;
+            Path::OneOrMore(Box::new(primary))
+        } else if rest.starts_with('?') && !rest[1..].starts_with(is_name_char) {
+            self.pos += 1;
+            Path::ZeroOrOne(Box::new(primary))
+        } else {
+            primary
+        };
+        Ok(if inverse {Path::Inverse(Box::new(path))} else {path})
+    }
+
+    /// Parses an IRI, prefixed name or `a`
+    fn iri(&mut self) -> Result<IRIResource, RDFError>{
+        self.skip();
+        let pos = self.pos;
+        match self.term()? {
+            Term::Constant(Node::IRINode { iri }) => Ok(iri),
+            _ => Err(self.error(ErrorKind::Syntax, format!("Expected an IRI, but found '{}'.", &self.text[pos..self.pos]), pos))
+        }
+    }
+
+    /// Parses a variable, an IRI, a blank node or a literal
+    fn term(&mut self) -> Result<Term, RDFError>{
+        self.skip();
+        let pos = self.pos;
+        let rest = &self.text[pos..];
+        if rest.starts_with(['?', '$']) {
+            return self.variable().map(Term::Variable)
+        }
+        if rest.starts_with(['"', '\'']) {
+            if rest.starts_with("\"\"\"") || rest.starts_with("'''") {
+                return Err(self.error(ErrorKind::InvalidQuery, String::from("Long literals are not supported."), pos))
+            }
+            if closing_quote(rest).is_none() {
+                return Err(self.error(ErrorKind::Syntax, String::from("Literal is not closed."), pos))
+            }
+            self.pos += literal_length(rest);
+        } else if self.iri_token().is_none() {
+            let word = self.word();
+            if word.is_empty() {
+                return Err(self.unexpected("a term"))
+            }
+            if word == "a" {
+                return Ok(Term::iri(rdf("type")))
+            }
+            if let Some(label) = word.strip_prefix("_:") {
+                return Ok(if self.template {
+                    Term::Constant(Node::BNode { bnode: BlankNode::create_blank_node(label) })
+                } else {
+                    Term::Variable(String::from(word))
+                })
+            }
+            if !word.contains(':') && !["true", "false"].contains(&word) && !word.starts_with(|c: char| c.is_ascii_digit() || "+-.".contains(c)) {
+                return Err(self.error(ErrorKind::Syntax, format!("Expected a term, but found '{}'.", word), pos))
+            }
+        }
+        parse_object(&self.text[pos..self.pos], &self.prefixes, false).map(Term::Constant).map_err(|err| self.locate(err, pos))
+    }
+
+    /// Parses `?name` or `$name` and returns the name
+    fn variable(&mut self) -> Result<String, RDFError>{
+        self.skip();
+        let pos = self.pos;
+        let rest = &self.text[pos..];
+        if !rest.starts_with(['?', '$']) {
+            return Err(self.unexpected("a variable"))
+        }
+        let len = rest[1..].find(|c: char| !(c.is_alphanumeric() || c == '_')).unwrap_or(rest.len() - 1);
+        if len == 0 {
+            return Err(self.error(ErrorKind::Syntax, String::from("Expected the name of a variable."), pos))
+        }
+        self.pos += len + 1;
+        Ok(String::from(&rest[1..len + 1]))
+    }
+
+    /// Parses a non-negative integer of `LIMIT` or `OFFSET`
+    fn integer(&mut self) -> Result<usize, RDFError>{
+        self.skip();
+        let pos = self.pos;
+        let word = self.word();
+        word.parse().map_err(|_| self.error(ErrorKind::Syntax, format!("Expected a non-negative integer, but found '{}'.", word), pos))
+    }
+
+    /// Parses `VALUES` after the keyword
+    fn values(&mut self) -> Result<Pattern, RDFError>{
+        let variables = if self.eat("(") {
+            let mut variables = Vec::new();
+            while self.peek() != Some(')') {
+                variables.push(self.variable()?);
+            }
+            self.pos += 1;
+            variables
+        } else {
+            vec![self.variable()?]
+        };
+        self.expect("{")?;
+        let mut rows = Vec::new();
+        while !self.eat("}") {
+            let pos = self.pos;
+            let row = if variables.len() == 1 && self.peek() != Some('(') {
+                vec![self.value()?]
+            } else {
+                self.expect("(")?;
+                let mut row = Vec::new();
+                while !self.eat(")") {
+                    row.push(self.value()?);
+                }
+                row
+            };
+            if row.len() != variables.len() {
+                return Err(self.error(ErrorKind::Syntax, format!("Expected {} values.", variables.len()), pos))
+            }
+            rows.push(row);
+        }
+        Ok(Pattern::Values(variables, rows))
+    }
+
+    /// Parses a data value of `VALUES`, None for `UNDEF`
+    fn value(&mut self) -> Result<Option<Node>, RDFError>{
+        if self.keyword("UNDEF") {
+            return Ok(None)
+        }
+        let pos = self.pos;
+        match self.term()? {
+            Term::Constant(node) if !node.is_bnode() => Ok(Some(node)),
+            _ => Err(self.error(ErrorKind::Syntax, String::from("Expected an IRI, a literal or UNDEF."), pos))
+        }
+    }
+
+    /// Parses the constraint of a `FILTER`, a bracketted expression or a function call
+    fn constraint(&mut self) -> Result<Expression, RDFError>{
+        if self.peek() == Some('(') {
+            self.pos += 1;
+            let expression = self.expression()?;
+            self.expect(")")?;
+            return Ok(expression)
+        }
+        self.primary()
+    }
+
+    fn expression(&mut self) -> Result<Expression, RDFError>{
+        let mut expression = self.conjunction()?;
+        while self.eat("||") {
+            expression = Expression::Binary(Operator::Or, Box::new(expression), Box::new(self.conjunction()?));
+        }
+        Ok(expression)
+    }
+
+    fn conjunction(&mut self) -> Result<Expression, RDFError>{
+        let mut expression = self.relation()?;
+        while self.eat("&&") {
+            expression = Expression::Binary(Operator::And, Box::new(expression), Box::new(self.relation()?));
+        }
+        Ok(expression)
+    }
+
+    fn relation(&mut self) -> Result<Expression, RDFError>{
+        let expression = self.sum()?;
+        let operators = [("<=", Operator::LessOrEqual), (">=", Operator::GreaterOrEqual), ("!=", Operator::NotEqual),
+            ("=", Operator::Equal), ("<", Operator::Less), (">", Operator::Greater)];
+        for (token, operator) in operators {
+            if self.eat(token) {
+                return Ok(Expression::Binary(operator, Box::new(expression), Box::new(self.sum()?)))
+            }
+        }
+        let negated = self.keyword("NOT");
+        if negated || self.at_keyword("IN") {
+            self.expect_keyword("IN")?;
+            return Ok(Expression::In(Box::new(expression), self.arguments()?, negated))
+        }
+        Ok(expression)
+    }
+
+    fn sum(&mut self) -> Result<Expression, RDFError>{
+        let mut expression = self.product()?;
+        loop {
+            let operator = if self.eat("+") {
+                Operator::Add
+            } else if self.eat("-") {
+                Operator::Subtract
+            } else {
+                return Ok(expression)
+            };
+            expression = Expression::Binary(operator, Box::new(expression), Box::new(self.product()?));
+        }
+    }
+
+    fn product(&mut self) -> Result<Expression, RDFError>{
+        let mut expression = self.unary()?;
+        loop {
+            let operator = if self.eat("*") {
+                Operator::Multiply
+            } else if self.eat("/") {
+                Operator::Divide
+            } else {
+                return Ok(expression)
+            };
+            expression = Expression::Binary(operator, Box::new(expression), Box::new(self.unary()?));
+        }
+    }
+
+    fn unary(&mut self) -> Result<Expression, RDFError>{
+        if self.peek() == Some('!') && !self.text[self.pos..].starts_with("!=") {
+            self.pos += 1;
+            Ok(Expression::Not(Box::new(self.unary()?)))
+        } else if self.eat("-") {
+            Ok(Expression::Negate(Box::new(self.unary()?)))
+        } else {
+            self.eat("+");
+            self.primary()
+        }
+    }
+
+    /// Parses a bracketted expression, a function call, `(NOT) EXISTS`, a variable or a constant
+    fn primary(&mut self) -> Result<Expression, RDFError>{
+        let pos = self.pos;
+        match self.peek() {
+            Some('(') => {
+                self.pos += 1;
+                let expression = self.expression()?;
+                self.expect(")")?;
+                return Ok(expression)
+            }
+            Some('?' | '$') => return Ok(Expression::Variable(self.variable()?)),
+            _ => ()
+        }
+        if self.keyword("EXISTS") {
+            return Ok(Expression::Exists(self.group()?, false))
+        }
+        if self.keyword("NOT") {
+            self.expect_keyword("EXISTS")?;
+            return Ok(Expression::Exists(self.group()?, true))
+        }
+        let rest = &self.text[self.pos..];
+        let name_length = rest.find(|c: char| !(c.is_alphanumeric() || c == '_')).unwrap_or(rest.len());
+        if name_length > 0 && rest[name_length..].trim_start().starts_with('(') && !rest[..name_length].contains(':') {
+            let name = &rest[..name_length];
+            let function = match Function::from_name(name) {
+                Some(function) => function,
+                None => return Err(self.error(ErrorKind::InvalidQuery, format!("The function {} is not supported.", name), pos))
+            };
+            self.pos += name_length;
+            let arguments = self.arguments()?;
+            if !function.arity().contains(&arguments.len()) {
+                return Err(self.error(ErrorKind::Syntax, format!("Wrong number of arguments of {}.", name), pos))
+            }
+            if function == Function::Bound && !matches!(arguments[0], Expression::Variable(_)) {
+                return Err(self.error(ErrorKind::Syntax, String::from("The argument of BOUND has to be a variable."), pos))
+            }
+            return Ok(Expression::Function(function, arguments))
+        }
+        match self.term()? {
+            Term::Constant(Node::BNode { .. }) | Term::Variable(_) => Err(self.error(ErrorKind::Syntax, String::from("Blank nodes can't be used in expressions."), pos)),
+            Term::Constant(node) => {
+                if self.peek() == Some('(') {
+                    return Err(self.error(ErrorKind::InvalidQuery, String::from("Functions named by IRIs are not supported."), pos))
+                }
+                Ok(Expression::Constant(node))
+            }
+        }
+    }
+
+    /// Parses a bracketted, comma separated list of expressions
+    fn arguments(&mut self) -> Result<Vec<Expression>, RDFError>{
+        self.expect("(")?;
+        let mut arguments = Vec::new();
+        if self.eat(")") {
+            return Ok(arguments)
+        }
+        loop {
+            arguments.push(self.expression()?);
+            if self.eat(")") {
+                return Ok(arguments)
+            }
+            self.expect(",")?;
+        }
+    }
+
+    /// Reads an IRI in angle brackets, None if the text doesn't start with one
+    fn iri_token(&mut self) -> Option<&'a str>{
+        let rest = &self.text[self.pos..];
+        if !rest.starts_with('<') {
+            return None
+        }
+        let len = rest[1..].find(|c: char| c == '>' || c.is_whitespace() || "<\"{}|^`\\".contains(c))?;
+        if !rest[1..].get(len..)?.starts_with('>') {
+            return None
+        }
+        self.pos += len + 2;
+        Some(&rest[..len + 2])
+    }
+
+    /// Reads a keyword, prefixed name, blank node label or number, a trailing `.` ends the triple and isn't part of it
+    fn word(&mut self) -> &'a str{
+        let rest = &self.text[self.pos..];
+        let len = if rest.starts_with(|c: char| c.is_ascii_digit() || c == '+' || c == '-') || rest.starts_with(".") && rest[1..].starts_with(|c: char| c.is_ascii_digit()) {
+            number_length(rest)
+        } else {
+            let len = rest.find(|c: char| !(is_name_char(c) || c == ':' || c == '.' || c == '%')).unwrap_or(rest.len());
+            rest[..len].trim_end_matches('.').len()
+        };
+        self.pos += len;
+        &rest[..len]
+    }
+
+    /// Checks if the next word is `keyword` (case-insensitive) and consumes it
+    fn keyword(&mut self, keyword: &str) -> bool{
+        if self.at_keyword(keyword) {
+            self.pos += keyword.len();
+            return true
+        }
+        false
+    }
+
+    /// Checks if the next word is `keyword` (case-insensitive)
+    fn at_keyword(&mut self, keyword: &str) -> bool{
+        self.skip();
+        let rest = &self.text[self.pos..];
+        rest.get(..keyword.len()).is_some_and(|word| word.eq_ignore_ascii_case(keyword))
+            && !rest[keyword.len()..].starts_with(|c: char| is_name_char(c) || c == ':')
+    }
+
+    fn expect_keyword(&mut self, keyword: &str) -> Result<(), RDFError>{
+        if !self.keyword(keyword) {
+            return Err(self.unexpected(keyword))
+        }
+        Ok(())
+    }
+
+    /// Consumes `token` if the text continues with it
+    fn eat(&mut self, token: &str) -> bool{
+        self.skip();
+        if self.text[self.pos..].starts_with(token) {
+            self.pos += token.len();
+            return true
+        }
+        false
+    }
+
+    /// Skips whitespace and comments
+    fn skip(&mut self){
+        loop {
+            let rest = &self.text[self.pos..];
+            let trimmed = rest.trim_start();
+            self.pos += rest.len() - trimmed.len();
+            if !trimmed.starts_with('#') {
+                return
+            }
+            self.pos += trimmed.find('\n').unwrap_or(trimmed.len());
+        }
+    }
+
+    fn peek(&mut self) -> Option<char>{
+        self.skip();
+        self.text[self.pos..].chars().next()
+    }
+
+    fn expect(&mut self, expected: &str) -> Result<(), RDFError>{
+        if !self.eat(expected) {
+            return Err(self.unexpected(&format!("'{}'", expected)))
+        }
+        Ok(())
+    }
+
+    fn unexpected(&mut self, expected: &str) -> RDFError{
+        self.skip();
+        let found = match self.text[self.pos..].split_whitespace().next() {
+            Some(found) => format!("'{}'", found),
+            None => String::from("the end of the query")
+        };
+        self.error(ErrorKind::Syntax, format!("Expected {}, but found {}.", expected, found), self.pos)
+    }
+
+    fn error(&self, kind: ErrorKind, msg: String, pos: usize) -> RDFError{
+        self.locate(RDFError::new(kind, msg), pos)
+    }
+
+    /// Locates `err` at the byte position `pos` of the text
+    fn locate(&self, err: RDFError, pos: usize) -> RDFError{
+        let line_start = self.text[..pos].rfind('\n').map_or(0, |i| i + 1);
+        let line_end = self.text[pos..].find('\n').map_or(self.text.len(), |i| pos + i);
+        let line_no = self.text[..pos].matches('\n').count() + 1;
+        err.with_position(line_no, &self.text[line_start..line_end], pos - line_start).offset_by(line_start)
+    }
+}
+
+/// The length of the number `text` starts with, e.g. `-1.5e3`
+fn number_length(text: &str) -> usize{
+    let digits = |from: usize| text[from..].find(|c: char| !c.is_ascii_digit()).unwrap_or(text.len() - from);
+    let mut len = if text.starts_with(['+', '-']) {1} else {0};
+    len += digits(len);
+    if text[len..].starts_with('.') && text[len + 1..].starts_with(|c: char| c.is_ascii_digit()) {
+        len += 1 + digits(len + 1);
+    }
+    if text[len..].starts_with(['e', 'E']) {
+        let sign = if text[len + 1..].starts_with(['+', '-']) {1} else {0};
+        if text[len + 1 + sign..].starts_with(|c: char| c.is_ascii_digit()) {
+            len += 1 + sign + digits(len + 1 + sign);
+        }
+    }
+    len
+}
+
+fn is_name_char(c: char) -> bool{
+    c.is_alphanumeric() || c == '_' || c == '-'
+}
+
+/// The variables bound by the patterns of a group so far
+fn in_scope(patterns: &[Pattern]) -> HashSet<String>{
+    let mut variables = HashSet::new();
+    for pattern in patterns {
+        match pattern {
+            Pattern::Triple { subject, predicate, object } => variables.extend([subject, predicate, object].iter().filter_map(|term| variable(term))),
+            Pattern::Path { subject, object, .. } => variables.extend([subject, object].iter().filter_map(|term| variable(term))),
+            Pattern::Optional(group) | Pattern::Group(group) => variables.extend(in_scope(group)),
+            Pattern::Union(groups) => groups.iter().for_each(|group| variables.extend(in_scope(group))),
+            Pattern::Bind(_, bound) => {
+                variables.insert(bound.clone());
+            }
+            Pattern::Values(bound, _) => variables.extend(bound.iter().cloned()),
+            Pattern::Filter(_) | Pattern::Minus(_) => ()
+        }
+    }
+    variables
+}
+
+fn variable(term: &Term) -> Option<String>{
+    match term {
+        Term::Variable(name) => Some(name.clone()),
+        Term::Constant(_) => None
+    }
+}
@@ -0,0 +1,738 @@
+//!
+//! SPARQL queries and their evaluation over a `Graph`.
+//!
+//! A subset of SPARQL 1.1 is supported, which covers the queries typically used by SHACL-SPARQL:
+//! `SELECT` (with `DISTINCT`, projected expressions, `ORDER BY`, `LIMIT` and `OFFSET`), `ASK` and `CONSTRUCT` queries
+//! with triple patterns, property paths, `FILTER`, `OPTIONAL`, `MINUS`, `UNION`, `BIND`, `VALUES` and `FILTER (NOT) EXISTS`.
+//! Expressions support the logical, comparison and arithmetic operators, `IN` and the functions of `Function`.
+//! Aggregates, subqueries, `GRAPH`, `SERVICE` and negated property sets are not supported.
+//!
+//! Variables can be pre-bound: the evaluation starts with the given solution instead of the empty one,
+//! like SHACL does for `$this`. Blank nodes in patterns are variables which can't be projected.
+//!
+//! # Example
+//!
+//! ```
+//! use rdf4rust::sparql::query::{Query, Solution};
+//! use rdf4rust::rdf::graph::{Graph, SimpleGraph, Statement, Node, ResourceNode};
+//! use rdf4rust::rdf::node_factory::{Literal, iri};
+//! use std::collections::HashMap;
+//!
+//! let ex = |s: &str| iri(&format!("http://example.com/{}", s));
+//! let mut graph = SimpleGraph::new();
+//! graph.add_statement(Statement::create(ResourceNode::IRINode { iri: ex("alice") }, ex("age"), Node::LiteralNode { literal: Literal::from(42) }));
+//! graph.add_statement(Statement::create(ResourceNode::IRINode { iri: ex("bob") }, ex("age"), Node::LiteralNode { literal: Literal::from(17) }));
+//! graph.add_statement(Statement::create(ResourceNode::IRINode { iri: ex("bob") }, ex("knows"), Node::IRINode { iri: ex("alice") }));
+//!
+//! let query = Query::parse("PREFIX ex: <http://example.com/>
+//!     SELECT ?person ?age WHERE { ?person ex:age ?age FILTER (?age >= 18) }", &HashMap::new()).expect("Is a valid query");
+//! let solutions = query.select(&graph, &Solution::new());
+//! assert_eq!(1, solutions.len());
+//! assert!(solutions[0]["person"] == Node::IRINode { iri: ex("alice") });
+//!
+//! // pre-bind ?this like SHACL-SPARQL does
+//! let query: Query = "ASK { $this <http://example.com/knows>/<http://example.com/age> ?age }".parse().expect("Is a valid query");
+//! let mut this = Solution::new();
+//! this.insert(String::from("this"), Node::IRINode { iri: ex("bob") });
+//! assert!(query.ask(&graph, &this));
+//! this.insert(String::from("this"), Node::IRINode { iri: ex("alice") });
+//! assert!(!query.ask(&graph, &this));
+//! ```
+//!
+
+use crate::error::{ErrorKind, RDFError};
+use crate::rdf::graph::{Graph, Node, Statement};
+use crate::rdf::node_factory::{iri, BlankNode, Literal, RDFNode};
+use crate::rdf::xsd::{RDF_LANG_STRING, XSD_STRING};
+use crate::reasoning::builtins::{self, Builtin};
+use crate::rdf::term::Term;
+use crate::shacl::shapes::Path;
+use crate::sparql::parser;
+use crate::util::decimal::BigInt;
+use std::cmp::Ordering;
+use std::collections::{HashMap, HashSet};
+use std::convert::TryFrom;
+use std::str::FromStr;
+
+/// A solution of a query, mapping variable names (without `?`) to nodes
+pub type Solution = HashMap<String, Node>;
+
+///
+/// The functions which can be called in expressions, named like in SPARQL (case-insensitive)
+///
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum Function{
+    Bound,
+    If,
+    Coalesce,
+    IsIRI,
+    IsBlank,
+    IsLiteral,
+    IsNumeric,
+    Str,
+    Lang,
+    Datatype,
+    Strlen,
+    Ucase,
+    Lcase,
+    Contains,
+    StrStarts,
+    StrEnds,
+    Concat,
+    Regex,
+    LangMatches,
+    SameTerm
+}
+
+impl Function{
+
+    /// Gets the function named `name`, `isURI` is an alias of `isIRI`
+    pub fn from_name(name: &str) -> Option<Function>{
+        Some(match name.to_uppercase().as_str() {
+            "BOUND" => Function::Bound,
+            "IF" => Function::If,
+            "COALESCE" => Function::Coalesce,
+            "ISIRI" | "ISURI" => Function::IsIRI,
+            "ISBLANK" => Function::IsBlank,
+            "ISLITERAL" => Function::IsLiteral,
+            "ISNUMERIC" => Function::IsNumeric,
+            "STR" => Function::Str,
+            "LANG" => Function::Lang,
+            "DATATYPE" => Function::Datatype,
+            "STRLEN" => Function::Strlen,
+            "UCASE" => Function::Ucase,
+            "LCASE" => Function::Lcase,
+            "CONTAINS" => Function::Contains,
+            "STRSTARTS" => Function::StrStarts,
+            "STRENDS" => Function::StrEnds,
+            "CONCAT" => Function::Concat,
+            "REGEX" => Function::Regex,
+            "LANGMATCHES" => Function::LangMatches,
+            "SAMETERM" => Function::SameTerm,
+            _ => return None
+        })
+    }
+
+    /// The allowed numbers of arguments
+    pub(crate) fn arity(&self) -> std::ops::RangeInclusive<usize>{
+        match self {
+            Function::Coalesce | Function::Concat => 0..=usize::MAX,
+            Function::If => 3..=3,
+            Function::Regex => 2..=3,
+            Function::Contains | Function::StrStarts | Function::StrEnds | Function::LangMatches | Function::SameTerm => 2..=2,
+            _ => 1..=1
+        }
+    }
+}
+
+///
+/// The binary operators of expressions
+///
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum Operator{
+    Or,
+    And,
+    Equal,
+    NotEqual,
+    Less,
+    Greater,
+    LessOrEqual,
+    GreaterOrEqual,
+    Add,
+    Subtract,
+    Multiply,
+    Divide
+}
+
+///
+/// An expression of a `FILTER` or `BIND`
+///
+#[derive(Clone)]
+pub enum Expression{
+    Variable(String),
+    Constant(Node),
+    Binary(Operator, Box<Expression>, Box<Expression>),
+    Not(Box<Expression>),
+    Negate(Box<Expression>),
+    /// `IN`, or `NOT IN` if the flag is true
+    In(Box<Expression>, Vec<Expression>, bool),
+    Function(Function, Vec<Expression>),
+    /// `EXISTS`, or `NOT EXISTS` if the flag is true
+    Exists(Vec<Pattern>, bool)
+}
+
+///
+/// An element of a group graph pattern
+///
+#[derive(Clone)]
+pub enum Pattern{
+    /// A triple pattern, blank nodes are variables named `_:label`
+    Triple{subject: Term, predicate: Term, object: Term},
+    /// A triple pattern with a property path
+    Path{subject: Term, path: Path, object: Term},
+    /// A filter, which applies to the solutions of the whole group
+    Filter(Expression),
+    Optional(Vec<Pattern>),
+    Minus(Vec<Pattern>),
+    Union(Vec<Vec<Pattern>>),
+    Group(Vec<Pattern>),
+    Bind(Expression, String),
+    /// `VALUES` with the variables and the rows, None is `UNDEF`
+    Values(Vec<String>, Vec<Vec<Option<Node>>>)
+}
+
+///
+/// The form of a query with the parts specific to it
+///
+#[derive(Clone)]
+pub enum QueryForm{
+    /// `SELECT`, the projected variables are None for `SELECT *`
+    Select{distinct: bool, variables: Option<Vec<String>>},
+    Ask,
+    /// `CONSTRUCT` with its template, blank nodes in the template are new for every solution
+    Construct(Vec<(Term, Term, Term)>)
+}
+
+///
+/// A parsed SPARQL query
+///
+#[derive(Clone)]
+pub struct Query{
+    form: QueryForm,
+    pattern: Vec<Pattern>,
+    /// The `ORDER BY` conditions, true for descending ones
+    order: Vec<(Expression, bool)>,
+    limit: Option<usize>,
+    offset: usize
+}
+
+impl FromStr for Query{
+    type Err = RDFError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Query::parse(s, &HashMap::new())
+    }
+}
+
+impl Query{
+
+    pub(crate) fn new(form: QueryForm, pattern: Vec<Pattern>, order: Vec<(Expression, bool)>, limit: Option<usize>, offset: usize) -> Query{
+        Query{ form, pattern, order, limit, offset }
+    }
+
+    ///
+    /// Parses a query
+    ///
+    /// # Parameters
+    ///
+    /// * `query` - The text of the query
+    /// * `prefixes` - Prefixes which can be used without declaring them in the query, e.g. from `sh:prefixes`
+    ///
+    /// # Returns
+    ///
+    /// The query or an error of kind `ErrorKind::Syntax`, `ErrorKind::UnknownPrefix` or `ErrorKind::InvalidQuery`
+    /// (for unsupported features), located in the query
+    ///
+    pub fn parse(query: &str, prefixes: &HashMap<String, String>) -> Result<Query, RDFError>{
+        parser::parse(query, prefixes)
+    }
+
+    pub fn get_form(&self) -> &QueryForm{
+        &self.form
+    }
+
+    /// Gets the group graph pattern of the `WHERE` clause
+    pub fn get_pattern(&self) -> &Vec<Pattern>{
+        &self.pattern
+    }
+
+    ///
+    /// Checks if the query may be evaluated with the variables `pre_bound`, like SHACL-SPARQL does (SHACL 5.2.1)
+    ///
+    /// Such a query must not contain `MINUS`, `VALUES` or `SERVICE` (which the parser rejects anyway),
+    /// and must not bind one of the variables with `AS`, neither in `BIND` nor in the projection.
+    ///
+    /// # Returns
+    ///
+    /// An error of kind `ErrorKind::InvalidQuery` naming the first feature which isn't allowed
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use rdf4rust::sparql::query::Query;
+    ///
+    /// let pre_bound = ["this"];
+    /// let query: Query = "SELECT ?this ?name WHERE { $this <http://example.com/name> ?name }".parse().expect("Is a valid query");
+    /// assert!(query.check_pre_binding(&pre_bound).is_ok());
+    /// let query: Query = "SELECT ?name WHERE { $this <http://example.com/name> ?name MINUS { $this a ?type } }".parse().expect("Is a valid query");
+    /// assert!(query.check_pre_binding(&pre_bound).is_err());
+    /// let query: Query = "SELECT (?x AS ?this) WHERE { ?x <http://example.com/name> ?name }".parse().expect("Is a valid query");
+    /// assert!(query.check_pre_binding(&pre_bound).is_err());
+    /// ```
+    ///
+    pub fn check_pre_binding(&self, pre_bound: &[&str]) -> Result<(), RDFError>{
+        match pre_binding_violation(&self.pattern, pre_bound) {
+            Some(violation) => Err(RDFError::new(ErrorKind::InvalidQuery, format!("{} is not allowed in queries with pre-bound variables.", violation))),
+            None => Ok(())
+        }
+    }
+
+    /// Gets the solutions of the `WHERE` clause starting from `bindings` in the order of `ORDER BY`
+    fn solutions(&self, graph: &dyn Graph, bindings: &Solution) -> Vec<Solution>{
+        let mut solutions = evaluate_group(graph, &self.pattern, vec![bindings.clone()]);
+        if !self.order.is_empty() {
+            let mut keyed: Vec<(Vec<Option<Node>>, Solution)> = solutions.into_iter()
+                .map(|solution| (self.order.iter().map(|(expression, _)| evaluate(graph, expression, &solution)).collect(), solution))
+                .collect();
+            keyed.sort_by(|(a, _), (b, _)| {
+                a.iter().zip(b).zip(&self.order)
+                    .map(|((a, b), (_, descending))| if *descending {order(b.as_ref(), a.as_ref())} else {order(a.as_ref(), b.as_ref())})
+                    .find(|ordering| ordering.is_ne())
+                    .unwrap_or(Ordering::Equal)
+            });
+            solutions = keyed.into_iter().map(|(_, solution)| solution).collect();
+        }
+        solutions
+    }
+
+    /// Applies `OFFSET` and `LIMIT`
+    fn slice(&self, solutions: Vec<Solution>) -> Vec<Solution>{
+        let end = self.limit.map_or(solutions.len(), |limit| self.offset.saturating_add(limit));
+        solutions.into_iter().take(end).skip(self.offset).collect()
+    }
+
+    ///
+    /// Evaluates a `SELECT` query, other forms select all variables
+    ///
+    /// # Parameters
+    ///
+    /// * `graph` - The graph the query is evaluated on
+    /// * `bindings` - The pre-bound variables
+    ///
+    /// # Returns
+    ///
+    /// The solutions projected to the selected variables, pre-bound variables are part of the solutions if selected
+    ///
+    pub fn select(&self, graph: &dyn Graph, bindings: &Solution) -> Vec<Solution>{
+        let mut solutions = self.solutions(graph, bindings);
+        if let QueryForm::Select { distinct, variables } = &self.form {
+            for solution in solutions.iter_mut() {
+                match variables {
+                    Some(variables) => solution.retain(|variable, _| variables.contains(variable)),
+                    None => solution.retain(|variable, _| !variable.starts_with("_:"))
+                }
+            }
+            if *distinct {
+                let mut seen = Vec::new();
+                solutions.retain(|solution| {
+                    let mut entries: Vec<(&String, &Node)> = solution.iter().collect();
+                    entries.sort_by(|a, b| a.0.cmp(b.0));
+                    let key: Vec<(String, Node)> = entries.into_iter().map(|(variable, node)| (variable.clone(), node.clone())).collect();
+                    if seen.contains(&key) {
+                        return false
+                    }
+                    seen.push(key);
+                    true
+                });
+            }
+        }
+        self.slice(solutions)
+    }
+
+    ///
+    /// Evaluates the query as `ASK` query, i.e. checks if there is a solution starting from `bindings`
+    ///
+    pub fn ask(&self, graph: &dyn Graph, bindings: &Solution) -> bool{
+        !evaluate_group(graph, &self.pattern, vec![bindings.clone()]).is_empty()
+    }
+
+    ///
+    /// Evaluates a `CONSTRUCT` query, other forms construct nothing
+    ///
+    /// # Returns
+    ///
+    /// The statements of the template instantiated with every solution, each statement once.
+    /// Instances with unbound variables or a literal as subject are left out.
+    ///
+    pub fn construct(&self, graph: &dyn Graph, bindings: &Solution) -> Vec<Statement>{
+        self.instantiate(graph, bindings, &|_, _| BlankNode::generate_blank_node())
+    }
+
+    /// Constructs like `construct`, but the blank nodes of the template are the same for the same solution and `scope`
+    pub(crate) fn construct_skolemized(&self, graph: &dyn Graph, bindings: &Solution, scope: &str) -> Vec<Statement>{
+        self.instantiate(graph, bindings, &|bnode, solution| {
+            let mut entries: Vec<String> = solution.iter().map(|(variable, node)| format!("{}={}", variable, node.as_string())).collect();
+            entries.sort();
+            BlankNode::generate_from_string(&format!("{} {} {}", scope, bnode.as_string(false), entries.join(" ")))
+        })
+    }
+
+    /// Instantiates the template with all solutions, `blank_node` creates the blank node replacing a template blank node
+    fn instantiate(&self, graph: &dyn Graph, bindings: &Solution, blank_node: &dyn Fn(&BlankNode, &Solution) -> BlankNode) -> Vec<Statement>{
+        let template = match &self.form {
+            QueryForm::Construct(template) => template,
+            _ => return Vec::new()
+        };
+        let mut stmts = Vec::new();
+        let mut seen = HashSet::new();
+        for solution in self.slice(self.solutions(graph, bindings)) {
+            let mut blank_nodes: HashMap<BlankNode, BlankNode> = HashMap::new();
+            let mut instantiate = |term: &Term| -> Option<Node> {
+                match term {
+                    Term::Variable(variable) => solution.get(variable).cloned(),
+                    Term::Constant(Node::BNode { bnode }) => Some(Node::BNode { bnode: blank_nodes.entry(bnode.clone()).or_insert_with(|| blank_node(bnode, &solution)).clone() }),
+                    Term::Constant(node) => Some(node.clone())
+                }
+            };
+            for (subject, predicate, object) in template {
+                let subject = instantiate(subject).and_then(|subject| subject.to_resource());
+                let predicate = match instantiate(predicate) {
+                    Some(Node::IRINode { iri }) => Some(iri),
+                    _ => None
+                };
+                if let (Some(subject), Some(predicate), Some(object)) = (subject, predicate, instantiate(object)) {
+                    let stmt = Statement::create(subject, predicate, object);
+                    if seen.insert(stmt.clone()) {
+                        stmts.push(stmt);
+                    }
+                }
+            }
+        }
+        stmts
+    }
+}
+
+/// Evaluates the patterns of a group for each of the `input` solutions, filters apply to the whole group
+fn evaluate_group(graph: &dyn Graph, patterns: &[Pattern], input: Vec<Solution>) -> Vec<Solution>{
+    let mut solutions = input;
+    let mut filters = Vec::new();
+    for pattern in patterns {
+        solutions = match pattern {
+            Pattern::Triple { subject, predicate, object } => solutions.into_iter()
+                .flat_map(|solution| match_triple(graph, subject, predicate, object, solution))
+                .collect(),
+            Pattern::Path { subject, path, object } => solutions.into_iter()
+                .flat_map(|solution| match_path(graph, subject, path, object, solution))
+                .collect(),
+            Pattern::Filter(expression) => {
+                filters.push(expression);
+                solutions
+            }
+            Pattern::Optional(group) => solutions.into_iter()
+                .flat_map(|solution| {
+                    let extended = evaluate_group(graph, group, vec![solution.clone()]);
+                    if extended.is_empty() {vec![solution]} else {extended}
+                })
+                .collect(),
+            Pattern::Minus(group) => {
+                let removed = evaluate_group(graph, group, vec![Solution::new()]);
+                solutions.into_iter()
+                    .filter(|solution| !removed.iter().any(|other| compatible(solution, other) && other.keys().any(|variable| solution.contains_key(variable))))
+                    .collect()
+            }
+            Pattern::Union(groups) => solutions.into_iter()
+                .flat_map(|solution| groups.iter().flat_map(|group| evaluate_group(graph, group, vec![solution.clone()])).collect::<Vec<_>>())
+                .collect(),
+            Pattern::Group(group) => solutions.into_iter()
+                .flat_map(|solution| evaluate_group(graph, group, vec![solution]))
+                .collect(),
+            Pattern::Bind(expression, variable) => solutions.into_iter()
+                .map(|mut solution| {
+                    if let Some(value) = evaluate(graph, expression, &solution) {
+                        solution.insert(variable.clone(), value);
+                    }
+                    solution
+                })
+                .collect(),
+            Pattern::Values(variables, rows) => solutions.into_iter()
+                .flat_map(|solution| rows.iter().filter_map(|row| {
+                    let mut extended = solution.clone();
+                    for (variable, value) in variables.iter().zip(row) {
+                        if let Some(value) = value {
+                            if !bind(&mut extended, variable, value) {
+                                return None
+                            }
+                        }
+                    }
+                    Some(extended)
+                }).collect::<Vec<_>>())
+                .collect()
+        };
+    }
+    solutions.retain(|solution| filters.iter().all(|filter| effective_boolean_value(evaluate(graph, filter, solution).as_ref()) == Some(true)));
+    solutions
+}
+
+/// The order of `ORDER BY`: unbound values, blank nodes, IRIs and literals, which are compared by value if possible
+fn order(a: Option<&Node>, b: Option<&Node>) -> Ordering{
+    let rank = |node: Option<&Node>| match node {
+        None => 0,
+        Some(Node::BNode { .. }) => 1,
+        Some(Node::IRINode { .. }) => 2,
+        Some(_) => 3
+    };
+    match (a, b) {
+        (Some(a), Some(b)) => builtins::compare(a, b)
+            .unwrap_or_else(|| rank(Some(a)).cmp(&rank(Some(b))).then_with(|| a.as_string().cmp(&b.as_string()))),
+        _ => rank(a).cmp(&rank(b))
+    }
+}
+
+/// Checks if both solutions agree on their common variables
+fn compatible(a: &Solution, b: &Solution) -> bool{
+    a.iter().all(|(variable, node)| b.get(variable).is_none_or(|other| other == node))
+}
+
+/// Binds `variable` to `node`, false if it is already bound to another node
+fn bind(solution: &mut Solution, variable: &str, node: &Node) -> bool{
+    match solution.get(variable) {
+        Some(bound) => bound == node,
+        None => {
+            solution.insert(String::from(variable), node.clone());
+            true
+        }
+    }
+}
+
+/// The node of a term, None for unbound variables
+/// The first element of `patterns` which isn't allowed with the variables `pre_bound`, see `Query::check_pre_binding`
+fn pre_binding_violation(patterns: &[Pattern], pre_bound: &[&str]) -> Option<String>{
+    patterns.iter().find_map(|pattern| match pattern {
+        Pattern::Triple { .. } | Pattern::Path { .. } => None,
+        Pattern::Filter(expression) => expression_violation(expression, pre_bound),
+        Pattern::Optional(group) | Pattern::Group(group) => pre_binding_violation(group, pre_bound),
+        Pattern::Minus(_) => Some(String::from("MINUS")),
+        Pattern::Union(groups) => groups.iter().find_map(|group| pre_binding_violation(group, pre_bound)),
+        Pattern::Bind(_, variable) if pre_bound.contains(&variable.as_str()) => Some(format!("AS ?{}", variable)),
+        Pattern::Bind(expression, _) => expression_violation(expression, pre_bound),
+        Pattern::Values(..) => Some(String::from("VALUES"))
+    })
+}
+
+/// The first pattern of an `EXISTS` in `expression` which isn't allowed with the variables `pre_bound`
+fn expression_violation(expression: &Expression, pre_bound: &[&str]) -> Option<String>{
+    match expression {
+        Expression::Variable(_) | Expression::Constant(_) => None,
+        Expression::Binary(_, a, b) => expression_violation(a, pre_bound).or_else(|| expression_violation(b, pre_bound)),
+        Expression::Not(a) | Expression::Negate(a) => expression_violation(a, pre_bound),
+        Expression::In(a, list, _) => expression_violation(a, pre_bound).or_else(|| list.iter().find_map(|b| expression_violation(b, pre_bound))),
+        Expression::Function(_, arguments) => arguments.iter().find_map(|argument| expression_violation(argument, pre_bound)),
+        Expression::Exists(group, _) => pre_binding_violation(group, pre_bound)
+    }
+}
+
+fn resolve(term: &Term, solution: &Solution) -> Option<Node>{
+    match term {
+        Term::Variable(variable) => solution.get(variable).cloned(),
+        Term::Constant(node) => Some(node.clone())
+    }
+}
+
+/// Binds the term to `node` if it's a variable, checks that it is `node` otherwise
+fn unify(term: &Term, node: &Node, solution: &mut Solution) -> bool{
+    match term {
+        Term::Variable(variable) => bind(solution, variable, node),
+        Term::Constant(constant) => constant == node
+    }
+}
+
+fn match_triple(graph: &dyn Graph, subject: &Term, predicate: &Term, object: &Term, solution: Solution) -> Vec<Solution>{
+    let s = match resolve(subject, &solution) {
+        Some(node) => match node.to_resource() {
+            Some(resource) => Some(resource),
+            None => return Vec::new()
+        },
+        None => None
+    };
+    let p = match resolve(predicate, &solution) {
+        Some(Node::IRINode { iri }) => Some(iri),
+        Some(_) => return Vec::new(),
+        None => None
+    };
+    let o = resolve(object, &solution);
+    graph.list_statements(s, p, o).into_iter()
+        .filter_map(|stmt| {
+            let mut extended = solution.clone();
+            let matches = unify(subject, &Node::from(stmt.get_subject().clone()), &mut extended)
+                && unify(predicate, &Node::IRINode { iri: stmt.get_predicate().clone() }, &mut extended)
+                && unify(object, stmt.get_object(), &mut extended);
+            if matches {Some(extended)} else {None}
+        })
+        .collect()
+}
+
+fn match_path(graph: &dyn Graph, subject: &Term, path: &Path, object: &Term, solution: Solution) -> Vec<Solution>{
+    let pairs: Vec<(Node, Node)> = match (resolve(subject, &solution), resolve(object, &solution)) {
+        (Some(start), _) => path.evaluate(graph, &start).into_iter().map(|end| (start.clone(), end)).collect(),
+        (None, Some(end)) => Path::Inverse(Box::new(path.clone())).evaluate(graph, &end).into_iter().map(|start| (start, end.clone())).collect(),
+        (None, None) => {
+            let mut nodes: Vec<Node> = Vec::new();
+            let mut seen = HashSet::new();
+            for stmt in graph.list_statements(None, None, None) {
+                for node in [Node::from(stmt.get_subject().clone()), stmt.get_object().clone()] {
+                    if seen.insert(node.clone()) {
+                        nodes.push(node);
+                    }
+                }
+            }
+            nodes.into_iter().flat_map(|start| path.evaluate(graph, &start).into_iter().map(move |end| (start.clone(), end)).collect::<Vec<_>>()).collect()
+        }
+    };
+    pairs.into_iter()
+        .filter_map(|(start, end)| {
+            let mut extended = solution.clone();
+            if unify(subject, &start, &mut extended) && unify(object, &end, &mut extended) {Some(extended)} else {None}
+        })
+        .collect()
+}
+
+fn boolean(value: bool) -> Option<Node>{
+    Some(Node::LiteralNode { literal: Literal::from(value) })
+}
+
+fn string(value: String) -> Option<Node>{
+    Some(Node::LiteralNode { literal: Literal::from(value) })
+}
+
+/// The effective boolean value of a node, None if it has none (an error in SPARQL)
+fn effective_boolean_value(node: Option<&Node>) -> Option<bool>{
+    let literal = node?.as_literal().ok()?;
+    if let Ok(value) = bool::try_from(literal) {
+        return Some(value)
+    }
+    if builtins::is_numeric(node?) {
+        return Some(literal.value().ok().and_then(|value| value.as_f64()).is_some_and(|value| value != 0.0 && !value.is_nan()))
+    }
+    if is_string(literal) {
+        return Some(!literal.get_value().is_empty())
+    }
+    None
+}
+
+/// Checks if a literal is a simple or language-tagged string
+fn is_string(literal: &Literal) -> bool{
+    literal.get_datatype() == &*XSD_STRING || literal.get_datatype() == &*RDF_LANG_STRING
+}
+
+/// The string argument of a string function, None for other nodes
+fn string_argument(node: &Node) -> Option<&Literal>{
+    node.as_literal().ok().filter(|literal| is_string(literal))
+}
+
+/// Evaluates an expression, None if it's an error or evaluates to an unbound variable
+fn evaluate(graph: &dyn Graph, expression: &Expression, solution: &Solution) -> Option<Node>{
+    let value = |expression: &Expression| evaluate(graph, expression, solution);
+    match expression {
+        Expression::Variable(variable) => solution.get(variable).cloned(),
+        Expression::Constant(node) => Some(node.clone()),
+        Expression::Binary(Operator::Or, a, b) => match (effective_boolean_value(value(a).as_ref()), effective_boolean_value(value(b).as_ref())) {
+            (Some(true), _) | (_, Some(true)) => boolean(true),
+            (Some(false), Some(false)) => boolean(false),
+            _ => None
+        },
+        Expression::Binary(Operator::And, a, b) => match (effective_boolean_value(value(a).as_ref()), effective_boolean_value(value(b).as_ref())) {
+            (Some(false), _) | (_, Some(false)) => boolean(false),
+            (Some(true), Some(true)) => boolean(true),
+            _ => None
+        },
+        Expression::Binary(operator, a, b) => {
+            let (a, b) = (value(a)?, value(b)?);
+            match operator {
+                Operator::Equal => boolean(builtins::equal(&a, &b)),
+                Operator::NotEqual => boolean(!builtins::equal(&a, &b)),
+                Operator::Less => boolean(builtins::compare(&a, &b)? == Ordering::Less),
+                Operator::Greater => boolean(builtins::compare(&a, &b)? == Ordering::Greater),
+                Operator::LessOrEqual => boolean(builtins::compare(&a, &b)? != Ordering::Greater),
+                Operator::GreaterOrEqual => boolean(builtins::compare(&a, &b)? != Ordering::Less),
+                Operator::Add => builtins::calculate(Builtin::Sum, &a, &b).map(|literal| Node::LiteralNode { literal }),
+                Operator::Subtract => builtins::calculate(Builtin::Difference, &a, &b).map(|literal| Node::LiteralNode { literal }),
+                Operator::Multiply => builtins::calculate(Builtin::Product, &a, &b).map(|literal| Node::LiteralNode { literal }),
+                _ => builtins::calculate(Builtin::Quotient, &a, &b).map(|literal| Node::LiteralNode { literal })
+            }
+        }
+        Expression::Not(a) => boolean(!effective_boolean_value(value(a).as_ref())?),
+        Expression::Negate(a) => builtins::calculate(Builtin::Product, &value(a)?, &Node::LiteralNode { literal: Literal::from(-1) })
+            .map(|literal| Node::LiteralNode { literal }),
+        Expression::In(a, members, negated) => {
+            let a = value(a)?;
+            let found = members.iter().any(|member| value(member).is_some_and(|member| builtins::equal(&a, &member)));
+            boolean(found != *negated)
+        }
+        Expression::Exists(group, negated) => boolean(evaluate_group(graph, group, vec![solution.clone()]).is_empty() == *negated),
+        Expression::Function(function, arguments) => call(graph, *function, arguments, solution)
+    }
+}
+
+fn call(graph: &dyn Graph, function: Function, arguments: &[Expression], solution: &Solution) -> Option<Node>{
+    let value = |i: usize| evaluate(graph, &arguments[i], solution);
+    match function {
+        Function::Bound => match &arguments[0] {
+            Expression::Variable(variable) => boolean(solution.contains_key(variable)),
+            _ => None
+        },
+        Function::If => if effective_boolean_value(value(0).as_ref())? {value(1)} else {value(2)},
+        Function::Coalesce => (0..arguments.len()).find_map(value),
+        Function::IsIRI => boolean(value(0)?.is_uri()),
+        Function::IsBlank => boolean(value(0)?.is_bnode()),
+        Function::IsLiteral => boolean(value(0)?.is_literal()),
+        Function::IsNumeric => boolean(builtins::is_numeric(&value(0)?)),
+        Function::Str => match value(0)? {
+            Node::IRINode { iri } => string(iri.get_value()),
+            Node::LiteralNode { literal } => string(literal.get_value().clone()),
+            _ => None
+        },
+        Function::Lang => match value(0)? {
+            Node::LiteralNode { literal } => string(literal.get_lang().clone().unwrap_or_default()),
+            _ => None
+        },
+        Function::Datatype => match value(0)? {
+            Node::LiteralNode { literal } => Some(Node::IRINode { iri: iri(literal.get_datatype().get_value()) }),
+            _ => None
+        },
+        Function::Strlen => Some(Node::LiteralNode { literal: Literal::from(BigInt::from(string_argument(&value(0)?)?.get_value().chars().count())) }),
+        Function::Ucase | Function::Lcase => {
+            let node = value(0)?;
+            let literal = string_argument(&node)?;
+            let converted = if function == Function::Ucase {literal.get_value().to_uppercase()} else {literal.get_value().to_lowercase()};
+            match literal.get_lang() {
+                Some(lang) => Literal::create_lang_literal(converted, lang.clone()).ok().map(|literal| Node::LiteralNode { literal }),
+                None => string(converted)
+            }
+        }
+        Function::Contains | Function::StrStarts | Function::StrEnds => {
+            let (a, b) = (value(0)?, value(1)?);
+            let (a, b) = (string_argument(&a)?.get_value(), string_argument(&b)?.get_value());
+            boolean(match function {
+                Function::Contains => a.contains(b.as_str()),
+                Function::StrStarts => a.starts_with(b.as_str()),
+                _ => a.ends_with(b.as_str())
+            })
+        }
+        Function::Concat => {
+            let mut concatenated = String::new();
+            for i in 0..arguments.len() {
+                concatenated.push_str(string_argument(&value(i)?)?.get_value());
+            }
+            string(concatenated)
+        }
+        Function::Regex => {
+            let text = value(0)?;
+            let pattern = value(1)?;
+            let flags = match arguments.len() {
+                3 => string_argument(&value(2)?)?.get_value().clone(),
+                _ => String::new()
+            };
+            let regex = builtins::regex(string_argument(&pattern)?.get_value(), &flags)?;
+            boolean(regex.is_match(string_argument(&text)?.get_value()))
+        }
+        Function::LangMatches => {
+            let (tag, range) = (value(0)?, value(1)?);
+            let (tag, range) = (string_argument(&tag)?.get_value(), string_argument(&range)?.get_value());
+            if tag.is_empty() {
+                return boolean(false)
+            }
+            boolean(Literal::create_lang_literal(String::new(), tag.clone()).ok()?.lang_matches(range))
+        }
+        Function::SameTerm => boolean(value(0)? == value(1)?)
+    }
+}
@@ -1,2 +1,5 @@
 pub mod iri;
-pub mod hex_binary;
\ No newline at end of file
+pub mod hex_binary;
+pub mod date_time;
+pub mod decimal;
+pub mod language_tag;
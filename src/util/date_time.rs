@@ -0,0 +1,1258 @@
+//!
+//! Values of the XSD date and time types (xsd:dateTime, xsd:date, xsd:time, the g* types and the durations).
+//!
+//! All types parse their lexical form via `FromStr` and print their canonical form via `Display`.
+//! Dates follow the proleptic Gregorian calendar of XSD 1.1, year `0000` is 1 BCE.
+//! Fractional seconds are kept with nanosecond precision, further digits are cut off.
+//! Years are supported between -999999999 and 999999999.
+//!
+
+use crate::error::{RDFError, ErrorKind};
+use std::cmp::Ordering;
+use std::convert::TryFrom;
+use std::fmt;
+use std::ops::{Neg, Sub};
+use std::str::FromStr;
+
+const NANOS_PER_SECOND: i128 = 1_000_000_000;
+const NANOS_PER_MINUTE: i128 = 60 * NANOS_PER_SECOND;
+const NANOS_PER_HOUR: i128 = 60 * NANOS_PER_MINUTE;
+const NANOS_PER_DAY: i128 = 24 * NANOS_PER_HOUR;
+/// The maximum timezone offset of 14 hours, in nanoseconds
+const MAX_TIMEZONE_NANOS: i128 = 14 * NANOS_PER_HOUR;
+const MAX_YEAR: i64 = 999_999_999;
+
+///
+/// Error of invalid lexical forms or values, always of kind `ErrorKind::InvalidLiteral`
+///
+pub type DateTimeError = RDFError;
+
+fn invalid(msg: String) -> DateTimeError{
+    DateTimeError::new(ErrorKind::InvalidLiteral, msg)
+}
+
+///
+/// A timezone offset between `-14:00` and `+14:00`
+///
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub struct Timezone{
+    offset: i16
+}
+
+impl Timezone{
+
+    ///
+    /// Creates a timezone from its offset to UTC in minutes, fails if it's not between -840 and 840
+    ///
+    pub fn create(offset_minutes: i16) -> Result<Timezone, DateTimeError>{
+        if offset_minutes.abs() > 14 * 60 {
+            return Err(invalid(format!("Timezone offset of {} minutes is out of range.", offset_minutes)))
+        }
+        Ok(Timezone{ offset: offset_minutes })
+    }
+
+    pub fn utc() -> Timezone{
+        Timezone{ offset: 0 }
+    }
+
+    /// The offset to UTC in minutes
+    pub fn get_offset_minutes(&self) -> i16{
+        self.offset
+    }
+
+    fn offset_nanos(&self) -> i128{
+        self.offset as i128 * NANOS_PER_MINUTE
+    }
+}
+
+impl fmt::Display for Timezone{
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        if self.offset == 0 {
+            return write!(f, "Z")
+        }
+        let sign = if self.offset < 0 { '-' } else { '+' };
+        write!(f, "{}{:02}:{:02}", sign, self.offset.abs() / 60, self.offset.abs() % 60)
+    }
+}
+
+impl FromStr for Timezone{
+    type Err = DateTimeError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        if s == "Z" {
+            return Ok(Timezone::utc())
+        }
+        let bytes = s.as_bytes();
+        if bytes.len() != 6 || (bytes[0] != b'+' && bytes[0] != b'-') || bytes[3] != b':' {
+            return Err(invalid(format!("<{}> is not a valid timezone, expected Z or (+|-)hh:mm.", s)))
+        }
+        let hours = parse_digits(&s[1..3], 0, 14, "timezone hour")?;
+        let minutes = parse_digits(&s[4..6], 0, 59, "timezone minute")?;
+        if hours == 14 && minutes != 0 {
+            return Err(invalid(format!("Timezone <{}> is out of range.", s)))
+        }
+        let offset = (hours * 60 + minutes) as i16;
+        Timezone::create(if bytes[0] == b'-' { -offset } else { offset })
+    }
+}
+
+///
+/// A point in time as xsd:dateTime, with or without timezone.
+///
+/// xsd:dateTimeStamp uses the same type, but requires the timezone.
+///
+/// # Comparison and arithmetic
+///
+/// Values are compared on the timeline. If only one of both values has a timezone, they can only be ordered
+/// if they are more than 14 hours apart, otherwise `partial_cmp` is `None` (XSD 1.1 section 3.3.7).
+/// Subtracting two values results in a day-time `Duration`, values without timezone are treated as UTC then.
+///
+/// # Example
+///
+/// ```
+/// use rdf4rust::util::date_time::{DateTime, Duration};
+///
+/// let start: DateTime = "2002-04-02T12:00:00-01:00".parse().expect("Is valid dateTime");
+/// let end: DateTime = "2002-04-02T17:00:00+04:00".parse().expect("Is valid dateTime");
+/// assert!(start == end);
+///
+/// let later: DateTime = "2002-04-03T13:30:00Z".parse().expect("Is valid dateTime");
+/// assert!(start < later);
+/// assert_eq!("P1DT30M", (later - start).to_string());
+///
+/// let duration: Duration = "P1M".parse().expect("Is valid duration");
+/// let date_time: DateTime = "2000-01-31T24:00:00".parse().expect("Is valid dateTime");
+/// assert_eq!("2000-03-01T00:00:00", date_time.add_duration(&duration).expect("In range").to_string());
+///
+/// // local times are only comparable to times with a timezone if they are more than 14 hours apart
+/// let local: DateTime = "2002-04-02T12:00:00".parse().expect("Is valid dateTime");
+/// assert!(local.partial_cmp(&start).is_none());
+/// assert!(local < later);
+/// ```
+///
+#[derive(Clone, Copy, Debug)]
+pub struct DateTime{
+    year: i64,
+    month: u8,
+    day: u8,
+    hour: u8,
+    minute: u8,
+    second: u8,
+    nanosecond: u32,
+    timezone: Option<Timezone>
+}
+
+impl DateTime{
+
+    ///
+    /// Creates a dateTime, fails if any of the fields is out of range
+    ///
+    /// `hour` 24 is allowed for the end of the day (with zero minutes and seconds), it is stored as 00:00:00 of the next day.
+    ///
+    #[allow(clippy::too_many_arguments)]
+    pub fn create(year: i64, month: u8, day: u8, hour: u8, minute: u8, second: u8, nanosecond: u32, timezone: Option<Timezone>) -> Result<DateTime, DateTimeError>{
+        check_date(year, month, day)?;
+        check_time(hour, minute, second, nanosecond)?;
+        let date_time = DateTime{ year, month, day, hour, minute, second, nanosecond, timezone };
+        if hour == 24 {
+            let (year, month, day) = civil_from_days(days_from_civil(year, month, day) + 1);
+            return Ok(DateTime{ year, month, day, hour: 0, ..date_time })
+        }
+        Ok(date_time)
+    }
+
+    pub fn get_year(&self) -> i64{
+        self.year
+    }
+
+    pub fn get_month(&self) -> u8{
+        self.month
+    }
+
+    pub fn get_day(&self) -> u8{
+        self.day
+    }
+
+    pub fn get_hour(&self) -> u8{
+        self.hour
+    }
+
+    pub fn get_minute(&self) -> u8{
+        self.minute
+    }
+
+    pub fn get_second(&self) -> u8{
+        self.second
+    }
+
+    pub fn get_nanosecond(&self) -> u32{
+        self.nanosecond
+    }
+
+    pub fn get_timezone(&self) -> Option<Timezone>{
+        self.timezone
+    }
+
+    pub fn get_date(&self) -> Date{
+        Date{ year: self.year, month: self.month, day: self.day, timezone: self.timezone }
+    }
+
+    pub fn get_time(&self) -> Time{
+        Time{ hour: self.hour, minute: self.minute, second: self.second, nanosecond: self.nanosecond, timezone: self.timezone }
+    }
+
+    ///
+    /// Adds `duration` following XSD 1.1 appendix E.3.3: months are added first, the day is pinned to the
+    /// last day of the resulting month if necessary, then the day-time part is added.
+    ///
+    /// # Returns
+    ///
+    /// The new dateTime in the same timezone, or `None` if the year overflows
+    ///
+    pub fn add_duration(&self, duration: &Duration) -> Option<DateTime>{
+        let months = (self.year as i128) * 12 + (self.month as i128 - 1) + duration.months as i128;
+        let year = i64::try_from(months.div_euclid(12)).ok().filter(|year| year.abs() <= MAX_YEAR)?;
+        let month = (months.rem_euclid(12) + 1) as u8;
+        let day = self.day.min(days_in_month(year, month));
+        let local = DateTime{ year, month, day, ..*self }.local_nanos() + duration.nanos;
+        DateTime::from_local_nanos(local, self.timezone)
+    }
+
+    ///
+    /// Subtracts `duration`, see `add_duration`
+    ///
+    pub fn sub_duration(&self, duration: &Duration) -> Option<DateTime>{
+        self.add_duration(&-*duration)
+    }
+
+    ///
+    /// Converts the value to the timezone `timezone`, keeping the point in time.
+    /// Values without timezone are taken as UTC.
+    ///
+    pub fn to_timezone(&self, timezone: Timezone) -> Option<DateTime>{
+        DateTime::from_local_nanos(self.timeline_nanos() + timezone.offset_nanos(), Some(timezone))
+    }
+
+    /// Nanoseconds since 1970-01-01T00:00:00 ignoring the timezone
+    fn local_nanos(&self) -> i128{
+        days_from_civil(self.year, self.month, self.day) as i128 * NANOS_PER_DAY
+            + self.hour as i128 * NANOS_PER_HOUR
+            + self.minute as i128 * NANOS_PER_MINUTE
+            + self.second as i128 * NANOS_PER_SECOND
+            + self.nanosecond as i128
+    }
+
+    /// Nanoseconds since 1970-01-01T00:00:00Z, values without timezone are taken as UTC
+    fn timeline_nanos(&self) -> i128{
+        match self.timezone {
+            Some(timezone) => self.local_nanos() - timezone.offset_nanos(),
+            None => self.local_nanos()
+        }
+    }
+
+    fn from_local_nanos(nanos: i128, timezone: Option<Timezone>) -> Option<DateTime>{
+        if nanos.abs() / NANOS_PER_DAY > (MAX_YEAR as i128 + 1) * 366 {
+            return None
+        }
+        let days = nanos.div_euclid(NANOS_PER_DAY) as i64;
+        let rest = nanos.rem_euclid(NANOS_PER_DAY);
+        let (year, month, day) = civil_from_days(days);
+        if year.abs() > MAX_YEAR {
+            return None
+        }
+        Some(DateTime{
+            year,
+            month,
+            day,
+            hour: (rest / NANOS_PER_HOUR) as u8,
+            minute: (rest % NANOS_PER_HOUR / NANOS_PER_MINUTE) as u8,
+            second: (rest % NANOS_PER_MINUTE / NANOS_PER_SECOND) as u8,
+            nanosecond: (rest % NANOS_PER_SECOND) as u32,
+            timezone
+        })
+    }
+}
+
+impl fmt::Display for DateTime{
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}T{}", format_date(self.year, self.month, self.day), format_time(self.hour, self.minute, self.second, self.nanosecond))?;
+        format_timezone(f, self.timezone)
+    }
+}
+
+impl FromStr for DateTime{
+    type Err = DateTimeError;
+
+    ///
+    /// Parses `-?yyyy-mm-ddThh:mm:ss(.s+)?(zzzzzz)?`
+    ///
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let (rest, timezone) = split_timezone(s)?;
+        let (date, time) = match rest.split_once('T') {
+            Some(parts) => parts,
+            None => return Err(invalid(format!("<{}> is not a valid dateTime, expected T between date and time.", s)))
+        };
+        let (year, month, day) = parse_date(date)?;
+        let (hour, minute, second, nanosecond) = parse_time(time)?;
+        DateTime::create(year, month, day, hour, minute, second, nanosecond, timezone)
+    }
+}
+
+impl PartialEq for DateTime{
+    fn eq(&self, other: &Self) -> bool {
+        self.partial_cmp(other) == Some(Ordering::Equal)
+    }
+}
+
+impl PartialOrd for DateTime{
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        compare_timeline(self.timeline_nanos(), self.timezone.is_some(), other.timeline_nanos(), other.timezone.is_some())
+    }
+}
+
+impl Sub for DateTime{
+    type Output = Duration;
+
+    fn sub(self, other: Self) -> Duration {
+        Duration{ months: 0, nanos: self.timeline_nanos() - other.timeline_nanos() }
+    }
+}
+
+///
+/// A calendar day as xsd:date, with or without timezone.
+///
+/// Dates are compared and subtracted by their first moment, see `DateTime`.
+///
+#[derive(Clone, Copy, Debug)]
+pub struct Date{
+    year: i64,
+    month: u8,
+    day: u8,
+    timezone: Option<Timezone>
+}
+
+impl Date{
+
+    pub fn create(year: i64, month: u8, day: u8, timezone: Option<Timezone>) -> Result<Date, DateTimeError>{
+        check_date(year, month, day)?;
+        Ok(Date{ year, month, day, timezone })
+    }
+
+    pub fn get_year(&self) -> i64{
+        self.year
+    }
+
+    pub fn get_month(&self) -> u8{
+        self.month
+    }
+
+    pub fn get_day(&self) -> u8{
+        self.day
+    }
+
+    pub fn get_timezone(&self) -> Option<Timezone>{
+        self.timezone
+    }
+
+    ///
+    /// The first moment of the day
+    ///
+    pub fn to_date_time(&self) -> DateTime{
+        DateTime{ year: self.year, month: self.month, day: self.day, hour: 0, minute: 0, second: 0, nanosecond: 0, timezone: self.timezone }
+    }
+
+    ///
+    /// Adds `duration` to the first moment of the day and returns the date of the result, see `DateTime::add_duration`
+    ///
+    pub fn add_duration(&self, duration: &Duration) -> Option<Date>{
+        self.to_date_time().add_duration(duration).map(|date_time| date_time.get_date())
+    }
+
+    pub fn sub_duration(&self, duration: &Duration) -> Option<Date>{
+        self.add_duration(&-*duration)
+    }
+}
+
+impl fmt::Display for Date{
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}", format_date(self.year, self.month, self.day))?;
+        format_timezone(f, self.timezone)
+    }
+}
+
+impl FromStr for Date{
+    type Err = DateTimeError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let (rest, timezone) = split_timezone(s)?;
+        let (year, month, day) = parse_date(rest)?;
+        Date::create(year, month, day, timezone)
+    }
+}
+
+impl PartialEq for Date{
+    fn eq(&self, other: &Self) -> bool {
+        self.partial_cmp(other) == Some(Ordering::Equal)
+    }
+}
+
+impl PartialOrd for Date{
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        self.to_date_time().partial_cmp(&other.to_date_time())
+    }
+}
+
+impl Sub for Date{
+    type Output = Duration;
+
+    fn sub(self, other: Self) -> Duration {
+        self.to_date_time() - other.to_date_time()
+    }
+}
+
+///
+/// A time of day as xsd:time, with or without timezone.
+///
+/// Times are compared and subtracted as dateTimes on the reference day 1972-12-31, see `DateTime`.
+/// Adding a duration wraps around midnight.
+///
+#[derive(Clone, Copy, Debug)]
+pub struct Time{
+    hour: u8,
+    minute: u8,
+    second: u8,
+    nanosecond: u32,
+    timezone: Option<Timezone>
+}
+
+impl Time{
+
+    ///
+    /// Creates a time, `hour` 24 (with zero minutes and seconds) is stored as 00:00:00
+    ///
+    pub fn create(hour: u8, minute: u8, second: u8, nanosecond: u32, timezone: Option<Timezone>) -> Result<Time, DateTimeError>{
+        check_time(hour, minute, second, nanosecond)?;
+        Ok(Time{ hour: hour % 24, minute, second, nanosecond, timezone })
+    }
+
+    pub fn get_hour(&self) -> u8{
+        self.hour
+    }
+
+    pub fn get_minute(&self) -> u8{
+        self.minute
+    }
+
+    pub fn get_second(&self) -> u8{
+        self.second
+    }
+
+    pub fn get_nanosecond(&self) -> u32{
+        self.nanosecond
+    }
+
+    pub fn get_timezone(&self) -> Option<Timezone>{
+        self.timezone
+    }
+
+    fn to_date_time(self) -> DateTime{
+        DateTime{ year: 1972, month: 12, day: 31, hour: self.hour, minute: self.minute, second: self.second, nanosecond: self.nanosecond, timezone: self.timezone }
+    }
+
+    ///
+    /// Adds the day-time part of `duration`, wrapping around midnight.
+    ///
+    /// # Returns
+    ///
+    /// The new time or `None` if `duration` has months
+    ///
+    pub fn add_duration(&self, duration: &Duration) -> Option<Time>{
+        if duration.months != 0 {
+            return None
+        }
+        let nanos = (self.to_date_time().local_nanos() + duration.nanos).rem_euclid(NANOS_PER_DAY);
+        DateTime::from_local_nanos(nanos, self.timezone).map(|date_time| date_time.get_time())
+    }
+
+    pub fn sub_duration(&self, duration: &Duration) -> Option<Time>{
+        self.add_duration(&-*duration)
+    }
+}
+
+impl fmt::Display for Time{
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}", format_time(self.hour, self.minute, self.second, self.nanosecond))?;
+        format_timezone(f, self.timezone)
+    }
+}
+
+impl FromStr for Time{
+    type Err = DateTimeError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let (rest, timezone) = split_timezone(s)?;
+        let (hour, minute, second, nanosecond) = parse_time(rest)?;
+        Time::create(hour, minute, second, nanosecond, timezone)
+    }
+}
+
+impl PartialEq for Time{
+    fn eq(&self, other: &Self) -> bool {
+        self.partial_cmp(other) == Some(Ordering::Equal)
+    }
+}
+
+impl PartialOrd for Time{
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        self.to_date_time().partial_cmp(&other.to_date_time())
+    }
+}
+
+impl Sub for Time{
+    type Output = Duration;
+
+    fn sub(self, other: Self) -> Duration {
+        self.to_date_time() - other.to_date_time()
+    }
+}
+
+///
+/// A Gregorian year as xsd:gYear, e.g. `2004` or `-0044+01:00`
+///
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub struct GYear{
+    year: i64,
+    timezone: Option<Timezone>
+}
+
+impl GYear{
+
+    pub fn create(year: i64, timezone: Option<Timezone>) -> GYear{
+        GYear{ year, timezone }
+    }
+
+    pub fn get_year(&self) -> i64{
+        self.year
+    }
+
+    pub fn get_timezone(&self) -> Option<Timezone>{
+        self.timezone
+    }
+}
+
+impl fmt::Display for GYear{
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}", format_year(self.year))?;
+        format_timezone(f, self.timezone)
+    }
+}
+
+impl FromStr for GYear{
+    type Err = DateTimeError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let (rest, timezone) = split_timezone(s)?;
+        Ok(GYear{ year: parse_year(rest)?, timezone })
+    }
+}
+
+///
+/// A month of a Gregorian year as xsd:gYearMonth, e.g. `2004-04`
+///
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub struct GYearMonth{
+    year: i64,
+    month: u8,
+    timezone: Option<Timezone>
+}
+
+impl GYearMonth{
+
+    pub fn create(year: i64, month: u8, timezone: Option<Timezone>) -> Result<GYearMonth, DateTimeError>{
+        check_date(year, month, 1)?;
+        Ok(GYearMonth{ year, month, timezone })
+    }
+
+    pub fn get_year(&self) -> i64{
+        self.year
+    }
+
+    pub fn get_month(&self) -> u8{
+        self.month
+    }
+
+    pub fn get_timezone(&self) -> Option<Timezone>{
+        self.timezone
+    }
+}
+
+impl fmt::Display for GYearMonth{
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}-{:02}", format_year(self.year), self.month)?;
+        format_timezone(f, self.timezone)
+    }
+}
+
+impl FromStr for GYearMonth{
+    type Err = DateTimeError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let (rest, timezone) = split_timezone(s)?;
+        let index = match rest.rfind('-') {
+            Some(index) if index > 0 => index,
+            _ => return Err(invalid(format!("<{}> is not a valid gYearMonth, expected yyyy-mm.", s)))
+        };
+        let year = parse_year(&rest[..index])?;
+        let month = parse_digits(&rest[index+1..], 1, 12, "month")? as u8;
+        GYearMonth::create(year, month, timezone)
+    }
+}
+
+///
+/// A recurring month as xsd:gMonth, e.g. `--04`
+///
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub struct GMonth{
+    month: u8,
+    timezone: Option<Timezone>
+}
+
+impl GMonth{
+
+    pub fn create(month: u8, timezone: Option<Timezone>) -> Result<GMonth, DateTimeError>{
+        check_date(1972, month, 1)?;
+        Ok(GMonth{ month, timezone })
+    }
+
+    pub fn get_month(&self) -> u8{
+        self.month
+    }
+
+    pub fn get_timezone(&self) -> Option<Timezone>{
+        self.timezone
+    }
+}
+
+impl fmt::Display for GMonth{
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "--{:02}", self.month)?;
+        format_timezone(f, self.timezone)
+    }
+}
+
+impl FromStr for GMonth{
+    type Err = DateTimeError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let (rest, timezone) = split_timezone(s)?;
+        match rest.strip_prefix("--") {
+            Some(month) => GMonth::create(parse_digits(month, 1, 12, "month")? as u8, timezone),
+            None => Err(invalid(format!("<{}> is not a valid gMonth, expected --mm.", s)))
+        }
+    }
+}
+
+///
+/// A recurring day of the month as xsd:gDay, e.g. `---15`
+///
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub struct GDay{
+    day: u8,
+    timezone: Option<Timezone>
+}
+
+impl GDay{
+
+    pub fn create(day: u8, timezone: Option<Timezone>) -> Result<GDay, DateTimeError>{
+        check_date(1972, 1, day)?;
+        Ok(GDay{ day, timezone })
+    }
+
+    pub fn get_day(&self) -> u8{
+        self.day
+    }
+
+    pub fn get_timezone(&self) -> Option<Timezone>{
+        self.timezone
+    }
+}
+
+impl fmt::Display for GDay{
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "---{:02}", self.day)?;
+        format_timezone(f, self.timezone)
+    }
+}
+
+impl FromStr for GDay{
+    type Err = DateTimeError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let (rest, timezone) = split_timezone(s)?;
+        match rest.strip_prefix("---") {
+            Some(day) => GDay::create(parse_digits(day, 1, 31, "day")? as u8, timezone),
+            None => Err(invalid(format!("<{}> is not a valid gDay, expected ---dd.", s)))
+        }
+    }
+}
+
+///
+/// A recurring day of the year as xsd:gMonthDay, e.g. `--02-29`
+///
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub struct GMonthDay{
+    month: u8,
+    day: u8,
+    timezone: Option<Timezone>
+}
+
+impl GMonthDay{
+
+    ///
+    /// Creates the recurring day, the day has to exist in a leap year
+    ///
+    pub fn create(month: u8, day: u8, timezone: Option<Timezone>) -> Result<GMonthDay, DateTimeError>{
+        //1972 is a leap year, so --02-29 is valid
+        check_date(1972, month, day)?;
+        Ok(GMonthDay{ month, day, timezone })
+    }
+
+    pub fn get_month(&self) -> u8{
+        self.month
+    }
+
+    pub fn get_day(&self) -> u8{
+        self.day
+    }
+
+    pub fn get_timezone(&self) -> Option<Timezone>{
+        self.timezone
+    }
+}
+
+impl fmt::Display for GMonthDay{
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "--{:02}-{:02}", self.month, self.day)?;
+        format_timezone(f, self.timezone)
+    }
+}
+
+impl FromStr for GMonthDay{
+    type Err = DateTimeError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let (rest, timezone) = split_timezone(s)?;
+        let bytes = rest.as_bytes();
+        if bytes.len() != 7 || !rest.starts_with("--") || bytes[4] != b'-' {
+            return Err(invalid(format!("<{}> is not a valid gMonthDay, expected --mm-dd.", s)))
+        }
+        let month = parse_digits(&rest[2..4], 1, 12, "month")? as u8;
+        let day = parse_digits(&rest[5..7], 1, 31, "day")? as u8;
+        GMonthDay::create(month, day, timezone)
+    }
+}
+
+///
+/// A duration as xsd:duration, consisting of a number of months and a number of (nano)seconds with the same sign.
+///
+/// xsd:dayTimeDuration and xsd:yearMonthDuration use the same type with only one of both parts,
+/// see `parse_day_time` and `parse_year_month`.
+///
+/// # Comparison and arithmetic
+///
+/// Durations are equal if both parts are equal. They are ordered by adding them to the four reference dateTimes
+/// of XSD 1.1 section 3.3.6.2, if the results don't agree (e.g. `P1M` and `P30D`) `partial_cmp` is `None`.
+///
+/// # Example
+///
+/// ```
+/// use rdf4rust::util::date_time::Duration;
+///
+/// let duration: Duration = "P1Y2M3DT4H5M6.7S".parse().expect("Is valid duration");
+/// assert_eq!(14, duration.get_total_months());
+/// assert_eq!(3, duration.get_days());
+/// assert_eq!(6.7, duration.get_seconds());
+///
+/// let day: Duration = "PT24H".parse().expect("Is valid duration");
+/// assert_eq!("P1D", day.to_string());
+/// assert!(day < "P2D".parse::<Duration>().expect("Is valid duration"));
+///
+/// let month: Duration = "P1M".parse().expect("Is valid duration");
+/// assert!(month.partial_cmp(&"P30D".parse().expect("Is valid duration")).is_none());
+///
+/// let half_day = Duration::parse_day_time("PT12H").expect("Is valid dayTimeDuration");
+/// assert_eq!("P1DT12H", day.checked_add(&half_day).expect("Same sign").to_string());
+/// assert_eq!(Some(2.0), day.divide_by_duration(&half_day));
+/// assert!(Duration::parse_day_time("P1M").is_err());
+/// ```
+///
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub struct Duration{
+    months: i64,
+    nanos: i128
+}
+
+impl Duration{
+
+    ///
+    /// Creates a duration, fails if `months` and `nanoseconds` have different signs
+    ///
+    pub fn create(months: i64, nanoseconds: i128) -> Result<Duration, DateTimeError>{
+        if (months < 0 && nanoseconds > 0) || (months > 0 && nanoseconds < 0) {
+            return Err(invalid(String::from("Months and seconds of a duration need to have the same sign.")))
+        }
+        Ok(Duration{ months, nanos: nanoseconds })
+    }
+
+    ///
+    /// Parses a xsd:dayTimeDuration, which has no years and months, e.g. `P1DT2H`
+    ///
+    pub fn parse_day_time(s: &str) -> Result<Duration, DateTimeError>{
+        let duration: Duration = s.parse()?;
+        let date_part = s[..s.find('T').unwrap_or(s.len())].to_string();
+        if date_part.contains('Y') || date_part.contains('M') {
+            return Err(invalid(format!("<{}> is not a valid dayTimeDuration, it must not contain years or months.", s)))
+        }
+        Ok(duration)
+    }
+
+    ///
+    /// Parses a xsd:yearMonthDuration, which has only years and months, e.g. `P1Y2M`
+    ///
+    pub fn parse_year_month(s: &str) -> Result<Duration, DateTimeError>{
+        let duration: Duration = s.parse()?;
+        if s.contains('D') || s.contains('T') {
+            return Err(invalid(format!("<{}> is not a valid yearMonthDuration, it must only contain years and months.", s)))
+        }
+        Ok(duration)
+    }
+
+    pub fn is_negative(&self) -> bool{
+        self.months < 0 || self.nanos < 0
+    }
+
+    /// True if the duration has no months, so it is a valid xsd:dayTimeDuration value
+    pub fn is_day_time(&self) -> bool{
+        self.months == 0
+    }
+
+    /// True if the duration has no days, hours, minutes or seconds, so it is a valid xsd:yearMonthDuration value
+    pub fn is_year_month(&self) -> bool{
+        self.nanos == 0
+    }
+
+    /// The months part including the years, negative for negative durations
+    pub fn get_total_months(&self) -> i64{
+        self.months
+    }
+
+    /// The day-time part in nanoseconds, negative for negative durations
+    pub fn get_total_nanoseconds(&self) -> i128{
+        self.nanos
+    }
+
+    /// The years of the months part, negative for negative durations
+    pub fn get_years(&self) -> i64{
+        self.months / 12
+    }
+
+    /// The months of the months part without the years, negative for negative durations
+    pub fn get_months(&self) -> i64{
+        self.months % 12
+    }
+
+    pub fn get_days(&self) -> i64{
+        (self.nanos / NANOS_PER_DAY) as i64
+    }
+
+    pub fn get_hours(&self) -> i64{
+        (self.nanos % NANOS_PER_DAY / NANOS_PER_HOUR) as i64
+    }
+
+    pub fn get_minutes(&self) -> i64{
+        (self.nanos % NANOS_PER_HOUR / NANOS_PER_MINUTE) as i64
+    }
+
+    /// The seconds including their fraction, without the minutes
+    pub fn get_seconds(&self) -> f64{
+        (self.nanos % NANOS_PER_MINUTE) as f64 / NANOS_PER_SECOND as f64
+    }
+
+    ///
+    /// Adds both durations, `None` if the result would have months and seconds of different signs
+    ///
+    pub fn checked_add(&self, other: &Duration) -> Option<Duration>{
+        Duration::create(self.months.checked_add(other.months)?, self.nanos.checked_add(other.nanos)?).ok()
+    }
+
+    pub fn checked_sub(&self, other: &Duration) -> Option<Duration>{
+        self.checked_add(&-*other)
+    }
+
+    ///
+    /// Multiplies the duration by `factor`, rounding months and nanoseconds to the nearest value.
+    ///
+    /// `None` if `factor` is not finite or the duration has both months and seconds.
+    ///
+    pub fn multiply(&self, factor: f64) -> Option<Duration>{
+        if !factor.is_finite() || (self.months != 0 && self.nanos != 0) {
+            return None
+        }
+        Some(Duration{
+            months: (self.months as f64 * factor).round() as i64,
+            nanos: (self.nanos as f64 * factor).round() as i128
+        })
+    }
+
+    ///
+    /// Divides the duration by `divisor`, see `multiply`
+    ///
+    pub fn divide(&self, divisor: f64) -> Option<Duration>{
+        if divisor == 0.0 {
+            return None
+        }
+        self.multiply(1.0 / divisor)
+    }
+
+    ///
+    /// Divides two day-time or two year-month durations, e.g. `P1D` divided by `PT12H` is 2
+    ///
+    pub fn divide_by_duration(&self, divisor: &Duration) -> Option<f64>{
+        if self.is_day_time() && divisor.is_day_time() && divisor.nanos != 0 {
+            Some(self.nanos as f64 / divisor.nanos as f64)
+        }
+        else if self.is_year_month() && divisor.is_year_month() && divisor.months != 0 {
+            Some(self.months as f64 / divisor.months as f64)
+        }
+        else {
+            None
+        }
+    }
+}
+
+impl Neg for Duration{
+    type Output = Duration;
+
+    fn neg(self) -> Duration {
+        Duration{ months: -self.months, nanos: -self.nanos }
+    }
+}
+
+impl PartialOrd for Duration{
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        if self == other {
+            return Some(Ordering::Equal)
+        }
+        let mut order = None;
+        for (year, month) in [(1696, 9), (1697, 2), (1903, 3), (1903, 7)].iter() {
+            let reference = DateTime::create(*year, *month, 1, 0, 0, 0, 0, Some(Timezone::utc())).expect("Is valid dateTime");
+            let current = reference.add_duration(self)?.partial_cmp(&reference.add_duration(other)?)?;
+            match order {
+                None => order = Some(current),
+                Some(order) if order != current => return None,
+                _ => {}
+            }
+        }
+        order
+    }
+}
+
+impl fmt::Display for Duration{
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        if self.is_negative() {
+            write!(f, "-")?;
+        }
+        write!(f, "P")?;
+        let months = self.months.unsigned_abs();
+        let nanos = self.nanos.unsigned_abs();
+        if months == 0 && nanos == 0 {
+            return write!(f, "T0S")
+        }
+        if months / 12 != 0 {
+            write!(f, "{}Y", months / 12)?;
+        }
+        if months % 12 != 0 {
+            write!(f, "{}M", months % 12)?;
+        }
+        let (days, hours) = (nanos / NANOS_PER_DAY as u128, nanos % NANOS_PER_DAY as u128 / NANOS_PER_HOUR as u128);
+        let (minutes, seconds) = (nanos % NANOS_PER_HOUR as u128 / NANOS_PER_MINUTE as u128, nanos % NANOS_PER_MINUTE as u128);
+        if days != 0 {
+            write!(f, "{}D", days)?;
+        }
+        if hours != 0 || minutes != 0 || seconds != 0 {
+            write!(f, "T")?;
+            if hours != 0 {
+                write!(f, "{}H", hours)?;
+            }
+            if minutes != 0 {
+                write!(f, "{}M", minutes)?;
+            }
+            if seconds != 0 {
+                write!(f, "{}{}S", seconds / NANOS_PER_SECOND as u128, format_fraction((seconds % NANOS_PER_SECOND as u128) as u32))?;
+            }
+        }
+        Ok(())
+    }
+}
+
+impl FromStr for Duration{
+    type Err = DateTimeError;
+
+    ///
+    /// Parses `-?P(nY)?(nM)?(nD)?(T(nH)?(nM)?(n(.n+)?S)?)?` with at least one component
+    ///
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let error = || invalid(format!("<{}> is not a valid duration, expected -?PnYnMnDTnHnMnS.", s));
+        let (negative, rest) = match s.strip_prefix('-') {
+            Some(rest) => (true, rest),
+            None => (false, s)
+        };
+        let rest = rest.strip_prefix('P').ok_or_else(error)?;
+        let (date_part, time_part) = match rest.split_once('T') {
+            Some((_, "")) => return Err(error()),
+            Some((date_part, time_part)) => (date_part, Some(time_part)),
+            None => (rest, None)
+        };
+        if date_part.is_empty() && time_part.is_none() {
+            return Err(error())
+        }
+        let mut months: i128 = 0;
+        let mut nanos: i128 = 0;
+        //the designators of each part in their required order, with their value in months or nanoseconds
+        let date_designators: [(char, i128); 3] = [('Y', 12), ('M', 1), ('D', NANOS_PER_DAY)];
+        let time_designators: [(char, i128); 3] = [('H', NANOS_PER_HOUR), ('M', NANOS_PER_MINUTE), ('S', NANOS_PER_SECOND)];
+        for (part, designators, is_date_part) in [(Some(date_part), &date_designators, true), (time_part, &time_designators, false)].iter() {
+            let mut part = match part {
+                Some(part) => *part,
+                None => continue
+            };
+            let mut next = 0;
+            while !part.is_empty() {
+                let end = part.find(|c: char| !(c.is_ascii_digit() || c == '.')).ok_or_else(error)?;
+                let number = &part[..end];
+                let designator = part[end..].chars().next().expect("Found a non digit char");
+                let index = designators[next..].iter().position(|(c, _)| *c == designator).ok_or_else(error)? + next;
+                next = index + 1;
+                let (integer, fraction) = match number.split_once('.') {
+                    Some((integer, fraction)) if designator == 'S' && !fraction.is_empty() => (integer, fraction),
+                    Some(_) => return Err(error()),
+                    None => (number, "")
+                };
+                if integer.is_empty() || !fraction.chars().all(|c| c.is_ascii_digit()) {
+                    return Err(error())
+                }
+                let value: i128 = integer.parse().map_err(|_| error())?;
+                let factor = designators[index].1;
+                if *is_date_part && designator != 'D' {
+                    months = value.checked_mul(factor).and_then(|value| months.checked_add(value)).ok_or_else(error)?;
+                }
+                else {
+                    nanos = value.checked_mul(factor).and_then(|value| nanos.checked_add(value)).ok_or_else(error)?;
+                    nanos += parse_fraction(fraction) as i128;
+                }
+                part = &part[end + 1..];
+            }
+        }
+        let months = i64::try_from(months).map_err(|_| error())?;
+        Ok(if negative { Duration{ months: -months, nanos: -nanos } } else { Duration{ months, nanos } })
+    }
+}
+
+fn compare_timeline(a: i128, a_timezone: bool, b: i128, b_timezone: bool) -> Option<Ordering>{
+    if a_timezone == b_timezone {
+        return Some(a.cmp(&b))
+    }
+    //the value without timezone could be at any point within 14 hours around its UTC value
+    let (local, zoned, flip) = if a_timezone { (b, a, true) } else { (a, b, false) };
+    let order = if local + MAX_TIMEZONE_NANOS < zoned {
+        Ordering::Less
+    }
+    else if local - MAX_TIMEZONE_NANOS > zoned {
+        Ordering::Greater
+    }
+    else {
+        return None
+    };
+    Some(if flip { order.reverse() } else { order })
+}
+
+fn is_leap_year(year: i64) -> bool{
+    year.rem_euclid(4) == 0 && (year.rem_euclid(100) != 0 || year.rem_euclid(400) == 0)
+}
+
+fn days_in_month(year: i64, month: u8) -> u8{
+    match month {
+        2 if is_leap_year(year) => 29,
+        2 => 28,
+        4 | 6 | 9 | 11 => 30,
+        _ => 31
+    }
+}
+
+fn check_date(year: i64, month: u8, day: u8) -> Result<(), DateTimeError>{
+    if year.abs() > MAX_YEAR {
+        return Err(invalid(format!("Year {} is out of range.", year)))
+    }
+    if !(1..=12).contains(&month) {
+        return Err(invalid(format!("Month {} is out of range.", month)))
+    }
+    if day < 1 || day > days_in_month(year, month) {
+        return Err(invalid(format!("Day {} is out of range for {}-{:02}.", day, format_year(year), month)))
+    }
+    Ok(())
+}
+
+fn check_time(hour: u8, minute: u8, second: u8, nanosecond: u32) -> Result<(), DateTimeError>{
+    if hour > 24 || minute > 59 || second > 59 || nanosecond >= 1_000_000_000 {
+        return Err(invalid(format!("Time {:02}:{:02}:{:02} is out of range.", hour, minute, second)))
+    }
+    if hour == 24 && (minute != 0 || second != 0 || nanosecond != 0) {
+        return Err(invalid(String::from("Hour 24 is only allowed for 24:00:00.")))
+    }
+    Ok(())
+}
+
+///
+/// Days since 1970-01-01 of the proleptic Gregorian calendar
+///
+fn days_from_civil(year: i64, month: u8, day: u8) -> i64{
+    let year = if month <= 2 { year - 1 } else { year };
+    let era = year.div_euclid(400);
+    let year_of_era = year - era * 400;
+    let month = month as i64;
+    let day_of_year = (153 * (if month > 2 { month - 3 } else { month + 9 }) + 2) / 5 + day as i64 - 1;
+    let day_of_era = year_of_era * 365 + year_of_era / 4 - year_of_era / 100 + day_of_year;
+    era * 146097 + day_of_era - 719468
+}
+
+fn civil_from_days(days: i64) -> (i64, u8, u8){
+    let days = days + 719468;
+    let era = days.div_euclid(146097);
+    let day_of_era = days - era * 146097;
+    let year_of_era = (day_of_era - day_of_era / 1460 + day_of_era / 36524 - day_of_era / 146096) / 365;
+    let day_of_year = day_of_era - (365 * year_of_era + year_of_era / 4 - year_of_era / 100);
+    let month_index = (5 * day_of_year + 2) / 153;
+    let day = (day_of_year - (153 * month_index + 2) / 5 + 1) as u8;
+    let month = (if month_index < 10 { month_index + 3 } else { month_index - 9 }) as u8;
+    let year = year_of_era + era * 400 + if month <= 2 { 1 } else { 0 };
+    (year, month, day)
+}
+
+///
+/// Splits off a trailing timezone `Z` or `(+|-)hh:mm`
+///
+fn split_timezone(s: &str) -> Result<(&str, Option<Timezone>), DateTimeError>{
+    if let Some(rest) = s.strip_suffix('Z') {
+        return Ok((rest, Some(Timezone::utc())))
+    }
+    let bytes = s.as_bytes();
+    if bytes.len() > 6 && (bytes[bytes.len() - 6] == b'+' || bytes[bytes.len() - 6] == b'-') && bytes[bytes.len() - 3] == b':' {
+        let index = s.len() - 6;
+        return Ok((&s[..index], Some(s[index..].parse()?)))
+    }
+    Ok((s, None))
+}
+
+fn parse_digits(s: &str, min: u32, max: u32, name: &str) -> Result<u32, DateTimeError>{
+    if s.len() != 2 || !s.chars().all(|c| c.is_ascii_digit()) {
+        return Err(invalid(format!("<{}> is not a valid {}, expected two digits.", s, name)))
+    }
+    let value: u32 = s.parse().expect("Two digits are a valid number");
+    if value < min || value > max {
+        return Err(invalid(format!("The {} {} is out of range.", name, value)))
+    }
+    Ok(value)
+}
+
+///
+/// Parses `-?yyyy`, years with more than four digits must not start with 0
+///
+fn parse_year(s: &str) -> Result<i64, DateTimeError>{
+    let digits = s.strip_prefix('-').unwrap_or(s);
+    if digits.len() < 4 || !digits.chars().all(|c| c.is_ascii_digit()) || (digits.len() > 4 && digits.starts_with('0')) {
+        return Err(invalid(format!("<{}> is not a valid year, expected at least four digits.", s)))
+    }
+    match s.parse::<i64>() {
+        Ok(year) if year.abs() <= MAX_YEAR => Ok(year),
+        _ => Err(invalid(format!("Year {} is out of range.", s)))
+    }
+}
+
+///
+/// Parses `-?yyyy-mm-dd` into year, month and day
+///
+fn parse_date(s: &str) -> Result<(i64, u8, u8), DateTimeError>{
+    let error = || invalid(format!("<{}> is not a valid date, expected yyyy-mm-dd.", s));
+    let (rest, day) = s.rsplit_once('-').ok_or_else(error)?;
+    let (year, month) = rest.rsplit_once('-').ok_or_else(error)?;
+    if year.is_empty() || year == "-" {
+        return Err(error())
+    }
+    let year = parse_year(year)?;
+    let month = parse_digits(month, 1, 12, "month")? as u8;
+    let day = parse_digits(day, 1, 31, "day")? as u8;
+    check_date(year, month, day)?;
+    Ok((year, month, day))
+}
+
+///
+/// Parses `hh:mm:ss(.s+)?` into hour, minute, second and nanosecond
+///
+fn parse_time(s: &str) -> Result<(u8, u8, u8, u32), DateTimeError>{
+    let bytes = s.as_bytes();
+    if bytes.len() < 8 || bytes[2] != b':' || bytes[5] != b':' {
+        return Err(invalid(format!("<{}> is not a valid time, expected hh:mm:ss.", s)))
+    }
+    let hour = parse_digits(&s[..2], 0, 24, "hour")? as u8;
+    let minute = parse_digits(&s[3..5], 0, 59, "minute")? as u8;
+    let second = parse_digits(&s[6..8], 0, 59, "second")? as u8;
+    let nanosecond = match s[8..].strip_prefix('.') {
+        Some(fraction) if !fraction.is_empty() && fraction.chars().all(|c| c.is_ascii_digit()) => parse_fraction(fraction),
+        None if s.len() == 8 => 0,
+        _ => return Err(invalid(format!("<{}> is not a valid time, expected digits after <.>.", s)))
+    };
+    check_time(hour, minute, second, nanosecond)?;
+    Ok((hour, minute, second, nanosecond))
+}
+
+/// The nanoseconds of the fraction digits after the decimal point, further digits are cut off
+fn parse_fraction(fraction: &str) -> u32{
+    let mut nanos = 0;
+    for (index, c) in fraction.chars().take(9).enumerate() {
+        nanos += c.to_digit(10).expect("Checked digits") * 10u32.pow(8 - index as u32);
+    }
+    nanos
+}
+
+fn format_year(year: i64) -> String{
+    if year < 0 {
+        format!("-{:04}", year.unsigned_abs())
+    }
+    else {
+        format!("{:04}", year)
+    }
+}
+
+fn format_date(year: i64, month: u8, day: u8) -> String{
+    format!("{}-{:02}-{:02}", format_year(year), month, day)
+}
+
+fn format_time(hour: u8, minute: u8, second: u8, nanosecond: u32) -> String{
+    format!("{:02}:{:02}:{:02}{}", hour, minute, second, format_fraction(nanosecond))
+}
+
+/// The fraction of a second without trailing zeros, empty if there is no fraction
+fn format_fraction(nanosecond: u32) -> String{
+    if nanosecond == 0 {
+        return String::new()
+    }
+    let fraction = format!(".{:09}", nanosecond);
+    String::from(fraction.trim_end_matches('0'))
+}
+
+fn format_timezone(f: &mut fmt::Formatter, timezone: Option<Timezone>) -> fmt::Result{
+    match timezone {
+        Some(timezone) => write!(f, "{}", timezone),
+        None => Ok(())
+    }
+}
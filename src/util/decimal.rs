@@ -0,0 +1,344 @@
+//!
+//! Arbitrary-precision values of xsd:decimal and xsd:integer (and the types derived from it).
+//!
+//! Integers are represented as `BigInt`, decimals as `Decimal`, an exact decimal number of any size.
+//! Addition, subtraction, multiplication and comparison are exact, division is rounded to `DIVISION_SCALE` fraction digits.
+//! Both print their XSD 1.1 canonical form via `Display`.
+//!
+
+pub use num_bigint::BigInt;
+use num_bigint::Sign;
+use num_traits::{Zero, Signed, Pow, ToPrimitive};
+use crate::error::{RDFError, ErrorKind};
+use std::cmp::Ordering;
+use std::convert::TryFrom;
+use std::fmt;
+use std::ops::{Add, Sub, Mul, Div, Neg};
+use std::str::FromStr;
+
+/// The number of fraction digits a `Decimal` division will be rounded to
+pub const DIVISION_SCALE: u32 = 24;
+
+///
+/// Error of invalid lexical forms or values, always of kind `ErrorKind::InvalidLiteral`
+///
+pub type DecimalError = RDFError;
+
+fn invalid(msg: String) -> DecimalError{
+    DecimalError::new(ErrorKind::InvalidLiteral, msg)
+}
+
+fn ten_pow(exponent: u32) -> BigInt{
+    Pow::pow(BigInt::from(10u8), exponent)
+}
+
+///
+/// An exact decimal number, the value of xsd:decimal
+///
+/// The value is `unscaled * 10^-scale`, kept without trailing fraction zeros, so equal values are always equal structurally.
+///
+/// # Example
+///
+/// ```
+/// use rdf4rust::util::decimal::Decimal;
+///
+/// let a = "0.1".parse::<Decimal>().unwrap();
+/// let b = "0.2".parse::<Decimal>().unwrap();
+/// assert_eq!("0.3".parse::<Decimal>().unwrap(), a.clone() + b);
+///
+/// let big = "123456789012345678901234567890.000000001".parse::<Decimal>().unwrap();
+/// assert_eq!("246913578024691357802469135780.000000002", (big.clone() + big).to_string());
+///
+/// // canonical forms
+/// assert_eq!("1", "+01.000".parse::<Decimal>().unwrap().to_string());
+/// assert_eq!("-0.5", "-.50".parse::<Decimal>().unwrap().to_string());
+///
+/// assert_eq!("0.333333333333333333333333", (Decimal::from(1) / Decimal::from(3)).to_string());
+/// assert!(a < "0.10000000000000000000001".parse::<Decimal>().unwrap());
+/// ```
+///
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub struct Decimal{
+    unscaled: BigInt,
+    scale: u32
+}
+
+impl Decimal{
+
+    ///
+    /// Creates the decimal `unscaled * 10^-scale`
+    ///
+    pub fn create(unscaled: BigInt, scale: u32) -> Decimal{
+        let mut unscaled = unscaled;
+        let mut scale = scale;
+        let ten = BigInt::from(10u8);
+        while scale > 0 && (&unscaled % &ten).is_zero(){
+            unscaled /= &ten;
+            scale -= 1;
+        }
+        if unscaled.is_zero(){
+            scale = 0;
+        }
+        Decimal{ unscaled, scale }
+    }
+
+    pub fn get_unscaled(&self) -> &BigInt{
+        &self.unscaled
+    }
+
+    pub fn get_scale(&self) -> u32{
+        self.scale
+    }
+
+    pub fn is_zero(&self) -> bool{
+        self.unscaled.is_zero()
+    }
+
+    pub fn is_negative(&self) -> bool{
+        self.unscaled.is_negative()
+    }
+
+    /// Checks if the decimal has no fraction digits
+    pub fn is_integer(&self) -> bool{
+        self.scale == 0
+    }
+
+    /// The integer part of the decimal, truncated towards zero
+    pub fn to_integer(&self) -> BigInt{
+        &self.unscaled / ten_pow(self.scale)
+    }
+
+    /// The value as `i64`, None if it has fraction digits or doesn't fit
+    pub fn to_i64(&self) -> Option<i64>{
+        if self.is_integer() {self.unscaled.to_i64()} else {None}
+    }
+
+    /// The closest `f64` to this decimal, which may be infinite for very large values
+    pub fn to_f64(&self) -> f64{
+        self.to_string().parse::<f64>().unwrap_or(f64::NAN)
+    }
+
+    pub fn abs(&self) -> Decimal{
+        Decimal{ unscaled: self.unscaled.abs(), scale: self.scale }
+    }
+
+    /// The largest integer less than or equal to this decimal
+    pub fn floor(&self) -> Decimal{
+        let truncated = self.to_integer();
+        if self.is_negative() && !self.is_integer(){
+            Decimal::from(truncated - 1)
+        }else{
+            Decimal::from(truncated)
+        }
+    }
+
+    /// The smallest integer greater than or equal to this decimal
+    pub fn ceil(&self) -> Decimal{
+        let truncated = self.to_integer();
+        if !self.is_negative() && !self.is_integer(){
+            Decimal::from(truncated + 1)
+        }else{
+            Decimal::from(truncated)
+        }
+    }
+
+    /// The closest integer, halves are rounded towards positive infinity like `fn:round`
+    pub fn round(&self) -> Decimal{
+        (self.clone() + Decimal::create(BigInt::from(5u8), 1)).floor()
+    }
+
+    ///
+    /// Divides this decimal by `other`, rounding half away from zero to `scale` fraction digits
+    ///
+    /// # Returns
+    ///
+    /// The quotient or None if `other` is zero
+    ///
+    pub fn checked_div(&self, other: &Decimal, scale: u32) -> Option<Decimal>{
+        if other.is_zero(){
+            return None
+        }
+        let numerator = &self.unscaled * ten_pow(other.scale + scale);
+        let denominator = &other.unscaled * ten_pow(self.scale);
+        let mut quotient = &numerator / &denominator;
+        let remainder = &numerator % &denominator;
+        if remainder.abs() * 2 >= denominator.abs(){
+            if numerator.sign() == denominator.sign(){
+                quotient += 1;
+            }else{
+                quotient -= 1;
+            }
+        }
+        Some(Decimal::create(quotient, scale))
+    }
+
+    /// Brings both unscaled values to the same scale
+    fn align(&self, other: &Decimal) -> (BigInt, BigInt, u32){
+        match self.scale.cmp(&other.scale){
+            Ordering::Less => (&self.unscaled * ten_pow(other.scale - self.scale), other.unscaled.clone(), other.scale),
+            Ordering::Greater => (self.unscaled.clone(), &other.unscaled * ten_pow(self.scale - other.scale), self.scale),
+            Ordering::Equal => (self.unscaled.clone(), other.unscaled.clone(), self.scale)
+        }
+    }
+}
+
+impl From<BigInt> for Decimal{
+    fn from(value: BigInt) -> Self {
+        Decimal{ unscaled: value, scale: 0 }
+    }
+}
+
+impl From<i64> for Decimal{
+    fn from(value: i64) -> Self {
+        Decimal::from(BigInt::from(value))
+    }
+}
+
+impl From<u64> for Decimal{
+    fn from(value: u64) -> Self {
+        Decimal::from(BigInt::from(value))
+    }
+}
+
+impl From<i32> for Decimal{
+    fn from(value: i32) -> Self {
+        Decimal::from(BigInt::from(value))
+    }
+}
+
+impl TryFrom<f64> for Decimal{
+    type Error = DecimalError;
+
+    ///
+    /// Converts the shortest representation of `value` which reads back as the same `f64`, e.g. `0.1` will be exactly 0.1
+    ///
+    fn try_from(value: f64) -> Result<Self, Self::Error> {
+        if !value.is_finite(){
+            return Err(invalid(format!("{} is not a decimal.", value)))
+        }
+        value.to_string().parse::<Decimal>()
+    }
+}
+
+impl Add for Decimal{
+    type Output = Decimal;
+
+    fn add(self, rhs: Decimal) -> Decimal {
+        let (a, b, scale) = self.align(&rhs);
+        Decimal::create(a + b, scale)
+    }
+}
+
+impl Sub for Decimal{
+    type Output = Decimal;
+
+    fn sub(self, rhs: Decimal) -> Decimal {
+        let (a, b, scale) = self.align(&rhs);
+        Decimal::create(a - b, scale)
+    }
+}
+
+impl Mul for Decimal{
+    type Output = Decimal;
+
+    fn mul(self, rhs: Decimal) -> Decimal {
+        Decimal::create(self.unscaled * rhs.unscaled, self.scale + rhs.scale)
+    }
+}
+
+impl Div for Decimal{
+    type Output = Decimal;
+
+    ///
+    /// Divides rounding to `DIVISION_SCALE` fraction digits, use `checked_div` to choose the scale
+    ///
+    /// # Panics
+    ///
+    /// If `rhs` is zero
+    ///
+    fn div(self, rhs: Decimal) -> Decimal {
+        self.checked_div(&rhs, DIVISION_SCALE).expect("Division by zero")
+    }
+}
+
+impl Neg for Decimal{
+    type Output = Decimal;
+
+    fn neg(self) -> Decimal {
+        Decimal{ unscaled: -self.unscaled, scale: self.scale }
+    }
+}
+
+impl PartialOrd for Decimal{
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Ord for Decimal{
+    fn cmp(&self, other: &Self) -> Ordering {
+        let (a, b, _) = self.align(other);
+        a.cmp(&b)
+    }
+}
+
+impl fmt::Display for Decimal{
+    ///
+    /// Prints the canonical form, without a decimal point for integers, e.g. `1`, `-0.5`
+    ///
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if self.scale == 0 {
+            return write!(f, "{}", self.unscaled)
+        }
+        let digits = self.unscaled.magnitude().to_string();
+        let digits = format!("{:0>width$}", digits, width = self.scale as usize + 1);
+        let (int, fraction) = digits.split_at(digits.len() - self.scale as usize);
+        let sign = if self.unscaled.sign() == Sign::Minus {"-"} else {""};
+        write!(f, "{}{}.{}", sign, int, fraction)
+    }
+}
+
+impl FromStr for Decimal{
+    type Err = DecimalError;
+
+    ///
+    /// Parses the lexical form of xsd:decimal, e.g. `-1.50`, `+.5` or `12.`
+    ///
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let unsigned = s.strip_prefix(|c| c == '+' || c == '-').unwrap_or(s);
+        let (int, fraction) = unsigned.split_once('.').unwrap_or((unsigned, ""));
+        if int.is_empty() && fraction.is_empty()
+            || !int.bytes().all(|b| b.is_ascii_digit())
+            || !fraction.bytes().all(|b| b.is_ascii_digit()){
+            return Err(invalid(format!("{} is not a valid decimal.", s)))
+        }
+        let mut unscaled = BigInt::parse_bytes(format!("{}{}", int, fraction).as_bytes(), 10).unwrap_or_else(BigInt::zero);
+        if s.starts_with('-'){
+            unscaled = -unscaled;
+        }
+        Ok(Decimal::create(unscaled, fraction.len() as u32))
+    }
+}
+
+///
+/// Parses the lexical form of xsd:integer, e.g. `-0012` or `+5`
+///
+/// # Example
+///
+/// ```
+/// use rdf4rust::util::decimal::{parse_integer, BigInt};
+///
+/// let big = parse_integer("+123456789012345678901234567890").unwrap();
+/// assert_eq!("246913578024691357802469135780", (big.clone() + big).to_string());
+/// assert!(parse_integer("1_000").is_err());
+/// assert!(parse_integer("1.0").is_err());
+/// ```
+///
+pub fn parse_integer(s: &str) -> Result<BigInt, DecimalError>{
+    let unsigned = s.strip_prefix(|c| c == '+' || c == '-').unwrap_or(s);
+    if unsigned.is_empty() || !unsigned.bytes().all(|b| b.is_ascii_digit()){
+        return Err(invalid(format!("{} is not a valid integer.", s)))
+    }
+    let value = BigInt::parse_bytes(unsigned.as_bytes(), 10).unwrap_or_else(BigInt::zero);
+    Ok(if s.starts_with('-') {-value} else {value})
+}
@@ -37,12 +37,12 @@ fn map_to_binary(c: char)->u8{
         '7' => 7,
         '8' => 8,
         '9' => 9,
-        'A' => 10,
-        'B' => 11,
-        'C' => 12,
-        'D' => 13,
-        'E' => 14,
-        'F' => 15,
+        'A' | 'a' => 10,
+        'B' | 'b' => 11,
+        'C' | 'c' => 12,
+        'D' | 'd' => 13,
+        'E' | 'e' => 14,
+        'F' | 'f' => 15,
         _ => 16,
     }
 }
\ No newline at end of file
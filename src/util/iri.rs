@@ -1,103 +1,75 @@
 use std::fmt;
 use std::ops::Add;
 use std::fmt::Formatter;
-use std::collections::HashMap;
-use std::error::Error;
-use std::cmp::Ordering;
-use std::hash::{Hash, Hasher};
+use std::net::{Ipv4Addr, Ipv6Addr};
+use crate::error::{RDFError, ErrorKind};
+use unicode_normalization::UnicodeNormalization;
 
-#[derive(Eq)]
+///
+/// The query of an IRI, the part between `?` and `#`, kept exactly as it was written.
+///
+#[derive(Clone, Ord, PartialOrd, PartialEq, Eq, Hash)]
 pub struct Query {
-    key_value: HashMap<String, String>,
+    query: String
 }
 
 impl fmt::Display for Query{
     fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
-        write!(f, "{}", self.as_string())
-    }
-}
-
-impl Hash for Query{
-    fn hash<H: Hasher>(&self, state: &mut H) {
-        self.as_string().hash(state)
-    }
-
-    fn hash_slice<H: Hasher>(data: &[Self], state: &mut H) where
-        Self: Sized, {
-        todo!()
-    }
-}
-
-impl PartialOrd for Query{
-    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
-        self.as_string().partial_cmp(&other.as_string())
-    }
-
-    fn lt(&self, other: &Self) -> bool {
-        self.as_string().lt(&other.as_string())
-    }
-
-    fn le(&self, other: &Self) -> bool {
-        self.as_string().le(&other.as_string())
-    }
-
-    fn gt(&self, other: &Self) -> bool {
-        self.as_string().gt(&other.as_string())
-    }
-
-    fn ge(&self, other: &Self) -> bool {
-        self.as_string().ge(&other.as_string())
+        write!(f, "{}", self.query)
     }
 }
 
-impl PartialEq for Query{
-    //TODO!
-    fn eq(&self, other: &Self) -> bool {
-        self.as_string().eq(&other.as_string())
-    }
-
-    fn ne(&self, other: &Self) -> bool {
-        self.as_string().ne(&other.as_string())
+impl Query {
 
+    ///
+    /// Splits the query into `key=value` pairs separated by `&`.
+    ///
+    /// The pairs keep their order and duplicate keys. Parts without `=` (e.g. `?q`) are keys with an empty value.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use rdf4rust::util::iri::IRI;
+    ///
+    /// let iri = IRI::create_iri(&String::from("http://example.com/?b=1&a=2&b=3&flag")).expect("Is valid IRI");
+    /// let query = iri.get_query().as_ref().expect("Has query");
+    ///
+    /// assert_eq!("b=1&a=2&b=3&flag", query.as_str());
+    /// assert_eq!(vec![("b", "1"), ("a", "2"), ("b", "3"), ("flag", "")], query.get_key_value_pairs());
+    /// ```
+    ///
+    pub fn get_key_value_pairs(&self) -> Vec<(&str, &str)>{
+        self.query.split('&')
+            .filter(|pair| !pair.is_empty())
+            .map(|pair| match pair.find('=') {
+                Some(index) => (&pair[..index], &pair[index+1..]),
+                None => (pair, "")
+            })
+            .collect()
     }
-}
-
-impl Query {
 
-    pub fn get_key_value_pairs(&self) -> &HashMap<String, String>{
-        &self.key_value
+    pub fn as_str(&self) -> &str {
+        &self.query
     }
 
-    fn as_string(&self) -> String {
-        let mut ret = String::from("");
-        for (index, (key, value)) in self.key_value.iter().enumerate(){
-            ret = ret.add(key).add("=").add(value);
-            if index < self.key_value.len()-1{
-                ret = ret.add("&");
-            }
-        }
-        ret
+    pub fn as_string(&self) -> String {
+        self.query.clone()
     }
 
+    ///
+    /// Creates the query from the part of an IRI between `?` and `#`.
+    ///
+    /// Fails if the query contains characters not allowed by RFC 3987 (`iquery`).
+    ///
     pub fn create_query(query: String) -> Result<Query, IRIInvalidError>{
-        let v: Vec<&str> = query.split("&").collect();
-        let mut key_value: HashMap<String, String> = HashMap::new();
-        for fragment in v.iter() {
-            match fragment.find("=") {
-                Some(value) => {
-                    let key = String::from(&fragment[..value]);
-                    let value = String::from(&fragment[value+1..]);
-                    key_value.insert(key, value);
-                },
-                None => return Err(IRIInvalidError::new(String::from("Invalid Query part, should be set of <key=value> pairs seperated by <&>")))
-            };
-        }
-        Result::Ok(Query{
-            key_value
+        check_chars(&query, 0, is_query_char, "query")?;
+        Ok(Query{
+            query
         })
     }
 }
-#[derive(Ord, PartialOrd, PartialEq, Eq, Hash)]
+
+#[derive(Clone, Ord, PartialOrd, PartialEq, Eq, Hash)]
 pub struct User {
     user: String,
     password: Option<String>,
@@ -148,11 +120,28 @@ impl User {
         return ret;
     }
 }
-#[derive(Ord, PartialOrd, PartialEq, Eq, Hash)]
+
+///
+/// The syntactic form of the host of an `Authority`
+///
+#[derive(Clone, Copy, Debug, Ord, PartialOrd, PartialEq, Eq, Hash)]
+pub enum HostType {
+    /// A registered name like `example.com`, possibly empty as in `file:///`
+    RegName,
+    /// A dotted decimal IPv4 address like `127.0.0.1`
+    IPv4,
+    /// An IPv6 literal like `[::1]`
+    IPv6,
+    /// A future IP literal like `[v7.fe80::a+en1]`
+    IPvFuture
+}
+
+#[derive(Clone, Ord, PartialOrd, PartialEq, Eq, Hash)]
 pub struct Authority {
     user: Option<User>,
     host: String,
-    port: Option<u32>
+    host_type: HostType,
+    port: Option<String>
 }
 
 impl fmt::Display for Authority {
@@ -163,48 +152,95 @@ impl fmt::Display for Authority {
 
 impl Authority {
 
-    pub fn get_port(&self) -> &Option<u32>{
+    ///
+    /// The port as written, which may be empty (e.g. `http://example.com:/`)
+    ///
+    pub fn get_port(&self) -> &Option<String>{
         &self.port
     }
 
+    ///
+    /// The port as number, `None` if there is no port, it is empty or too big
+    ///
+    pub fn get_port_number(&self) -> Option<u32>{
+        match &self.port {
+            Some(port) => port.parse().ok(),
+            None => None
+        }
+    }
+
+    ///
+    /// The host as written, IP literals include the brackets
+    ///
     pub fn get_host(&self) -> &String{
         &self.host
     }
 
+    pub fn get_host_type(&self) -> HostType{
+        self.host_type
+    }
+
     pub fn get_user(&self) -> &Option<User>{
         &self.user
     }
 
-    fn create_authority(string: String)-> Result<Authority, IRIInvalidError>{
-        //regex check ([^\s:@]+(:[^\s:@]*)?@)?[^:]+(:[0-9]+)?
-        //xyz:yul@ <-
-        //uli.bla.d:0000
-        let mut index= match string.find('@'){
-            Some(num) => num,
-            None => 0
-        };
+    ///
+    /// Parses `iauthority = [ iuserinfo "@" ] ihost [ ":" port ]`, `offset` is the position of the authority in the IRI.
+    ///
+    fn create_authority(string: &str, offset: usize) -> Result<Authority, IRIInvalidError>{
         let mut user = None;
-        let mut port = None;
-        if index>0 {
+        let mut host_start = 0;
+        if let Some(index) = string.find('@') {
+            check_chars(&string[..index], offset, is_userinfo_char, "user info")?;
             user = Some(User::create_user(String::from(&string[..index])));
-            index = index+1;
+            host_start = index + 1;
         }
-
-        let host_end_index = match string[index..].find(':'){
-            Some(num) => num+index,
-            None => string.len()
-        };
-        let host = String::from(&string[index..host_end_index]);
-        if host_end_index+1 <string.len(){
-            let port_no:u32 = match string[host_end_index+1..].parse(){
-                Ok(num) => num,
-                Err(_) => return Err(IRIInvalidError::new(String::from("IRI is not valid, expected number as port.")))
+        let rest = &string[host_start..];
+        let host_offset = offset + host_start;
+        let (host, host_type, port_part) = if rest.starts_with('[') {
+            let end = match rest.find(']') {
+                Some(end) => end,
+                None => return Err(invalid_iri(String::from("IP literal is missing the closing <]>."), host_offset))
+            };
+            let literal = &rest[1..end];
+            let host_type = if literal.starts_with('v') || literal.starts_with('V') {
+                if !is_ip_future(literal) {
+                    return Err(invalid_iri(format!("<{}> is not a valid IPvFuture literal.", literal), host_offset + 1))
+                }
+                HostType::IPvFuture
+            }
+            else {
+                if literal.parse::<Ipv6Addr>().is_err() {
+                    return Err(invalid_iri(format!("<{}> is not a valid IPv6 address.", literal), host_offset + 1))
+                }
+                HostType::IPv6
             };
-            port =  Some(port_no);
+            (&rest[..end+1], host_type, &rest[end+1..])
+        }
+        else {
+            let end = rest.find(':').unwrap_or(rest.len());
+            let host = &rest[..end];
+            check_chars(host, host_offset, is_reg_name_char, "host")?;
+            let host_type = if host.parse::<Ipv4Addr>().is_ok() { HostType::IPv4 } else { HostType::RegName };
+            (host, host_type, &rest[end..])
+        };
+        let port = if port_part.is_empty() {
+            None
         }
+        else {
+            let port_offset = host_offset + host.len();
+            if !port_part.starts_with(':') {
+                return Err(invalid_iri(String::from("Expected <:> before the port."), port_offset))
+            }
+            if let Some(pos) = port_part[1..].find(|c: char| !c.is_ascii_digit()) {
+                return Err(invalid_iri(String::from("Port has to be a number."), port_offset + 1 + pos))
+            }
+            Some(String::from(&port_part[1..]))
+        };
         Result::Ok(Authority{
             user,
-            host,
+            host: String::from(host),
+            host_type,
             port
         })
     }
@@ -213,20 +249,24 @@ impl Authority {
         let mut ret = String::from("");
         if let Some(user) = &self.user{
             ret = ret.add(user.as_string().as_str()).add("@");
-
-        }
-        if self.user.is_some(){
         }
         ret = ret.add(self.host.as_str());
-        if self.port.is_some(){
-            ret = ret.add(":").add(self.port.expect("").to_string().as_str());
+        if let Some(port) = &self.port{
+            ret = ret.add(":").add(port);
         }
         ret
     }
 }
 
-#[derive(PartialOrd, PartialEq, Eq, Hash)]
+///
+/// An absolute IRI as defined by RFC 3987.
+///
+/// The IRI keeps its exact lexical form, `as_string` returns the IRI as it was passed to `create_iri`.
+/// Two IRIs are equal if their lexical forms are equal.
+///
+#[derive(Clone, PartialOrd, PartialEq, Eq, Hash)]
 pub struct IRI {
+    iri: String,
     scheme: String,
     authority: Option<Authority>,
     path:  String,
@@ -236,14 +276,197 @@ pub struct IRI {
 
 impl fmt::Display for IRI {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
-        write!(f,"{}",self.as_string())
+        write!(f,"{}",self.iri)
+    }
+}
+
+impl fmt::Debug for IRI {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f,"<{}>",self.iri)
     }
 }
 
 impl IRI {
 
+    ///
+    /// Checks if the IRI is a URI as well (RFC 3986).
+    ///
+    /// As `create_iri` already checks the IRI grammar, which only extends the URI grammar by non ASCII chars,
+    /// any IRI consisting of ASCII chars only is a URI. Use `to_uri` to map an IRI to a URI.
+    ///
     pub fn is_uri(&self) -> bool{
-        self.as_string().is_ascii()
+        self.iri.is_ascii()
+    }
+
+    ///
+    /// Applies the syntax-based normalization of RFC 3987 section 5.3.2 and the scheme-based normalization
+    /// of section 5.3.3 for common schemes:
+    ///
+    /// * Unicode normalization form C
+    /// * lower case scheme and host, upper case hex digits in percent-encodings
+    /// * decoding of percent-encoded unreserved chars
+    /// * removal of dot segments in hierarchical paths
+    /// * removal of empty and default ports (http, https, ws, wss, ftp), `/` as path of http(s) IRIs without path
+    ///
+    /// IRIs are still compared by their lexical form, normalize both IRIs (or use `is_equivalent`) to compare them syntax-based.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use rdf4rust::util::iri::IRI;
+    ///
+    /// let iri = IRI::create_iri(&String::from("HTTP://Example.COM:80/a/./b/../%7euser/%c3%a4?q=%3f#Frag")).expect("Is valid IRI");
+    /// assert_eq!("http://example.com/a/~user/ä?q=%3F#Frag", iri.normalize().as_string());
+    ///
+    /// // percent-encodings in the host keep their upper case hex digits
+    /// let iri = IRI::create_iri(&String::from("http://Ex%ffAmple.%41BC/")).expect("Is valid IRI");
+    /// assert_eq!("http://ex%FFample.abc/", iri.normalize().as_string());
+    ///
+    /// let other = IRI::create_iri(&String::from("http://example.com")).expect("Is valid IRI");
+    /// assert!(other != IRI::create_iri(&String::from("http://EXAMPLE.com:/")).expect("Is valid IRI"));
+    /// assert!(other.is_equivalent(&IRI::create_iri(&String::from("http://EXAMPLE.com:/")).expect("Is valid IRI")));
+    /// ```
+    ///
+    pub fn normalize(&self) -> IRI{
+        let scheme = self.scheme.to_ascii_lowercase();
+        let authority = self.authority.as_ref().map(|authority| {
+            let mut ret = String::new();
+            if let Some(user) = &authority.user {
+                ret = ret.add(&normalize_percent_encoding(&user.as_string().nfc().collect::<String>())).add("@");
+            }
+            ret = ret.add(&lowercase_host(&normalize_percent_encoding(&authority.host.nfc().collect::<String>())));
+            if let Some(port) = &authority.port {
+                if !port.is_empty() && default_port(&scheme) != port.parse().ok() {
+                    ret = ret.add(":").add(port);
+                }
+            }
+            ret
+        });
+        let mut path = normalize_percent_encoding(&self.path.nfc().collect::<String>());
+        if authority.is_some() || path.starts_with('/') {
+            path = IRI::remove_dot_segments(&path);
+        }
+        if authority.is_some() && path.is_empty() && (scheme == "http" || scheme == "https") {
+            path = String::from("/");
+        }
+        let query = self.query.as_ref().map(|query| normalize_percent_encoding(&query.query.nfc().collect::<String>()));
+        let fragment = self.fragment.as_ref().map(|fragment| normalize_percent_encoding(&fragment.nfc().collect::<String>()));
+        match IRI::create_iri(&compose(&scheme, authority.as_deref(), &path, query.as_deref(), fragment.as_deref())) {
+            Ok(iri) => iri,
+            //the normalized parts are valid, anything else would be a bug, keep the IRI as it is then
+            Err(_) => self.clone()
+        }
+    }
+
+    ///
+    /// Checks if both IRIs are equal after normalization, see `normalize`
+    ///
+    pub fn is_equivalent(&self, other: &IRI) -> bool{
+        self == other || self.normalize() == other.normalize()
+    }
+
+    ///
+    /// Maps the IRI to a URI as described in RFC 3987 section 3.1.
+    ///
+    /// Non ASCII chars are percent-encoded as UTF-8, except in registered names which are converted
+    /// to ASCII using IDNA (punycode), as the percent-encoded form is not understood by DNS.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use rdf4rust::util::iri::IRI;
+    ///
+    /// let iri = IRI::create_iri(&String::from("http://bücher.example/straße?ä#ö")).expect("Is valid IRI");
+    /// let uri = iri.to_uri();
+    ///
+    /// assert!(!iri.is_uri());
+    /// assert!(uri.is_uri());
+    /// assert_eq!("http://xn--bcher-kva.example/stra%C3%9Fe?%C3%A4#%C3%B6", uri.as_string());
+    ///
+    /// assert!(uri.to_iri() == iri);
+    /// ```
+    ///
+    pub fn to_uri(&self) -> IRI{
+        if self.is_uri() {
+            return self.clone()
+        }
+        let encode = |part: &str| percent_encode_chars(part, |c| !c.is_ascii());
+        let authority = self.authority.as_ref().map(|authority| {
+            let mut ret = String::new();
+            if let Some(user) = &authority.user {
+                ret = ret.add(&encode(&user.as_string())).add("@");
+            }
+            let host = if authority.host.is_ascii() {
+                authority.host.clone()
+            }
+            else {
+                domain_to_ascii(&authority.host).unwrap_or_else(|_| encode(&authority.host))
+            };
+            ret = ret.add(&host);
+            if let Some(port) = &authority.port {
+                ret = ret.add(":").add(port);
+            }
+            ret
+        });
+        let query = self.query.as_ref().map(|query| encode(&query.query));
+        let fragment = self.fragment.as_ref().map(|fragment| encode(fragment));
+        match IRI::create_iri(&compose(&self.scheme, authority.as_deref(), &encode(&self.path), query.as_deref(), fragment.as_deref())) {
+            Ok(iri) => iri,
+            Err(_) => self.clone()
+        }
+    }
+
+    ///
+    /// Maps a URI to an IRI as described in RFC 3987 section 3.2.
+    ///
+    /// Percent-encoded UTF-8 sequences of chars allowed in IRIs are decoded, and punycode labels (`xn--`) of
+    /// registered names are converted to unicode. Everything else stays percent-encoded.
+    /// Punycode labels which don't encode a unicode label are kept (see `domain_to_unicode`), so a host can't turn into another one.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use rdf4rust::util::iri::IRI;
+    ///
+    /// let uri = IRI::create_iri(&String::from("http://xn--bcher-kva.example/stra%C3%9Fe")).expect("Is valid IRI");
+    /// assert_eq!("http://bücher.example/straße", uri.to_iri().as_string());
+    ///
+    /// // decodes to the plain ASCII label "example"
+    /// let uri = IRI::create_iri(&String::from("http://xn--example-.com/")).expect("Is valid IRI");
+    /// assert_eq!("http://xn--example-.com/", uri.to_iri().as_string());
+    /// ```
+    ///
+    pub fn to_iri(&self) -> IRI{
+        let decode = |part: &str, private: bool| decode_percent_encoding(part, |c| is_ucschar(c) || (private && is_iprivate(c)));
+        let authority = self.authority.as_ref().map(|authority| {
+            let mut ret = String::new();
+            if let Some(user) = &authority.user {
+                ret = ret.add(&decode(&user.as_string(), false)).add("@");
+            }
+            let host = if authority.host_type == HostType::RegName {
+                let host = decode(&authority.host, false);
+                if host.split('.').any(|label| label.to_ascii_lowercase().starts_with("xn--")) {
+                    domain_to_unicode(&host)
+                }
+                else {
+                    host
+                }
+            }
+            else {
+                authority.host.clone()
+            };
+            ret = ret.add(&host);
+            if let Some(port) = &authority.port {
+                ret = ret.add(":").add(port);
+            }
+            ret
+        });
+        let query = self.query.as_ref().map(|query| decode(&query.query, true));
+        let fragment = self.fragment.as_ref().map(|fragment| decode(fragment, false));
+        match IRI::create_iri(&compose(&self.scheme, authority.as_deref(), &decode(&self.path, false), query.as_deref(), fragment.as_deref())) {
+            Ok(iri) => iri,
+            Err(_) => self.clone()
+        }
     }
 
     pub fn get_authority(&self) -> &Option<Authority> {
@@ -267,128 +490,674 @@ impl IRI {
     }
 
     pub fn as_string(&self) -> String{
-        let mut ret = String::from(&self.scheme).add(":");
-        if let Some(auth) = &self.authority{
-            ret = ret.add("//").add(auth.as_string().as_str());
-        }
-        ret = ret.add(self.path.as_str());
-        if let Some(q) = &self.query{
-            ret = ret.add("?").add(q.to_string().as_str());
-        }
-        if let Some(frag) = &self.fragment{
-            ret = ret.add("#").add(frag);
-        }
-        ret
+        self.iri.clone()
     }
 
+    ///
+    /// Parses an absolute IRI following the grammar of RFC 3987:
+    ///
+    /// `IRI = scheme ":" ihier-part [ "?" iquery ] [ "#" ifragment ]`
+    ///
+    /// Hosts may be registered names, IPv4 addresses or IPv6 and IPvFuture literals in brackets.
+    /// Percent-encodings have to consist of `%` followed by two hex digits.
+    ///
+    /// # Returns
+    ///
+    /// The IRI or an error naming the first invalid character and its position
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use rdf4rust::util::iri::{IRI, HostType};
+    ///
+    /// let iri = IRI::create_iri(&String::from("http://user@[2001:db8::7]:8080/a/%C3%A4/b?x=1&x=2#frag")).expect("Is valid IRI");
+    /// let authority = iri.get_authority().as_ref().expect("Has authority");
+    ///
+    /// assert_eq!("[2001:db8::7]", authority.get_host());
+    /// assert_eq!(HostType::IPv6, authority.get_host_type());
+    /// assert_eq!(Some(8080), authority.get_port_number());
+    /// assert_eq!("/a/%C3%A4/b", iri.get_path());
+    /// assert_eq!("http://user@[2001:db8::7]:8080/a/%C3%A4/b?x=1&x=2#frag", iri.as_string());
+    ///
+    /// assert!(IRI::create_iri(&String::from("http://example.com/a b")).is_err());
+    /// assert!(IRI::create_iri(&String::from("http://example.com/%zz")).is_err());
+    /// assert!(IRI::create_iri(&String::from("http://[::1/")).is_err());
+    /// assert!(IRI::create_iri(&String::from("relative/path")).is_err());
+    /// ```
+    ///
     pub fn create_iri(iri: &String) -> Result<IRI, IRIInvalidError> {
-        if iri.split_whitespace().count() > 1{
-            return Err(IRIInvalidError::new(String::from("iri is not valid. Whitespaces are not allowed.")))
-        }
-        //0. regex check schema:(//(user(:pwd)?@)?[^/]+)?[^\?\#]*(\?[^#\?]+)?(\#[^\s\#]+)?
-        //1. split to schema:XYZ
-        let mut index = match iri.find(':'){
+        //scheme = ALPHA *( ALPHA / DIGIT / "+" / "-" / "." )
+        let scheme_end = match iri.find(':'){
             Some(num) => num,
-            None => return Err(IRIInvalidError::new(String::from("iri is not valid, expected <:> after schema.")))
+            None => return Err(IRIInvalidError::new(ErrorKind::InvalidIri, format!("IRI <{}> is not valid, expected <:> after scheme.", iri)))
+        };
+        let scheme = &iri[..scheme_end];
+        if !scheme.starts_with(|c: char| c.is_ascii_alphabetic()) {
+            return Err(invalid_iri(format!("IRI <{}> is not valid, scheme has to start with a letter.", iri), 0))
+        }
+        if let Some(pos) = scheme.find(|c: char| !(c.is_ascii_alphanumeric() || c == '+' || c == '-' || c == '.')) {
+            return Err(invalid_char(iri, pos, 0, "scheme"))
+        }
+        let mut index = scheme_end + 1;
+        //the fragment and query delimiters end all parts before them
+        let fragment_start = iri.find('#');
+        let hier_end = match iri[..fragment_start.unwrap_or(iri.len())].find('?') {
+            Some(pos) => pos,
+            None => fragment_start.unwrap_or(iri.len())
         };
-        let scheme = String::from(&iri[..index]);
-        //2. if YZ starts with // -> authority
         let mut authority = None;
-        let mut path = String::from("/");
-        //+1 for :
-        if iri[index+1..].starts_with("//"){
-            // +3 for ://
-            let auth_end_index= match iri[index+3..].find(|c: char| (c=='/' || c=='?' || c=='#')){
-                Some(val) => val+index+3,
-                None => {
-                    //iri is finished
-                    iri.len()
-                }
+        if iri[index..hier_end].starts_with("//") {
+            let auth_start = index + 2;
+            let auth_end = match iri[auth_start..hier_end].find('/') {
+                Some(pos) => pos + auth_start,
+                None => hier_end
             };
-            authority = match Authority::create_authority(String::from(&iri[index+3..auth_end_index])){
-                Ok(auth)   => Some(auth),
-                Err(e) => return Err(e)
-            };
-            index = auth_end_index;
-        }else{
-            //colon
-            index +=1;
-        }
-        //3. path
-        if index != iri.len() && !iri[index..].starts_with('?') && !iri[index..].starts_with('#') {
-            if authority.is_some() && !iri[index..].starts_with('/'){
-                return Result::Err(IRIInvalidError::new(String::from("iri is not valid, authority is set, but path does not begin with </>.")))
-            }
-            let path_end_index = match iri[index + 1..].find(|c: char| (c == '?' || c == '#')) {
-                Some(val) => val+index+1,
-                None => iri.len()
-            };
-            path = String::from(&iri[index..path_end_index]);
-            index = path_end_index;
+            authority = Some(Authority::create_authority(&iri[auth_start..auth_end], auth_start)?);
+            index = auth_end;
         }
+        let path = &iri[index..hier_end];
+        check_chars(path, index, |c| is_ipchar(c) || c == '/', "path")?;
         let mut query = None;
-        //4. has ? -> query
-        if index != iri.len() && iri[index..].starts_with('?'){
-            let query_end_index = match iri[index + 1..].find(|c: char|  c == '#') {
-                Some(val) => val+index+1,
-                None => iri.len()
-            };
-            query = match Query::create_query(String::from(&iri[index+1..query_end_index])){
-                Ok(val) => Some(val),
-                Err(e) => return Err(e)
-            };
-            index = query_end_index;
+        if hier_end < iri.len() && iri[hier_end..].starts_with('?') {
+            let query_end = fragment_start.unwrap_or(iri.len());
+            check_chars(&iri[hier_end + 1..query_end], hier_end + 1, is_query_char, "query")?;
+            query = Some(Query{ query: String::from(&iri[hier_end + 1..query_end]) });
         }
         let mut fragment = None;
-        //5. has # fragment
-        if index != iri.len() && iri[index..].starts_with('#'){
-            let tmp = String::from(&iri[index+1..iri.len()]);
-            if tmp.chars().all(|c| ( c != '#' && c!= '%' && c!= '^' && c!= '\\' && c!= '{' && c!= '}' &&
-                c!= '[' && c!= ']' && c!= '|') ) {
-                fragment = Some(tmp);
-            }
-            else{
-                let mut error = String::from("iri is not valid, iri has invalid part <");
-                error = error.add(&tmp).add(">");
-                return Result::Err(IRIInvalidError::new(error))
-            }
-
+        if let Some(fragment_start) = fragment_start {
+            check_chars(&iri[fragment_start + 1..], fragment_start + 1, |c| is_ipchar(c) || c == '/' || c == '?', "fragment")?;
+            fragment = Some(String::from(&iri[fragment_start + 1..]));
         }
 
         Ok(IRI {
-            scheme,
+            iri: iri.clone(),
+            scheme: String::from(scheme),
             authority,
-            path,
+            path: String::from(path),
             query,
             fragment,
         })
-
     }
 
     pub fn is_valid_iri(iri: &String) ->bool{
         IRI::create_iri(iri).is_ok()
     }
+
+    ///
+    /// Resolves the (relative) IRI reference `reference` against this IRI as base, following RFC 3986 section 5.2.
+    ///
+    /// Dot segments of the resulting path are removed. References with a scheme are returned as they are (strict parsing).
+    ///
+    /// # Parameters
+    ///
+    /// * `reference` - A relative or absolute IRI reference, e.g. `../g`, `?y`, `#s` or `http://example.com/`
+    ///
+    /// # Returns
+    ///
+    /// The resolved absolute IRI or an error if the result is not a valid IRI
+    ///
+    /// # Example
+    ///
+    /// The normal and abnormal examples of RFC 3986 section 5.4:
+    ///
+    /// ```
+    /// use rdf4rust::util::iri::IRI;
+    ///
+    /// let base = IRI::create_iri(&String::from("http://a/b/c/d;p?q")).expect("Is valid IRI");
+    ///
+    /// let examples = [
+    ///     //5.4.1 normal examples
+    ///     ("g:h", "g:h"), ("g", "http://a/b/c/g"), ("./g", "http://a/b/c/g"), ("g/", "http://a/b/c/g/"),
+    ///     ("/g", "http://a/g"), ("//g", "http://g"), ("?y", "http://a/b/c/d;p?y"), ("g?y", "http://a/b/c/g?y"),
+    ///     ("#s", "http://a/b/c/d;p?q#s"), ("g#s", "http://a/b/c/g#s"), ("g?y#s", "http://a/b/c/g?y#s"),
+    ///     (";x", "http://a/b/c/;x"), ("g;x", "http://a/b/c/g;x"), ("g;x?y#s", "http://a/b/c/g;x?y#s"),
+    ///     ("", "http://a/b/c/d;p?q"), (".", "http://a/b/c/"), ("./", "http://a/b/c/"), ("..", "http://a/b/"),
+    ///     ("../", "http://a/b/"), ("../g", "http://a/b/g"), ("../..", "http://a/"), ("../../", "http://a/"),
+    ///     ("../../g", "http://a/g"),
+    ///     //5.4.2 abnormal examples
+    ///     ("../../../g", "http://a/g"), ("../../../../g", "http://a/g"), ("/./g", "http://a/g"),
+    ///     ("/../g", "http://a/g"), ("g.", "http://a/b/c/g."), (".g", "http://a/b/c/.g"), ("g..", "http://a/b/c/g.."),
+    ///     ("..g", "http://a/b/c/..g"), ("./../g", "http://a/b/g"), ("./g/.", "http://a/b/c/g/"),
+    ///     ("g/./h", "http://a/b/c/g/h"), ("g/../h", "http://a/b/c/h"), ("g;x=1/./y", "http://a/b/c/g;x=1/y"),
+    ///     ("g;x=1/../y", "http://a/b/c/y"), ("g?y/./x", "http://a/b/c/g?y/./x"), ("g?y/../x", "http://a/b/c/g?y/../x"),
+    ///     ("g#s/./x", "http://a/b/c/g#s/./x"), ("g#s/../x", "http://a/b/c/g#s/../x"), ("http:g", "http:g"),
+    /// ];
+    ///
+    /// for (reference, expected) in examples.iter() {
+    ///     assert_eq!(*expected, base.resolve(reference).expect("Resolves to a valid IRI").as_string());
+    /// }
+    /// ```
+    ///
+    pub fn resolve(&self, reference: &str) -> Result<IRI, IRIInvalidError>{
+        let (r_scheme, r_authority, r_path, r_query, r_fragment) = IRI::split_reference(reference);
+        let base_authority = self.authority.as_ref().map(|auth| auth.as_string());
+        let base_query = self.query.as_ref().map(|query| query.as_string());
+
+        let scheme;
+        let authority;
+        let path;
+        let query;
+        if let Some(r_scheme) = r_scheme {
+            scheme = String::from(r_scheme);
+            authority = r_authority.map(String::from);
+            path = IRI::remove_dot_segments(r_path);
+            query = r_query.map(String::from);
+        }
+        else{
+            scheme = self.scheme.clone();
+            if let Some(r_authority) = r_authority {
+                authority = Some(String::from(r_authority));
+                path = IRI::remove_dot_segments(r_path);
+                query = r_query.map(String::from);
+            }
+            else{
+                authority = base_authority;
+                if r_path.is_empty() {
+                    path = self.path.clone();
+                    query = match r_query {
+                        Some(r_query) => Some(String::from(r_query)),
+                        None => base_query
+                    };
+                }
+                else{
+                    if r_path.starts_with('/') {
+                        path = IRI::remove_dot_segments(r_path);
+                    }
+                    else{
+                        path = IRI::remove_dot_segments(&self.merge(r_path));
+                    }
+                    query = r_query.map(String::from);
+                }
+            }
+        }
+
+        IRI::create_iri(&compose(&scheme, authority.as_deref(), &path, query.as_deref(), r_fragment))
+    }
+
+    ///
+    /// Creates the shortest reference which resolves to `iri` using this IRI as base, the inverse of `resolve`.
+    ///
+    /// Paths are only made relative if both IRIs share the scheme, the authority and at least the first path segment,
+    /// otherwise an absolute path or the complete IRI is returned.
+    ///
+    /// # Returns
+    ///
+    /// A reference `r` with `self.resolve(r) == iri`
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use rdf4rust::util::iri::IRI;
+    ///
+    /// let base = IRI::create_iri(&String::from("http://a/b/c/d;p?q")).expect("Is valid IRI");
+    /// let iri = |s: &str| IRI::create_iri(&String::from(s)).expect("Is valid IRI");
+    ///
+    /// assert_eq!("g", base.relativize(&iri("http://a/b/c/g")));
+    /// assert_eq!("../g?y", base.relativize(&iri("http://a/b/g?y")));
+    /// assert_eq!("#s", base.relativize(&iri("http://a/b/c/d;p?q#s")));
+    /// assert_eq!("/g", base.relativize(&iri("http://a/g")));
+    /// assert_eq!("https://a/b/c/g", base.relativize(&iri("https://a/b/c/g")));
+    ///
+    /// let other = iri("http://a/b/c/x/y?z#f");
+    /// assert!(base.resolve(&base.relativize(&other)).expect("Resolves to a valid IRI") == other);
+    /// ```
+    ///
+    pub fn relativize(&self, iri: &IRI) -> String{
+        let absolute = iri.as_string();
+        if self.scheme != iri.scheme || self.authority != iri.authority {
+            return absolute
+        }
+        let mut reference = String::new();
+        if self.path != iri.path || (iri.query.is_none() && self.query.is_some()) {
+            reference = reference.add(&self.relativize_path(&iri.path));
+            if let Some(query) = &iri.query {
+                reference = reference.add("?").add(&query.as_string());
+            }
+        }
+        else if iri.query != self.query {
+            reference = reference.add("?").add(&iri.query.as_ref().expect("Query differs from base").as_string());
+        }
+        if let Some(fragment) = &iri.fragment {
+            reference = reference.add("#").add(fragment);
+        }
+        //fall back to the absolute IRI for anything not covered above, e.g. paths of IRIs without authority
+        match self.resolve(&reference) {
+            Ok(resolved) if resolved == *iri => reference,
+            _ => absolute
+        }
+    }
+
+    ///
+    /// The relative path reference from the path of this IRI to `path`
+    ///
+    fn relativize_path(&self, path: &str) -> String{
+        let base_dir = match self.path.rfind('/') {
+            Some(index) => &self.path[..index+1],
+            None => "/"
+        };
+        if !path.starts_with('/') || !base_dir.starts_with('/') {
+            return String::from(path)
+        }
+        let base_segments: Vec<&str> = if base_dir.len() > 1 { base_dir[1..base_dir.len()-1].split('/').collect() } else { Vec::new() };
+        let target_segments: Vec<&str> = path[1..].split('/').collect();
+        //the last target segment is the "file" name, it is never a common directory
+        let mut common = 0;
+        while common < base_segments.len() && common + 1 < target_segments.len() && base_segments[common] == target_segments[common] {
+            common += 1;
+        }
+        if common == 0 && !base_segments.is_empty() {
+            //nothing in common but the root, an absolute path is clearer than climbing up
+            return if path.starts_with("//") { String::from("/.").add(path) } else { String::from(path) }
+        }
+        let mut reference = "../".repeat(base_segments.len() - common);
+        let rest = target_segments[common..].join("/");
+        if reference.is_empty() && (rest.is_empty() || rest.split('/').next().unwrap_or("").contains(':') || rest.starts_with('/')) {
+            //an empty reference would keep the base path, a colon would be taken for a scheme and a slash for an absolute path
+            reference = String::from("./");
+        }
+        reference.add(&rest)
+    }
+
+    ///
+    /// Splits the reference into scheme, authority, path, query and fragment following RFC 3986 appendix B.
+    ///
+    fn split_reference(reference: &str) -> (Option<&str>, Option<&str>, &str, Option<&str>, Option<&str>){
+        let mut rest = reference;
+        let mut fragment = None;
+        if let Some(index) = rest.find('#') {
+            fragment = Some(&rest[index+1..]);
+            rest = &rest[..index];
+        }
+        let mut query = None;
+        if let Some(index) = rest.find('?') {
+            query = Some(&rest[index+1..]);
+            rest = &rest[..index];
+        }
+        let mut scheme = None;
+        if let Some(index) = rest.find([':', '/']) {
+            let candidate = &rest[..index];
+            if rest[index..].starts_with(':') && candidate.starts_with(|c: char| c.is_ascii_alphabetic())
+                && candidate.chars().all(|c| c.is_ascii_alphanumeric() || c == '+' || c == '-' || c == '.') {
+                scheme = Some(candidate);
+                rest = &rest[index+1..];
+            }
+        }
+        let mut authority = None;
+        if let Some(after_slashes) = rest.strip_prefix("//") {
+            let end = after_slashes.find('/').unwrap_or(after_slashes.len());
+            authority = Some(&after_slashes[..end]);
+            rest = &after_slashes[end..];
+        }
+        (scheme, authority, rest, query, fragment)
+    }
+
+    ///
+    /// Merges the relative path `reference` with the path of this IRI (RFC 3986 section 5.2.3)
+    ///
+    fn merge(&self, reference: &str) -> String{
+        if self.authority.is_some() && self.path.is_empty() {
+            return String::from("/").add(reference)
+        }
+        match self.path.rfind('/') {
+            Some(index) => String::from(&self.path[..index+1]).add(reference),
+            None => String::from(reference)
+        }
+    }
+
+    ///
+    /// Removes the `.` and `..` segments of `path` (RFC 3986 section 5.2.4)
+    ///
+    fn remove_dot_segments(path: &str) -> String{
+        let mut input = path;
+        let mut output = String::new();
+        while !input.is_empty() {
+            if let Some(rest) = input.strip_prefix("../") {
+                input = rest;
+            }
+            else if let Some(rest) = input.strip_prefix("./") {
+                input = rest;
+            }
+            else if input.starts_with("/./") {
+                input = &input[2..];
+            }
+            else if input == "/." {
+                input = "/";
+            }
+            else if input.starts_with("/../") || input == "/.." {
+                input = if input == "/.." { "/" } else { &input[3..] };
+                match output.rfind('/') {
+                    Some(index) => output.truncate(index),
+                    None => output.clear()
+                }
+            }
+            else if input == "." || input == ".." {
+                input = "";
+            }
+            else{
+                //move the first segment including its leading slash to the output
+                let start = if input.starts_with('/') { 1 } else { 0 };
+                let end = match input[start..].find('/') {
+                    Some(index) => index + start,
+                    None => input.len()
+                };
+                output.push_str(&input[..end]);
+                input = &input[end..];
+            }
+        }
+        output
+    }
 }
 
-#[derive(Debug)]
-pub struct IRIInvalidError {
-    pub msg: String
+///
+/// Error which occurred while parsing an IRI, always of kind `ErrorKind::InvalidIri`
+///
+pub type IRIInvalidError = RDFError;
+
+///
+/// Percent-encodes all chars of `input` except the unreserved ASCII chars `A-Z a-z 0-9 - . _ ~`.
+///
+/// Chars are encoded as their UTF-8 bytes, using upper case hex digits.
+///
+/// # Example
+///
+/// ```
+/// use rdf4rust::util::iri::{percent_encode, percent_decode};
+///
+/// assert_eq!("a%20b%2Fc%C3%A4", percent_encode("a b/cä"));
+/// assert_eq!("a b/cä", percent_decode("a%20b%2fc%C3%A4").expect("Is valid UTF-8"));
+///
+/// assert!(percent_decode("%C3").is_err());
+/// assert!(percent_decode("%zz").is_err());
+/// ```
+///
+pub fn percent_encode(input: &str) -> String{
+    percent_encode_chars(input, |c| !is_unreserved(c))
 }
 
-impl IRIInvalidError {
-    pub fn new(msg: String) -> IRIInvalidError {
-        IRIInvalidError {msg}
+///
+/// Decodes all percent-encodings of `input`.
+///
+/// Fails if a `%` is not followed by two hex digits or the decoded bytes are not valid UTF-8.
+///
+pub fn percent_decode(input: &str) -> Result<String, IRIInvalidError>{
+    let bytes = input.as_bytes();
+    let mut decoded = Vec::with_capacity(bytes.len());
+    let mut pos = 0;
+    while pos < bytes.len() {
+        if bytes[pos] == b'%' {
+            match hex_byte(bytes, pos) {
+                Some(byte) => decoded.push(byte),
+                None => return Err(invalid_iri(String::from("Invalid percent-encoding, expected two hex digits after <%>."), pos))
+            }
+            pos += 3;
+        }
+        else {
+            decoded.push(bytes[pos]);
+            pos += 1;
+        }
+    }
+    match String::from_utf8(decoded) {
+        Ok(decoded) => Ok(decoded),
+        Err(err) => Err(IRIInvalidError::new(ErrorKind::InvalidIri, String::from("Percent-encoded bytes are not valid UTF-8.")).with_source(err))
     }
 }
 
+///
+/// Converts an internationalized domain name to its ASCII form using IDNA (punycode), e.g. `bücher.example` to `xn--bcher-kva.example`
+///
+pub fn domain_to_ascii(domain: &str) -> Result<String, IRIInvalidError>{
+    match idna::domain_to_ascii(domain) {
+        Ok(domain) => Ok(domain),
+        Err(err) => Err(IRIInvalidError::new(ErrorKind::InvalidIri, format!("<{}> is not a valid internationalized domain name: {:?}", domain, err)))
+    }
+}
 
-impl Error for IRIInvalidError{
+///
+/// Converts the punycode labels (`xn--`) of a domain name to unicode, e.g. `xn--bcher-kva.example` to `bücher.example`.
+///
+/// Labels which cannot be converted are kept as they are. So are labels which decode to plain ASCII or which are not
+/// the IDNA encoding of their decoded form, e.g. `xn--example-` would otherwise become `example`.
+///
+/// # Example
+///
+/// ```
+/// use rdf4rust::util::iri::domain_to_unicode;
+///
+/// assert_eq!("bücher.example", domain_to_unicode("xn--bcher-kva.example"));
+/// assert_eq!("xn--example-.com", domain_to_unicode("xn--example-.com"));
+/// assert_eq!("bücher.xn--xample-.com", domain_to_unicode("XN--bcher-kva.xn--xample-.com"));
+/// ```
+///
+pub fn domain_to_unicode(domain: &str) -> String{
+    domain.split('.')
+        .map(|label| label_to_unicode(label).unwrap_or_else(|| String::from(label)))
+        .collect::<Vec<String>>()
+        .join(".")
+}
+
+/// Converts a punycode label to unicode, None if it isn't one or doesn't encode a unicode label
+fn label_to_unicode(label: &str) -> Option<String>{
+    let label = label.to_ascii_lowercase();
+    if !label.starts_with("xn--") {
+        return None
+    }
+    let (unicode, result) = idna::domain_to_unicode(&label);
+    result.ok()?;
+    if unicode.is_ascii() || idna::domain_to_ascii(&unicode).ok()? != label {
+        return None
+    }
+    Some(unicode)
 }
 
+fn compose(scheme: &str, authority: Option<&str>, path: &str, query: Option<&str>, fragment: Option<&str>) -> String{
+    let mut iri = String::from(scheme).add(":");
+    if let Some(authority) = authority {
+        iri = iri.add("//").add(authority);
+    }
+    iri = iri.add(path);
+    if let Some(query) = query {
+        iri = iri.add("?").add(query);
+    }
+    if let Some(fragment) = fragment {
+        iri = iri.add("#").add(fragment);
+    }
+    iri
+}
 
-impl fmt::Display for IRIInvalidError {
-    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
-        write!(f,"{}",self.msg)
+fn default_port(scheme: &str) -> Option<u32>{
+    match scheme {
+        "http" | "ws" => Some(80),
+        "https" | "wss" => Some(443),
+        "ftp" => Some(21),
+        _ => None
+    }
+}
+
+fn hex_byte(bytes: &[u8], pos: usize) -> Option<u8>{
+    if pos + 2 >= bytes.len() {
+        return None
+    }
+    let hex = std::str::from_utf8(&bytes[pos+1..pos+3]).ok()?;
+    if !hex.chars().all(|c| c.is_ascii_hexdigit()) {
+        return None
+    }
+    u8::from_str_radix(hex, 16).ok()
+}
+
+fn percent_encode_chars(input: &str, encode: fn(char) -> bool) -> String{
+    let mut ret = String::with_capacity(input.len());
+    let mut buffer = [0u8; 4];
+    for c in input.chars() {
+        if encode(c) {
+            for byte in c.encode_utf8(&mut buffer).bytes() {
+                ret.push_str(&format!("%{:02X}", byte));
+            }
+        }
+        else {
+            ret.push(c);
+        }
+    }
+    ret
+}
+
+///
+/// Decodes the percent-encoded UTF-8 sequences of `input` whose chars match `decode`,
+/// all other percent-encodings are kept with upper case hex digits.
+///
+fn decode_percent_encoding<F: Fn(char) -> bool>(input: &str, decode: F) -> String{
+    let bytes = input.as_bytes();
+    let mut ret = String::with_capacity(input.len());
+    let mut pos = 0;
+    while pos < bytes.len() {
+        if bytes[pos] != b'%' {
+            let c = input[pos..].chars().next().expect("Position is a char boundary");
+            ret.push(c);
+            pos += c.len_utf8();
+            continue;
+        }
+        //collect the bytes of the sequence of consecutive percent-encodings
+        let mut encoded = Vec::new();
+        while pos < bytes.len() && bytes[pos] == b'%' {
+            match hex_byte(bytes, pos) {
+                Some(byte) => encoded.push(byte),
+                None => break
+            }
+            pos += 3;
+        }
+        if encoded.is_empty() {
+            //invalid percent-encoding, can't occur in a valid IRI
+            ret.push('%');
+            pos += 1;
+            continue;
+        }
+        let mut start = 0;
+        while start < encoded.len() {
+            //length of the UTF-8 sequence by its first byte
+            let len = match encoded[start] {
+                0x00..=0x7F => 1,
+                0xC0..=0xDF => 2,
+                0xE0..=0xEF => 3,
+                0xF0..=0xF7 => 4,
+                _ => 1
+            };
+            let c = encoded.get(start..start + len)
+                .and_then(|sequence| std::str::from_utf8(sequence).ok())
+                .and_then(|sequence| sequence.chars().next());
+            match c {
+                Some(c) if decode(c) => {
+                    ret.push(c);
+                    start += len;
+                }
+                _ => {
+                    ret.push_str(&format!("%{:02X}", encoded[start]));
+                    start += 1;
+                }
+            }
+        }
     }
+    ret
+}
+
+///
+/// Decodes percent-encoded unreserved chars and upper cases the hex digits of the others (RFC 3987 section 5.3.2.1 and 5.3.2.3)
+///
+fn normalize_percent_encoding(input: &str) -> String{
+    decode_percent_encoding(input, is_iunreserved)
+}
+
+///
+/// Lower cases the host, except the hex digits of percent-encodings which have to stay upper case (RFC 3986 section 6.2.2.1)
+///
+fn lowercase_host(host: &str) -> String{
+    let mut ret = String::with_capacity(host.len());
+    let mut chars = host.chars();
+    while let Some(c) = chars.next() {
+        if c == '%' {
+            ret.push(c);
+            ret.extend(chars.by_ref().take(2));
+        } else {
+            ret.extend(c.to_lowercase());
+        }
+    }
+    ret
+}
+
+fn invalid_iri(msg: String, pos: usize) -> IRIInvalidError{
+    IRIInvalidError::new(ErrorKind::InvalidIri, msg.add(&format!(" [pos: {}]", pos)))
 }
 
+fn invalid_char(part: &str, pos: usize, offset: usize, name: &str) -> IRIInvalidError{
+    let c = part[pos..].chars().next().unwrap_or(' ');
+    invalid_iri(format!("Invalid character <{}> in {} of IRI.", c.escape_default(), name), offset + pos)
+}
+
+///
+/// Checks that `part` consists of `allowed` chars and valid percent-encodings, `offset` is the position of `part` in the IRI
+///
+fn check_chars(part: &str, offset: usize, allowed: fn(char) -> bool, name: &str) -> Result<(), IRIInvalidError>{
+    let bytes = part.as_bytes();
+    for (pos, c) in part.char_indices() {
+        if c == '%' {
+            if pos + 2 >= bytes.len() || !bytes[pos+1].is_ascii_hexdigit() || !bytes[pos+2].is_ascii_hexdigit() {
+                return Err(invalid_iri(format!("Invalid percent-encoding in {} of IRI, expected two hex digits after <%>.", name), offset + pos))
+            }
+        }
+        else if !allowed(c) {
+            return Err(invalid_char(part, pos, offset, name))
+        }
+    }
+    Ok(())
+}
+
+fn is_sub_delim(c: char) -> bool{
+    matches!(c, '!' | '$' | '&' | '\'' | '(' | ')' | '*' | '+' | ',' | ';' | '=')
+}
+
+fn is_unreserved(c: char) -> bool{
+    c.is_ascii_alphanumeric() || matches!(c, '-' | '.' | '_' | '~')
+}
+
+fn is_ucschar(c: char) -> bool{
+    matches!(c as u32, 0xA0..=0xD7FF | 0xF900..=0xFDCF | 0xFDF0..=0xFFEF) ||
+        //the planes 1 to 14, except their last two code points
+        (0x10000..=0xEFFFD).contains(&(c as u32)) && (c as u32 & 0xFFFF) < 0xFFFE
+}
+
+fn is_iprivate(c: char) -> bool{
+    matches!(c as u32, 0xE000..=0xF8FF | 0xF0000..=0xFFFFD | 0x100000..=0x10FFFD)
+}
+
+fn is_iunreserved(c: char) -> bool{
+    is_unreserved(c) || is_ucschar(c)
+}
+
+fn is_ipchar(c: char) -> bool{
+    is_iunreserved(c) || is_sub_delim(c) || c == ':' || c == '@'
+}
+
+fn is_query_char(c: char) -> bool{
+    is_ipchar(c) || is_iprivate(c) || c == '/' || c == '?'
+}
+
+fn is_userinfo_char(c: char) -> bool{
+    is_iunreserved(c) || is_sub_delim(c) || c == ':'
+}
+
+fn is_reg_name_char(c: char) -> bool{
+    is_iunreserved(c) || is_sub_delim(c)
+}
+
+///
+/// `IPvFuture = "v" 1*HEXDIG "." 1*( unreserved / sub-delims / ":" )`
+///
+fn is_ip_future(literal: &str) -> bool{
+    match literal[1..].find('.') {
+        Some(dot) => {
+            let version = &literal[1..dot+1];
+            let address = &literal[dot+2..];
+            !version.is_empty() && version.chars().all(|c| c.is_ascii_hexdigit()) &&
+                !address.is_empty() && address.chars().all(|c| is_unreserved(c) || is_sub_delim(c) || c == ':')
+        }
+        None => false
+    }
+}
@@ -0,0 +1,182 @@
+//!
+//! Language tags of language-tagged literals according to BCP 47 (RFC 5646) and language range matching (RFC 4647).
+//!
+//! Tags are checked for well-formedness only, subtags aren't looked up in the IANA registry.
+//! Tags are compared case-insensitively, thus they are normalized to the case conventions of RFC 5646 section 2.1.1,
+//! e.g. `EN-us` becomes `en-US` and `zh-hant-tw` becomes `zh-Hant-TW`.
+//!
+
+use crate::error::{RDFError, ErrorKind};
+use std::collections::HashSet;
+
+/// Grandfathered tags, which are valid although they don't follow the tag syntax, in lowercase
+const GRANDFATHERED: [&str; 26] = ["en-gb-oed", "i-ami", "i-bnn", "i-default", "i-enochian", "i-hak", "i-klingon", "i-lux",
+    "i-mingo", "i-navajo", "i-pwn", "i-tao", "i-tay", "i-tsu", "sgn-be-fr", "sgn-be-nl", "sgn-ch-de",
+    "art-lojban", "cel-gaulish", "no-bok", "no-nyn", "zh-guoyu", "zh-hakka", "zh-min", "zh-min-nan", "zh-xiang"];
+
+///
+/// Error of malformed language tags, always of kind `ErrorKind::InvalidLiteral`
+///
+pub type LanguageTagError = RDFError;
+
+///
+/// Checks if `tag` is a well-formed BCP 47 language tag
+///
+/// # Example
+///
+/// ```
+/// use rdf4rust::util::language_tag::is_valid_language_tag;
+///
+/// assert!(is_valid_language_tag("en"));
+/// assert!(is_valid_language_tag("zh-Hant-TW"));
+/// assert!(is_valid_language_tag("de-CH-1996"));
+/// assert!(is_valid_language_tag("en-a-bbb-x-private"));
+/// assert!(is_valid_language_tag("i-klingon"));
+///
+/// assert!(!is_valid_language_tag(""));
+/// assert!(!is_valid_language_tag("languages"));
+/// assert!(!is_valid_language_tag("en_US"));
+/// assert!(!is_valid_language_tag("en--ltr"));
+/// assert!(!is_valid_language_tag("de-1996-1996"));
+/// ```
+///
+pub fn is_valid_language_tag(tag: &str) -> bool{
+    let lower = tag.to_ascii_lowercase();
+    if GRANDFATHERED.contains(&lower.as_str()){
+        return true
+    }
+    let subtags: Vec<&str> = lower.split('-').collect();
+    if subtags.iter().any(|subtag| subtag.is_empty() || subtag.len() > 8 || !subtag.bytes().all(|b| b.is_ascii_alphanumeric())){
+        return false
+    }
+    if subtags[0] == "x"{
+        return subtags.len() > 1
+    }
+    let language = subtags[0];
+    if language.len() < 2 || !is_alpha(language){
+        return false
+    }
+    let mut i = 1;
+    if language.len() <= 3{
+        let mut extlangs = 0;
+        while extlangs < 3 && i < subtags.len() && subtags[i].len() == 3 && is_alpha(subtags[i]){
+            extlangs += 1;
+            i += 1;
+        }
+    }
+    if i < subtags.len() && subtags[i].len() == 4 && is_alpha(subtags[i]){
+        i += 1;
+    }
+    if i < subtags.len() && (subtags[i].len() == 2 && is_alpha(subtags[i]) || subtags[i].len() == 3 && is_digit(subtags[i])){
+        i += 1;
+    }
+    let mut variants = HashSet::new();
+    while i < subtags.len() && is_variant(subtags[i]){
+        if !variants.insert(subtags[i]){
+            return false
+        }
+        i += 1;
+    }
+    let mut singletons = HashSet::new();
+    while i < subtags.len() && subtags[i].len() == 1 && subtags[i] != "x"{
+        if !singletons.insert(subtags[i]){
+            return false
+        }
+        i += 1;
+        let start = i;
+        while i < subtags.len() && subtags[i].len() >= 2{
+            i += 1;
+        }
+        if i == start{
+            return false
+        }
+    }
+    if i < subtags.len() && subtags[i] == "x"{
+        return i + 1 < subtags.len()
+    }
+    i == subtags.len()
+}
+
+///
+/// Normalizes the case of a language tag
+///
+/// The language is lowercase, scripts are titlecase, regions uppercase and all other subtags lowercase.
+///
+/// # Returns
+///
+/// The normalized tag or a LanguageTagError if `tag` is not well-formed
+///
+/// # Example
+///
+/// ```
+/// use rdf4rust::util::language_tag::normalize_language_tag;
+///
+/// assert_eq!("en-US", normalize_language_tag("EN-us").unwrap());
+/// assert_eq!("zh-Hant-TW", normalize_language_tag("ZH-HANT-tw").unwrap());
+/// assert_eq!("en-a-bb-x-ab", normalize_language_tag("en-a-BB-x-AB").unwrap());
+/// assert!(normalize_language_tag("en US").is_err());
+/// ```
+///
+pub fn normalize_language_tag(tag: &str) -> Result<String, LanguageTagError>{
+    if !is_valid_language_tag(tag){
+        return Err(LanguageTagError::new(ErrorKind::InvalidLiteral, format!("{} is not a valid BCP 47 language tag.", tag)))
+    }
+    // subtags after a singleton belong to an extension or private use and are always lowercase
+    let mut after_singleton = false;
+    let subtags: Vec<String> = tag.split('-').enumerate().map(|(i, subtag)| {
+        if subtag.len() == 1{
+            after_singleton = true;
+        }
+        if i == 0 || after_singleton{
+            subtag.to_ascii_lowercase()
+        }else if subtag.len() == 2{
+            subtag.to_ascii_uppercase()
+        }else if subtag.len() == 4 && is_alpha(subtag){
+            subtag[..1].to_ascii_uppercase() + &subtag[1..].to_ascii_lowercase()
+        }else{
+            subtag.to_ascii_lowercase()
+        }
+    }).collect();
+    Ok(subtags.join("-"))
+}
+
+///
+/// Checks if the language tag matches the language range like the SPARQL function `langMatches`
+///
+/// Uses the basic filtering of RFC 4647: the range matches if it equals the tag or a prefix of it ending before a `-`,
+/// both compared case-insensitively. The range `*` matches all non-empty tags.
+///
+/// # Example
+///
+/// ```
+/// use rdf4rust::util::language_tag::lang_matches;
+///
+/// assert!(lang_matches("de-CH", "de"));
+/// assert!(lang_matches("de-CH", "DE-ch"));
+/// assert!(lang_matches("de", "*"));
+/// assert!(!lang_matches("", "*"));
+/// assert!(!lang_matches("de", "de-CH"));
+/// assert!(!lang_matches("den", "de"));
+/// ```
+///
+pub fn lang_matches(tag: &str, range: &str) -> bool{
+    if range == "*"{
+        return !tag.is_empty()
+    }
+    let (tag, range) = (tag.as_bytes(), range.as_bytes());
+    tag.len() >= range.len()
+        && tag[..range.len()].eq_ignore_ascii_case(range)
+        && (tag.len() == range.len() || tag[range.len()] == b'-')
+}
+
+fn is_alpha(subtag: &str) -> bool{
+    subtag.bytes().all(|b| b.is_ascii_alphabetic())
+}
+
+fn is_digit(subtag: &str) -> bool{
+    subtag.bytes().all(|b| b.is_ascii_digit())
+}
+
+fn is_variant(subtag: &str) -> bool{
+    subtag.len() >= 5 || subtag.len() == 4 && subtag.as_bytes()[0].is_ascii_digit()
+}
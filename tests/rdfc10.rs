@@ -0,0 +1,91 @@
+//!
+//! Runs the RDFC-1.0 test manifest in `tests/rdf-canon` against the `Canonicalizer`.
+//!
+//! The official manifest has the same layout, `rdfc10_official_manifest` runs it from the directory `RDF_CANON_TEST_SUITE`
+//! when ignored tests are included (see `tests/rdf-canon/README.md`).
+//!
+
+use rdf4rust::error::ErrorKind;
+use rdf4rust::io::reader::{QuadIterator, Lang};
+use rdf4rust::rdf::canonicalization::{Canonicalizer, HashAlgorithm};
+use rdf4rust::rdf::graph::Quad;
+use serde_json::Value;
+use std::collections::HashMap;
+use std::env;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+fn suite_dir() -> PathBuf{
+    Path::new(env!("CARGO_MANIFEST_DIR")).join("tests").join("rdf-canon")
+}
+
+fn read_quads(dir: &Path, file: &str) -> Vec<Quad>{
+    let input = fs::read_to_string(dir.join(file)).expect("Test input exists");
+    QuadIterator::new(input.as_bytes(), &Lang::NQ)
+        .with_preserved_blank_node_labels(true)
+        .map(|quad| quad.unwrap_or_else(|err| panic!("{} is not valid N-Quads: {:?}", file, err)))
+        .collect()
+}
+
+fn canonicalizer(entry: &Value) -> Canonicalizer{
+    match entry["hashAlgorithm"].as_str() {
+        Some("SHA384") => Canonicalizer::new().with_hash_algorithm(HashAlgorithm::Sha384),
+        Some("SHA256") | None => Canonicalizer::new(),
+        Some(other) => panic!("Unsupported hash algorithm {}", other)
+    }
+}
+
+/// Runs the entries of `manifest.jsonld` in `dir`, the paths of actions and results are relative to `dir`
+fn run_manifest(dir: &Path){
+    let manifest: Value = serde_json::from_str(&fs::read_to_string(dir.join("manifest.jsonld")).expect("Manifest exists"))
+        .expect("Manifest is valid JSON");
+    let entries = manifest["entries"].as_array().expect("Manifest has entries");
+    assert!(!entries.is_empty());
+    let mut failures = Vec::new();
+    for entry in entries {
+        let id = entry["id"].as_str().expect("Test has an id");
+        let quads = read_quads(dir, entry["action"].as_str().expect("Test has an action"));
+        let result = canonicalizer(entry).canonicalize(&quads);
+        match entry["type"].as_str().expect("Test has a type") {
+            "rdfc:RDFC10EvalTest" => {
+                let expected = fs::read_to_string(dir.join(entry["result"].as_str().expect("Test has a result"))).expect("Result exists");
+                match result {
+                    Ok(canonical) if canonical.get_nquads() == expected => {}
+                    Ok(canonical) => failures.push(format!("{}: expected\n{}got\n{}", id, expected, canonical.get_nquads())),
+                    Err(err) => failures.push(format!("{}: {}", id, err))
+                }
+            }
+            "rdfc:RDFC10MapTest" => {
+                let expected: HashMap<String, String> = serde_json::from_str(&fs::read_to_string(dir.join(entry["result"].as_str().expect("Test has a result"))).expect("Result exists"))
+                    .expect("Result is a JSON object of strings");
+                match result {
+                    Ok(canonical) if canonical.get_issued_identifiers() == &expected => {}
+                    Ok(canonical) => failures.push(format!("{}: expected {:?}, got {:?}", id, expected, canonical.get_issued_identifiers())),
+                    Err(err) => failures.push(format!("{}: {}", id, err))
+                }
+            }
+            "rdfc:RDFC10NegativeEvalTest" => {
+                match result {
+                    Err(err) if err.get_kind() == ErrorKind::LimitExceeded => {}
+                    Err(err) => failures.push(format!("{}: unexpected error {}", id, err)),
+                    Ok(_) => failures.push(format!("{}: expected the deep iteration limit to be exceeded", id))
+                }
+            }
+            other => panic!("Unsupported test type {}", other)
+        }
+    }
+    assert!(failures.is_empty(), "{} of {} tests failed:\n{}", failures.len(), entries.len(), failures.join("\n"));
+}
+
+#[test]
+fn rdfc10_manifest(){
+    run_manifest(&suite_dir());
+}
+
+/// Runs the official manifest in the directory `RDF_CANON_TEST_SUITE`, it is ignored as the suite is not vendored
+#[test]
+#[ignore = "needs a checkout of the W3C rdf-canon tests in RDF_CANON_TEST_SUITE"]
+fn rdfc10_official_manifest(){
+    let dir = env::var_os("RDF_CANON_TEST_SUITE").expect("RDF_CANON_TEST_SUITE is set to the tests directory of the W3C rdf-canon repository");
+    run_manifest(Path::new(&dir));
+}
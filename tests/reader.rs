@@ -0,0 +1,237 @@
+//!
+//! Tests of the readers for inputs the format specifications allow but which are easy to get wrong.
+//!
+
+use rdf4rust::io::bulk_loader::BulkLoader;
+use rdf4rust::error::ErrorKind;
+use rdf4rust::io::reader::{Lang, ParseOptions, QuadIterator, Reader};
+use rdf4rust::rdf::database::{self, Database, GraphPattern};
+use rdf4rust::rdf::graph::GraphType;
+use std::env;
+use std::fs;
+use std::path::PathBuf;
+
+/// Writes `content` to a file in the temporary directory which is unique for `name`
+fn temp_file(name: &str, content: &[u8]) -> PathBuf{
+    let file = env::temp_dir().join(format!("rdf4rust-{}-{}", std::process::id(), name));
+    fs::write(&file, content).expect("Can write temporary file");
+    file
+}
+
+#[test]
+fn whitespace_only_lines_are_skipped(){
+    let input = "\n\n   \n\t\n<http://example.com/s> <http://example.com/p> \"o\" .\n  \n";
+    let quads: Vec<_> = QuadIterator::new(input.as_bytes(), &Lang::NT).collect();
+    assert_eq!(1, quads.len());
+    assert!(quads[0].is_ok());
+
+    let quads: Vec<_> = QuadIterator::new("\n\n   \n".as_bytes(), &Lang::NQ).collect();
+    assert!(quads.is_empty());
+
+    let file = temp_file("whitespace.nt", input.as_bytes());
+    let mut database = Database::new(GraphType::IndexedGraph);
+    let count = Reader::read_to_rdf(&mut database, file.to_str().expect("Is UTF-8"), Lang::NT);
+    fs::remove_file(&file).expect("Can remove temporary file");
+    assert_eq!(1, count.expect("Whitespace-only lines are valid"));
+}
+
+#[test]
+fn bulk_loader_reads_crlf_line_breaks(){
+    let input = "<http://example.com/s> <http://example.com/p> \"o\" .\r\n\
+        \r\n\
+        # comment\r\n\
+        <http://example.com/s> <http://example.com/p> _:b0 <http://example.com/g> .\r\n\
+        _:b0 <http://example.com/p> <http://example.com/o>\t.\r\n\
+        \"invalid\" <http://example.com/p> <http://example.com/o> .\r\n";
+    let file = temp_file("crlf.nq", input.as_bytes());
+    let mut database = Database::new(GraphType::IndexedGraph);
+    let result = BulkLoader::new().with_threads(2).with_options(ParseOptions::lenient().with_preserved_blank_node_labels(true))
+        .load(&mut database, file.to_str().expect("Is UTF-8"), Lang::NQ);
+    fs::remove_file(&file).expect("Can remove temporary file");
+    let (report, statistics) = result.expect("Can read file");
+    assert_eq!(1, report.get_errors().len());
+    let err = &report.get_errors()[0];
+    assert_eq!(Some(6), err.get_line());
+    assert_eq!(Some("\"invalid\" <http://example.com/p> <http://example.com/o> ."), err.get_text());
+    assert_eq!(3, report.get_statement_count());
+    assert_eq!(6, statistics.get_lines());
+    let mut expected: Vec<String> = QuadIterator::new(input.as_bytes(), &Lang::NQ).with_preserved_blank_node_labels(true)
+        .filter_map(|quad| quad.ok())
+        .map(|quad| quad.to_string())
+        .collect();
+    let mut actual: Vec<String> = database.quads_for_pattern(None, None, None, &GraphPattern::AnyGraph).iter()
+        .map(|quad| quad.to_string())
+        .collect();
+    expected.sort();
+    actual.sort();
+    assert_eq!(expected, actual);
+    assert!(actual.iter().all(|quad| !quad.contains('\r')));
+}
+
+#[test]
+fn invalid_utf8_is_an_error(){
+    let mut input = b"<http://example.com/s> <http://example.com/p> \"caf".to_vec();
+    input.extend_from_slice(&[0xC3, 0x28]);
+    input.extend_from_slice(b"\" .\n<http://example.com/s> <http://example.com/p> \"ok\" .\n");
+
+    let quads: Vec<_> = QuadIterator::new(input.as_slice(), &Lang::NT).collect();
+    assert_eq!(2, quads.len());
+    let err = quads[0].as_ref().err().expect("Is not valid UTF-8");
+    assert_eq!(ErrorKind::Syntax, err.get_kind());
+    assert_eq!(Some(1), err.get_line());
+    assert_eq!(Some(51), err.get_column());
+    assert!(quads[1].is_ok());
+
+    let file = temp_file("utf8.nt", &input);
+    let mut database = Database::new(GraphType::IndexedGraph);
+    let result = BulkLoader::new().with_options(ParseOptions::lenient()).load(&mut database, file.to_str().expect("Is UTF-8"), Lang::NT);
+    fs::remove_file(&file).expect("Can remove temporary file");
+    let (report, _) = result.expect("Can read file");
+    assert_eq!(1, report.get_statement_count());
+    assert_eq!(Some(1), report.get_errors()[0].get_line());
+    assert_eq!(Some(50), report.get_errors()[0].get_byte_offset());
+}
+
+/// Reads `input` and `expected` N-Quads into databases, failing on the first error
+#[test]
+fn unsupported_formats_are_errors(){
+    let input = "<rdf:RDF xmlns:rdf=\"http://www.w3.org/1999/02/22-rdf-syntax-ns#\"/>\n";
+    for lang in [Lang::RdfXml, Lang::TRIX] {
+        let quads: Vec<_> = QuadIterator::new(input.as_bytes(), &lang).collect();
+        assert_eq!(1, quads.len());
+        assert_eq!(ErrorKind::UnsupportedFormat, quads[0].as_ref().err().expect("Is not supported").get_kind());
+
+        let file = temp_file("unsupported.rdf", input.as_bytes());
+        let mut database = Database::new(GraphType::IndexedGraph);
+        let result = Reader::read_to_rdf_with_options(&mut database, file.to_str().expect("Is UTF-8"), lang, &ParseOptions::lenient());
+        fs::remove_file(&file).expect("Can remove temporary file");
+        assert_eq!(ErrorKind::UnsupportedFormat, result.err().expect("Is not supported").get_kind());
+    }
+}
+
+fn read_database(input: &str, lang: &Lang) -> Database{
+    let mut database = Database::new(GraphType::IndexedGraph);
+    for quad in QuadIterator::new(input.as_bytes(), lang) {
+        database.add_quad(quad.expect("Is valid"));
+    }
+    database
+}
+
+fn assert_isomorphic(input: &str, lang: &Lang, expected: &str){
+    let actual = read_database(input, lang);
+    let expected = read_database(expected, &Lang::NQ);
+    let mut quads: Vec<String> = actual.quads_for_pattern(None, None, None, &GraphPattern::AnyGraph).iter().map(|quad| quad.to_string()).collect();
+    quads.sort();
+    assert!(database::is_isomorphic(&actual, &expected), "Not isomorphic to the expected quads:\n{}", quads.join("\n"));
+}
+
+#[test]
+fn turtle_statements(){
+    let input = "# comment\n\
+        @prefix ex: <http://example.com/> .\n\
+        PREFIX xsd: <http://www.w3.org/2001/XMLSchema#>\n\
+        @base <http://example.com/base/> .\n\
+        ex:alice a ex:Person ;\n\
+        \tex:name \"Alice\"@en , 'Alicia'@es--ltr ; # trailing comment\n\
+        \tex:age 42 ; ex:height 1.70 ; ex:weight 6.5e1 ; ex:member true ;\n\
+        \tex:knows [ ex:name \"\"\"Bob\n\
+        \"the builder\\\"\"\"\" ] , _:c ;\n\
+        \tex:list ( 1 <rel> ex:a\\.b ) ;\n\
+        \tex:born \"2000-01-01\"^^xsd:date ;\n\
+        .\n\
+        [ ex:p ex:o ] .\n\
+        ex:s ex:p () .\n";
+    let expected = "<http://example.com/alice> <http://www.w3.org/1999/02/22-rdf-syntax-ns#type> <http://example.com/Person> .\n\
+        <http://example.com/alice> <http://example.com/name> \"Alice\"@en .\n\
+        <http://example.com/alice> <http://example.com/name> \"Alicia\"@es--ltr .\n\
+        <http://example.com/alice> <http://example.com/age> \"42\"^^<http://www.w3.org/2001/XMLSchema#integer> .\n\
+        <http://example.com/alice> <http://example.com/height> \"1.70\"^^<http://www.w3.org/2001/XMLSchema#decimal> .\n\
+        <http://example.com/alice> <http://example.com/weight> \"6.5e1\"^^<http://www.w3.org/2001/XMLSchema#double> .\n\
+        <http://example.com/alice> <http://example.com/member> \"true\"^^<http://www.w3.org/2001/XMLSchema#boolean> .\n\
+        <http://example.com/alice> <http://example.com/knows> _:bob .\n\
+        _:bob <http://example.com/name> \"Bob\\n\\\"the builder\\\"\" .\n\
+        <http://example.com/alice> <http://example.com/knows> _:c .\n\
+        <http://example.com/alice> <http://example.com/list> _:l1 .\n\
+        _:l1 <http://www.w3.org/1999/02/22-rdf-syntax-ns#first> \"1\"^^<http://www.w3.org/2001/XMLSchema#integer> .\n\
+        _:l1 <http://www.w3.org/1999/02/22-rdf-syntax-ns#rest> _:l2 .\n\
+        _:l2 <http://www.w3.org/1999/02/22-rdf-syntax-ns#first> <http://example.com/base/rel> .\n\
+        _:l2 <http://www.w3.org/1999/02/22-rdf-syntax-ns#rest> _:l3 .\n\
+        _:l3 <http://www.w3.org/1999/02/22-rdf-syntax-ns#first> <http://example.com/a.b> .\n\
+        _:l3 <http://www.w3.org/1999/02/22-rdf-syntax-ns#rest> <http://www.w3.org/1999/02/22-rdf-syntax-ns#nil> .\n\
+        <http://example.com/alice> <http://example.com/born> \"2000-01-01\"^^<http://www.w3.org/2001/XMLSchema#date> .\n\
+        _:anon <http://example.com/p> <http://example.com/o> .\n\
+        <http://example.com/s> <http://example.com/p> <http://www.w3.org/1999/02/22-rdf-syntax-ns#nil> .\n";
+    assert_isomorphic(input, &Lang::TTL, expected);
+}
+
+#[test]
+fn turtle_quoted_triples_and_annotations(){
+    let input = "PREFIX : <http://example.com/>\n\
+        :alice :knows :bob {| :source :survey ; :confidence 0.9 |} , :carol .\n\
+        << :alice :age 42 >> :certainty << [] :says \"so\" >> .\n\
+        :dave :claims << << :a :b :c >> :d _:e >> {| :since 2020 |} .\n";
+    let expected = "<http://example.com/alice> <http://example.com/knows> <http://example.com/bob> .\n\
+        << <http://example.com/alice> <http://example.com/knows> <http://example.com/bob> >> <http://example.com/source> <http://example.com/survey> .\n\
+        << <http://example.com/alice> <http://example.com/knows> <http://example.com/bob> >> <http://example.com/confidence> \"0.9\"^^<http://www.w3.org/2001/XMLSchema#decimal> .\n\
+        <http://example.com/alice> <http://example.com/knows> <http://example.com/carol> .\n\
+        << <http://example.com/alice> <http://example.com/age> \"42\"^^<http://www.w3.org/2001/XMLSchema#integer> >> <http://example.com/certainty> << _:x <http://example.com/says> \"so\" >> .\n\
+        <http://example.com/dave> <http://example.com/claims> << << <http://example.com/a> <http://example.com/b> <http://example.com/c> >> <http://example.com/d> _:e >> .\n\
+        << <http://example.com/dave> <http://example.com/claims> << << <http://example.com/a> <http://example.com/b> <http://example.com/c> >> <http://example.com/d> _:e >> >> <http://example.com/since> \"2020\"^^<http://www.w3.org/2001/XMLSchema#integer> .\n";
+    assert_isomorphic(input, &Lang::TTL, expected);
+
+    let quads: Vec<_> = QuadIterator::new("<http://example.com/s> <http://example.com/p> <http://example.com/o> {| |} .".as_bytes(), &Lang::TTL).collect();
+    assert!(quads.iter().any(|quad| quad.is_err()), "An annotation needs a predicate object list");
+}
+
+#[test]
+fn trig_graphs(){
+    let input = "@prefix : <http://example.com/> .\n\
+        :s :p :o .\n\
+        :g1 { :s :p :o1 . :s :p :o2 }\n\
+        GRAPH :g2 {\n\
+        \t:s :p [ :q :r ] .\n\
+        \t:s :p :o {| :source :x |}\n\
+        }\n\
+        { :s :p :o3 }\n\
+        _:g3 { :s :p :o4 . }\n";
+    let expected = "<http://example.com/s> <http://example.com/p> <http://example.com/o> .\n\
+        <http://example.com/s> <http://example.com/p> <http://example.com/o1> <http://example.com/g1> .\n\
+        <http://example.com/s> <http://example.com/p> <http://example.com/o2> <http://example.com/g1> .\n\
+        <http://example.com/s> <http://example.com/p> _:b <http://example.com/g2> .\n\
+        _:b <http://example.com/q> <http://example.com/r> <http://example.com/g2> .\n\
+        <http://example.com/s> <http://example.com/p> <http://example.com/o> <http://example.com/g2> .\n\
+        << <http://example.com/s> <http://example.com/p> <http://example.com/o> >> <http://example.com/source> <http://example.com/x> <http://example.com/g2> .\n\
+        <http://example.com/s> <http://example.com/p> <http://example.com/o3> .\n\
+        <http://example.com/s> <http://example.com/p> <http://example.com/o4> _:g3 .\n";
+    assert_isomorphic(input, &Lang::TRIG, expected);
+}
+
+#[test]
+fn turtle_errors_are_located_and_skipped(){
+    let input = "@prefix ex: <http://example.com/> .\n\
+        ex:s ex:p ex:o ;\n\
+        \tex:q unknown:o .\n\
+        ex:s ex:p \"ok\" .\n\
+        ex:s ex:p ex:o";
+    let quads: Vec<_> = QuadIterator::new(input.as_bytes(), &Lang::TTL).collect();
+    //the triples of an invalid statement are dropped together
+    assert_eq!(3, quads.len());
+    let err = quads[0].as_ref().err().expect("Prefix is not declared");
+    assert_eq!(ErrorKind::UnknownPrefix, err.get_kind());
+    assert_eq!(Some(3), err.get_line());
+    assert_eq!(Some(7), err.get_column());
+    assert_eq!(Some(input.find("unknown").unwrap()), err.get_byte_offset());
+    assert_eq!(Some("\tex:q unknown:o ."), err.get_text());
+    assert_eq!("\"ok\"", quads[1].as_ref().expect("Is valid").get_object().as_string());
+    let err = quads[2].as_ref().err().expect("Statement isn't complete");
+    assert_eq!(ErrorKind::Syntax, err.get_kind());
+    assert_eq!(Some(5), err.get_line());
+
+    let mut database = Database::new(GraphType::IndexedGraph);
+    let file = temp_file("errors.ttl", input.as_bytes());
+    let report = Reader::read_to_rdf_with_options(&mut database, file.to_str().expect("Is UTF-8"), Lang::TTL, &ParseOptions::lenient());
+    fs::remove_file(&file).expect("Can remove temporary file");
+    let report = report.expect("Can read file");
+    assert_eq!(1, report.get_statement_count());
+    assert_eq!(2, report.get_errors().len());
+}
@@ -0,0 +1,268 @@
+//!
+//! Runs the SHACL Core and SHACL-SPARQL test cases in `tests/shacl` against the `Validator` and the rule test cases
+//! against the `RuleEngine`.
+//!
+//! The validation tests are found through the manifests like in the W3C SHACL test suite, so `shacl_official_core_tests`
+//! runs the official core tests from the directory `SHACL_TEST_SUITE` when ignored tests are included
+//! (see `tests/shacl/README.md`).
+//!
+
+use rdf4rust::error::ErrorKind;
+use rdf4rust::io::reader::{QuadIterator, Lang};
+use rdf4rust::rdf::database::{Database, GraphPattern};
+use rdf4rust::rdf::graph::{is_isomorphic, Graph, GraphType, IndexedGraph, Node, ResourceNode, Statement};
+use rdf4rust::rdf::node_factory::{IRIResource, RDFNode};
+use rdf4rust::shacl::rules::RuleEngine;
+use rdf4rust::shacl::shapes::{sh_base, ShapesGraph};
+use rdf4rust::shacl::validation::Validator;
+use rdf4rust::util::iri::IRI;
+use rdf4rust::rdf::graph::read_list;
+use std::env;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+/// The entries of the official core tests which are known to fail with the reason, matched by the end of their IRI
+const SKIPPED: &[(&str, &str)] = &[];
+
+fn suite_dir() -> PathBuf{
+    Path::new(env!("CARGO_MANIFEST_DIR")).join("tests").join("shacl")
+}
+
+fn iri(iri: &str) -> IRIResource{
+    IRIResource::create_resource(IRI::create_iri(&String::from(iri)).expect("Is valid IRI"))
+}
+
+fn sh(local_name: &str) -> IRIResource{
+    iri(&(sh_base() + local_name))
+}
+
+fn mf(local_name: &str) -> IRIResource{
+    iri(&format!("http://www.w3.org/2001/sw/DataAccess/tests/test-manifest#{}", local_name))
+}
+
+fn sht(local_name: &str) -> IRIResource{
+    iri(&format!("http://www.w3.org/ns/shacl-test#{}", local_name))
+}
+
+fn test_files(dir: &Path, extension: &str, files: &mut Vec<PathBuf>){
+    for entry in fs::read_dir(dir).expect("Test directory exists") {
+        let path = entry.expect("Test directory is readable").path();
+        if path.is_dir() {
+            test_files(&path, extension, files);
+        } else if path.extension().is_some_and(|ext| ext == extension) {
+            files.push(path);
+        }
+    }
+}
+
+fn file_iri(file: &Path) -> IRI{
+    IRI::create_iri(&format!("file://{}", file.display())).expect("Is valid IRI")
+}
+
+fn iri_file(iri: &str) -> PathBuf{
+    PathBuf::from(iri.strip_prefix("file://").unwrap_or_else(|| panic!("{} is not a file", iri)))
+}
+
+/// Reads an N-Triples or Turtle file, relative IRIs are resolved against the location of the file
+fn read_graph(file: &Path) -> IndexedGraph{
+    let input = fs::read_to_string(file).unwrap_or_else(|_| panic!("{} exists", file.display()));
+    let lang = if file.extension().is_some_and(|ext| ext == "ttl") {Lang::TTL} else {Lang::NT};
+    let mut graph = IndexedGraph::new();
+    for quad in QuadIterator::new(input.as_bytes(), &lang).with_preserved_blank_node_labels(true).with_base_iri(file_iri(file)) {
+        let quad = quad.unwrap_or_else(|err| panic!("{} is not valid: {:?}", file.display(), err));
+        graph.add_statement(quad.into_statement().1);
+    }
+    graph
+}
+
+/// Collects the files with test entries of `manifest` and the manifests it includes with `mf:include`
+fn manifest_files(manifest: &Path, files: &mut Vec<PathBuf>){
+    let graph = read_graph(manifest);
+    if !graph.list_statements(None, Some(mf("entries")), None).is_empty() {
+        files.push(manifest.to_path_buf());
+    }
+    for stmt in graph.list_statements(None, Some(mf("include")), None) {
+        match stmt.get_object() {
+            Node::IRINode { iri } => manifest_files(&iri_file(&iri.get_value()), files),
+            other => panic!("{} includes {}, which is not a file", manifest.display(), other.as_string())
+        }
+    }
+}
+
+fn object(graph: &dyn Graph, subject: &ResourceNode, predicate: IRIResource) -> Node{
+    let stmts = graph.list_statements(Some(subject.clone()), Some(predicate.clone()), None);
+    assert_eq!(1, stmts.len(), "{} has not exactly one {}", subject.as_string(), predicate.get_value());
+    stmts[0].get_object().clone()
+}
+
+/// The statements describing `node` and its blank nodes, except the shapes, constraints and data nodes results refer to
+/// and `sh:resultMessage`
+fn describe(graph: &dyn Graph, node: &Node, stmts: &mut Vec<Statement>){
+    let subject = match node {
+        Node::BNode { bnode } => ResourceNode::BNode { bnode: bnode.clone() },
+        _ => return
+    };
+    for stmt in graph.list_statements(Some(subject), None, None) {
+        let predicate = stmt.get_predicate();
+        if predicate == &sh("resultMessage") {
+            continue
+        }
+        stmts.push(stmt.clone());
+        if predicate != &sh("sourceShape") && predicate != &sh("sourceConstraint") && predicate != &sh("focusNode")
+            && predicate != &sh("value") {
+            describe(graph, stmt.get_object(), stmts);
+        }
+    }
+}
+
+fn to_graph(stmts: Vec<Statement>) -> IndexedGraph{
+    let mut graph = IndexedGraph::new();
+    for stmt in stmts {
+        graph.add_statement(stmt);
+    }
+    graph
+}
+
+fn run_test(graph: &IndexedGraph, test: &ResourceNode) -> Result<(), String>{
+    let action = object(graph, test, mf("action")).to_resource().expect("Action is a node");
+    let data = object(graph, &action, sht("dataGraph"));
+    let shapes = object(graph, &action, sht("shapesGraph"));
+    //data and shapes graph are usually the test file itself, whose graph is already read
+    let (data_graph, shapes_graph);
+    let (data, shapes) = if data == shapes {
+        (graph, graph)
+    } else {
+        let read = |node: &Node| match node {
+            Node::IRINode { iri } => read_graph(&iri_file(&iri.get_value())),
+            other => panic!("{} is not a file", other.as_string())
+        };
+        data_graph = read(&data);
+        shapes_graph = read(&shapes);
+        (&data_graph, &shapes_graph)
+    };
+    let expected = object(graph, test, mf("result"));
+    let shapes = ShapesGraph::from_graph(shapes);
+    if expected == (Node::IRINode { iri: sht("Failure") }) {
+        return match shapes {
+            Err(err) if err.get_kind() == ErrorKind::InvalidShape => Ok(()),
+            Err(err) => Err(format!("unexpected error {}", err)),
+            Ok(_) => Err(String::from("expected the shapes graph to be rejected"))
+        }
+    }
+    let shapes = shapes.map_err(|err| err.to_string())?;
+    let report = Validator::new(&shapes).validate(data);
+    let mut expected_report = Vec::new();
+    describe(graph, &expected, &mut expected_report);
+    let mut actual_report = Vec::new();
+    let actual = report.to_statements();
+    let actual_graph = to_graph(actual.clone());
+    describe(&actual_graph, &Node::from(actual[0].get_subject().clone()), &mut actual_report);
+    let (expected_report, actual_report) = (to_graph(expected_report), to_graph(actual_report));
+    if is_isomorphic(&expected_report, &actual_report) {
+        Ok(())
+    } else {
+        Err(format!("expected\n{}got\n{}", nt(&expected_report), nt(&actual_report)))
+    }
+}
+
+fn nt(graph: &dyn Graph) -> String{
+    graph.list_statements(None, None, None).iter().map(|stmt| format!("{}\n", stmt)).collect()
+}
+
+/// Runs the `sht:Validate` entries of `manifest` and the manifests it includes, returns the number of test files
+fn run_suite(manifest: &Path, skipped: &[(&str, &str)]) -> usize{
+    let mut files = Vec::new();
+    manifest_files(manifest, &mut files);
+    assert!(!files.is_empty());
+    let mut failures = Vec::new();
+    let mut count = 0;
+    for file in &files {
+        let graph = read_graph(file);
+        let manifests = graph.list_statements(None, Some(mf("entries")), None);
+        for entry in manifests.iter().flat_map(|stmt| read_list(&graph, stmt.get_object()).expect("Entries are a list")) {
+            let entry = entry.to_resource().expect("Entry is a node");
+            if graph.list_statements(Some(entry.clone()), Some(iri("http://www.w3.org/1999/02/22-rdf-syntax-ns#type")), Some(Node::IRINode { iri: sht("Validate") })).is_empty() {
+                continue
+            }
+            let id = entry.as_string();
+            if let Some((_, reason)) = skipped.iter().find(|(skipped, _)| id.ends_with(skipped)) {
+                eprintln!("Skipped {}: {}", id, reason);
+                continue
+            }
+            if graph.list_statements(Some(entry.clone()), Some(mf("status")), Some(Node::IRINode { iri: sht("approved") })).is_empty() {
+                eprintln!("Skipped {}: the entry is not approved", id);
+                continue
+            }
+            count += 1;
+            if let Err(err) = run_test(&graph, &entry) {
+                failures.push(format!("{} ({}): {}", id, file.display(), err));
+            }
+        }
+    }
+    assert!(failures.is_empty(), "{} of {} tests failed:\n{}", failures.len(), count, failures.join("\n"));
+    files.len()
+}
+
+/// Runs the tests of `dir`, each of its N-Triples files has to be included by its manifest and hold one test
+fn run_fixtures(dir: &Path){
+    let mut files = Vec::new();
+    test_files(dir, "nt", &mut files);
+    assert_eq!(files.len(), run_suite(&dir.join("manifest.ttl"), &[]), "Each test file is included once");
+}
+
+/// Reads a rule test, the data and shapes are in the default graph and the expected inferences in the graph `mf:result`
+fn run_rule_test(file: &Path) -> Result<(), String>{
+    let input = fs::read_to_string(file).expect("Test file exists");
+    let mut database = Database::new(GraphType::IndexedGraph);
+    for quad in QuadIterator::new(input.as_bytes(), &Lang::NQ).with_preserved_blank_node_labels(true) {
+        database.add_quad(quad.unwrap_or_else(|err| panic!("{} is not valid N-Quads: {:?}", file.display(), err)));
+    }
+    let default_graph = database.get_default_graph();
+    let tests = default_graph.list_statements(None, Some(iri("http://www.w3.org/1999/02/22-rdf-syntax-ns#type")), Some(Node::IRINode { iri: sht("Infer") }));
+    assert_eq!(1, tests.len(), "Each test file has one test");
+    let expected = match object(default_graph.as_ref(), tests[0].get_subject(), mf("result")) {
+        Node::IRINode { iri } => iri,
+        _ => panic!("The result of {} is not a graph name", tests[0].get_subject().as_string())
+    };
+    let shapes = ShapesGraph::from_graph(default_graph.as_ref()).map_err(|err| err.to_string())?;
+    let actual = iri(&format!("{}-actual", expected.get_value()));
+    RuleEngine::new(&shapes).materialize_database(&mut database, &GraphPattern::DefaultGraph, Some(&ResourceNode::IRINode { iri: actual.clone() }));
+    let empty: Box<dyn Graph> = Box::new(IndexedGraph::new());
+    let expected = database.get_named_graph(&expected.get_value()).unwrap_or(&empty);
+    let actual = database.get_named_graph(&actual.get_value()).unwrap_or(&empty);
+    if is_isomorphic(expected.as_ref(), actual.as_ref()) {
+        Ok(())
+    } else {
+        Err(format!("expected\n{}got\n{}", nt(expected.as_ref()), nt(actual.as_ref())))
+    }
+}
+
+#[test]
+fn shacl_core_tests(){
+    run_fixtures(&suite_dir().join("core"));
+}
+
+#[test]
+fn shacl_sparql_tests(){
+    run_fixtures(&suite_dir().join("sparql"));
+}
+
+/// Runs the core tests of the official suite in the directory `SHACL_TEST_SUITE`, it is ignored as the suite is not vendored
+#[test]
+#[ignore = "needs a checkout of the W3C data-shapes test suite in SHACL_TEST_SUITE"]
+fn shacl_official_core_tests(){
+    let dir = env::var_os("SHACL_TEST_SUITE").expect("SHACL_TEST_SUITE is set to the tests directory of the W3C SHACL test suite");
+    run_suite(&Path::new(&dir).join("core").join("manifest.ttl"), SKIPPED);
+}
+
+#[test]
+fn shacl_rules_tests(){
+    let mut files = Vec::new();
+    test_files(&suite_dir().join("rules"), "nq", &mut files);
+    files.sort();
+    assert!(!files.is_empty());
+    let failures: Vec<String> = files.iter()
+        .filter_map(|file| run_rule_test(file).err().map(|err| format!("{}: {}", file.display(), err)))
+        .collect();
+    assert!(failures.is_empty(), "{} of {} tests failed:\n{}", failures.len(), files.len(), failures.join("\n"));
+}
@@ -0,0 +1,97 @@
+//!
+//! Round trip tests of the writers, reading their output has to give the written quads again.
+//!
+
+use rdf4rust::io::reader::{Lang, QuadIterator};
+use rdf4rust::io::writer::Writer;
+use rdf4rust::rdf::database::{self, Database, GraphPattern};
+use rdf4rust::rdf::graph::{GraphType, Quad};
+
+const INPUT: &str = "<http://example.com/alice> <http://www.w3.org/1999/02/22-rdf-syntax-ns#type> <http://example.com/Person> .\n\
+    <http://example.com/alice> <http://example.com/name> \"Alice \\\"A.\\\"\\nSmith\" .\n\
+    <http://example.com/alice> <http://example.com/name> \"Alicia\"@es .\n\
+    <http://example.com/alice> <http://example.com/name> \"أليس\"@ar--rtl .\n\
+    <http://example.com/alice> <http://example.com/age> \"42\"^^<http://www.w3.org/2001/XMLSchema#integer> .\n\
+    <http://example.com/alice> <http://example.com/height> \"1.70\"^^<http://www.w3.org/2001/XMLSchema#decimal> .\n\
+    <http://example.com/alice> <http://example.com/weight> \"6.5E1\"^^<http://www.w3.org/2001/XMLSchema#double> .\n\
+    <http://example.com/alice> <http://example.com/size> \"042.\"^^<http://www.w3.org/2001/XMLSchema#decimal> .\n\
+    <http://example.com/alice> <http://example.com/born> \"2000-01-01\"^^<http://www.w3.org/2001/XMLSchema#date> .\n\
+    <http://example.com/alice> <http://example.com/page> <http://example.com/pages/alice%20smith> .\n\
+    <http://example.com/alice> <http://example.com/knows> _:b0 .\n\
+    _:b0 <http://example.com/knows> <http://example.com/alice> .\n\
+    << <http://example.com/alice> <http://example.com/knows> _:b0 >> <http://example.com/source> <http://example.com/survey> .\n\
+    << << <http://example.com/alice> <http://example.com/knows> _:b0 >> <http://example.com/source> <http://example.com/survey> >> <http://example.com/checked> \"true\"^^<http://www.w3.org/2001/XMLSchema#boolean> .\n\
+    << <http://example.com/bob> <http://example.com/knows> <http://example.com/carol> >> <http://example.com/source> <http://example.com/rumor> .\n\
+    <http://example.com/alice> <http://example.com/says> << _:b0 <http://example.com/age> \"7\"^^<http://example.com/years> >> .\n\
+    <http://example.com/alice> <http://example.com/knows> <http://example.com/bob> <http://example.com/g> .\n\
+    << <http://example.com/alice> <http://example.com/knows> <http://example.com/bob> >> <http://example.com/since> \"2020\"^^<http://www.w3.org/2001/XMLSchema#integer> <http://example.com/g> .\n\
+    _:b1 <http://example.com/p> _:b0 _:b2 .\n";
+
+fn read(input: &[u8], lang: &Lang) -> Database{
+    let mut database = Database::new(GraphType::IndexedGraph);
+    for quad in QuadIterator::new(input, lang) {
+        database.add_quad(quad.unwrap_or_else(|err| panic!("{}\n{}", err, String::from_utf8_lossy(input))));
+    }
+    database
+}
+
+fn write(database: &Database, lang: Lang) -> Vec<u8>{
+    let mut out = Vec::new();
+    Writer::new(lang)
+        .with_prefix("ex", "http://example.com/")
+        .with_prefix("xsd", "http://www.w3.org/2001/XMLSchema#")
+        .write_database(database, &mut out)
+        .expect("Can write");
+    out
+}
+
+fn default_graph(database: &Database) -> Database{
+    let mut ret = Database::new(GraphType::IndexedGraph);
+    for quad in database.quads_for_pattern(None, None, None, &GraphPattern::DefaultGraph) {
+        ret.add_quad(quad);
+    }
+    ret
+}
+
+#[test]
+fn quads_round_trip(){
+    let database = read(INPUT.as_bytes(), &Lang::NQ);
+    assert_eq!(19, database.count());
+    let written = write(&database, Lang::NQ);
+    assert!(database::is_isomorphic(&database, &read(&written, &Lang::NQ)), "{}", String::from_utf8_lossy(&written));
+    let written = write(&database, Lang::TRIG);
+    assert!(database::is_isomorphic(&database, &read(&written, &Lang::TRIG)), "{}", String::from_utf8_lossy(&written));
+}
+
+#[test]
+fn triples_round_trip(){
+    let database = read(INPUT.as_bytes(), &Lang::NQ);
+    let expected = default_graph(&database);
+    assert_eq!(16, expected.count());
+    let written = write(&database, Lang::NT);
+    assert!(database::is_isomorphic(&expected, &read(&written, &Lang::NT)), "{}", String::from_utf8_lossy(&written));
+    let written = write(&database, Lang::TTL);
+    assert!(database::is_isomorphic(&expected, &read(&written, &Lang::TTL)), "{}", String::from_utf8_lossy(&written));
+}
+
+#[test]
+fn turtle_uses_annotations_and_prefixes(){
+    let database = read(INPUT.as_bytes(), &Lang::NQ);
+    let written = String::from_utf8(write(&database, Lang::TRIG)).expect("Is UTF-8");
+    assert!(written.starts_with("@prefix ex: <http://example.com/> .\n@prefix xsd: <http://www.w3.org/2001/XMLSchema#> .\n\n"));
+    //asserted triples carry their annotations, nested as well
+    assert!(written.contains(" {| ex:source ex:survey {| ex:checked true |} |}"), "{}", written);
+    //quoted triples which aren't asserted stay subjects
+    assert!(written.contains("<< ex:bob ex:knows ex:carol >> ex:source ex:rumor .\n"), "{}", written);
+    assert!(written.contains("ex:g {\n    ex:alice ex:knows ex:bob {| ex:since 2020 |} .\n}\n"), "{}", written);
+    assert!(written.contains("ex:alice a ex:Person ;\n"), "{}", written);
+    //IRIs which aren't valid local names keep their brackets
+    assert!(written.contains("<http://example.com/pages/alice%20smith>"), "{}", written);
+    assert!(written.contains("\"042.\"^^xsd:decimal"), "{}", written);
+}
+
+#[test]
+fn unsupported_formats_are_errors(){
+    let quads: Vec<Quad> = Vec::new();
+    assert!(Writer::new(Lang::RdfXml).write(&quads, &mut Vec::new()).is_err());
+}